@@ -1,9 +1,12 @@
-use serde_json::json;
+use serde_json::{json, Value};
 
 use super::client::Client;
 use super::errors::Error;
+use super::limits::{clamp_limit, MAX_AUTOCOMPLETE_SUGGESTIONS};
 use super::types::{AutocompleteRequest, AutocompleteResponse};
 
+const AUTOCOMPLETE_FIELD_MASK: &str = "suggestions.placePrediction,suggestions.queryPrediction";
+
 impl Client {
     pub async fn autocomplete(
         &self,
@@ -15,35 +18,28 @@ impl Client {
                 message: "input is required".into(),
             });
         }
-
-        let mut body = json!({
-            "input": req.input,
-        });
-
-        if let Some(ref token) = req.session_token {
-            body["sessionToken"] = json!(token);
-        }
         if let Some(ref loc) = req.location {
-            body["locationBias"] = json!({
-                "circle": {
-                    "center": { "latitude": loc.center.latitude, "longitude": loc.center.longitude },
-                    "radius": loc.radius,
-                }
-            });
+            loc.validate()?;
         }
-        if let Some(ref lang) = req.language {
-            body["languageCode"] = json!(lang);
+        if req.included_primary_types.len() > 5 {
+            return Err(Error::Validation {
+                field: "type".into(),
+                message: "at most 5 primary types may be given".into(),
+            });
         }
-        if let Some(ref region) = req.region {
-            body["regionCode"] = json!(region);
+        if req.included_primary_types.len() > 1
+            && req.included_primary_types.iter().any(|t| t.starts_with('('))
+        {
+            return Err(Error::Validation {
+                field: "type".into(),
+                message: "type collections like (regions) or (cities) cannot be combined with other types".into(),
+            });
         }
 
+        let body = build_autocomplete_body(req);
+
         let result = self
-            .places_post(
-                "/places:autocomplete",
-                "suggestions.placePrediction,suggestions.queryPrediction",
-                &body,
-            )
+            .places_post("/places:autocomplete", AUTOCOMPLETE_FIELD_MASK, &body)
             .await?;
 
         let mut response: AutocompleteResponse = serde_json::from_value(result).map_err(|e| {
@@ -55,9 +51,125 @@ impl Client {
 
         // Apply client-side limit (autocomplete API doesn't support maxResultCount)
         if let Some(limit) = req.limit {
-            response.suggestions.truncate(limit as usize);
+            response
+                .suggestions
+                .truncate(clamp_limit(limit, MAX_AUTOCOMPLETE_SUGGESTIONS).0 as usize);
         }
 
         Ok(response)
     }
 }
+
+/// Build the JSON body for POST /places:autocomplete
+pub(crate) fn build_autocomplete_body(req: &AutocompleteRequest) -> Value {
+    let mut body = json!({
+        "input": req.input,
+    });
+
+    if let Some(ref token) = req.session_token {
+        body["sessionToken"] = json!(token);
+    }
+    if let Some(ref loc) = req.location {
+        body["locationBias"] = json!({
+            "circle": {
+                "center": { "latitude": loc.center.latitude, "longitude": loc.center.longitude },
+                "radius": loc.radius,
+            }
+        });
+    }
+    if let Some(ref lang) = req.language {
+        body["languageCode"] = json!(lang);
+    }
+    if let Some(ref region) = req.region {
+        body["regionCode"] = json!(region);
+    }
+    if !req.included_primary_types.is_empty() {
+        body["includedPrimaryTypes"] = json!(req.included_primary_types);
+    }
+
+    body
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::api::mock::MockApi;
+
+    fn request(fields: Value) -> AutocompleteRequest {
+        serde_json::from_value(fields).unwrap()
+    }
+
+    #[test]
+    fn options_off_sends_only_the_input() {
+        let req = request(json!({ "input": "best ramen in" }));
+        assert_eq!(build_autocomplete_body(&req), json!({ "input": "best ramen in" }));
+    }
+
+    #[test]
+    fn options_on_are_all_sent() {
+        let req = request(json!({
+            "input": "coffee",
+            "sessionToken": "session-1",
+            "location": { "center": { "latitude": 48.2, "longitude": 16.37 }, "radius": 5000.0 },
+            "limit": 3,
+            "language": "de",
+            "region": "at",
+            "includedPrimaryTypes": ["cafe", "bakery"],
+        }));
+        assert_eq!(
+            build_autocomplete_body(&req),
+            json!({
+                "input": "coffee",
+                "sessionToken": "session-1",
+                "locationBias": {
+                    "circle": { "center": { "latitude": 48.2, "longitude": 16.37 }, "radius": 5000.0 }
+                },
+                "languageCode": "de",
+                "regionCode": "at",
+                "includedPrimaryTypes": ["cafe", "bakery"],
+            })
+        );
+    }
+
+    #[test]
+    fn limit_stays_out_of_the_body() {
+        // The endpoint has no maxResultCount; the limit is applied to the response
+        let req = request(json!({ "input": "coffee", "limit": 2 }));
+        assert!(build_autocomplete_body(&req).get("maxResultCount").is_none());
+    }
+
+    #[tokio::test]
+    async fn request_asks_for_both_prediction_kinds_and_the_limit_trims_the_reply() {
+        let api = MockApi::start(Duration::ZERO, |_| {
+            (
+                200,
+                json!({ "suggestions": [
+                    { "placePrediction": { "placeId": "ChIJ-cafe", "text": { "text": "Cafe Central, Wien" } } },
+                    { "queryPrediction": { "text": { "text": "coffee near Wien" } } },
+                    { "placePrediction": { "placeId": "ChIJ-other", "text": { "text": "Cafe Other, Wien" } } },
+                ] }),
+            )
+        })
+        .await;
+        let client = Client::new("test-key".into()).unwrap().with_places_base_url(api.url());
+
+        let req = request(json!({ "input": "coffee", "limit": 2, "language": "de" }));
+        let resp = client.autocomplete(&req).await.unwrap();
+
+        let sent = &api.requests()[0];
+        assert_eq!(sent.path, "/places:autocomplete");
+        assert_eq!(
+            sent.field_mask.as_deref(),
+            Some("suggestions.placePrediction,suggestions.queryPrediction")
+        );
+        assert_eq!(sent.body, json!({ "input": "coffee", "languageCode": "de" }));
+
+        assert_eq!(resp.suggestions.len(), 2);
+        let place = resp.suggestions[0].place_prediction.as_ref().unwrap();
+        assert_eq!(place.place_id.as_deref(), Some("ChIJ-cafe"));
+        let query = resp.suggestions[1].query_prediction.as_ref().unwrap();
+        assert_eq!(query.text.as_ref().unwrap().text, "coffee near Wien");
+    }
+}
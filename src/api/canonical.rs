@@ -0,0 +1,165 @@
+use serde_json::{Map, Value};
+
+/// Decimal places coordinates are rounded to in request keys: 4 is about
+/// 11 m, well inside any search radius
+const KEY_COORD_DECIMALS: i32 = 4;
+
+/// Fields holding case-insensitive codes (BCP-47 languages, CLDR regions)
+const CODE_FIELDS: &[&str] = &["languageCode", "regionCode"];
+
+/// Fields holding coordinates
+const COORD_FIELDS: &[&str] = &["latitude", "longitude"];
+
+/// Key identifying a request by what it asks for, so that equivalent
+/// requests share one key: `method`, `url`, the field mask with its paths
+/// sorted, and the canonical `body`
+pub(crate) fn request_key(method: &str, url: &str, field_mask: &str, body: &Value) -> String {
+    let mut paths: Vec<&str> = field_mask
+        .split(',')
+        .map(str::trim)
+        .filter(|p| !p.is_empty())
+        .collect();
+    paths.sort_unstable();
+    paths.dedup();
+    format!("{} {} {} {}", method, url, paths.join(","), canonicalize(body))
+}
+
+/// Query parameters as a JSON object, so GET requests get the same
+/// treatment as request bodies
+pub(crate) fn query_to_value(query_params: &[(&str, &str)]) -> Value {
+    let map: Map<String, Value> = query_params
+        .iter()
+        .map(|(k, v)| (k.to_string(), Value::String(v.to_string())))
+        .collect();
+    Value::Object(map)
+}
+
+/// Normalize a request body: coordinates rounded to `KEY_COORD_DECIMALS`,
+/// language and region codes lowercased, and object keys sorted. Arrays keep
+/// their order, which can matter (route waypoints). Session tokens are kept:
+/// a response shared across autocomplete sessions would be billed to one
+/// session but used by another.
+pub(crate) fn canonicalize(value: &Value) -> Value {
+    match value {
+        Value::Object(map) => {
+            let mut entries: Vec<(&String, &Value)> = map.iter().collect();
+            entries.sort_by(|a, b| a.0.cmp(b.0));
+            let map: Map<String, Value> = entries
+                .into_iter()
+                .map(|(k, v)| (k.clone(), canonical_field(k, v)))
+                .collect();
+            Value::Object(map)
+        }
+        Value::Array(items) => Value::Array(items.iter().map(canonicalize).collect()),
+        other => other.clone(),
+    }
+}
+
+fn canonical_field(key: &str, value: &Value) -> Value {
+    if COORD_FIELDS.contains(&key) {
+        if let Some(x) = value.as_f64() {
+            let scale = 10f64.powi(KEY_COORD_DECIMALS);
+            // +0.0 turns a rounded -0.0 into 0.0, so both print the same
+            return Value::from((x * scale).round() / scale + 0.0);
+        }
+    }
+    if CODE_FIELDS.contains(&key) {
+        if let Some(code) = value.as_str() {
+            return Value::String(code.to_ascii_lowercase());
+        }
+    }
+    canonicalize(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    const URL: &str = "https://places.googleapis.com/v1/places:searchText";
+
+    fn key(field_mask: &str, body: Value) -> String {
+        request_key("POST", URL, field_mask, &body)
+    }
+
+    fn search(lat: f64, lng: f64, language: &str) -> Value {
+        json!({
+            "textQuery": "cafe",
+            "locationBias": { "circle": { "center": { "latitude": lat, "longitude": lng }, "radius": 500.0 } },
+            "languageCode": language,
+        })
+    }
+
+    #[test]
+    fn nearby_coordinates_share_a_key() {
+        // About 3 m apart
+        assert_eq!(
+            key("places.id", search(48.20821, 16.37381, "de")),
+            key("places.id", search(48.20824, 16.37378, "de"))
+        );
+    }
+
+    #[test]
+    fn distant_coordinates_do_not() {
+        // About 110 m apart
+        assert_ne!(
+            key("places.id", search(48.2082, 16.3738, "de")),
+            key("places.id", search(48.2092, 16.3738, "de"))
+        );
+    }
+
+    #[test]
+    fn key_order_and_code_case_do_not_matter() {
+        let reordered = json!({
+            "languageCode": "DE",
+            "locationBias": { "circle": { "radius": 500.0, "center": { "longitude": 16.3738, "latitude": 48.2082 } } },
+            "textQuery": "cafe",
+        });
+        assert_eq!(key("places.id", search(48.2082, 16.3738, "de")), key("places.id", reordered));
+    }
+
+    #[test]
+    fn field_mask_order_does_not_matter_but_its_paths_do() {
+        let body = search(48.2082, 16.3738, "de");
+        assert_eq!(
+            key("places.id,places.rating", body.clone()),
+            key("places.rating, places.id", body.clone())
+        );
+        assert_ne!(key("places.id", body.clone()), key("places.id,places.photos", body));
+    }
+
+    #[test]
+    fn query_and_language_are_material() {
+        let mut other_query = search(48.2082, 16.3738, "de");
+        other_query["textQuery"] = json!("Cafe");
+        assert_ne!(key("places.id", search(48.2082, 16.3738, "de")), key("places.id", other_query));
+        assert_ne!(
+            key("places.id", search(48.2082, 16.3738, "de")),
+            key("places.id", search(48.2082, 16.3738, "fr"))
+        );
+    }
+
+    #[test]
+    fn session_tokens_are_material() {
+        let autocomplete = |token: &str| json!({ "input": "caf", "sessionToken": token });
+        assert_eq!(key("", autocomplete("a")), key("", autocomplete("a")));
+        assert_ne!(key("", autocomplete("a")), key("", autocomplete("b")));
+    }
+
+    #[test]
+    fn negative_zero_rounds_like_zero() {
+        assert_eq!(
+            canonicalize(&json!({ "latitude": -0.00001 })),
+            canonicalize(&json!({ "latitude": 0.0 }))
+        );
+    }
+
+    #[test]
+    fn array_order_is_kept() {
+        assert_ne!(
+            canonicalize(&json!({ "includedTypes": ["cafe", "bakery"] })),
+            canonicalize(&json!({ "includedTypes": ["bakery", "cafe"] }))
+        );
+    }
+}
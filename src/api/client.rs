@@ -1,19 +1,84 @@
-use std::time::Duration;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
 use reqwest::header::{HeaderMap, HeaderValue};
 use serde_json::Value;
+use tokio::sync::watch;
+use tracing::{debug, debug_span, info, warn, Instrument};
 
-use super::errors::Error;
+use super::canonical::{query_to_value, request_key};
+use super::connections::{ConnectionStats, CountConnections, PoolSettings};
+use super::errors::{api_error_message, unexpected_body_message, Error};
+use super::throttle::RateLimiter;
+use super::types::Warning;
 
 const PLACES_BASE_URL: &str = "https://places.googleapis.com/v1";
 const ROUTES_BASE_URL: &str = "https://routes.googleapis.com";
 const MAX_RESPONSE_BYTES: usize = 1_048_576; // 1 MB
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Requests slower than this are always reported as slow
+pub const SLOW_REQUEST_THRESHOLD: Duration = Duration::from_secs(5);
+
+/// Events reported to the observer installed with `Client::with_observer`
+#[derive(Debug, Clone)]
+pub enum ClientEvent {
+    /// An API request completed, successfully or not
+    RequestFinished {
+        endpoint: String,
+        elapsed: Duration,
+        ok: bool,
+    },
+    /// Something went wrong without failing the request, e.g. part of a
+    /// response could not be parsed and was skipped
+    Warning(Warning),
+    /// A request identical to one already in flight shared its response
+    /// instead of being sent
+    Coalesced { endpoint: String },
+    /// The rate limiter held a request back for more than a second; sent
+    /// only the first time
+    Throttled { delay: Duration, qps: f64 },
+}
+
+pub type Observer = Arc<dyn Fn(&ClientEvent) + Send + Sync>;
+
+/// Result of an in-flight request, published to identical requests waiting on it
+type Shared = watch::Receiver<Option<Result<Value, Error>>>;
 
 pub struct Client {
     api_key: String,
     http: reqwest::Client,
+    timeout: Duration,
+    pool: PoolSettings,
+    /// Shared with the connector of every `http` built for this client
+    connections: Arc<ConnectionStats>,
     places_base_url: String,
     routes_base_url: String,
+    observer: Option<Observer>,
+    /// Requests being sent, by `canonical::request_key`
+    in_flight: Mutex<HashMap<String, Shared>>,
+    /// Set by `with_max_qps`; every request sent waits on it
+    limiter: Option<RateLimiter>,
+    throttle_reported: AtomicBool,
+    /// Set by `with_raw_places`
+    keep_raw_places: bool,
+}
+
+impl fmt::Debug for Client {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Client")
+            .field("api_key", &"[redacted]")
+            .field("places_base_url", &self.places_base_url)
+            .field("routes_base_url", &self.routes_base_url)
+            .field("observer", &self.observer.is_some())
+            .field("max_qps", &self.limiter.as_ref().map(RateLimiter::qps))
+            .field("timeout", &self.timeout)
+            .field("pool", &self.pool)
+            .finish()
+    }
 }
 
 impl Client {
@@ -22,27 +87,49 @@ impl Client {
             return Err(Error::MissingApiKey);
         }
 
-        let http = reqwest::Client::builder()
-            .timeout(Duration::from_secs(10))
-            .build()
-            .map_err(Error::Http)?;
+        let pool = PoolSettings::default();
+        let connections = Arc::new(ConnectionStats::default());
+        let http = build_http(DEFAULT_TIMEOUT, pool, &connections).map_err(Error::from)?;
 
         Ok(Client {
             api_key,
             http,
+            timeout: DEFAULT_TIMEOUT,
+            pool,
+            connections,
             places_base_url: PLACES_BASE_URL.to_string(),
             routes_base_url: ROUTES_BASE_URL.to_string(),
+            observer: None,
+            in_flight: Mutex::new(HashMap::new()),
+            limiter: None,
+            throttle_reported: AtomicBool::new(false),
+            keep_raw_places: false,
         })
     }
 
     pub fn with_timeout(mut self, timeout: Duration) -> Self {
-        self.http = reqwest::Client::builder()
-            .timeout(timeout)
-            .build()
-            .unwrap_or(self.http);
+        self.timeout = timeout;
+        self.rebuild_http()
+    }
+
+    /// Keep idle connections as `pool` says (config `network.*`)
+    pub fn with_pool(mut self, pool: PoolSettings) -> Self {
+        self.pool = pool;
+        self.rebuild_http()
+    }
+
+    fn rebuild_http(mut self) -> Self {
+        if let Ok(http) = build_http(self.timeout, self.pool, &self.connections) {
+            self.http = http;
+        }
         self
     }
 
+    /// Connections opened so far and the time spent opening them
+    pub fn connection_stats(&self) -> &ConnectionStats {
+        &self.connections
+    }
+
     pub fn with_places_base_url(mut self, url: String) -> Self {
         self.places_base_url = url;
         self
@@ -53,6 +140,56 @@ impl Client {
         self
     }
 
+    /// Send at most `qps` requests per second, across all concurrent callers
+    pub fn with_max_qps(mut self, qps: f64) -> Self {
+        self.limiter = Some(RateLimiter::new(qps));
+        self
+    }
+
+    /// Keep each parsed place's JSON in `Place::raw`
+    pub fn with_raw_places(mut self) -> Self {
+        self.keep_raw_places = true;
+        self
+    }
+
+    pub(crate) fn keeps_raw_places(&self) -> bool {
+        self.keep_raw_places
+    }
+
+    /// Install a callback that receives an event after every request
+    pub fn with_observer(mut self, observer: impl Fn(&ClientEvent) + Send + Sync + 'static) -> Self {
+        self.observer = Some(Arc::new(observer));
+        self
+    }
+
+    pub(crate) fn notify(&self, event: ClientEvent) {
+        if let Some(ref observer) = self.observer {
+            observer(&event);
+        }
+    }
+
+    fn notify_finished(&self, endpoint: String, start: Instant, ok: bool) {
+        self.notify(ClientEvent::RequestFinished {
+            endpoint,
+            elapsed: start.elapsed(),
+            ok,
+        });
+    }
+
+    /// Wait for the rate limiter, if one is set
+    async fn throttle(&self) {
+        let Some(ref limiter) = self.limiter else {
+            return;
+        };
+        let delay = limiter.acquire().await;
+        if delay > Duration::from_secs(1) && !self.throttle_reported.swap(true, Ordering::Relaxed) {
+            self.notify(ClientEvent::Throttled {
+                delay,
+                qps: limiter.qps(),
+            });
+        }
+    }
+
     fn auth_headers(&self) -> HeaderMap {
         let mut headers = HeaderMap::new();
         headers.insert(
@@ -76,15 +213,9 @@ impl Client {
             HeaderValue::from_str(field_mask).unwrap(),
         );
 
-        let resp = self
-            .http
-            .post(&url)
-            .headers(headers)
-            .json(body)
-            .send()
-            .await?;
-
-        self.handle_response(resp).await
+        let key = request_key("POST", &url, field_mask, body);
+        let req = self.http.post(&url).headers(headers).json(body);
+        self.coalesced(key, path, self.send("places", path, req)).await
     }
 
     /// GET from a Places API endpoint with field mask
@@ -103,15 +234,9 @@ impl Client {
             );
         }
 
-        let resp = self
-            .http
-            .get(&url)
-            .headers(headers)
-            .query(query_params)
-            .send()
-            .await?;
-
-        self.handle_response(resp).await
+        let key = request_key("GET", &url, field_mask, &query_to_value(query_params));
+        let req = self.http.get(&url).headers(headers).query(query_params);
+        self.coalesced(key, path, self.send("places", path, req)).await
     }
 
     /// POST to Routes API
@@ -128,23 +253,107 @@ impl Client {
             HeaderValue::from_str(field_mask).unwrap(),
         );
 
-        let resp = self
-            .http
-            .post(&url)
-            .headers(headers)
-            .json(body)
-            .send()
-            .await?;
+        let key = request_key("POST", &url, field_mask, body);
+        let req = self.http.post(&url).headers(headers).json(body);
+        self.coalesced(key, path, self.send("routes", path, req)).await
+    }
 
-        self.handle_response(resp).await
+    /// Run `send` unless an equivalent request (same `key`) is already in
+    /// flight; then wait for that one and share its response. If the request
+    /// being waited on is cancelled, this one is sent after all.
+    async fn coalesced(
+        &self,
+        key: String,
+        path: &str,
+        send: impl std::future::Future<Output = Result<Value, Error>>,
+    ) -> Result<Value, Error> {
+        let waiting = {
+            let mut in_flight = self.in_flight.lock().unwrap_or_else(|e| e.into_inner());
+            match in_flight.get(&key) {
+                Some(shared) => Err(shared.clone()),
+                None => {
+                    let (tx, rx) = watch::channel(None);
+                    in_flight.insert(key.clone(), rx);
+                    Ok(tx)
+                }
+            }
+        };
+
+        match waiting {
+            Ok(tx) => {
+                let _registered = InFlight { client: self, key };
+                self.throttle().await;
+                let result = send.await;
+                tx.send_replace(Some(result.clone()));
+                result
+            }
+            Err(mut shared) => {
+                debug!(endpoint = %path, "coalesced with an identical in-flight request");
+                if let Ok(done) = shared.wait_for(Option::is_some).await {
+                    if let Some(result) = done.as_ref() {
+                        self.notify(ClientEvent::Coalesced {
+                            endpoint: endpoint_label(path),
+                        });
+                        return result.clone();
+                    }
+                }
+                self.throttle().await;
+                send.await
+            }
+        }
+    }
+
+    /// Send a prepared request, logging endpoint, status, and duration.
+    /// Headers (and with them the API key) are never logged.
+    async fn send(
+        &self,
+        api: &'static str,
+        path: &str,
+        req: reqwest::RequestBuilder,
+    ) -> Result<Value, Error> {
+        let span = debug_span!("request", api, endpoint = %path);
+        async move {
+            let start = Instant::now();
+            let resp = match req.send().await {
+                Ok(r) => r,
+                Err(e) => {
+                    warn!(
+                        duration_ms = start.elapsed().as_millis() as u64,
+                        error = %e,
+                        "request failed"
+                    );
+                    self.notify_finished(endpoint_label(path), start, false);
+                    return Err(Error::from(e));
+                }
+            };
+            let status = resp.status().as_u16();
+            let result = self.handle_response(api, path, resp).await;
+            info!(
+                status,
+                duration_ms = start.elapsed().as_millis() as u64,
+                ok = result.is_ok(),
+                "request finished"
+            );
+            self.notify_finished(endpoint_label(path), start, result.is_ok());
+            result
+        }
+        .instrument(span)
+        .await
     }
 
     /// Download raw bytes from a URL (used for fetching photos)
     pub async fn download_bytes(&self, url: &str) -> Result<Vec<u8>, Error> {
+        let start = Instant::now();
+        let result = self.fetch_bytes(url).await;
+        self.notify_finished("download".into(), start, result.is_ok());
+        result
+    }
+
+    async fn fetch_bytes(&self, url: &str) -> Result<Vec<u8>, Error> {
         let resp = self.http.get(url).send().await?;
         let status = resp.status().as_u16();
         let bytes = resp.bytes().await?;
-        if status < 200 || status >= 300 {
+        if !(200..300).contains(&status) {
             return Err(Error::Api {
                 status,
                 message: format!("failed to download: HTTP {}", status),
@@ -153,8 +362,14 @@ impl Client {
         Ok(bytes.to_vec())
     }
 
-    async fn handle_response(&self, resp: reqwest::Response) -> Result<Value, Error> {
+    async fn handle_response(&self, api: &str, path: &str, resp: reqwest::Response) -> Result<Value, Error> {
         let status = resp.status().as_u16();
+        let url = resp.url().to_string();
+        let content_type = resp
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_string);
 
         // Read body with size limit
         let bytes = resp.bytes().await?;
@@ -165,22 +380,253 @@ impl Client {
             });
         }
 
-        if status < 200 || status >= 300 {
-            let body_str = String::from_utf8_lossy(&bytes).to_string();
-            return Err(Error::Api {
-                status,
-                message: body_str,
-            });
+        if !(200..300).contains(&status) {
+            // An HTML error page means something other than the API answered
+            let message = unexpected_body_message(&url, content_type.as_deref(), &bytes)
+                .filter(|m| m.contains("HTML"))
+                .unwrap_or_else(|| api_error_message(api, status, &String::from_utf8_lossy(&bytes)));
+            return Err(Error::Api { status, message });
         }
 
-        // Empty body is valid for some endpoints (photo redirect)
-        if bytes.is_empty() {
+        // Empty body is valid for the photo media endpoint (redirect)
+        if bytes.is_empty() && path.ends_with("/media") {
             return Ok(Value::Null);
         }
 
         serde_json::from_slice(&bytes).map_err(|e| Error::Api {
             status,
-            message: format!("failed to parse JSON response: {}", e),
+            message: unexpected_body_message(&url, content_type.as_deref(), &bytes)
+                .unwrap_or_else(|| format!("failed to parse JSON response: {}", e)),
         })
     }
 }
+
+fn build_http(
+    timeout: Duration,
+    pool: PoolSettings,
+    connections: &Arc<ConnectionStats>,
+) -> reqwest::Result<reqwest::Client> {
+    reqwest::Client::builder()
+        .timeout(timeout)
+        .pool_idle_timeout(pool.idle_timeout)
+        .pool_max_idle_per_host(pool.max_idle_per_host)
+        .tcp_keepalive(pool.tcp_keepalive)
+        .connector_layer(CountConnections(connections.clone()))
+        .build()
+}
+
+/// Removes a request from `Client::in_flight` when it completes or is dropped
+struct InFlight<'a> {
+    client: &'a Client,
+    key: String,
+}
+
+impl Drop for InFlight<'_> {
+    fn drop(&mut self) {
+        let mut in_flight = self.client.in_flight.lock().unwrap_or_else(|e| e.into_inner());
+        in_flight.remove(&self.key);
+    }
+}
+
+/// Short human label for an endpoint path, e.g. "searchText", "details", "photo media"
+fn endpoint_label(path: &str) -> String {
+    if path.ends_with("/media") {
+        "photo media".into()
+    } else if let Some((_, method)) = path.rsplit_once(':') {
+        method.into()
+    } else if path.starts_with("/places/") {
+        "details".into()
+    } else {
+        path.into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::AtomicUsize;
+
+    use serde_json::json;
+
+    use super::*;
+    use crate::api::mock::MockApi;
+    use crate::api::types::{AutocompleteRequest, Circle, DetailsRequest, LatLng};
+
+    const PLACE_ID: &str = "ChIJ0000000000000001";
+
+    fn details(place_id: &str) -> DetailsRequest {
+        DetailsRequest {
+            place_id: place_id.into(),
+            include_reviews: false,
+            include_photos: false,
+            language: None,
+            region: None,
+        }
+    }
+
+    /// A client against `api` and the number of coalesced events it reported
+    fn client(api: &MockApi) -> (Client, Arc<AtomicUsize>) {
+        let coalesced = Arc::new(AtomicUsize::new(0));
+        let counter = coalesced.clone();
+        let client = Client::new("test-key".into())
+            .unwrap()
+            .with_places_base_url(api.url())
+            .with_observer(move |event| {
+                if let ClientEvent::Coalesced { .. } = event {
+                    counter.fetch_add(1, Ordering::SeqCst);
+                }
+            });
+        (client, coalesced)
+    }
+
+    #[tokio::test]
+    async fn identical_concurrent_requests_hit_the_api_once() {
+        let api = MockApi::start(Duration::from_millis(200), |_| {
+            (200, json!({ "id": PLACE_ID, "displayName": { "text": "Cafe" } }))
+        })
+        .await;
+        let (client, coalesced) = client(&api);
+
+        let req = details(PLACE_ID);
+        let results = futures::future::join_all((0..10).map(|_| client.details(&req))).await;
+
+        assert_eq!(api.hits(), 1);
+        assert_eq!(coalesced.load(Ordering::SeqCst), 9);
+        for result in results {
+            assert_eq!(result.unwrap().id, PLACE_ID);
+        }
+    }
+
+    #[tokio::test]
+    async fn different_requests_are_not_coalesced() {
+        let api = MockApi::start(Duration::from_millis(100), |seen| {
+            let id = seen.path.trim_start_matches("/places/").to_string();
+            (200, json!({ "id": id }))
+        })
+        .await;
+        let (client, coalesced) = client(&api);
+
+        let (first, second) = (details(PLACE_ID), details("ChIJ0000000000000002"));
+        let (a, b) = tokio::join!(client.details(&first), client.details(&second));
+
+        assert_eq!(api.hits(), 2);
+        assert_eq!(coalesced.load(Ordering::SeqCst), 0);
+        assert_eq!(a.unwrap().id, PLACE_ID);
+        assert_eq!(b.unwrap().id, "ChIJ0000000000000002");
+    }
+
+    #[tokio::test]
+    async fn waiters_get_the_same_network_error() {
+        let api = MockApi::start(Duration::from_secs(5), |_| (200, json!({}))).await;
+        let (client, _) = client(&api);
+        let client = client.with_timeout(Duration::from_millis(300));
+
+        let req = details(PLACE_ID);
+        let results = futures::future::join_all((0..10).map(|_| client.details(&req))).await;
+
+        assert_eq!(api.hits(), 1);
+        for result in results {
+            match result {
+                Err(Error::Http(e)) => assert!(e.is_timeout()),
+                other => panic!("expected a timeout, got {:?}", other),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn waiters_get_the_same_api_error() {
+        let api = MockApi::start(Duration::from_millis(100), |_| {
+            (429, json!({ "error": { "message": "quota exceeded" } }))
+        })
+        .await;
+        let (client, _) = client(&api);
+
+        let req = details(PLACE_ID);
+        let results = futures::future::join_all((0..3).map(|_| client.details(&req))).await;
+
+        assert_eq!(api.hits(), 1);
+        for result in results {
+            assert!(matches!(result, Err(Error::Api { status: 429, .. })));
+        }
+    }
+
+    fn autocomplete(session_token: &str, latitude: f64) -> AutocompleteRequest {
+        AutocompleteRequest {
+            input: "caf".into(),
+            session_token: Some(session_token.into()),
+            location: Some(Circle {
+                center: LatLng {
+                    latitude,
+                    longitude: 16.3738,
+                },
+                radius: 1000.0,
+            }),
+            limit: None,
+            language: None,
+            region: None,
+            included_primary_types: Vec::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn equivalent_requests_in_one_session_are_coalesced() {
+        let api = MockApi::start(Duration::from_millis(200), |_| (200, json!({ "suggestions": [] }))).await;
+        let (client, coalesced) = client(&api);
+
+        // 3 m apart, so the same canonical request
+        let (first, second) = (autocomplete("session-a", 48.20821), autocomplete("session-a", 48.20824));
+        let (a, b) = tokio::join!(client.autocomplete(&first), client.autocomplete(&second));
+
+        assert!(a.is_ok() && b.is_ok());
+        assert_eq!(api.hits(), 1);
+        assert_eq!(coalesced.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn requests_from_different_sessions_are_not_coalesced() {
+        let api = MockApi::start(Duration::from_millis(200), |_| (200, json!({ "suggestions": [] }))).await;
+        let (client, coalesced) = client(&api);
+
+        let (first, second) = (autocomplete("session-a", 48.2082), autocomplete("session-b", 48.2082));
+        let (a, b) = tokio::join!(client.autocomplete(&first), client.autocomplete(&second));
+
+        assert!(a.is_ok() && b.is_ok());
+        assert_eq!(api.hits(), 2);
+        assert_eq!(coalesced.load(Ordering::SeqCst), 0);
+    }
+
+    /// Ten back-to-back details calls for different places
+    async fn ten_sequential_details(client: &Client) {
+        for i in 0..10 {
+            let req = details(&format!("ChIJ00000000000000{:02}", i));
+            client.details(&req).await.unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn sequential_requests_reuse_one_connection() {
+        let api = MockApi::start(Duration::ZERO, |_| (200, json!({ "id": PLACE_ID }))).await;
+        let (client, _) = client(&api);
+
+        ten_sequential_details(&client).await;
+
+        assert_eq!(api.hits(), 10);
+        assert_eq!(api.connections(), 1);
+        assert_eq!(client.connection_stats().opened(), 1);
+    }
+
+    #[tokio::test]
+    async fn without_idle_connections_every_request_connects() {
+        let api = MockApi::start(Duration::ZERO, |_| (200, json!({ "id": PLACE_ID }))).await;
+        let (client, _) = client(&api);
+        let client = client.with_pool(PoolSettings {
+            max_idle_per_host: 0,
+            ..PoolSettings::default()
+        });
+
+        ten_sequential_details(&client).await;
+
+        assert_eq!(api.hits(), 10);
+        assert_eq!(api.connections(), 10);
+        assert_eq!(client.connection_stats().opened(), 10);
+    }
+}
@@ -0,0 +1,108 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+
+use tower::{Layer, Service};
+
+/// How long an idle connection stays in the pool. Longer than reqwest's 90 s
+/// so slow sequential calls (route waypoints, `monitor`) keep their TLS session.
+pub const DEFAULT_POOL_IDLE_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Idle connections kept per host; zupo talks to at most two hosts and rarely
+/// has more than a handful of requests in flight
+pub const DEFAULT_POOL_MAX_IDLE_PER_HOST: usize = 8;
+
+/// TCP keepalive interval, so NAT and proxies don't drop pooled connections
+pub const DEFAULT_TCP_KEEPALIVE: Duration = Duration::from_secs(30);
+
+/// Connection pool settings for the HTTP client (config `network.*`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PoolSettings {
+    pub idle_timeout: Duration,
+    pub max_idle_per_host: usize,
+    /// None turns keepalive off
+    pub tcp_keepalive: Option<Duration>,
+}
+
+impl Default for PoolSettings {
+    fn default() -> Self {
+        PoolSettings {
+            idle_timeout: DEFAULT_POOL_IDLE_TIMEOUT,
+            max_idle_per_host: DEFAULT_POOL_MAX_IDLE_PER_HOST,
+            tcp_keepalive: Some(DEFAULT_TCP_KEEPALIVE),
+        }
+    }
+}
+
+/// Connections opened so far and the time spent opening them (DNS, TCP, and
+/// TLS). Requests that reuse a pooled connection add nothing here.
+#[derive(Debug, Default)]
+pub struct ConnectionStats {
+    opened: AtomicU64,
+    connect_micros: AtomicU64,
+}
+
+impl ConnectionStats {
+    pub fn opened(&self) -> u64 {
+        self.opened.load(Ordering::Relaxed)
+    }
+
+    pub fn connect_time(&self) -> Duration {
+        Duration::from_micros(self.connect_micros.load(Ordering::Relaxed))
+    }
+
+    fn record(&self, elapsed: Duration) {
+        self.opened.fetch_add(1, Ordering::Relaxed);
+        self.connect_micros
+            .fetch_add(elapsed.as_micros() as u64, Ordering::Relaxed);
+    }
+}
+
+/// Connector layer timing every new connection into `ConnectionStats`
+#[derive(Debug, Clone)]
+pub(crate) struct CountConnections(pub Arc<ConnectionStats>);
+
+impl<S> Layer<S> for CountConnections {
+    type Service = Counted<S>;
+
+    fn layer(&self, inner: S) -> Counted<S> {
+        Counted {
+            inner,
+            stats: self.0.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct Counted<S> {
+    inner: S,
+    stats: Arc<ConnectionStats>,
+}
+
+impl<S, R> Service<R> for Counted<S>
+where
+    S: Service<R>,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<S::Response, S::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), S::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, dst: R) -> Self::Future {
+        let start = Instant::now();
+        let stats = self.stats.clone();
+        let connecting = self.inner.call(dst);
+        Box::pin(async move {
+            let conn = connecting.await?;
+            stats.record(start.elapsed());
+            Ok(conn)
+        })
+    }
+}
@@ -1,16 +1,54 @@
 use super::client::Client;
 use super::errors::Error;
-use super::types::{DetailsRequest, Place};
+use super::types::{DetailsRequest, Photo, Place, ReviewsRequest};
+
+/// Field mask for `reviews`: enough to title the output, plus the reviews
+const REVIEW_FIELDS: &str = "id,displayName,rating,userRatingCount,reviews";
+
+/// Field mask for `place_photos`
+const PHOTO_FIELDS: &str = "photos";
+
+/// Bounds on place ID length; typical IDs are 27 characters, some run to hundreds
+const PLACE_ID_MIN_LEN: usize = 16;
+const PLACE_ID_MAX_LEN: usize = 1024;
+
+/// Clean up a pasted place ID: trim whitespace and quotes, and strip the
+/// `places/` prefix of the resource-name form. What remains must be
+/// URL-safe base64 (`A-Z a-z 0-9 _ -`) of a plausible length.
+pub(crate) fn normalize_place_id(raw: &str) -> Result<String, Error> {
+    let id = raw.trim_matches(|c: char| c.is_whitespace() || matches!(c, '"' | '\'' | '`'));
+    let id = id.strip_prefix("places/").unwrap_or(id);
+
+    let invalid = |problem: String| Error::Validation {
+        field: "place_id".into(),
+        message: format!(
+            "{}; place IDs look like ChIJN1t_tDeuEmsRUsoyG83frY4 (from search results)",
+            problem
+        ),
+    };
+    if id.is_empty() {
+        return Err(invalid("place_id is required".into()));
+    }
+    if let Some(c) = id
+        .chars()
+        .find(|c| !(c.is_ascii_alphanumeric() || matches!(c, '_' | '-')))
+    {
+        return Err(invalid(format!("'{}' contains {:?}, which can't appear in a place ID", id, c)));
+    }
+    if !(PLACE_ID_MIN_LEN..=PLACE_ID_MAX_LEN).contains(&id.len()) {
+        return Err(invalid(format!(
+            "'{}' is {} characters, expected {} to {}",
+            id,
+            id.len(),
+            PLACE_ID_MIN_LEN,
+            PLACE_ID_MAX_LEN
+        )));
+    }
+    Ok(id.to_string())
+}
 
 impl Client {
     pub async fn details(&self, req: &DetailsRequest) -> Result<Place, Error> {
-        if req.place_id.is_empty() {
-            return Err(Error::Validation {
-                field: "place_id".into(),
-                message: "place_id is required".into(),
-            });
-        }
-
         let mut fields = vec![
             "id",
             "displayName",
@@ -29,6 +67,7 @@ impl Client {
             "internationalPhoneNumber",
             "currentOpeningHours",
             "regularOpeningHours",
+            "utcOffsetMinutes",
             "businessStatus",
             "editorialSummary",
         ];
@@ -40,26 +79,42 @@ impl Client {
             fields.push("photos");
         }
 
-        let field_mask = fields.join(",");
-        let path = format!("/places/{}", req.place_id);
+        self.fetch_place(&req.place_id, &fields.join(","), req.language.as_deref(), req.region.as_deref())
+            .await
+    }
+
+    /// A place's name, rating, and reviews, with the narrow reviews-only mask
+    pub async fn reviews(&self, req: &ReviewsRequest) -> Result<Place, Error> {
+        self.fetch_place(&req.place_id, REVIEW_FIELDS, req.language.as_deref(), req.region.as_deref())
+            .await
+    }
+
+    /// A place's photo list (names, sizes, and authors), with a photos-only mask
+    pub async fn place_photos(&self, place_id: &str) -> Result<Vec<Photo>, Error> {
+        let place = self.fetch_place(place_id, PHOTO_FIELDS, None, None).await?;
+        Ok(place.photos.unwrap_or_default())
+    }
+
+    async fn fetch_place(
+        &self,
+        place_id: &str,
+        field_mask: &str,
+        language: Option<&str>,
+        region: Option<&str>,
+    ) -> Result<Place, Error> {
+        let place_id = normalize_place_id(place_id)?;
+        let path = format!("/places/{}", place_id);
 
         let mut query_params: Vec<(&str, &str)> = Vec::new();
-        let lang_val;
-        let region_val;
-        if let Some(ref lang) = req.language {
-            lang_val = lang.clone();
-            query_params.push(("languageCode", &lang_val));
+        if let Some(lang) = language {
+            query_params.push(("languageCode", lang));
         }
-        if let Some(ref region) = req.region {
-            region_val = region.clone();
-            query_params.push(("regionCode", &region_val));
+        if let Some(region) = region {
+            query_params.push(("regionCode", region));
         }
 
-        let result = self.places_get(&path, &field_mask, &query_params).await?;
+        let result = self.places_get(&path, field_mask, &query_params).await?;
 
-        serde_json::from_value(result).map_err(|e| Error::Api {
-            status: 0,
-            message: format!("failed to parse details response: {}", e),
-        })
+        self.parse_place_lenient(result, "details")
     }
 }
@@ -1,6 +1,8 @@
 use std::fmt;
+use std::sync::Arc;
 
-#[derive(Debug)]
+/// Cloneable, so a coalesced request can hand every waiter the same error
+#[derive(Debug, Clone)]
 pub enum Error {
     /// API key is missing
     MissingApiKey,
@@ -8,8 +10,9 @@ pub enum Error {
     Validation { field: String, message: String },
     /// HTTP/API error with status code and body
     Api { status: u16, message: String },
-    /// Network or other reqwest error
-    Http(reqwest::Error),
+    /// Network or other reqwest error; shared rather than copied, since
+    /// reqwest errors can't be cloned
+    Http(Arc<reqwest::Error>),
 }
 
 impl fmt::Display for Error {
@@ -33,7 +36,7 @@ impl fmt::Display for Error {
 impl std::error::Error for Error {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
         match self {
-            Error::Http(e) => Some(e),
+            Error::Http(e) => Some(e.as_ref()),
             _ => None,
         }
     }
@@ -41,6 +44,312 @@ impl std::error::Error for Error {
 
 impl From<reqwest::Error> for Error {
     fn from(e: reqwest::Error) -> Self {
-        Error::Http(e)
+        Error::Http(Arc::new(e))
+    }
+}
+
+/// Message for a non-2xx response from `api` ("places" or "routes"): the raw
+/// body, unless it is one of the well-known 403s a key gets when the API is
+/// not enabled for its project or the key is restricted from calling it
+pub(crate) fn api_error_message(api: &str, status: u16, body: &str) -> String {
+    if status == 403 {
+        if let Some(hint) = access_hint(api, body) {
+            return hint;
+        }
+    }
+    body.to_string()
+}
+
+/// Why a 403 body says the key can't reach the API
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum AccessProblem {
+    /// The API is not enabled in the key's project (`SERVICE_DISABLED`)
+    Disabled,
+    /// The key's API restrictions exclude this API (`API_KEY_SERVICE_BLOCKED`,
+    /// "not authorized to use this service")
+    KeyRestricted,
+}
+
+fn access_hint(api: &str, body: &str) -> Option<String> {
+    let json: Option<serde_json::Value> = serde_json::from_str(body).ok();
+    let error = json.as_ref().and_then(|v| v.get("error"));
+    let message = error
+        .and_then(|e| e.get("message"))
+        .and_then(|m| m.as_str())
+        .unwrap_or(body);
+    let details = error
+        .and_then(|e| e.get("details"))
+        .and_then(|d| d.as_array())
+        .map(Vec::as_slice)
+        .unwrap_or_default();
+    let has_reason = |reason: &str| {
+        details
+            .iter()
+            .any(|d| d.get("reason").and_then(|r| r.as_str()) == Some(reason))
+    };
+
+    let problem = if has_reason("SERVICE_DISABLED")
+        || message.contains("has not been used in project")
+        || message.contains("it is disabled")
+    {
+        AccessProblem::Disabled
+    } else if has_reason("API_KEY_SERVICE_BLOCKED")
+        || message.contains("are blocked")
+        || message.contains("not authorized to use this service")
+    {
+        AccessProblem::KeyRestricted
+    } else {
+        return None;
+    };
+
+    let (name, service) = match api {
+        "routes" => ("Routes API", "routes.googleapis.com"),
+        _ => ("Places API (New)", "places.googleapis.com"),
+    };
+    let project = details
+        .iter()
+        .filter_map(|d| d.pointer("/metadata/consumer")?.as_str())
+        .find_map(|c| c.strip_prefix("projects/"))
+        .map(str::to_string)
+        .or_else(|| project_in_message(message));
+    let enable_url = match project {
+        Some(p) => format!("https://console.cloud.google.com/apis/library/{}?project={}", service, p),
+        None => format!("https://console.cloud.google.com/apis/library/{}", service),
+    };
+
+    Some(match problem {
+        AccessProblem::Disabled => format!(
+            "{} is not enabled for this API key's project. Enable it at {} and retry after a few minutes",
+            name, enable_url
+        ),
+        AccessProblem::KeyRestricted => format!(
+            "this API key is not allowed to call {}. Add {} to the key's API restrictions at \
+             https://console.cloud.google.com/apis/credentials, and check it is enabled at {}",
+            name, name, enable_url
+        ),
+    })
+}
+
+/// The project number in "… has not been used in project 123456 before …"
+fn project_in_message(message: &str) -> Option<String> {
+    let rest = &message[message.find("in project ")? + "in project ".len()..];
+    let id: String = rest
+        .chars()
+        .take_while(|c| c.is_ascii_alphanumeric() || *c == '-')
+        .collect();
+    (!id.is_empty()).then_some(id)
+}
+
+/// Characters of an unexpected body quoted in the error
+const BODY_SNIPPET_CHARS: usize = 60;
+
+/// Message for a body that isn't the JSON the API sends, when it looks like
+/// something else answered instead (an HTML login page or captive portal, a
+/// plain-text proxy error, or nothing at all), which usually means
+/// `--base-url` is wrong or a proxy is in the way. `url` is where the
+/// response came from, after redirects.
+pub(crate) fn unexpected_body_message(url: &str, content_type: Option<&str>, body: &[u8]) -> Option<String> {
+    let text = String::from_utf8_lossy(body);
+    let text = text.trim();
+    let content_type = content_type.unwrap_or("").to_ascii_lowercase();
+
+    if text.is_empty() {
+        return Some(format!(
+            "{} returned an empty response instead of JSON; is --base-url correct?",
+            url
+        ));
+    }
+    let start = text.get(..text.len().min(16)).unwrap_or("").to_ascii_lowercase();
+    if content_type.contains("html") || start.starts_with("<!doctype") || start.starts_with("<html") {
+        return Some(format!(
+            "{} returned an HTML page instead of JSON; is --base-url correct, or are you behind a captive portal or login proxy?",
+            url
+        ));
+    }
+    if text.starts_with(['{', '[']) || content_type.contains("json") {
+        return None;
+    }
+    let snippet: String = crate::format::sanitize_display(text)
+        .chars()
+        .take(BODY_SNIPPET_CHARS)
+        .collect();
+    let kind = match content_type.split(';').next().map(str::trim) {
+        Some("") | None => "a non-JSON response".to_string(),
+        Some(kind) => kind.to_string(),
+    };
+    Some(format!(
+        "{} returned {} instead of JSON (\"{}\"); is --base-url correct?",
+        url, kind, snippet
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Places API (New) never enabled in the key's project
+    const SERVICE_DISABLED: &str = r#"{
+      "error": {
+        "code": 403,
+        "message": "Places API (New) has not been used in project 123456789012 before or it is disabled. Enable it by visiting https://console.developers.google.com/apis/api/places.googleapis.com/overview?project=123456789012 then retry.",
+        "status": "PERMISSION_DENIED",
+        "details": [
+          {
+            "@type": "type.googleapis.com/google.rpc.ErrorInfo",
+            "reason": "SERVICE_DISABLED",
+            "domain": "googleapis.com",
+            "metadata": {
+              "consumer": "projects/123456789012",
+              "service": "places.googleapis.com"
+            }
+          }
+        ]
+      }
+    }"#;
+
+    /// A key whose API restrictions leave out the API
+    const KEY_SERVICE_BLOCKED: &str = r#"{
+      "error": {
+        "code": 403,
+        "message": "Requests to this API places.googleapis.com method google.maps.places.v1.Places.SearchText are blocked.",
+        "status": "PERMISSION_DENIED",
+        "details": [
+          {
+            "@type": "type.googleapis.com/google.rpc.ErrorInfo",
+            "reason": "API_KEY_SERVICE_BLOCKED",
+            "domain": "googleapis.com",
+            "metadata": {
+              "consumer": "projects/987654321098",
+              "service": "places.googleapis.com"
+            }
+          }
+        ]
+      }
+    }"#;
+
+    /// The older wording, without structured details
+    const NOT_AUTHORIZED: &str = r#"{
+      "error": {
+        "code": 403,
+        "message": "This API key is not authorized to use this service or API.",
+        "status": "PERMISSION_DENIED"
+      }
+    }"#;
+
+    #[test]
+    fn disabled_api_names_the_api_and_project() {
+        assert_eq!(
+            api_error_message("places", 403, SERVICE_DISABLED),
+            "Places API (New) is not enabled for this API key's project. Enable it at \
+             https://console.cloud.google.com/apis/library/places.googleapis.com?project=123456789012 \
+             and retry after a few minutes"
+        );
+    }
+
+    #[test]
+    fn disabled_api_project_can_come_from_the_message() {
+        let body = r#"{"error":{"code":403,"message":"Routes API has not been used in project my-proj-42 before or it is disabled.","status":"PERMISSION_DENIED"}}"#;
+        let message = api_error_message("routes", 403, body);
+        assert!(message.starts_with("Routes API is not enabled"), "{}", message);
+        assert!(message.contains("library/routes.googleapis.com?project=my-proj-42"), "{}", message);
+    }
+
+    #[test]
+    fn blocked_key_points_at_the_key_restrictions() {
+        assert_eq!(
+            api_error_message("places", 403, KEY_SERVICE_BLOCKED),
+            "this API key is not allowed to call Places API (New). Add Places API (New) to the key's API \
+             restrictions at https://console.cloud.google.com/apis/credentials, and check it is enabled at \
+             https://console.cloud.google.com/apis/library/places.googleapis.com?project=987654321098"
+        );
+    }
+
+    #[test]
+    fn not_authorized_key_without_project() {
+        assert_eq!(
+            api_error_message("places", 403, NOT_AUTHORIZED),
+            "this API key is not allowed to call Places API (New). Add Places API (New) to the key's API \
+             restrictions at https://console.cloud.google.com/apis/credentials, and check it is enabled at \
+             https://console.cloud.google.com/apis/library/places.googleapis.com"
+        );
+    }
+
+    #[test]
+    fn routes_base_url_names_the_routes_api() {
+        for body in [SERVICE_DISABLED, KEY_SERVICE_BLOCKED, NOT_AUTHORIZED] {
+            let message = api_error_message("routes", 403, body);
+            assert!(message.contains("Routes API"), "{}", message);
+            assert!(message.contains("library/routes.googleapis.com"), "{}", message);
+            assert!(!message.contains("Places API"), "{}", message);
+        }
+    }
+
+    #[test]
+    fn plain_text_403_is_recognized() {
+        let body = "This API key is not authorized to use this service or API.";
+        let message = api_error_message("places", 403, body);
+        assert!(message.starts_with("this API key is not allowed to call Places API (New)"), "{}", message);
+    }
+
+    #[test]
+    fn other_errors_keep_the_raw_body() {
+        let denied = r#"{"error":{"code":403,"message":"The caller does not have permission","status":"PERMISSION_DENIED"}}"#;
+        assert_eq!(api_error_message("places", 403, denied), denied);
+        // Only 403s are rewritten
+        assert_eq!(api_error_message("places", 400, SERVICE_DISABLED), SERVICE_DISABLED);
+    }
+
+    const URL: &str = "http://localhost:8080/v1/places:searchText";
+
+    fn unexpected(content_type: Option<&str>, body: &str) -> Option<String> {
+        unexpected_body_message(URL, content_type, body.as_bytes())
+    }
+
+    #[test]
+    fn html_page_suggests_base_url_or_portal() {
+        let expected = "http://localhost:8080/v1/places:searchText returned an HTML page instead of JSON; \
+                        is --base-url correct, or are you behind a captive portal or login proxy?";
+        let page = "<html><head><title>Sign in</title></head><body>SSO</body></html>";
+        assert_eq!(unexpected(Some("text/html; charset=utf-8"), page).as_deref(), Some(expected));
+        // Recognized by its first bytes when the Content-Type is missing or wrong
+        assert_eq!(unexpected(None, "\n  <!DOCTYPE html><html></html>").as_deref(), Some(expected));
+        assert_eq!(unexpected(Some("application/json"), "<HTML>").as_deref(), Some(expected));
+    }
+
+    #[test]
+    fn empty_body() {
+        assert_eq!(
+            unexpected(Some("application/json"), "  \n").as_deref(),
+            Some(
+                "http://localhost:8080/v1/places:searchText returned an empty response instead of JSON; \
+                 is --base-url correct?"
+            )
+        );
+    }
+
+    #[test]
+    fn plain_text_body_is_quoted() {
+        assert_eq!(
+            unexpected(Some("text/plain"), "Bad Gateway: upstream unreachable\n").as_deref(),
+            Some(
+                "http://localhost:8080/v1/places:searchText returned text/plain instead of JSON \
+                 (\"Bad Gateway: upstream unreachable\"); is --base-url correct?"
+            )
+        );
+    }
+
+    #[test]
+    fn long_text_is_cut_and_untyped_text_is_named_generically() {
+        let message = unexpected(None, &"x".repeat(500)).unwrap();
+        assert!(message.contains("returned a non-JSON response instead of JSON"), "{}", message);
+        assert!(message.contains(&format!("(\"{}\")", "x".repeat(BODY_SNIPPET_CHARS))), "{}", message);
+        assert!(!message.contains(&"x".repeat(BODY_SNIPPET_CHARS + 1)), "{}", message);
+    }
+
+    #[test]
+    fn json_bodies_are_left_to_the_json_parser() {
+        assert_eq!(unexpected(Some("application/json"), r#"{"places": [}"#), None);
+        assert_eq!(unexpected(None, "[1, 2"), None);
+        assert_eq!(unexpected(Some("application/json"), "not json"), None);
     }
 }
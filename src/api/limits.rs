@@ -0,0 +1,94 @@
+use std::fmt;
+
+/// searchText: results per request
+pub const MAX_SEARCH_RESULTS: u32 = 20;
+/// Nearby `--limit` ceiling; above one request's 20 results the circle is
+/// split into rings (see `Client::nearby_search_rings`)
+pub const MAX_NEARBY_LIMIT: u32 = 60;
+/// searchText when resolving an address
+pub const MAX_RESOLVE_RESULTS: u32 = 10;
+/// autocomplete returns at most five predictions
+pub const MAX_AUTOCOMPLETE_SUGGESTIONS: u32 = 5;
+
+/// A requested limit was lowered to the endpoint's maximum
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LimitWarning {
+    pub requested: u32,
+    pub max: u32,
+}
+
+impl fmt::Display for LimitWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "requested {}, the maximum is {}; showing at most {}",
+            self.requested, self.max, self.max
+        )
+    }
+}
+
+/// Clamp `requested` to `endpoint_max`, with a warning when it was lowered
+pub fn clamp_limit(requested: u32, endpoint_max: u32) -> (u32, Option<LimitWarning>) {
+    if requested > endpoint_max {
+        let warning = LimitWarning {
+            requested,
+            max: endpoint_max,
+        };
+        (endpoint_max, Some(warning))
+    } else {
+        (requested, None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ENDPOINT_MAXIMUMS: [u32; 4] = [
+        MAX_SEARCH_RESULTS,
+        MAX_NEARBY_LIMIT,
+        MAX_RESOLVE_RESULTS,
+        MAX_AUTOCOMPLETE_SUGGESTIONS,
+    ];
+
+    #[test]
+    fn limits_up_to_the_maximum_pass_unchanged() {
+        for max in ENDPOINT_MAXIMUMS {
+            for requested in [0, 1, max - 1, max] {
+                assert_eq!(clamp_limit(requested, max), (requested, None), "{} of {}", requested, max);
+            }
+        }
+    }
+
+    #[test]
+    fn limits_above_the_maximum_are_lowered_with_a_warning() {
+        for max in ENDPOINT_MAXIMUMS {
+            for requested in [max + 1, 100, u32::MAX] {
+                let (limit, warning) = clamp_limit(requested, max);
+                assert_eq!(limit, max);
+                assert_eq!(warning, Some(LimitWarning { requested, max }));
+            }
+        }
+    }
+
+    #[test]
+    fn warning_names_the_request_and_each_maximum() {
+        let text = |requested, max| clamp_limit(requested, max).1.unwrap().to_string();
+        assert_eq!(
+            text(25, MAX_SEARCH_RESULTS),
+            "requested 25, the maximum is 20; showing at most 20"
+        );
+        assert_eq!(
+            text(100, MAX_NEARBY_LIMIT),
+            "requested 100, the maximum is 60; showing at most 60"
+        );
+        assert_eq!(
+            text(11, MAX_RESOLVE_RESULTS),
+            "requested 11, the maximum is 10; showing at most 10"
+        );
+        assert_eq!(
+            text(8, MAX_AUTOCOMPLETE_SUGGESTIONS),
+            "requested 8, the maximum is 5; showing at most 5"
+        );
+    }
+}
@@ -0,0 +1,118 @@
+use std::convert::Infallible;
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use http_body_util::{BodyExt, Full};
+use hyper::body::Bytes;
+use hyper::server::conn::http1;
+use hyper::service::service_fn;
+use hyper::{Response, StatusCode};
+use hyper_util::rt::TokioIo;
+use serde_json::Value;
+use tokio::net::TcpListener;
+
+/// One request as the mock saw it
+#[derive(Debug, Clone)]
+pub struct Seen {
+    pub path: String,
+    /// The `X-Goog-FieldMask` header
+    pub field_mask: Option<String>,
+    /// The JSON body, or null when there was none
+    pub body: Value,
+}
+
+type Responder = dyn Fn(&Seen) -> (u16, Value) + Send + Sync;
+
+#[derive(Default)]
+struct Counters {
+    connections: AtomicUsize,
+    requests: Mutex<Vec<Seen>>,
+}
+
+/// Stand-in for the Places and Routes APIs on 127.0.0.1, for client tests.
+/// Keeps connections alive like the real API and counts connections and
+/// requests.
+/// Stops with the test's runtime.
+pub struct MockApi {
+    pub addr: SocketAddr,
+    counters: Arc<Counters>,
+}
+
+impl MockApi {
+    /// Answer every request with `respond`'s status and JSON after `delay`
+    pub async fn start(delay: Duration, respond: impl Fn(&Seen) -> (u16, Value) + Send + Sync + 'static) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let counters = Arc::new(Counters::default());
+        let respond: Arc<Responder> = Arc::new(respond);
+
+        let shared = counters.clone();
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                shared.connections.fetch_add(1, Ordering::SeqCst);
+                let counters = shared.clone();
+                let respond = respond.clone();
+                let service = service_fn(move |req: hyper::Request<hyper::body::Incoming>| {
+                    let counters = counters.clone();
+                    let respond = respond.clone();
+                    async move {
+                        let path = req.uri().path().to_string();
+                        let field_mask = req
+                            .headers()
+                            .get("x-goog-fieldmask")
+                            .and_then(|v| v.to_str().ok())
+                            .map(str::to_string);
+                        let bytes = req.into_body().collect().await.map(|b| b.to_bytes()).unwrap_or_default();
+                        let seen = Seen {
+                            path,
+                            field_mask,
+                            body: serde_json::from_slice(&bytes).unwrap_or(Value::Null),
+                        };
+                        counters.requests.lock().unwrap().push(seen.clone());
+                        tokio::time::sleep(delay).await;
+                        let (status, body) = respond(&seen);
+                        let response = Response::builder()
+                            .status(StatusCode::from_u16(status).unwrap())
+                            .header("content-type", "application/json")
+                            .body(Full::new(Bytes::from(body.to_string())))
+                            .unwrap();
+                        Ok::<_, Infallible>(response)
+                    }
+                });
+                tokio::spawn(http1::Builder::new().serve_connection(TokioIo::new(stream), service));
+            }
+        });
+
+        MockApi { addr, counters }
+    }
+
+    pub fn url(&self) -> String {
+        format!("http://{}", self.addr)
+    }
+
+    /// TCP connections accepted so far
+    pub fn connections(&self) -> usize {
+        self.counters.connections.load(Ordering::SeqCst)
+    }
+
+    pub fn hits(&self) -> usize {
+        self.counters.requests.lock().unwrap().len()
+    }
+
+    /// Request paths in arrival order
+    pub fn paths(&self) -> Vec<String> {
+        self.counters.requests.lock().unwrap().iter().map(|r| r.path.clone()).collect()
+    }
+
+    /// Request bodies in arrival order
+    pub fn bodies(&self) -> Vec<Value> {
+        self.counters.requests.lock().unwrap().iter().map(|r| r.body.clone()).collect()
+    }
+
+    /// Every request in arrival order
+    pub fn requests(&self) -> Vec<Seen> {
+        self.counters.requests.lock().unwrap().clone()
+    }
+}
@@ -1,11 +1,21 @@
 pub mod client;
+pub mod connections;
 pub mod errors;
+pub mod limits;
+pub mod polyline;
+pub mod throttle;
 pub mod types;
 
 mod autocomplete;
+mod canonical;
 mod details;
+#[cfg(test)]
+pub(crate) mod mock;
 mod nearby;
+pub(crate) mod parse;
 mod photo;
 mod resolve;
 mod route;
 mod search;
+
+pub(crate) use details::normalize_place_id;
@@ -1,75 +1,456 @@
-use serde_json::json;
+use std::collections::HashSet;
+
+use serde_json::{json, Value};
 
 use super::client::Client;
 use super::errors::Error;
-use super::types::{NearbySearchRequest, NearbySearchResponse};
+use super::limits::MAX_NEARBY_LIMIT;
+use super::types::{
+    validate_coords, validate_radius, Circle, NearbySearchRequest, NearbySearchResponse, Place,
+    PlaceCount, RingSearch,
+};
+use crate::place_types;
 
 const NEARBY_FIELD_MASK: &str = "places.id,places.displayName,places.formattedAddress,\
 places.shortFormattedAddress,places.types,places.primaryType,places.primaryTypeDisplayName,\
 places.location,places.rating,places.userRatingCount,places.priceLevel,\
-places.websiteUri,places.googleMapsUri,places.businessStatus,places.editorialSummary";
+places.websiteUri,places.googleMapsUri,places.businessStatus,places.editorialSummary,\
+places.currentOpeningHours,places.utcOffsetMinutes,places.postalAddress";
+
+/// Count-only mask; searchNearby has no pagination, so IDs are enough
+const NEARBY_COUNT_FIELD_MASK: &str = "places.id";
+
+/// searchNearby returns at most 20 results and cannot page
+const NEARBY_MAX_RESULTS: u32 = 20;
 
 impl Client {
     pub async fn nearby_search(
         &self,
         req: &NearbySearchRequest,
     ) -> Result<NearbySearchResponse, Error> {
-        validate_coords(req.lat, req.lng)?;
-        if req.radius <= 0.0 {
-            return Err(Error::Validation {
-                field: "radius".into(),
-                message: "radius must be positive".into(),
+        validate_nearby(req)?;
+
+        let body = build_nearby_body(req);
+
+        let result = self
+            .places_post("/places:searchNearby", NEARBY_FIELD_MASK, &body)
+            .await?;
+
+        let places = self.parse_places_lenient(result, "nearby")?;
+        Ok(NearbySearchResponse { places })
+    }
+
+    /// Search for up to `req.limit` places (at most `MAX_NEARBY_LIMIT`). Limits
+    /// above one request's 20 split the circle into equal-area rings: the inner
+    /// circle is searched first, then each larger circle, dropping places already
+    /// seen. This is approximate: popular places near the center can fill a larger
+    /// circle's 20 slots, so fewer than `limit` places may come back.
+    pub async fn nearby_search_rings(&self, req: &NearbySearchRequest) -> Result<RingSearch, Error> {
+        validate_nearby(req)?;
+
+        let limit = req.limit.unwrap_or(NEARBY_MAX_RESULTS) as usize;
+        let rings = limit.div_ceil(NEARBY_MAX_RESULTS as usize);
+        if rings <= 1 {
+            let response = self.nearby_search(req).await?;
+            let added = vec![response.places.len()];
+            return Ok(RingSearch {
+                response,
+                radii: vec![req.radius],
+                added,
             });
         }
 
-        let mut body = json!({
-            "locationRestriction": {
-                "circle": {
-                    "center": { "latitude": req.lat, "longitude": req.lng },
-                    "radius": req.radius,
-                }
-            },
+        let radii = ring_radii(req.radius, rings);
+        let mut seen = HashSet::new();
+        let mut places = Vec::new();
+        let mut added = Vec::with_capacity(rings);
+        for &radius in &radii {
+            let ring = NearbySearchRequest {
+                radius,
+                limit: Some(NEARBY_MAX_RESULTS),
+                ..req.clone()
+            };
+            let resp = self.nearby_search(&ring).await?;
+            let before = places.len();
+            places.extend(resp.places.into_iter().filter(|p| seen.insert(p.id.clone())));
+            added.push(places.len() - before);
+        }
+        places.truncate(limit);
+
+        Ok(RingSearch {
+            response: NearbySearchResponse { places },
+            radii,
+            added,
+        })
+    }
+
+    /// One `nearby_search_rings` per circle, all in flight at once; results
+    /// come back in the order of `circles`. Any failed search fails the whole call.
+    pub async fn nearby_search_at(
+        &self,
+        req: &NearbySearchRequest,
+        circles: &[Circle],
+    ) -> Result<Vec<Vec<Place>>, Error> {
+        validate_nearby(req)?;
+
+        let searches = circles.iter().map(|circle| {
+            let at = NearbySearchRequest {
+                lat: circle.center.latitude,
+                lng: circle.center.longitude,
+                radius: circle.radius,
+                ..req.clone()
+            };
+            async move { self.nearby_search_rings(&at).await.map(|r| r.response.places) }
         });
+        futures::future::try_join_all(searches).await
+    }
 
-        if !req.included_types.is_empty() {
-            body["includedTypes"] = json!(req.included_types);
-        }
-        if !req.excluded_types.is_empty() {
-            body["excludedTypes"] = json!(req.excluded_types);
-        }
-        if let Some(limit) = req.limit {
-            body["maxResultCount"] = json!(limit.min(20));
-        }
-        if let Some(ref lang) = req.language {
-            body["languageCode"] = json!(lang);
-        }
-        if let Some(ref region) = req.region {
-            body["regionCode"] = json!(region);
-        }
+    /// Count places in the circle (capped at the API's 20-result maximum)
+    pub async fn nearby_count(&self, req: &NearbySearchRequest) -> Result<PlaceCount, Error> {
+        validate_nearby(req)?;
+
+        let mut body = build_nearby_body(req);
+        body["maxResultCount"] = json!(NEARBY_MAX_RESULTS);
 
         let result = self
-            .places_post("/places:searchNearby", NEARBY_FIELD_MASK, &body)
+            .places_post("/places:searchNearby", NEARBY_COUNT_FIELD_MASK, &body)
             .await?;
+        let count = result
+            .get("places")
+            .and_then(Value::as_array)
+            .map_or(0, Vec::len);
 
-        serde_json::from_value(result).map_err(|e| Error::Api {
-            status: 0,
-            message: format!("failed to parse nearby response: {}", e),
+        Ok(PlaceCount {
+            count,
+            capped: count >= NEARBY_MAX_RESULTS as usize,
+            field_mask: NEARBY_COUNT_FIELD_MASK,
+            requests: 1,
         })
     }
 }
 
-fn validate_coords(lat: f64, lng: f64) -> Result<(), Error> {
-    if !(-90.0..=90.0).contains(&lat) {
+fn validate_nearby(req: &NearbySearchRequest) -> Result<(), Error> {
+    validate_coords(req.lat, req.lng)?;
+    validate_radius(req.radius)?;
+    if req.limit.is_some_and(|l| l == 0 || l > MAX_NEARBY_LIMIT) {
         return Err(Error::Validation {
-            field: "lat".into(),
-            message: "latitude must be between -90 and 90".into(),
+            field: "limit".into(),
+            message: format!("limit must be between 1 and {}", MAX_NEARBY_LIMIT),
         });
     }
-    if !(-180.0..=180.0).contains(&lng) {
-        return Err(Error::Validation {
-            field: "lng".into(),
-            message: "longitude must be between -180 and 180".into(),
-        });
+    validate_types("include-primary-type", &req.included_primary_types)?;
+    validate_types("exclude-primary-type", &req.excluded_primary_types)?;
+    Ok(())
+}
+
+/// Build the JSON body for POST /places:searchNearby
+pub(crate) fn build_nearby_body(req: &NearbySearchRequest) -> Value {
+    let mut body = json!({
+        "locationRestriction": {
+            "circle": {
+                "center": { "latitude": req.lat, "longitude": req.lng },
+                "radius": req.radius,
+            }
+        },
+        "rankPreference": req.rank_preference.as_api_str(),
+    });
+
+    if !req.included_types.is_empty() {
+        body["includedTypes"] = json!(req.included_types);
+    }
+    if !req.excluded_types.is_empty() {
+        body["excludedTypes"] = json!(req.excluded_types);
+    }
+    if !req.included_primary_types.is_empty() {
+        body["includedPrimaryTypes"] = json!(req.included_primary_types);
+    }
+    if !req.excluded_primary_types.is_empty() {
+        body["excludedPrimaryTypes"] = json!(req.excluded_primary_types);
+    }
+    if let Some(limit) = req.limit {
+        body["maxResultCount"] = json!(limit.min(NEARBY_MAX_RESULTS));
+    }
+    if let Some(ref lang) = req.language {
+        body["languageCode"] = json!(lang);
+    }
+    if let Some(ref region) = req.region {
+        body["regionCode"] = json!(region);
+    }
+
+    body
+}
+
+/// Radii of `rings` concentric circles splitting a circle of `radius` into
+/// equal-area rings: r_k = radius * sqrt(k / rings), so the last is `radius`
+fn ring_radii(radius: f64, rings: usize) -> Vec<f64> {
+    (1..=rings)
+        .map(|k| radius * (k as f64 / rings as f64).sqrt())
+        .collect()
+}
+
+fn validate_types(field: &str, types: &[String]) -> Result<(), Error> {
+    for t in types {
+        if !place_types::is_known_type(t) {
+            let hint = place_types::filter_types(&t.replace('_', " "), 3);
+            let message = if hint.is_empty() {
+                format!("unknown place type '{}'", t)
+            } else {
+                format!("unknown place type '{}' (did you mean: {}?)", t, hint.join(", "))
+            };
+            return Err(Error::Validation {
+                field: field.into(),
+                message,
+            });
+        }
     }
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::api::mock::MockApi;
+    use crate::api::types::NearbyRankPreference;
+
+    fn request(fields: Value) -> NearbySearchRequest {
+        serde_json::from_value(fields).unwrap()
+    }
+
+    /// `{"places": [...]}` with places `p<from>` up to but excluding `p<to>`
+    fn places(from: usize, to: usize) -> Value {
+        let places: Vec<Value> = (from..to)
+            .map(|i| json!({ "id": format!("p{}", i), "displayName": { "text": format!("Place {}", i) } }))
+            .collect();
+        json!({ "places": places })
+    }
+
+    /// The field and message of `validate_nearby`'s error for `fields`
+    fn rejection(fields: Value) -> (String, String) {
+        match validate_nearby(&request(fields)) {
+            Err(Error::Validation { field, message }) => (field, message),
+            other => panic!("expected a validation error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn radius_above_50_km_is_rejected() {
+        assert_eq!(
+            rejection(json!({ "lat": 48.2, "lng": 16.37, "radius": 50_001.0 })),
+            ("radius".into(), "radius 50001 m exceeds the maximum of 50000 m".into())
+        );
+        assert!(validate_nearby(&request(json!({ "lat": 48.2, "lng": 16.37, "radius": 50_000.0 }))).is_ok());
+    }
+
+    #[test]
+    fn limit_must_be_between_one_and_the_maximum() {
+        for limit in [0, MAX_NEARBY_LIMIT + 1] {
+            assert_eq!(
+                rejection(json!({ "lat": 48.2, "lng": 16.37, "radius": 500.0, "limit": limit })),
+                ("limit".into(), "limit must be between 1 and 60".into())
+            );
+        }
+        for limit in [1, MAX_NEARBY_LIMIT] {
+            let req = request(json!({ "lat": 48.2, "lng": 16.37, "radius": 500.0, "limit": limit }));
+            assert!(validate_nearby(&req).is_ok(), "limit {}", limit);
+        }
+    }
+
+    #[tokio::test]
+    async fn invalid_requests_are_not_sent() {
+        let api = MockApi::start(Duration::ZERO, |_| (200, places(0, 1))).await;
+        let client = Client::new("test-key".into()).unwrap().with_places_base_url(api.url());
+
+        let zero = request(json!({ "lat": 48.2, "lng": 16.37, "radius": 500.0, "limit": 0 }));
+        assert!(matches!(client.nearby_search(&zero).await, Err(Error::Validation { .. })));
+        assert!(matches!(client.nearby_search_rings(&zero).await, Err(Error::Validation { .. })));
+        let wide = request(json!({ "lat": 48.2, "lng": 16.37, "radius": 60_000.0 }));
+        assert!(matches!(client.nearby_count(&wide).await, Err(Error::Validation { .. })));
+        assert_eq!(api.hits(), 0);
+    }
+
+    #[tokio::test]
+    async fn rank_preference_defaults_to_popularity() {
+        let api = MockApi::start(Duration::ZERO, |_| (200, places(0, 1))).await;
+        let client = Client::new("test-key".into()).unwrap().with_places_base_url(api.url());
+
+        let req = request(json!({ "lat": 48.2, "lng": 16.37, "radius": 500.0 }));
+        assert_eq!(req.rank_preference, NearbyRankPreference::Popularity);
+        client.nearby_search(&req).await.unwrap();
+
+        assert_eq!(api.bodies()[0]["rankPreference"], "POPULARITY");
+    }
+
+    #[test]
+    fn ring_radii_end_at_the_radius() {
+        for rings in 1..=3 {
+            let radii = ring_radii(900.0, rings);
+            assert_eq!(radii.len(), rings);
+            assert_eq!(*radii.last().unwrap(), 900.0);
+            assert!(radii.windows(2).all(|w| w[0] < w[1]), "{:?}", radii);
+        }
+        assert_eq!(ring_radii(900.0, 1), [900.0]);
+    }
+
+    #[test]
+    fn ring_radii_split_the_circle_into_equal_areas() {
+        for rings in 2..=3 {
+            let radii = ring_radii(900.0, rings);
+            let share = 900.0_f64.powi(2) / rings as f64;
+            let mut inner = 0.0;
+            for &r in &radii {
+                // The area between this circle and the last, over pi
+                let ring = r * r - inner * inner;
+                assert!((ring - share).abs() < 1e-6, "{} rings: {:?}", rings, radii);
+                inner = r;
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn rings_dedupe_across_requests_and_truncate_to_the_limit() {
+        let radii = ring_radii(900.0, 3);
+        let inner = radii.clone();
+        let api = MockApi::start(Duration::ZERO, move |seen| {
+            let radius = seen.body["locationRestriction"]["circle"]["radius"].as_f64().unwrap();
+            // Each larger circle repeats some places of the smaller ones
+            match inner.iter().position(|&r| r == radius) {
+                Some(0) => (200, places(0, 20)),
+                Some(1) => (200, places(10, 30)),
+                Some(2) => (200, places(25, 45)),
+                _ => (500, json!({})),
+            }
+        })
+        .await;
+        let client = Client::new("test-key".into()).unwrap().with_places_base_url(api.url());
+
+        let req = request(json!({ "lat": 48.2, "lng": 16.37, "radius": 900.0, "limit": 41 }));
+        let search = client.nearby_search_rings(&req).await.unwrap();
+
+        assert_eq!(search.radii, radii);
+        assert_eq!(search.added, [20, 10, 15]);
+        let ids: Vec<String> = search.response.places.iter().map(|p| p.id.clone()).collect();
+        let expected: Vec<String> = (0..41).map(|i| format!("p{}", i)).collect();
+        assert_eq!(ids, expected);
+
+        let sent: Vec<(f64, Value)> = api
+            .bodies()
+            .iter()
+            .map(|b| (b["locationRestriction"]["circle"]["radius"].as_f64().unwrap(), b["maxResultCount"].clone()))
+            .collect();
+        assert_eq!(
+            sent,
+            radii.iter().map(|&r| (r, json!(NEARBY_MAX_RESULTS))).collect::<Vec<_>>()
+        );
+    }
+
+    #[tokio::test]
+    async fn limits_up_to_twenty_are_one_request() {
+        let api = MockApi::start(Duration::ZERO, |_| (200, places(0, 20))).await;
+        let client = Client::new("test-key".into()).unwrap().with_places_base_url(api.url());
+
+        let req = request(json!({ "lat": 48.2, "lng": 16.37, "radius": 900.0, "limit": 20 }));
+        let search = client.nearby_search_rings(&req).await.unwrap();
+
+        assert_eq!(search.radii, [900.0]);
+        assert_eq!(search.added, [20]);
+        assert_eq!(api.hits(), 1);
+    }
+
+    #[test]
+    fn filters_off_sends_the_restriction_and_rank_preference() {
+        let req = request(json!({ "lat": 48.2, "lng": 16.37, "radius": 500.0 }));
+        assert_eq!(
+            build_nearby_body(&req),
+            json!({
+                "locationRestriction": {
+                    "circle": { "center": { "latitude": 48.2, "longitude": 16.37 }, "radius": 500.0 }
+                },
+                "rankPreference": "POPULARITY",
+            })
+        );
+    }
+
+    #[test]
+    fn filters_on_are_all_sent() {
+        let req = request(json!({
+            "lat": 48.2,
+            "lng": 16.37,
+            "radius": 500.0,
+            "includedTypes": ["cafe"],
+            "excludedTypes": ["fast_food_restaurant"],
+            "includedPrimaryTypes": ["coffee_shop"],
+            "excludedPrimaryTypes": ["bakery"],
+            "limit": 10,
+            "language": "de",
+            "region": "at",
+            "rankPreference": "distance",
+        }));
+        assert_eq!(
+            build_nearby_body(&req),
+            json!({
+                "locationRestriction": {
+                    "circle": { "center": { "latitude": 48.2, "longitude": 16.37 }, "radius": 500.0 }
+                },
+                "rankPreference": "DISTANCE",
+                "includedTypes": ["cafe"],
+                "excludedTypes": ["fast_food_restaurant"],
+                "includedPrimaryTypes": ["coffee_shop"],
+                "excludedPrimaryTypes": ["bakery"],
+                "maxResultCount": 10,
+                "languageCode": "de",
+                "regionCode": "at",
+            })
+        );
+    }
+
+    #[test]
+    fn limit_is_clamped_to_one_request() {
+        let req = request(json!({ "lat": 48.2, "lng": 16.37, "radius": 500.0, "limit": MAX_NEARBY_LIMIT }));
+        assert_eq!(build_nearby_body(&req)["maxResultCount"], json!(NEARBY_MAX_RESULTS));
+    }
+
+    #[tokio::test]
+    async fn count_asks_for_ids_of_a_full_page() {
+        let api = MockApi::start(Duration::ZERO, |_| (200, places(0, 20))).await;
+        let client = Client::new("test-key".into()).unwrap().with_places_base_url(api.url());
+
+        let req = request(json!({ "lat": 48.2, "lng": 16.37, "radius": 500.0, "limit": 5, "includedTypes": ["cafe"] }));
+        let count = client.nearby_count(&req).await.unwrap();
+
+        assert_eq!((count.count, count.capped, count.requests), (20, true, 1));
+        let sent = &api.requests()[0];
+        assert_eq!(sent.path, "/places:searchNearby");
+        assert_eq!(sent.field_mask.as_deref(), Some("places.id"));
+        // The whole page is counted, whatever --limit says
+        assert_eq!(sent.body["maxResultCount"], 20);
+        assert_eq!(sent.body["includedTypes"], json!(["cafe"]));
+    }
+
+    #[tokio::test]
+    async fn count_below_a_full_page_is_exact() {
+        let api = MockApi::start(Duration::ZERO, |_| (200, places(0, 3))).await;
+        let client = Client::new("test-key".into()).unwrap().with_places_base_url(api.url());
+
+        let req = request(json!({ "lat": 48.2, "lng": 16.37, "radius": 500.0 }));
+        let count = client.nearby_count(&req).await.unwrap();
+
+        assert_eq!((count.count, count.capped), (3, false));
+    }
+
+    #[tokio::test]
+    async fn search_asks_for_the_list_fields_without_photos() {
+        let api = MockApi::start(Duration::ZERO, |_| (200, places(0, 1))).await;
+        let client = Client::new("test-key".into()).unwrap().with_places_base_url(api.url());
+
+        let req = request(json!({ "lat": 48.2, "lng": 16.37, "radius": 500.0 }));
+        client.nearby_search(&req).await.unwrap();
+
+        let sent = &api.requests()[0];
+        assert_eq!(sent.body, build_nearby_body(&req));
+        let mask = sent.field_mask.as_deref().unwrap();
+        assert!(mask.split(',').any(|f| f == "places.shortFormattedAddress"), "{}", mask);
+        assert!(!mask.contains("places.photos"), "{}", mask);
+    }
+}
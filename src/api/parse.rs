@@ -0,0 +1,326 @@
+use std::sync::Arc;
+
+use serde_json::{Map, Value};
+
+use super::client::{Client, ClientEvent};
+use super::errors::Error;
+use super::types::{Place, Warning};
+
+impl Client {
+    /// Parse a single Place, degrading gracefully when some fields have an
+    /// unexpected shape. Dropped fields are reported as client warnings.
+    pub(crate) fn parse_place_lenient(&self, value: Value, what: &str) -> Result<Place, Error> {
+        let raw = self.keeps_raw_places().then(|| Arc::new(value.clone()));
+        let (mut place, dropped) = parse_place(value).map_err(|e| Error::Api {
+            status: 0,
+            message: format!("failed to parse {} response: {}", what, e),
+        })?;
+        self.warn_dropped(&place, &dropped);
+        place.raw = raw;
+        Ok(place)
+    }
+
+    /// Parse the `places` array of a search/nearby/resolve response, keeping
+    /// every place that can be salvaged.
+    pub(crate) fn parse_places_lenient(&self, mut value: Value, what: &str) -> Result<Vec<Place>, Error> {
+        let items = match value.get_mut("places").map(Value::take) {
+            None | Some(Value::Null) => return Ok(Vec::new()),
+            Some(Value::Array(items)) => items,
+            Some(_) => {
+                return Err(Error::Api {
+                    status: 0,
+                    message: format!("failed to parse {} response: `places` is not an array", what),
+                })
+            }
+        };
+
+        let mut places = Vec::with_capacity(items.len());
+        for (i, item) in items.into_iter().enumerate() {
+            let raw = self.keeps_raw_places().then(|| Arc::new(item.clone()));
+            match parse_place(item) {
+                Ok((mut place, dropped)) => {
+                    self.warn_dropped(&place, &dropped);
+                    place.raw = raw;
+                    places.push(place);
+                }
+                Err(e) => self.warn(Warning::Parse {
+                    message: format!("skipped {} result {}: {}", what, i + 1, e),
+                }),
+            }
+        }
+        Ok(places)
+    }
+
+    fn warn_dropped(&self, place: &Place, dropped: &[String]) {
+        if dropped.is_empty() {
+            return;
+        }
+        let name = place
+            .display_name
+            .as_ref()
+            .map(|n| n.text.as_str())
+            .unwrap_or(place.id.as_str());
+        self.warn(Warning::Parse {
+            message: format!("ignored unparseable field(s) {} for '{}'", dropped.join(", "), name),
+        });
+    }
+
+    pub(crate) fn warn(&self, warning: Warning) {
+        tracing::warn!("{}", warning);
+        self.notify(ClientEvent::Warning(warning));
+    }
+}
+
+/// Strict typed parse first; on failure, re-parse with only the top-level
+/// fields that deserialize on their own. Returns the place and the names of
+/// the fields that were dropped.
+pub(crate) fn parse_place(value: Value) -> Result<(Place, Vec<String>), serde_json::Error> {
+    let err = match serde_json::from_value::<Place>(value.clone()) {
+        Ok(place) => return Ok((place, Vec::new())),
+        Err(e) => e,
+    };
+    let Value::Object(map) = value else {
+        return Err(err);
+    };
+
+    let mut kept = Map::new();
+    let mut dropped = Vec::new();
+    for (key, field) in map {
+        let single = Value::Object(Map::from_iter([(key.clone(), field.clone())]));
+        if serde_json::from_value::<Place>(single).is_ok() {
+            kept.insert(key, field);
+        } else {
+            dropped.push(key);
+        }
+    }
+
+    let place = serde_json::from_value(Value::Object(kept))?;
+    Ok((place, dropped))
+}
+
+/// Strict parse of saved place JSON: a single place, an array of places, or a
+/// `{"places": [...]}` result. Errors name the offending JSON path, e.g.
+/// `places[2].rating: invalid type: string "4.5", expected f64`.
+pub(crate) fn places_from_json(value: Value) -> Result<Vec<Place>, String> {
+    match value {
+        Value::Array(items) => parse_items(items, ""),
+        Value::Object(mut map) => match map.remove("places") {
+            Some(Value::Array(items)) => parse_items(items, "places"),
+            Some(_) => Err("places: expected an array".to_string()),
+            None => parse_strict(Value::Object(map), "").map(|p| vec![p]),
+        },
+        other => Err(format!(
+            "expected a place object or an array of places, found {}",
+            json_kind(&other)
+        )),
+    }
+}
+
+fn parse_items(items: Vec<Value>, path: &str) -> Result<Vec<Place>, String> {
+    items
+        .into_iter()
+        .enumerate()
+        .map(|(i, item)| parse_strict(item, &format!("{}[{}]", path, i)))
+        .collect()
+}
+
+/// Parse one place, reporting the first top-level field that fails on its own
+fn parse_strict(value: Value, path: &str) -> Result<Place, String> {
+    let err = match serde_json::from_value::<Place>(value.clone()) {
+        Ok(place) => return Ok(place),
+        Err(e) => e,
+    };
+    let at = |field: &str| match (path.is_empty(), field.is_empty()) {
+        (true, true) => "$".to_string(),
+        (true, false) => field.to_string(),
+        (false, true) => path.to_string(),
+        (false, false) => format!("{}.{}", path, field),
+    };
+    let Value::Object(map) = value else {
+        return Err(format!("{}: expected a place object, found {}", at(""), json_kind(&value)));
+    };
+    for (key, field) in map {
+        let single = Value::Object(Map::from_iter([(key.clone(), field)]));
+        if let Err(e) = serde_json::from_value::<Place>(single) {
+            return Err(format!("{}: {}", at(&key), e));
+        }
+    }
+    Err(format!("{}: {}", at(""), err))
+}
+
+fn json_kind(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "a boolean",
+        Value::Number(_) => "a number",
+        Value::String(_) => "a string",
+        Value::Array(_) => "an array",
+        Value::Object(_) => "an object",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use serde_json::json;
+
+    use super::*;
+
+    /// A well-formed place with the fields most likely to change shape
+    fn fixture() -> Value {
+        json!({
+            "id": "ChIJ-parse-fixture-01",
+            "displayName": { "text": "Cafe Central", "languageCode": "de" },
+            "formattedAddress": "Herrengasse 14, 1010 Wien",
+            "location": { "latitude": 48.2104, "longitude": 16.3655 },
+            "rating": 4.4,
+            "userRatingCount": 31000,
+            "currentOpeningHours": {
+                "openNow": true,
+                "weekdayDescriptions": ["Monday: 8:00 AM – 9:00 PM"],
+            },
+            "reviews": [{ "rating": 5, "text": { "text": "Great", "languageCode": "en" } }],
+        })
+    }
+
+    fn corrupt(field: &str, value: Value) -> Value {
+        let mut place = fixture();
+        place[field] = value;
+        place
+    }
+
+    /// The survivors every corrupted fixture must keep
+    fn assert_core_fields(place: &Place) {
+        assert_eq!(place.id, "ChIJ-parse-fixture-01");
+        assert_eq!(place.display_name.as_ref().unwrap().text, "Cafe Central");
+        assert_eq!(place.formatted_address.as_deref(), Some("Herrengasse 14, 1010 Wien"));
+        assert_eq!(place.rating, Some(4.4));
+        assert_eq!(place.user_rating_count, Some(31000));
+    }
+
+    #[test]
+    fn well_formed_place_drops_nothing() {
+        let (place, dropped) = parse_place(fixture()).unwrap();
+        assert!(dropped.is_empty());
+        assert_core_fields(&place);
+        assert_eq!(place.reviews.unwrap().len(), 1);
+    }
+
+    #[test]
+    fn bad_review_enum_drops_only_reviews() {
+        // A rating sent as a new enum value instead of a number
+        let value = corrupt("reviews", json!([{ "rating": "FIVE_STARS", "text": { "text": "Great" } }]));
+        let (place, dropped) = parse_place(value).unwrap();
+        assert_eq!(dropped, ["reviews"]);
+        assert_core_fields(&place);
+        assert!(place.reviews.is_none());
+        assert!(place.current_opening_hours.is_some());
+        assert!(place.location.is_some());
+    }
+
+    #[test]
+    fn bad_location_type_drops_only_location() {
+        let (place, dropped) = parse_place(corrupt("location", json!("48.2104,16.3655"))).unwrap();
+        assert_eq!(dropped, ["location"]);
+        assert_core_fields(&place);
+        assert!(place.location.is_none());
+        assert!(place.reviews.is_some());
+    }
+
+    #[test]
+    fn bad_opening_hours_drops_only_opening_hours() {
+        let value = corrupt("currentOpeningHours", json!({ "openNow": "yes" }));
+        let (place, dropped) = parse_place(value).unwrap();
+        assert_eq!(dropped, ["currentOpeningHours"]);
+        assert_core_fields(&place);
+        assert!(place.current_opening_hours.is_none());
+        assert!(place.location.is_some());
+    }
+
+    #[test]
+    fn every_bad_field_is_named() {
+        let mut value = corrupt("location", json!(true));
+        value["reviews"] = json!("none");
+        let (place, mut dropped) = parse_place(value).unwrap();
+        dropped.sort();
+        assert_eq!(dropped, ["location", "reviews"]);
+        assert_core_fields(&place);
+    }
+
+    #[test]
+    fn non_object_place_is_an_error() {
+        assert!(parse_place(json!(["not", "a", "place"])).is_err());
+    }
+
+    /// A client that records the warnings it emits
+    fn client() -> (Client, Arc<Mutex<Vec<String>>>) {
+        let warnings = Arc::new(Mutex::new(Vec::new()));
+        let seen = warnings.clone();
+        let client = Client::new("test-key".into()).unwrap().with_observer(move |event| {
+            if let ClientEvent::Warning(w) = event {
+                seen.lock().unwrap().push(w.to_string());
+            }
+        });
+        (client, warnings)
+    }
+
+    #[test]
+    fn lenient_place_warns_with_the_field_name() {
+        let (client, warnings) = client();
+        let place = client
+            .parse_place_lenient(corrupt("reviews", json!({ "rating": 5 })), "details")
+            .unwrap();
+        assert_core_fields(&place);
+        assert_eq!(
+            *warnings.lock().unwrap(),
+            ["ignored unparseable field(s) reviews for 'Cafe Central'"]
+        );
+    }
+
+    #[test]
+    fn lenient_places_keep_salvageable_results_and_skip_the_rest() {
+        let (client, warnings) = client();
+        let response = json!({
+            "places": [
+                fixture(),
+                corrupt("location", json!(null)),
+                corrupt("location", json!(1)),
+                "garbage",
+            ]
+        });
+        let places = client.parse_places_lenient(response, "search").unwrap();
+
+        assert_eq!(places.len(), 3);
+        places.iter().for_each(assert_core_fields);
+        assert!(places[2].location.is_none());
+        let warnings = warnings.lock().unwrap();
+        assert_eq!(warnings.len(), 2);
+        assert_eq!(warnings[0], "ignored unparseable field(s) location for 'Cafe Central'");
+        assert!(warnings[1].starts_with("skipped search result 4: "), "{}", warnings[1]);
+    }
+
+    #[test]
+    fn lenient_places_reject_a_non_array() {
+        let (client, _) = client();
+        let err = client.parse_places_lenient(json!({ "places": {} }), "nearby").unwrap_err();
+        assert!(err.to_string().contains("`places` is not an array"), "{}", err);
+        assert!(client.parse_places_lenient(json!({}), "nearby").unwrap().is_empty());
+    }
+
+    #[test]
+    fn strict_json_errors_name_the_path() {
+        let saved = json!({ "places": [fixture(), corrupt("rating", json!("4.5"))] });
+        let err = places_from_json(saved).unwrap_err();
+        assert!(err.starts_with("places[1].rating: invalid type: string \"4.5\""), "{}", err);
+
+        let err = places_from_json(json!([corrupt("location", json!("here"))])).unwrap_err();
+        assert!(err.starts_with("[0].location: "), "{}", err);
+
+        assert_eq!(
+            places_from_json(json!(42)).unwrap_err(),
+            "expected a place object or an array of places, found a number"
+        );
+        assert_eq!(places_from_json(fixture()).unwrap().len(), 1);
+    }
+}
@@ -1,17 +1,62 @@
 use super::client::Client;
 use super::errors::Error;
-use super::types::{PhotoMediaRequest, PhotoMediaResponse};
+use super::types::{PhotoContext, PhotoMediaRequest, PhotoMediaResponse};
 
-impl Client {
-    pub async fn photo_media(&self, req: &PhotoMediaRequest) -> Result<PhotoMediaResponse, Error> {
-        if req.name.is_empty() {
-            return Err(Error::Validation {
-                field: "name".into(),
-                message: "photo resource name is required".into(),
-            });
+/// A photo resource name split into its place ID and photo reference
+struct PhotoName<'a> {
+    place_id: &'a str,
+    photo: &'a str,
+}
+
+impl<'a> PhotoName<'a> {
+    /// Parse `places/{place}/photos/{photo}`, with or without a leading `/`
+    fn parse(name: &'a str) -> Result<Self, Error> {
+        let invalid = |problem: &str| Error::Validation {
+            field: "name".into(),
+            message: format!(
+                "{}; photo names look like places/ChIJN1t_tDeuEmsRUsoyG83frY4/photos/AUc7... (from details --photos)",
+                problem
+            ),
+        };
+        let trimmed = name.trim().trim_start_matches('/');
+        if trimmed.is_empty() {
+            return Err(invalid("photo resource name is required"));
+        }
+        match trimmed.split('/').collect::<Vec<_>>()[..] {
+            ["places", place_id, "photos", photo] if !place_id.is_empty() && !photo.is_empty() => {
+                Ok(PhotoName { place_id, photo })
+            }
+            _ => Err(invalid(&format!("'{}' is not a photo resource name", name))),
+        }
+    }
+
+    /// Path of the media endpoint, each segment percent-encoded
+    fn media_path(&self) -> String {
+        format!(
+            "/places/{}/photos/{}/media",
+            encode_segment(self.place_id),
+            encode_segment(self.photo)
+        )
+    }
+}
+
+/// Percent-encode everything but RFC 3986 unreserved characters, so a
+/// segment can't be misread as a path separator, query, or encoded byte
+fn encode_segment(segment: &str) -> String {
+    let mut out = String::with_capacity(segment.len());
+    for byte in segment.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'.' | b'_' | b'~') {
+            out.push(byte as char);
+        } else {
+            out.push_str(&format!("%{:02X}", byte));
         }
+    }
+    out
+}
 
-        let path = format!("/{}/media", req.name);
+impl Client {
+    pub async fn photo_media(&self, req: &PhotoMediaRequest) -> Result<PhotoMediaResponse, Error> {
+        let path = PhotoName::parse(&req.name)?.media_path();
         let mut query_params: Vec<(&str, String)> = Vec::new();
 
         if let Some(w) = req.max_width {
@@ -40,4 +85,117 @@ impl Client {
             message: format!("failed to parse photo response: {}", e),
         })
     }
+
+    /// Size and authors of a photo named `places/{id}/photos/{ref}`, looked
+    /// up in its place's photo list. None for other names or when the place
+    /// no longer lists the photo.
+    pub async fn photo_context(&self, name: &str) -> Result<Option<PhotoContext>, Error> {
+        let Ok(parsed) = PhotoName::parse(name) else {
+            return Ok(None);
+        };
+        let photos = self.place_photos(parsed.place_id).await?;
+        Ok(photos
+            .iter()
+            .filter_map(|p| Some((p, PhotoName::parse(&p.name).ok()?)))
+            .find(|(_, listed)| listed.photo == parsed.photo)
+            .map(|(p, _)| PhotoContext::from(p)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use serde_json::json;
+
+    use super::*;
+    use crate::api::mock::MockApi;
+
+    fn media_path(name: &str) -> String {
+        PhotoName::parse(name).unwrap().media_path()
+    }
+
+    #[test]
+    fn plain_name_is_unchanged() {
+        assert_eq!(
+            media_path("places/ChIJN1t_tDeuEmsRUsoyG83frY4/photos/AUc7tXW-abc_123"),
+            "/places/ChIJN1t_tDeuEmsRUsoyG83frY4/photos/AUc7tXW-abc_123/media"
+        );
+    }
+
+    #[test]
+    fn plus_and_equals_are_encoded() {
+        assert_eq!(
+            media_path("places/ChIJ0000000000000001/photos/AUc7+tXW=="),
+            "/places/ChIJ0000000000000001/photos/AUc7%2BtXW%3D%3D/media"
+        );
+        assert_eq!(
+            media_path("places/ChIJ0000000000000001/photos/a+b=c"),
+            "/places/ChIJ0000000000000001/photos/a%2Bb%3Dc/media"
+        );
+    }
+
+    #[test]
+    fn non_ascii_is_encoded_as_utf8_bytes() {
+        assert_eq!(
+            media_path("places/ChIJ0000000000000001/photos/café☕"),
+            "/places/ChIJ0000000000000001/photos/caf%C3%A9%E2%98%95/media"
+        );
+    }
+
+    #[test]
+    fn reserved_characters_cannot_escape_the_segment() {
+        assert_eq!(encode_segment("a?b#c%20d e"), "a%3Fb%23c%2520d%20e");
+        assert_eq!(encode_segment("A-z0.9_~"), "A-z0.9_~");
+    }
+
+    #[test]
+    fn leading_slash_and_whitespace_are_tolerated() {
+        let name = PhotoName::parse("  /places/ChIJ0000000000000001/photos/AUc7 \n").unwrap();
+        assert_eq!(name.place_id, "ChIJ0000000000000001");
+        assert_eq!(name.photo, "AUc7");
+    }
+
+    fn rejected(name: &str) -> String {
+        match PhotoName::parse(name) {
+            Err(Error::Validation { field, message }) => {
+                assert_eq!(field, "name");
+                assert!(message.contains("places/ChIJN1t_tDeuEmsRUsoyG83frY4/photos/"), "{}", message);
+                message
+            }
+            Err(other) => panic!("expected a validation error, got {:?}", other),
+            Ok(_) => panic!("'{}' was accepted", name),
+        }
+    }
+
+    #[test]
+    fn malformed_names_are_validation_errors() {
+        assert!(rejected("").starts_with("photo resource name is required"));
+        assert!(rejected("AUc7tXW").starts_with("'AUc7tXW' is not a photo resource name"));
+        rejected("places/ChIJ0000000000000001");
+        rejected("places/ChIJ0000000000000001/photos/");
+        rejected("places//photos/AUc7");
+        rejected("places/ChIJ0000000000000001/reviews/AUc7");
+        rejected("places/ChIJ0000000000000001/photos/AUc7/media");
+        rejected("https://places.googleapis.com/v1/places/ChIJ0000000000000001/photos/AUc7");
+    }
+
+    #[tokio::test]
+    async fn media_request_uses_the_encoded_path() {
+        let api = MockApi::start(Duration::ZERO, |_| {
+            (200, json!({ "name": "places/p/photos/x", "photoUri": "https://example.com/x.jpg" }))
+        })
+        .await;
+        let client = Client::new("test-key".into()).unwrap().with_places_base_url(api.url());
+        let req = PhotoMediaRequest {
+            name: "places/ChIJ0000000000000001/photos/a+b=é".into(),
+            max_width: None,
+            max_height: None,
+        };
+
+        let photo = client.photo_media(&req).await.unwrap();
+
+        assert_eq!(photo.photo_uri, "https://example.com/x.jpg");
+        assert_eq!(api.paths(), ["/places/ChIJ0000000000000001/photos/a%2Bb%3D%C3%A9/media"]);
+    }
 }
@@ -0,0 +1,392 @@
+use std::fmt;
+
+use super::types::LatLng;
+
+/// Why an encoded polyline could not be decoded
+#[derive(Debug, Clone)]
+pub enum PolylineError {
+    /// The input ended partway through a value, or after a latitude with no
+    /// longitude
+    Truncated { offset: usize },
+    /// A byte outside the polyline alphabet ('?' to '~')
+    InvalidByte { offset: usize, byte: u8 },
+    /// A value ran on for more chunks than fit in 64 bits
+    Overflow { offset: usize },
+    /// A decoded point lies outside ±90° latitude or ±180° longitude
+    OutOfRange { index: usize, point: LatLng },
+}
+
+impl fmt::Display for PolylineError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PolylineError::Truncated { offset } => {
+                write!(f, "input ends mid-value at byte {}", offset)
+            }
+            PolylineError::InvalidByte { offset, byte } => {
+                write!(f, "invalid byte 0x{:02x} at byte {}", byte, offset)
+            }
+            PolylineError::Overflow { offset } => {
+                write!(f, "value starting at byte {} is too long", offset)
+            }
+            PolylineError::OutOfRange { index, point } => write!(
+                f,
+                "point {} ({}, {}) is out of range",
+                index, point.latitude, point.longitude
+            ),
+        }
+    }
+}
+
+/// Decode a Google encoded polyline string into a list of LatLng points,
+/// rejecting truncated or corrupted input
+pub fn decode_polyline(encoded: &str) -> Result<Vec<LatLng>, PolylineError> {
+    let mut decoder = Decoder::new(encoded.as_bytes());
+    let mut points = Vec::new();
+    while let Some(point) = decoder.next_point()? {
+        if !in_range(&point) {
+            return Err(PolylineError::OutOfRange {
+                index: points.len(),
+                point,
+            });
+        }
+        points.push(point);
+    }
+    Ok(points)
+}
+
+/// Decode as much of a polyline as is well-formed: the points before the
+/// first error
+pub fn decode_polyline_lenient(encoded: &str) -> Vec<LatLng> {
+    let mut decoder = Decoder::new(encoded.as_bytes());
+    let mut points = Vec::new();
+    while let Ok(Some(point)) = decoder.next_point() {
+        if !in_range(&point) {
+            break;
+        }
+        points.push(point);
+    }
+    points
+}
+
+fn in_range(point: &LatLng) -> bool {
+    (-90.0..=90.0).contains(&point.latitude) && (-180.0..=180.0).contains(&point.longitude)
+}
+
+/// Running state over the polyline bytes: values are zig-zag encoded deltas
+/// in 5-bit chunks, each chunk offset by 63, with 0x20 marking a continuation
+struct Decoder<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+    lat: i64,
+    lng: i64,
+}
+
+impl<'a> Decoder<'a> {
+    fn new(bytes: &'a [u8]) -> Self {
+        Decoder {
+            bytes,
+            pos: 0,
+            lat: 0,
+            lng: 0,
+        }
+    }
+
+    /// The next point, or None at the end of the input
+    fn next_point(&mut self) -> Result<Option<LatLng>, PolylineError> {
+        if self.pos >= self.bytes.len() {
+            return Ok(None);
+        }
+        let d_lat = self.next_value()?;
+        if self.pos >= self.bytes.len() {
+            return Err(PolylineError::Truncated { offset: self.pos });
+        }
+        let d_lng = self.next_value()?;
+        self.lat = self.lat.saturating_add(d_lat);
+        self.lng = self.lng.saturating_add(d_lng);
+        Ok(Some(LatLng {
+            latitude: self.lat as f64 / 1e5,
+            longitude: self.lng as f64 / 1e5,
+        }))
+    }
+
+    fn next_value(&mut self) -> Result<i64, PolylineError> {
+        let start = self.pos;
+        let mut shift = 0u32;
+        let mut result: i64 = 0;
+        loop {
+            let Some(&byte) = self.bytes.get(self.pos) else {
+                return Err(PolylineError::Truncated { offset: self.pos });
+            };
+            if !(63..=126).contains(&byte) {
+                return Err(PolylineError::InvalidByte {
+                    offset: self.pos,
+                    byte,
+                });
+            }
+            if shift >= 64 {
+                return Err(PolylineError::Overflow { offset: start });
+            }
+            self.pos += 1;
+            let b = (byte - 63) as i64;
+            result |= (b & 0x1f) << shift;
+            shift += 5;
+            if b < 0x20 {
+                break;
+            }
+        }
+        Ok(if result & 1 != 0 {
+            !(result >> 1)
+        } else {
+            result >> 1
+        })
+    }
+}
+
+/// Encode points as a Google encoded polyline string (inverse of `decode_polyline`)
+pub fn encode_polyline(points: &[LatLng]) -> String {
+    let mut out = String::new();
+    let mut prev_lat: i64 = 0;
+    let mut prev_lng: i64 = 0;
+
+    for p in points {
+        let lat = (p.latitude * 1e5).round() as i64;
+        let lng = (p.longitude * 1e5).round() as i64;
+        encode_value(lat - prev_lat, &mut out);
+        encode_value(lng - prev_lng, &mut out);
+        prev_lat = lat;
+        prev_lng = lng;
+    }
+
+    out
+}
+
+fn encode_value(value: i64, out: &mut String) {
+    let mut v = if value < 0 { !(value << 1) } else { value << 1 };
+    while v >= 0x20 {
+        out.push((((v & 0x1f) | 0x20) as u8 + 63) as char);
+        v >>= 5;
+    }
+    out.push((v as u8 + 63) as char);
+}
+
+/// Simplify a path with the Douglas–Peucker algorithm, dropping points that
+/// lie within `tolerance_m` meters of the simplified line. Endpoints are kept.
+pub fn simplify(points: &[LatLng], tolerance_m: f64) -> Vec<LatLng> {
+    if points.len() <= 2 || tolerance_m <= 0.0 {
+        return points.to_vec();
+    }
+
+    let mut keep = vec![false; points.len()];
+    keep[0] = true;
+    keep[points.len() - 1] = true;
+
+    // Iterative to avoid deep recursion on long routes
+    let mut stack = vec![(0, points.len() - 1)];
+    while let Some((start, end)) = stack.pop() {
+        let mut max_dist = 0.0;
+        let mut max_idx = start;
+        for i in start + 1..end {
+            let d = perpendicular_distance(&points[i], &points[start], &points[end]);
+            if d > max_dist {
+                max_dist = d;
+                max_idx = i;
+            }
+        }
+        if max_dist > tolerance_m {
+            keep[max_idx] = true;
+            stack.push((start, max_idx));
+            stack.push((max_idx, end));
+        }
+    }
+
+    points
+        .iter()
+        .zip(keep)
+        .filter(|(_, k)| *k)
+        .map(|(p, _)| p.clone())
+        .collect()
+}
+
+/// Distance in meters from `p` to the segment `a`–`b`, using a local
+/// equirectangular projection (accurate enough at simplification scales)
+fn perpendicular_distance(p: &LatLng, a: &LatLng, b: &LatLng) -> f64 {
+    const R: f64 = 6_371_000.0;
+    let cos_lat = a.latitude.to_radians().cos();
+    let project = |q: &LatLng| {
+        (
+            (q.longitude - a.longitude).to_radians() * cos_lat * R,
+            (q.latitude - a.latitude).to_radians() * R,
+        )
+    };
+    let (px, py) = project(p);
+    let (bx, by) = project(b);
+
+    let len_sq = bx * bx + by * by;
+    if len_sq == 0.0 {
+        return (px * px + py * py).sqrt();
+    }
+    let t = ((px * bx + py * by) / len_sq).clamp(0.0, 1.0);
+    let (dx, dy) = (px - t * bx, py - t * by);
+    (dx * dx + dy * dy).sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The example from Google's polyline algorithm documentation
+    const GOOGLE_SAMPLE: &str = "_p~iF~ps|U_ulLnnqC_mqNvxq`@";
+
+    fn at(latitude: f64, longitude: f64) -> LatLng {
+        LatLng { latitude, longitude }
+    }
+
+    fn coords(points: &[LatLng]) -> Vec<(f64, f64)> {
+        points.iter().map(|p| (p.latitude, p.longitude)).collect()
+    }
+
+    #[test]
+    fn decodes_google_sample() {
+        let points = decode_polyline(GOOGLE_SAMPLE).unwrap();
+        assert_eq!(coords(&points), [(38.5, -120.2), (40.7, -120.95), (43.252, -126.453)]);
+    }
+
+    #[test]
+    fn empty_input_is_no_points() {
+        assert!(decode_polyline("").unwrap().is_empty());
+    }
+
+    #[test]
+    fn truncated_mid_value() {
+        let cut = &GOOGLE_SAMPLE[..GOOGLE_SAMPLE.len() - 1];
+        assert!(matches!(decode_polyline(cut), Err(PolylineError::Truncated { offset: 26 })));
+    }
+
+    #[test]
+    fn truncated_after_latitude() {
+        // "_p~iF" is the first latitude alone
+        assert!(matches!(decode_polyline("_p~iF"), Err(PolylineError::Truncated { offset: 5 })));
+    }
+
+    #[test]
+    fn corrupted_byte() {
+        let corrupted = GOOGLE_SAMPLE.replacen('|', " ", 1);
+        assert!(matches!(
+            decode_polyline(&corrupted),
+            Err(PolylineError::InvalidByte { offset: 8, byte: b' ' })
+        ));
+    }
+
+    #[test]
+    fn endless_value_overflows() {
+        // '~' always sets the continuation bit
+        let endless = "~".repeat(20);
+        assert!(matches!(decode_polyline(&endless), Err(PolylineError::Overflow { offset: 0 })));
+    }
+
+    #[test]
+    fn out_of_range_point() {
+        let encoded = encode_polyline(&[at(10.0, 20.0), at(95.0, 20.0)]);
+        match decode_polyline(&encoded) {
+            Err(PolylineError::OutOfRange { index, point }) => {
+                assert_eq!(index, 1);
+                assert_eq!(point.latitude, 95.0);
+            }
+            other => panic!("expected OutOfRange, got {:?}", other),
+        }
+
+        let encoded = encode_polyline(&[at(0.0, -181.0)]);
+        assert!(matches!(decode_polyline(&encoded), Err(PolylineError::OutOfRange { index: 0, .. })));
+    }
+
+    #[test]
+    fn errors_name_the_position() {
+        let error = decode_polyline("_p~iF").unwrap_err();
+        assert_eq!(error.to_string(), "input ends mid-value at byte 5");
+        let error = decode_polyline("_p~iF ").unwrap_err();
+        assert_eq!(error.to_string(), "invalid byte 0x20 at byte 5");
+    }
+
+    #[test]
+    fn lenient_keeps_points_before_the_error() {
+        let cut = &GOOGLE_SAMPLE[..GOOGLE_SAMPLE.len() - 1];
+        assert_eq!(coords(&decode_polyline_lenient(cut)), [(38.5, -120.2), (40.7, -120.95)]);
+
+        let corrupted = format!("{}!{}", &GOOGLE_SAMPLE[..10], &GOOGLE_SAMPLE[10..]);
+        assert_eq!(coords(&decode_polyline_lenient(&corrupted)), [(38.5, -120.2)]);
+
+        let encoded = encode_polyline(&[at(10.0, 20.0), at(95.0, 20.0), at(10.0, 20.0)]);
+        assert_eq!(coords(&decode_polyline_lenient(&encoded)), [(10.0, 20.0)]);
+
+        assert_eq!(decode_polyline_lenient(GOOGLE_SAMPLE).len(), 3);
+    }
+
+    #[test]
+    fn encodes_google_sample() {
+        let points = [at(38.5, -120.2), at(40.7, -120.95), at(43.252, -126.453)];
+        assert_eq!(encode_polyline(&points), GOOGLE_SAMPLE);
+    }
+
+    #[test]
+    fn round_trips_google_sample() {
+        assert_eq!(encode_polyline(&decode_polyline(GOOGLE_SAMPLE).unwrap()), GOOGLE_SAMPLE);
+    }
+
+    #[test]
+    fn round_trips_to_five_decimals() {
+        let points = [
+            at(0.0, 0.0),
+            at(-33.868_82, 151.209_29),
+            at(89.999_99, -179.999_99),
+            at(-0.000_01, 0.000_01),
+        ];
+        assert_eq!(coords(&decode_polyline(&encode_polyline(&points)).unwrap()), coords(&points));
+
+        // Beyond five decimals is rounded away
+        let decoded = decode_polyline(&encode_polyline(&[at(48.208_174_9, 16.373_819_1)])).unwrap();
+        assert_eq!(coords(&decoded), [(48.20817, 16.37382)]);
+    }
+
+    #[test]
+    fn encodes_nothing_as_empty() {
+        assert_eq!(encode_polyline(&[]), "");
+    }
+
+    #[test]
+    fn simplify_collapses_collinear_points() {
+        let line: Vec<LatLng> = (0..=10).map(|i| at(48.0, 16.0 + i as f64 * 0.01)).collect();
+        assert_eq!(coords(&simplify(&line, 1.0)), [(48.0, 16.0), (48.0, 16.1)]);
+    }
+
+    #[test]
+    fn simplify_keeps_endpoints_and_corners() {
+        // An L: east, then north; the corner is ~740 m off the start-end line
+        let path = [
+            at(48.0, 16.0),
+            at(48.0, 16.005),
+            at(48.0, 16.01),
+            at(48.005, 16.01),
+            at(48.01, 16.01),
+        ];
+        assert_eq!(coords(&simplify(&path, 10.0)), [(48.0, 16.0), (48.0, 16.01), (48.01, 16.01)]);
+
+        // A tolerance wider than the corner's offset drops it too
+        assert_eq!(coords(&simplify(&path, 1000.0)), [(48.0, 16.0), (48.01, 16.01)]);
+    }
+
+    #[test]
+    fn simplify_keeps_small_deviations_below_tolerance_only() {
+        // Middle point ~11 m north of the line
+        let path = [at(48.0, 16.0), at(48.0001, 16.005), at(48.0, 16.01)];
+        assert_eq!(simplify(&path, 5.0).len(), 3);
+        assert_eq!(simplify(&path, 20.0).len(), 2);
+    }
+
+    #[test]
+    fn simplify_leaves_short_paths_and_zero_tolerance_alone() {
+        let two = [at(48.0, 16.0), at(48.1, 16.1)];
+        assert_eq!(simplify(&two, 100.0).len(), 2);
+        let three = [at(48.0, 16.0), at(48.0, 16.005), at(48.0, 16.01)];
+        assert_eq!(simplify(&three, 0.0).len(), 3);
+    }
+}
@@ -1,7 +1,8 @@
-use serde_json::json;
+use serde_json::{json, Value};
 
 use super::client::Client;
 use super::errors::Error;
+use super::limits::{clamp_limit, MAX_RESOLVE_RESULTS};
 use super::types::{ResolveRequest, SearchResponse};
 
 const RESOLVE_FIELD_MASK: &str = "places.id,places.displayName,places.formattedAddress,\
@@ -18,27 +19,80 @@ impl Client {
             });
         }
 
-        let mut body = json!({
-            "textQuery": req.location,
-        });
-
-        if let Some(limit) = req.limit {
-            body["maxResultCount"] = json!(limit.min(10));
-        }
-        if let Some(ref lang) = req.language {
-            body["languageCode"] = json!(lang);
-        }
-        if let Some(ref region) = req.region {
-            body["regionCode"] = json!(region);
-        }
+        let body = build_resolve_body(req);
 
         let result = self
             .places_post("/places:searchText", RESOLVE_FIELD_MASK, &body)
             .await?;
 
-        serde_json::from_value(result).map_err(|e| Error::Api {
-            status: 0,
-            message: format!("failed to parse resolve response: {}", e),
-        })
+        let places = self.parse_places_lenient(result, "resolve")?;
+        Ok(SearchResponse { places })
+    }
+}
+
+/// Build the JSON body for POST /places:searchText (address resolution)
+pub(crate) fn build_resolve_body(req: &ResolveRequest) -> Value {
+    let mut body = json!({
+        "textQuery": req.location,
+    });
+
+    if let Some(limit) = req.limit {
+        body["maxResultCount"] = json!(clamp_limit(limit, MAX_RESOLVE_RESULTS).0);
+    }
+    if let Some(ref lang) = req.language {
+        body["languageCode"] = json!(lang);
+    }
+    if let Some(ref region) = req.region {
+        body["regionCode"] = json!(region);
+    }
+
+    body
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(limit: Option<u32>, language: Option<&str>, region: Option<&str>) -> ResolveRequest {
+        ResolveRequest {
+            location: "Stephansplatz, Vienna".into(),
+            limit,
+            language: language.map(String::from),
+            region: region.map(String::from),
+        }
+    }
+
+    #[test]
+    fn options_off_sends_only_the_text() {
+        assert_eq!(
+            build_resolve_body(&request(None, None, None)),
+            json!({ "textQuery": "Stephansplatz, Vienna" })
+        );
+    }
+
+    #[test]
+    fn options_on_are_all_sent() {
+        assert_eq!(
+            build_resolve_body(&request(Some(3), Some("de"), Some("at"))),
+            json!({
+                "textQuery": "Stephansplatz, Vienna",
+                "maxResultCount": 3,
+                "languageCode": "de",
+                "regionCode": "at",
+            })
+        );
+    }
+
+    #[test]
+    fn limit_is_clamped_to_the_endpoint_maximum() {
+        let body = build_resolve_body(&request(Some(50), None, None));
+        assert_eq!(body["maxResultCount"], json!(MAX_RESOLVE_RESULTS));
+    }
+
+    #[test]
+    fn field_mask_has_no_photos_or_hours() {
+        assert!(RESOLVE_FIELD_MASK.starts_with("places.id,"));
+        assert!(!RESOLVE_FIELD_MASK.contains("places.photos"));
+        assert!(!RESOLVE_FIELD_MASK.contains("currentOpeningHours"));
     }
 }
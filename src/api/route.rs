@@ -1,37 +1,184 @@
-use serde_json::json;
+use serde_json::{json, Value};
 
 use super::client::Client;
 use super::errors::Error;
+use super::polyline::{decode_polyline, decode_polyline_lenient, encode_polyline, simplify};
 use super::types::{
-    Circle, LatLng, RouteRequest, RouteSearchResponse, RouteWaypointResult, SearchRequest,
+    Circle, ComputedRoute, LatLng, Place, ResolveRequest, ResolvedEndpoint, RouteRequest,
+    RouteSearchResponse, RouteSource, RouteSummary, RouteWaypointResult, SearchRequest,
+    TransitDetails, TravelMode, Warning,
 };
+use crate::format::parse_duration_s;
+use crate::geo::{great_circle_points, haversine_distance};
+
+/// Radius for the reverse-resolve lookup behind --label-waypoints
+const LABEL_SEARCH_RADIUS: f64 = 5000.0;
+/// Address component types tried in order when labelling a waypoint
+const LOCALITY_TYPES: [&str; 4] = [
+    "locality",
+    "postal_town",
+    "administrative_area_level_3",
+    "administrative_area_level_2",
+];
+
+/// Points synthesized along a --straight-line route
+const STRAIGHT_LINE_POINTS: usize = 64;
+
+const ROUTES_FIELD_MASK: &str =
+    "routes.polyline.encodedPolyline,routes.distanceMeters,routes.duration";
+const ROUTES_TRANSIT_FIELD_MASK: &str = "routes.polyline.encodedPolyline,routes.distanceMeters,\
+routes.duration,routes.legs.steps.transitDetails";
+/// Distance and duration only, for quick summaries
+const ROUTES_SUMMARY_FIELD_MASK: &str = "routes.distanceMeters,routes.duration";
 
 impl Client {
     pub async fn route_search(&self, req: &RouteRequest) -> Result<RouteSearchResponse, Error> {
-        if req.query.is_empty() {
+        validate_route_request(req)?;
+
+        // Step 1: Compute route(s) via Routes API (or a straight line)
+        let (mut routes, source) = self.routes_or_straight_line(req).await?;
+        if req.route_index >= routes.len() {
             return Err(Error::Validation {
-                field: "query".into(),
-                message: "query is required".into(),
+                field: "route-index".into(),
+                message: format!(
+                    "route index {} out of range: the API returned {} route(s)",
+                    req.route_index,
+                    routes.len()
+                ),
             });
         }
-        if req.from.is_empty() {
-            return Err(Error::Validation {
-                field: "from".into(),
-                message: "origin is required".into(),
-            });
+        let route = routes.swap_remove(req.route_index);
+
+        // Steps 2-4: decode, sample, and search along the chosen route
+        self.search_along_route(req, req.route_index, route, source).await
+    }
+
+    /// Run the along-route search for every route the API returns
+    /// (primary plus alternatives), for side-by-side comparison
+    pub async fn route_compare(&self, req: &RouteRequest) -> Result<Vec<RouteSearchResponse>, Error> {
+        validate_route_request(req)?;
+
+        let (routes, source) = self.routes_or_straight_line(req).await?;
+        let mut responses = Vec::with_capacity(routes.len());
+        for (idx, route) in routes.into_iter().enumerate() {
+            responses.push(self.search_along_route(req, idx, route, source).await?);
         }
-        if req.to.is_empty() {
-            return Err(Error::Validation {
-                field: "to".into(),
-                message: "destination is required".into(),
-            });
+        Ok(responses)
+    }
+
+    /// Compute routes via the Routes API, or synthesize a straight line when
+    /// asked to or when the key isn't allowed to call the Routes API (403)
+    async fn routes_or_straight_line(
+        &self,
+        req: &RouteRequest,
+    ) -> Result<(Vec<ComputedRoute>, RouteSource), Error> {
+        if req.straight_line {
+            let route = self.straight_line_route(req).await?;
+            return Ok((vec![route], RouteSource::StraightLine));
+        }
+        match self.compute_routes(req).await {
+            Ok(routes) => Ok((routes, RouteSource::RoutesApi)),
+            // search_along_route notes the fallback on the response
+            Err(Error::Api { status: 403, .. }) => {
+                let route = self.straight_line_route(req).await?;
+                Ok((vec![route], RouteSource::StraightLine))
+            }
+            Err(e) => Err(e),
         }
+    }
+
+    /// Candidate places for both endpoints, resolved concurrently, best match
+    /// first; an endpoint with no match is a validation error
+    pub async fn resolve_route_endpoints(
+        &self,
+        req: &RouteRequest,
+        candidates: u32,
+    ) -> Result<(Vec<Place>, Vec<Place>), Error> {
+        validate_route_request(req)?;
+        let lookup = |field: &'static str, text: &str| {
+            let resolve_req = ResolveRequest {
+                location: text.to_string(),
+                limit: Some(candidates),
+                language: req.language.clone(),
+                region: req.region.clone(),
+            };
+            async move {
+                let places = self.resolve(&resolve_req).await?.places;
+                if places.is_empty() {
+                    return Err(Error::Validation {
+                        field: field.into(),
+                        message: format!("could not resolve '{}' to a place", resolve_req.location),
+                    });
+                }
+                Ok(places)
+            }
+        };
+        tokio::try_join!(lookup("from", &req.from), lookup("to", &req.to))
+    }
+
+    /// Resolve both endpoints via the Places API and join them with a great-circle line
+    async fn straight_line_route(&self, req: &RouteRequest) -> Result<ComputedRoute, Error> {
+        let from_point = req.from_resolved.as_ref().and_then(|e| e.location.clone());
+        let to_point = req.to_resolved.as_ref().and_then(|e| e.location.clone());
+        let (from, to) = tokio::try_join!(
+            async {
+                match from_point {
+                    Some(p) => Ok(p),
+                    None => self.resolve_point("from", &req.from, req).await,
+                }
+            },
+            async {
+                match to_point {
+                    Some(p) => Ok(p),
+                    None => self.resolve_point("to", &req.to, req).await,
+                }
+            },
+        )?;
 
-        // Step 1: Compute route via Routes API
-        let polyline = self.compute_route_polyline(req).await?;
+        let points = great_circle_points(&from, &to, STRAIGHT_LINE_POINTS);
+        Ok(ComputedRoute {
+            encoded_polyline: encode_polyline(&points),
+            distance_meters: Some(haversine_distance(&from, &to).round() as u64),
+            duration: None,
+            transit_steps: Vec::new(),
+        })
+    }
 
+    async fn resolve_point(&self, field: &str, text: &str, req: &RouteRequest) -> Result<LatLng, Error> {
+        let resolved = self
+            .resolve(&ResolveRequest {
+                location: text.to_string(),
+                limit: Some(1),
+                language: req.language.clone(),
+                region: req.region.clone(),
+            })
+            .await?;
+        resolved
+            .places
+            .into_iter()
+            .find_map(|p| p.location)
+            .ok_or_else(|| Error::Validation {
+                field: field.into(),
+                message: format!("could not resolve '{}' to a location", text),
+            })
+    }
+
+    async fn search_along_route(
+        &self,
+        req: &RouteRequest,
+        route_index: usize,
+        route: ComputedRoute,
+        route_source: RouteSource,
+    ) -> Result<RouteSearchResponse, Error> {
         // Step 2: Decode polyline into points
-        let points = decode_polyline(&polyline);
+        let points = decode_polyline(&route.encoded_polyline).map_err(|e| Error::Api {
+            status: 0,
+            message: format!(
+                "route polyline could not be decoded: {} ({} valid points before it)",
+                e,
+                decode_polyline_lenient(&route.encoded_polyline).len()
+            ),
+        })?;
         if points.is_empty() {
             return Err(Error::Api {
                 status: 0,
@@ -41,10 +188,29 @@ impl Client {
 
         // Step 3: Sample waypoints along the route
         let waypoints = sample_waypoints(&points, req.max_waypoints as usize);
+        let path_length: f64 = points.windows(2).map(|w| haversine_distance(&w[0], &w[1])).sum();
+        let route_fraction = |distance: f64| {
+            if path_length > 0.0 {
+                distance / path_length
+            } else {
+                0.0
+            }
+        };
+
+        let mut warnings = Vec::new();
+        if route_source == RouteSource::StraightLine && !req.straight_line {
+            warnings.push(Warning::StraightLineFallback);
+        }
 
         // Step 4: Search near each waypoint
         let mut results = Vec::new();
-        for (idx, wp) in waypoints.iter().enumerate() {
+        for (idx, (wp, distance_from_origin)) in waypoints.iter().enumerate() {
+            let label = if req.label_waypoints {
+                self.locality_label(wp).await
+            } else {
+                None
+            };
+
             let search_req = SearchRequest {
                 query: req.query.clone(),
                 included_type: None,
@@ -58,6 +224,7 @@ impl Client {
                 limit: Some(req.results_per_waypoint),
                 language: req.language.clone(),
                 region: req.region.clone(),
+                include_photos: false,
             };
 
             match self.search(&search_req).await {
@@ -65,138 +232,260 @@ impl Client {
                     results.push(RouteWaypointResult {
                         waypoint: wp.clone(),
                         waypoint_index: idx,
+                        distance_from_origin: *distance_from_origin,
+                        route_fraction: route_fraction(*distance_from_origin),
+                        label,
                         places: resp.places,
                     });
                 }
-                Err(_) => {
-                    // Skip waypoints that fail (e.g., no results in that area)
+                Err(e) => {
+                    // Keep the waypoint, without places, and say why
+                    warnings.push(Warning::WaypointSearch {
+                        waypoint_index: idx,
+                        label: label.clone(),
+                        error: e.to_string(),
+                    });
                     results.push(RouteWaypointResult {
                         waypoint: wp.clone(),
                         waypoint_index: idx,
+                        distance_from_origin: *distance_from_origin,
+                        route_fraction: route_fraction(*distance_from_origin),
+                        label,
                         places: vec![],
                     });
                 }
             }
         }
 
+        // Optional geometry output
+        let geometry = match req.simplify_tolerance {
+            Some(tolerance) => simplify(&points, tolerance),
+            None => points,
+        };
+        let polyline = if !req.include_polyline {
+            None
+        } else if req.simplify_tolerance.is_some() {
+            Some(encode_polyline(&geometry))
+        } else {
+            Some(route.encoded_polyline)
+        };
+        let path = req.include_path.then_some(geometry);
+
         Ok(RouteSearchResponse {
-            from: req.from.clone(),
-            to: req.to.clone(),
-            travel_mode: req.travel_mode.as_api_str().to_string(),
+            from: req
+                .from_resolved
+                .as_ref()
+                .map_or_else(|| req.from.clone(), |e| e.label.clone()),
+            to: req
+                .to_resolved
+                .as_ref()
+                .map_or_else(|| req.to.clone(), |e| e.label.clone()),
+            travel_mode: req.travel_mode,
+            route_source,
+            route_index,
+            distance_meters: route.distance_meters,
+            duration_seconds: route
+                .duration
+                .as_deref()
+                .and_then(parse_duration_s)
+                .map(|d| d.as_secs_f64().round() as u64),
+            duration: route.duration,
+            transit_legs: route.transit_steps,
+            polyline,
+            path,
             waypoints: results,
+            warnings,
         })
     }
 
-    async fn compute_route_polyline(&self, req: &RouteRequest) -> Result<String, Error> {
+    /// Reverse-resolve a point to its locality name using the closest place's
+    /// address components. Any failure yields None so callers fall back to coordinates.
+    async fn locality_label(&self, point: &LatLng) -> Option<String> {
         let body = json!({
-            "origin": {
-                "address": req.from,
+            "locationRestriction": {
+                "circle": {
+                    "center": { "latitude": point.latitude, "longitude": point.longitude },
+                    "radius": LABEL_SEARCH_RADIUS,
+                }
             },
-            "destination": {
-                "address": req.to,
+            "maxResultCount": 1,
+            "rankPreference": "DISTANCE",
+        });
+
+        let result = self
+            .places_post("/places:searchNearby", "places.addressComponents", &body)
+            .await
+            .ok()?;
+
+        let components = result["places"].get(0)?["addressComponents"].as_array()?;
+        LOCALITY_TYPES.iter().find_map(|wanted| {
+            components.iter().find_map(|c| {
+                let types = c["types"].as_array()?;
+                if types.iter().any(|t| t.as_str() == Some(wanted)) {
+                    c["longText"].as_str().map(String::from)
+                } else {
+                    None
+                }
+            })
+        })
+    }
+
+    /// Distance and travel time from a coordinate to a place, without the
+    /// polyline or an along-route search
+    pub async fn route_summary(
+        &self,
+        origin: &LatLng,
+        destination_place_id: &str,
+        mode: TravelMode,
+    ) -> Result<RouteSummary, Error> {
+        if destination_place_id.is_empty() {
+            return Err(Error::Validation {
+                field: "destination".into(),
+                message: "destination place ID is required".into(),
+            });
+        }
+
+        let body = json!({
+            "origin": {
+                "location": {
+                    "latLng": { "latitude": origin.latitude, "longitude": origin.longitude },
+                },
             },
-            "travelMode": req.travel_mode.as_api_str(),
-            "polylineEncoding": "ENCODED_POLYLINE",
+            "destination": { "placeId": destination_place_id },
+            "travelMode": mode.as_api_str(),
         });
 
         let result = self
-            .routes_post(
-                "/directions/v2:computeRoutes",
-                "routes.polyline.encodedPolyline",
-                &body,
-            )
+            .routes_post("/directions/v2:computeRoutes", ROUTES_SUMMARY_FIELD_MASK, &body)
             .await?;
 
-        // Extract encoded polyline from response
-        result["routes"]
+        let route = result["routes"].get(0).ok_or_else(|| Error::Api {
+            status: 0,
+            message: "no route found to this place".into(),
+        })?;
+        Ok(RouteSummary {
+            distance_meters: route["distanceMeters"].as_u64(),
+            duration_seconds: route["duration"]
+                .as_str()
+                .and_then(parse_duration_s)
+                .map(|d| d.as_secs_f64().round() as u64),
+        })
+    }
+
+    async fn compute_routes(&self, req: &RouteRequest) -> Result<Vec<ComputedRoute>, Error> {
+        let body = build_route_body(req);
+
+        let result = self
+            .routes_post("/directions/v2:computeRoutes", routes_field_mask(req.travel_mode), &body)
+            .await?;
+
+        let routes: Vec<ComputedRoute> = result["routes"]
             .as_array()
-            .and_then(|routes| routes.first())
-            .and_then(|route| route["polyline"]["encodedPolyline"].as_str())
-            .map(|s| s.to_string())
-            .ok_or_else(|| Error::Api {
+            .map(|routes| {
+                routes
+                    .iter()
+                    .filter_map(|route| {
+                        Some(ComputedRoute {
+                            encoded_polyline: route["polyline"]["encodedPolyline"]
+                                .as_str()?
+                                .to_string(),
+                            distance_meters: route["distanceMeters"].as_u64(),
+                            duration: route["duration"].as_str().map(String::from),
+                            transit_steps: parse_transit_steps(route),
+                        })
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        if routes.is_empty() {
+            return Err(Error::Api {
                 status: 0,
                 message: "no route found between origin and destination".into(),
-            })
+            });
+        }
+        Ok(routes)
     }
 }
 
-/// Decode a Google encoded polyline string into a list of LatLng points
-fn decode_polyline(encoded: &str) -> Vec<LatLng> {
-    let mut points = Vec::new();
-    let mut lat: i64 = 0;
-    let mut lng: i64 = 0;
-    let bytes = encoded.as_bytes();
-    let mut i = 0;
-
-    while i < bytes.len() {
-        // Decode latitude
-        let mut shift = 0u32;
-        let mut result: i64 = 0;
-        loop {
-            if i >= bytes.len() {
-                break;
-            }
-            let b = (bytes[i] as i64) - 63;
-            i += 1;
-            result |= (b & 0x1f) << shift;
-            shift += 5;
-            if b < 0x20 {
-                break;
-            }
-        }
-        lat += if result & 1 != 0 {
-            !(result >> 1)
-        } else {
-            result >> 1
-        };
-
-        // Decode longitude
-        shift = 0;
-        result = 0;
-        loop {
-            if i >= bytes.len() {
-                break;
-            }
-            let b = (bytes[i] as i64) - 63;
-            i += 1;
-            result |= (b & 0x1f) << shift;
-            shift += 5;
-            if b < 0x20 {
-                break;
-            }
-        }
-        lng += if result & 1 != 0 {
-            !(result >> 1)
-        } else {
-            result >> 1
-        };
+/// Collect the transitDetails of every step across all legs of a route
+fn parse_transit_steps(route: &Value) -> Vec<TransitDetails> {
+    route["legs"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|leg| leg["steps"].as_array())
+        .flatten()
+        .filter_map(|step| {
+            let details = step.get("transitDetails")?;
+            serde_json::from_value(details.clone()).ok()
+        })
+        .collect()
+}
 
-        points.push(LatLng {
-            latitude: lat as f64 / 1e5,
-            longitude: lng as f64 / 1e5,
+fn validate_route_request(req: &RouteRequest) -> Result<(), Error> {
+    if req.query.is_empty() {
+        return Err(Error::Validation {
+            field: "query".into(),
+            message: "query is required".into(),
+        });
+    }
+    if req.from.is_empty() {
+        return Err(Error::Validation {
+            field: "from".into(),
+            message: "origin is required".into(),
         });
     }
+    if req.to.is_empty() {
+        return Err(Error::Validation {
+            field: "to".into(),
+            message: "destination is required".into(),
+        });
+    }
+    Ok(())
+}
 
-    points
+/// Field mask for computeRoutes; transit adds the steps' line and stop details
+pub(crate) fn routes_field_mask(mode: TravelMode) -> &'static str {
+    if mode == TravelMode::Transit {
+        ROUTES_TRANSIT_FIELD_MASK
+    } else {
+        ROUTES_FIELD_MASK
+    }
 }
 
-/// Haversine distance in meters between two points
-fn haversine_distance(a: &LatLng, b: &LatLng) -> f64 {
-    const R: f64 = 6_371_000.0; // Earth radius in meters
-    let d_lat = (b.latitude - a.latitude).to_radians();
-    let d_lng = (b.longitude - a.longitude).to_radians();
-    let lat1 = a.latitude.to_radians();
-    let lat2 = b.latitude.to_radians();
-
-    let a_val = (d_lat / 2.0).sin().powi(2) + lat1.cos() * lat2.cos() * (d_lng / 2.0).sin().powi(2);
-    let c = 2.0 * a_val.sqrt().asin();
-    R * c
+/// Build the JSON body for POST /directions/v2:computeRoutes
+pub(crate) fn build_route_body(req: &RouteRequest) -> Value {
+    let endpoint = |text: &str, resolved: &Option<ResolvedEndpoint>| match resolved {
+        Some(e) => json!({ "placeId": e.place_id }),
+        None => json!({ "address": text }),
+    };
+    let mut body = json!({
+        "origin": endpoint(&req.from, &req.from_resolved),
+        "destination": endpoint(&req.to, &req.to_resolved),
+        "travelMode": req.travel_mode.as_api_str(),
+        "polylineEncoding": "ENCODED_POLYLINE",
+        "units": req.units.as_api_str(),
+    });
+
+    if let Some(ref lang) = req.language {
+        body["languageCode"] = json!(lang);
+    }
+    if let Some(ref region) = req.region {
+        body["regionCode"] = json!(region);
+    }
+    if req.alternatives {
+        body["computeAlternativeRoutes"] = json!(true);
+    }
+
+    body
 }
 
-/// Sample N evenly-spaced waypoints along a polyline path
-fn sample_waypoints(points: &[LatLng], n: usize) -> Vec<LatLng> {
+/// Sample N evenly-spaced waypoints along a polyline path, paired with
+/// each waypoint's distance from the start of the path in meters
+fn sample_waypoints(points: &[LatLng], n: usize) -> Vec<(LatLng, f64)> {
     if points.len() <= 1 || n == 0 {
-        return points.to_vec();
+        return points.iter().map(|p| (p.clone(), 0.0)).collect();
     }
 
     // Compute cumulative distances
@@ -208,7 +497,7 @@ fn sample_waypoints(points: &[LatLng], n: usize) -> Vec<LatLng> {
 
     let total_distance = *cumulative.last().unwrap();
     if total_distance == 0.0 {
-        return vec![points[0].clone()];
+        return vec![(points[0].clone(), 0.0)];
     }
 
     let mut waypoints = Vec::with_capacity(n);
@@ -225,16 +514,345 @@ fn sample_waypoints(points: &[LatLng], n: usize) -> Vec<LatLng> {
         let seg_len = cumulative[seg + 1] - seg_start;
 
         if seg_len == 0.0 {
-            waypoints.push(points[seg].clone());
+            waypoints.push((points[seg].clone(), target));
         } else {
             let t = (target - seg_start) / seg_len;
-            waypoints.push(LatLng {
+            let point = LatLng {
                 latitude: points[seg].latitude + t * (points[seg + 1].latitude - points[seg].latitude),
                 longitude: points[seg].longitude
                     + t * (points[seg + 1].longitude - points[seg].longitude),
-            });
+            };
+            waypoints.push((point, target));
         }
     }
 
     waypoints
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    use super::*;
+    use crate::api::mock::MockApi;
+    use crate::api::types::Units;
+
+    fn request() -> RouteRequest {
+        RouteRequest {
+            query: "coffee".into(),
+            from: "Vienna".into(),
+            to: "Graz".into(),
+            travel_mode: TravelMode::Drive,
+            search_radius: 1000.0,
+            max_waypoints: 5,
+            results_per_waypoint: 3,
+            language: None,
+            region: None,
+            units: Units::Metric,
+            alternatives: false,
+            route_index: 0,
+            label_waypoints: false,
+            straight_line: false,
+            include_polyline: false,
+            include_path: false,
+            simplify_tolerance: None,
+            from_resolved: None,
+            to_resolved: None,
+        }
+    }
+
+    #[test]
+    fn options_off_routes_between_addresses() {
+        assert_eq!(
+            build_route_body(&request()),
+            json!({
+                "origin": { "address": "Vienna" },
+                "destination": { "address": "Graz" },
+                "travelMode": "DRIVE",
+                "polylineEncoding": "ENCODED_POLYLINE",
+                "units": "METRIC",
+            })
+        );
+    }
+
+    #[test]
+    fn options_on_are_all_sent() {
+        let req = RouteRequest {
+            travel_mode: TravelMode::TwoWheeler,
+            language: Some("de".into()),
+            region: Some("at".into()),
+            units: Units::Imperial,
+            alternatives: true,
+            ..request()
+        };
+        assert_eq!(
+            build_route_body(&req),
+            json!({
+                "origin": { "address": "Vienna" },
+                "destination": { "address": "Graz" },
+                "travelMode": "TWO_WHEELER",
+                "polylineEncoding": "ENCODED_POLYLINE",
+                "units": "IMPERIAL",
+                "languageCode": "de",
+                "regionCode": "at",
+                "computeAlternativeRoutes": true,
+            })
+        );
+    }
+
+    #[test]
+    fn resolved_endpoints_route_by_place_id() {
+        let req = RouteRequest {
+            from_resolved: Some(ResolvedEndpoint {
+                place_id: "ChIJ-origin".into(),
+                label: "Vienna".into(),
+                location: None,
+            }),
+            ..request()
+        };
+        let body = build_route_body(&req);
+        assert_eq!(body["origin"], json!({ "placeId": "ChIJ-origin" }));
+        assert_eq!(body["destination"], json!({ "address": "Graz" }));
+    }
+
+
+    /// One U4 ride in Vienna, as computeRoutes returns a transit step
+    fn u4_ride() -> Value {
+        json!({
+            "stopDetails": {
+                "departureStop": { "name": "Schwedenplatz" },
+                "arrivalStop": { "name": "Karlsplatz" },
+                "departureTime": "2026-10-15T08:12:00Z",
+                "arrivalTime": "2026-10-15T08:19:00Z",
+            },
+            "localizedValues": {
+                "departureTime": { "time": { "text": "10:12" }, "timeZone": "Europe/Vienna" },
+                "arrivalTime": { "time": { "text": "10:19" }, "timeZone": "Europe/Vienna" },
+            },
+            "headsign": "Hütteldorf",
+            "stopCount": 3,
+            "transitLine": {
+                "name": "Heiligenstadt - Hütteldorf",
+                "nameShort": "U4",
+                "vehicle": { "name": { "text": "Subway" }, "type": "SUBWAY" },
+            },
+        })
+    }
+
+    #[test]
+    fn transit_step_parses_line_headsign_stops_and_departure() {
+        let route = json!({ "legs": [{ "steps": [
+            { "travelMode": "WALK", "distanceMeters": 240 },
+            { "travelMode": "TRANSIT", "transitDetails": u4_ride() },
+            { "travelMode": "WALK", "distanceMeters": 90 },
+        ] }] });
+
+        let steps = parse_transit_steps(&route);
+
+        assert_eq!(steps.len(), 1);
+        let ride = &steps[0];
+        assert_eq!(ride.line_name(), Some("U4"));
+        assert_eq!(ride.headsign.as_deref(), Some("Hütteldorf"));
+        assert_eq!(ride.stop_count, Some(3));
+        assert_eq!(ride.departure_display(), Some("10:12"));
+        let stops = ride.stop_details.as_ref().unwrap();
+        assert_eq!(stops.departure_stop.as_ref().unwrap().name.as_deref(), Some("Schwedenplatz"));
+        assert_eq!(stops.arrival_stop.as_ref().unwrap().name.as_deref(), Some("Karlsplatz"));
+    }
+
+    #[test]
+    fn departure_falls_back_to_the_timestamp() {
+        let mut ride = u4_ride();
+        ride.as_object_mut().unwrap().remove("localizedValues");
+        let route = json!({ "legs": [{ "steps": [{ "transitDetails": ride }] }] });
+
+        assert_eq!(parse_transit_steps(&route)[0].departure_display(), Some("2026-10-15T08:12:00Z"));
+    }
+
+    #[test]
+    fn steps_without_transit_details_parse_to_nothing() {
+        let drive = json!({ "legs": [{ "steps": [
+            { "travelMode": "DRIVE", "distanceMeters": 1200, "navigationInstruction": { "maneuver": "TURN_LEFT" } },
+        ] }] });
+        assert!(parse_transit_steps(&drive).is_empty());
+        assert!(parse_transit_steps(&json!({ "legs": [] })).is_empty());
+        assert!(parse_transit_steps(&json!({})).is_empty());
+    }
+
+    #[test]
+    fn malformed_transit_details_are_skipped() {
+        let mut bad = u4_ride();
+        bad["stopCount"] = json!("three");
+        let route = json!({ "legs": [
+            { "steps": [{ "transitDetails": bad }] },
+            { "steps": [{ "transitDetails": u4_ride() }] },
+        ] });
+
+        let steps = parse_transit_steps(&route);
+        assert_eq!(steps.len(), 1);
+        assert_eq!(steps[0].stop_count, Some(3));
+    }
+
+    /// A client whose Routes API answers with one transit route taking the
+    /// U4, and whose searches find nothing
+    async fn transit_route() -> (MockApi, Client) {
+        let api = MockApi::start(Duration::ZERO, |seen| {
+            if !seen.path.contains("computeRoutes") {
+                return (200, json!({}));
+            }
+            let steps = json!([
+                { "travelMode": "WALK" },
+                { "travelMode": "TRANSIT", "transitDetails": u4_ride() },
+            ]);
+            let route = json!({
+                "polyline": { "encodedPolyline": "_p~iF~ps|U_ulLnnqC_mqNvxq`@" },
+                "duration": "1320s",
+                "legs": [{ "steps": steps }],
+            });
+            (200, json!({ "routes": [route] }))
+        })
+        .await;
+        let client = Client::new("test-key".into())
+            .unwrap()
+            .with_routes_base_url(api.url())
+            .with_places_base_url(api.url());
+        (api, client)
+    }
+
+    #[tokio::test]
+    async fn transit_route_asks_for_and_returns_its_legs() {
+        let (api, client) = transit_route().await;
+        let req = RouteRequest {
+            travel_mode: TravelMode::Transit,
+            max_waypoints: 1,
+            ..request()
+        };
+
+        let resp = client.route_search(&req).await.unwrap();
+
+        let sent = &api.requests()[0];
+        assert_eq!(sent.path, "/directions/v2:computeRoutes");
+        assert_eq!(sent.body, build_route_body(&req));
+        assert_eq!(sent.body["travelMode"], "TRANSIT");
+        let mask = sent.field_mask.as_deref().unwrap();
+        assert!(mask.split(',').any(|f| f == "routes.legs.steps.transitDetails"), "{}", mask);
+
+        assert_eq!(resp.transit_legs.len(), 1);
+        assert_eq!(resp.transit_legs[0].line_name(), Some("U4"));
+        assert_eq!(resp.duration_seconds, Some(1320));
+    }
+
+    #[tokio::test]
+    async fn other_modes_leave_transit_details_out() {
+        let (api, client) = transit_route().await;
+        for mode in [TravelMode::Drive, TravelMode::Walk] {
+            let req = RouteRequest {
+                travel_mode: mode,
+                max_waypoints: 1,
+                ..request()
+            };
+            client.route_search(&req).await.unwrap();
+        }
+
+        let masks: Vec<String> = api
+            .requests()
+            .into_iter()
+            .filter(|r| r.path.contains("computeRoutes"))
+            .map(|r| r.field_mask.unwrap_or_default())
+            .collect();
+        assert_eq!(masks.len(), 2);
+        for mask in masks {
+            assert!(!mask.contains("transitDetails"), "{}", mask);
+            assert!(mask.split(',').any(|f| f == "routes.polyline.encodedPolyline"), "{}", mask);
+        }
+    }
+
+    /// A client whose Routes API answers with one route along `polyline`
+    async fn route_with_polyline(polyline: &'static str) -> (MockApi, Client) {
+        let api = MockApi::start(Duration::ZERO, move |_| {
+            (200, json!({ "routes": [{ "polyline": { "encodedPolyline": polyline } }] }))
+        })
+        .await;
+        let client = Client::new("test-key".into()).unwrap().with_routes_base_url(api.url());
+        (api, client)
+    }
+
+    #[tokio::test]
+    async fn failed_waypoint_search_keeps_the_waypoint_with_a_warning() {
+        let searches = AtomicUsize::new(0);
+        let api = MockApi::start(Duration::ZERO, move |seen| {
+            if seen.path.contains("computeRoutes") {
+                let route = json!({ "polyline": { "encodedPolyline": "_p~iF~ps|U_ulLnnqC_mqNvxq`@" } });
+                return (200, json!({ "routes": [route] }));
+            }
+            // The second waypoint's search fails
+            match searches.fetch_add(1, Ordering::SeqCst) {
+                1 => (500, json!({ "error": { "code": 500, "message": "Internal error encountered." } })),
+                n => (200, json!({ "places": [{ "id": format!("p{}", n), "displayName": { "text": "Cafe" } }] })),
+            }
+        })
+        .await;
+        let client = Client::new("test-key".into())
+            .unwrap()
+            .with_routes_base_url(api.url())
+            .with_places_base_url(api.url());
+
+        let req = RouteRequest {
+            max_waypoints: 3,
+            ..request()
+        };
+        let resp = client.route_search(&req).await.unwrap();
+
+        let places: Vec<usize> = resp.waypoints.iter().map(|w| w.places.len()).collect();
+        assert_eq!(places, [1, 0, 1]);
+        assert_eq!(resp.waypoints[1].waypoint_index, 1);
+        assert_eq!(resp.warnings.len(), 1);
+        let Warning::WaypointSearch {
+            waypoint_index,
+            label,
+            error,
+        } = &resp.warnings[0]
+        else {
+            panic!("expected a waypoint warning, got {:?}", resp.warnings);
+        };
+        assert_eq!((*waypoint_index, label.as_deref()), (1, None));
+        assert!(error.contains("Internal error encountered."), "{}", error);
+
+        // `--json` output carries it
+        let out = serde_json::to_value(&resp).unwrap();
+        assert_eq!(out["_warnings"][0]["kind"], "waypoint_search");
+        assert_eq!(out["_warnings"][0]["waypoint_index"], 1);
+        assert_eq!(out["_warnings"][0]["error"], json!(error));
+        assert_eq!(out["waypoints"][1]["places"], json!([]));
+    }
+
+    #[tokio::test]
+    async fn truncated_polyline_is_an_api_error() {
+        let (api, client) = route_with_polyline("_p~iF~ps|U_ulLnnqC_mqNvxq`").await;
+
+        match client.route_search(&request()).await {
+            Err(Error::Api { status: 0, message }) => assert_eq!(
+                message,
+                "route polyline could not be decoded: input ends mid-value at byte 26 (2 valid points before it)"
+            ),
+            other => panic!("expected an API error, got {:?}", other),
+        }
+        // No searches along a broken route
+        assert_eq!(api.hits(), 1);
+    }
+
+    #[tokio::test]
+    async fn out_of_range_polyline_is_an_api_error() {
+        // One point at latitude 95
+        let (_api, client) = route_with_polyline("_uybQ?").await;
+
+        match client.route_search(&request()).await {
+            Err(Error::Api { status: 0, message }) => assert_eq!(
+                message,
+                "route polyline could not be decoded: point 0 (95, 0) is out of range (0 valid points before it)"
+            ),
+            other => panic!("expected an API error, got {:?}", other),
+        }
+    }
+}
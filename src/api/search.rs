@@ -2,12 +2,21 @@ use serde_json::{json, Value};
 
 use super::client::Client;
 use super::errors::Error;
-use super::types::{SearchRequest, SearchResponse};
+use super::limits::{clamp_limit, MAX_SEARCH_RESULTS};
+use super::types::{PlaceCount, SearchRequest, SearchResponse};
 
 const SEARCH_FIELD_MASK: &str = "places.id,places.displayName,places.formattedAddress,\
 places.shortFormattedAddress,places.types,places.primaryType,places.primaryTypeDisplayName,\
 places.location,places.rating,places.userRatingCount,places.priceLevel,\
-places.websiteUri,places.googleMapsUri,places.businessStatus,places.editorialSummary";
+places.websiteUri,places.googleMapsUri,places.businessStatus,places.editorialSummary,\
+places.currentOpeningHours,places.utcOffsetMinutes,places.postalAddress";
+
+/// Count-only mask: IDs plus the token needed to page, the cheapest SKU
+const SEARCH_COUNT_FIELD_MASK: &str = "places.id,nextPageToken";
+
+/// searchText returns at most 3 pages of 20 results
+const MAX_SEARCH_PAGES: usize = 3;
+const PAGE_SIZE: u32 = 20;
 
 impl Client {
     pub async fn search(&self, req: &SearchRequest) -> Result<SearchResponse, Error> {
@@ -17,52 +26,180 @@ impl Client {
                 message: "query is required".into(),
             });
         }
+        if let Some(ref loc) = req.location {
+            loc.validate()?;
+        }
 
-        let mut body = json!({
-            "textQuery": req.query,
-        });
+        let body = build_search_body(req);
+        let field_mask = search_field_mask(req);
 
-        if let Some(ref t) = req.included_type {
-            body["includedType"] = json!(t);
-        }
-        if let Some(min) = req.min_rating {
-            body["minRating"] = json!(min);
-        }
-        if !req.price_levels.is_empty() {
-            body["priceLevels"] = json!(req.price_levels);
-        }
-        if req.open_now {
-            body["openNow"] = json!(true);
-        }
-        if let Some(ref loc) = req.location {
-            body["locationBias"] = json!({
-                "circle": {
-                    "center": { "latitude": loc.center.latitude, "longitude": loc.center.longitude },
-                    "radius": loc.radius,
-                }
+        let result = self
+            .places_post("/places:searchText", &field_mask, &body)
+            .await?;
+
+        let places = self.parse_places_lenient(result, "search")?;
+        Ok(SearchResponse { places })
+    }
+
+    /// Count matching places, following page tokens up to the API maximum
+    pub async fn search_count(&self, req: &SearchRequest) -> Result<PlaceCount, Error> {
+        if req.query.is_empty() {
+            return Err(Error::Validation {
+                field: "query".into(),
+                message: "query is required".into(),
             });
         }
-        if let Some(limit) = req.limit {
-            body["maxResultCount"] = json!(limit.min(20));
+        if let Some(ref loc) = req.location {
+            loc.validate()?;
         }
-        if let Some(ref lang) = req.language {
-            body["languageCode"] = json!(lang);
+
+        let mut body = build_search_body(req);
+        if let Some(obj) = body.as_object_mut() {
+            obj.remove("maxResultCount");
         }
-        if let Some(ref region) = req.region {
-            body["regionCode"] = json!(region);
+        body["pageSize"] = json!(PAGE_SIZE);
+
+        let mut count = 0;
+        let mut requests = 0;
+        let mut next_page = None;
+        while requests < MAX_SEARCH_PAGES {
+            if let Some(token) = next_page.take() {
+                body["pageToken"] = Value::String(token);
+            }
+            let result = self
+                .places_post("/places:searchText", SEARCH_COUNT_FIELD_MASK, &body)
+                .await?;
+            requests += 1;
+
+            count += result
+                .get("places")
+                .and_then(Value::as_array)
+                .map_or(0, Vec::len);
+            next_page = result
+                .get("nextPageToken")
+                .and_then(Value::as_str)
+                .filter(|t| !t.is_empty())
+                .map(String::from);
+            if next_page.is_none() {
+                break;
+            }
         }
 
-        let result = self
-            .places_post("/places:searchText", SEARCH_FIELD_MASK, &body)
-            .await?;
+        Ok(PlaceCount {
+            count,
+            capped: next_page.is_some(),
+            field_mask: SEARCH_COUNT_FIELD_MASK,
+            requests,
+        })
+    }
+}
 
-        parse_search_response(result)
+/// Field mask for a search; photo references are a pricier SKU, so only
+/// when asked for
+pub(crate) fn search_field_mask(req: &SearchRequest) -> String {
+    if req.include_photos {
+        format!("{},places.photos", SEARCH_FIELD_MASK)
+    } else {
+        SEARCH_FIELD_MASK.to_string()
     }
 }
 
-fn parse_search_response(value: Value) -> Result<SearchResponse, Error> {
-    serde_json::from_value(value).map_err(|e| Error::Api {
-        status: 0,
-        message: format!("failed to parse search response: {}", e),
-    })
+/// Build the JSON body for POST /places:searchText
+pub(crate) fn build_search_body(req: &SearchRequest) -> Value {
+    let mut body = json!({
+        "textQuery": req.query,
+    });
+
+    if let Some(ref t) = req.included_type {
+        body["includedType"] = json!(t);
+    }
+    if let Some(min) = req.min_rating {
+        body["minRating"] = json!(min);
+    }
+    if !req.price_levels.is_empty() {
+        body["priceLevels"] = json!(req.price_levels);
+    }
+    if req.open_now {
+        body["openNow"] = json!(true);
+    }
+    if let Some(ref loc) = req.location {
+        body["locationBias"] = json!({
+            "circle": {
+                "center": { "latitude": loc.center.latitude, "longitude": loc.center.longitude },
+                "radius": loc.radius,
+            }
+        });
+    }
+    if let Some(limit) = req.limit {
+        body["maxResultCount"] = json!(clamp_limit(limit, MAX_SEARCH_RESULTS).0);
+    }
+    if let Some(ref lang) = req.language {
+        body["languageCode"] = json!(lang);
+    }
+    if let Some(ref region) = req.region {
+        body["regionCode"] = json!(region);
+    }
+
+    body
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(fields: Value) -> SearchRequest {
+        serde_json::from_value(fields).unwrap()
+    }
+
+    #[test]
+    fn filters_off_sends_only_the_query() {
+        let req = request(json!({ "query": "ramen" }));
+        assert_eq!(build_search_body(&req), json!({ "textQuery": "ramen" }));
+    }
+
+    #[test]
+    fn filters_on_are_all_sent() {
+        let req = request(json!({
+            "query": "ramen",
+            "includedType": "restaurant",
+            "minRating": 4.0,
+            "priceLevels": ["PRICE_LEVEL_INEXPENSIVE", "PRICE_LEVEL_MODERATE"],
+            "openNow": true,
+            "location": { "center": { "latitude": 48.2, "longitude": 16.37 }, "radius": 1500.0 },
+            "limit": 5,
+            "language": "de",
+            "region": "at",
+        }));
+        assert_eq!(
+            build_search_body(&req),
+            json!({
+                "textQuery": "ramen",
+                "includedType": "restaurant",
+                "minRating": 4.0,
+                "priceLevels": ["PRICE_LEVEL_INEXPENSIVE", "PRICE_LEVEL_MODERATE"],
+                "openNow": true,
+                "locationBias": {
+                    "circle": { "center": { "latitude": 48.2, "longitude": 16.37 }, "radius": 1500.0 }
+                },
+                "maxResultCount": 5,
+                "languageCode": "de",
+                "regionCode": "at",
+            })
+        );
+    }
+
+    #[test]
+    fn limit_is_clamped_to_the_endpoint_maximum() {
+        let req = request(json!({ "query": "ramen", "limit": 60 }));
+        assert_eq!(build_search_body(&req)["maxResultCount"], json!(MAX_SEARCH_RESULTS));
+    }
+
+    #[test]
+    fn photos_widen_the_field_mask() {
+        let plain = search_field_mask(&request(json!({ "query": "ramen" })));
+        let photos = search_field_mask(&request(json!({ "query": "ramen", "includePhotos": true })));
+        assert_eq!(plain, SEARCH_FIELD_MASK);
+        assert!(!plain.contains("places.photos"));
+        assert_eq!(photos, format!("{},places.photos", SEARCH_FIELD_MASK));
+    }
 }
@@ -0,0 +1,152 @@
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Token bucket pacing requests to a maximum rate. Shared by every request a
+/// `Client` sends, so concurrent tasks are limited together.
+#[derive(Debug)]
+pub struct RateLimiter {
+    qps: f64,
+    /// Tokens that can be saved up: one second's worth, at least one
+    burst: f64,
+    bucket: Mutex<Bucket>,
+}
+
+#[derive(Debug)]
+struct Bucket {
+    /// Negative when requests have reserved tokens that haven't accrued yet
+    tokens: f64,
+    refilled: Instant,
+}
+
+impl RateLimiter {
+    pub fn new(qps: f64) -> Self {
+        let burst = qps.max(1.0).floor();
+        RateLimiter {
+            qps,
+            burst,
+            bucket: Mutex::new(Bucket {
+                tokens: burst,
+                refilled: Instant::now(),
+            }),
+        }
+    }
+
+    pub fn qps(&self) -> f64 {
+        self.qps
+    }
+
+    /// Take a token, returning how long the caller must wait for it.
+    /// Tokens are reserved in call order, so waiters don't race each other.
+    fn reserve(&self, now: Instant) -> Duration {
+        let mut bucket = self.bucket.lock().unwrap_or_else(|e| e.into_inner());
+        let accrued = now.saturating_duration_since(bucket.refilled).as_secs_f64() * self.qps;
+        bucket.tokens = (bucket.tokens + accrued).min(self.burst);
+        bucket.refilled = now;
+        bucket.tokens -= 1.0;
+        if bucket.tokens >= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(-bucket.tokens / self.qps)
+        }
+    }
+
+    /// Wait for a token; returns how long that took
+    pub async fn acquire(&self) -> Duration {
+        let delay = self.reserve(Instant::now());
+        if !delay.is_zero() {
+            tokio::time::sleep(delay).await;
+        }
+        delay
+    }
+}
+
+/// Parse a `--qps` / `network.max_qps` value: a positive, finite number
+pub fn parse_qps(text: &str) -> Result<f64, String> {
+    match text.trim().parse::<f64>() {
+        Ok(qps) if qps.is_finite() && qps > 0.0 => Ok(qps),
+        _ => Err(format!("'{}' is not a positive number of requests per second", text)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A limiter and the instant its bucket was last full
+    fn limiter(qps: f64) -> (RateLimiter, Instant) {
+        let limiter = RateLimiter::new(qps);
+        let start = Instant::now();
+        (limiter, start)
+    }
+
+    fn ms(millis: u64) -> Duration {
+        Duration::from_millis(millis)
+    }
+
+    /// Waits for `n` requests all made at `now`
+    fn burst(limiter: &RateLimiter, now: Instant, n: usize) -> Vec<Duration> {
+        (0..n).map(|_| limiter.reserve(now)).collect()
+    }
+
+    #[test]
+    fn bursts_up_to_qps_then_paces() {
+        let (limiter, start) = limiter(5.0);
+        assert_eq!(
+            burst(&limiter, start, 8),
+            [[Duration::ZERO; 5].as_slice(), &[ms(200), ms(400), ms(600)]].concat()
+        );
+    }
+
+    #[test]
+    fn tokens_refill_at_qps() {
+        let (limiter, start) = limiter(5.0);
+        burst(&limiter, start, 5);
+
+        assert_eq!(limiter.reserve(start + ms(200)), Duration::ZERO);
+        assert_eq!(limiter.reserve(start + ms(300)), ms(100));
+        // A full second refills the whole burst
+        assert_eq!(burst(&limiter, start + ms(1400), 5), [Duration::ZERO; 5]);
+        assert_eq!(limiter.reserve(start + ms(1400)), ms(200));
+    }
+
+    #[test]
+    fn idle_time_saves_at_most_one_burst() {
+        let (limiter, start) = limiter(5.0);
+        let later = start + Duration::from_secs(60);
+        assert_eq!(burst(&limiter, later, 5), [Duration::ZERO; 5]);
+        assert_eq!(limiter.reserve(later), ms(200));
+    }
+
+    #[test]
+    fn waiting_requests_queue_in_call_order() {
+        let (limiter, start) = limiter(2.0);
+        burst(&limiter, start, 2);
+
+        // Each reservation waits behind the ones before it, less the time passed
+        assert_eq!(limiter.reserve(start), ms(500));
+        assert_eq!(limiter.reserve(start + ms(250)), ms(750));
+        assert_eq!(limiter.reserve(start + ms(1000)), ms(500));
+    }
+
+    #[test]
+    fn fractional_qps_bursts_one() {
+        let (limiter, start) = limiter(0.5);
+        assert_eq!(limiter.reserve(start), Duration::ZERO);
+        assert_eq!(limiter.reserve(start), Duration::from_secs(2));
+        assert_eq!(limiter.reserve(start + Duration::from_secs(1)), Duration::from_secs(3));
+    }
+
+    #[test]
+    fn qps_must_be_positive_and_finite() {
+        assert_eq!(parse_qps("5"), Ok(5.0));
+        assert_eq!(parse_qps(" 0.5 "), Ok(0.5));
+        assert!(parse_qps("0").is_err());
+        assert!(parse_qps("-1").is_err());
+        assert!(parse_qps("inf").is_err());
+        assert!(parse_qps("NaN").is_err());
+        assert_eq!(
+            parse_qps("fast"),
+            Err("'fast' is not a positive number of requests per second".into())
+        );
+    }
+}
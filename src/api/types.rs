@@ -1,4 +1,10 @@
+use std::fmt;
+use std::sync::Arc;
+
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use super::errors::Error;
 
 // ─── Common types ───────────────────────────────────────────────────────────
 
@@ -15,6 +21,110 @@ pub struct Circle {
     pub radius: f64,
 }
 
+/// Largest circle radius the Places API accepts, for location bias and restriction
+pub const MAX_CIRCLE_RADIUS: f64 = 50_000.0;
+
+impl Circle {
+    /// Check the circle before it goes into a request body: finite
+    /// coordinates in range and a radius in (0, `MAX_CIRCLE_RADIUS`] meters.
+    /// NaN fails every check.
+    pub fn validate(&self) -> Result<(), Error> {
+        validate_coords(self.center.latitude, self.center.longitude)?;
+        validate_radius(self.radius)
+    }
+}
+
+pub(crate) fn validate_coords(lat: f64, lng: f64) -> Result<(), Error> {
+    if !(-90.0..=90.0).contains(&lat) {
+        return Err(Error::Validation {
+            field: "lat".into(),
+            message: "latitude must be between -90 and 90".into(),
+        });
+    }
+    if !(-180.0..=180.0).contains(&lng) {
+        return Err(Error::Validation {
+            field: "lng".into(),
+            message: "longitude must be between -180 and 180".into(),
+        });
+    }
+    Ok(())
+}
+
+pub(crate) fn validate_radius(radius: f64) -> Result<(), Error> {
+    match radius_problem(radius) {
+        Some(message) => Err(Error::Validation {
+            field: "radius".into(),
+            message,
+        }),
+        None => Ok(()),
+    }
+}
+
+/// Why `radius` can't be a circle's radius, if it can't
+pub(crate) fn radius_problem(radius: f64) -> Option<String> {
+    if !radius.is_finite() {
+        Some(format!("radius must be a number of meters, got {}", radius))
+    } else if radius <= 0.0 {
+        Some(format!("radius must be positive, got {}", radius))
+    } else if radius > MAX_CIRCLE_RADIUS {
+        Some(format!(
+            "radius {:.0} m exceeds the maximum of {:.0} m",
+            radius, MAX_CIRCLE_RADIUS
+        ))
+    } else {
+        None
+    }
+}
+
+// ─── Warnings ───────────────────────────────────────────────────────────────
+
+/// A non-fatal problem: the request went through, but with partial results or
+/// a fallback. Reported through `ClientEvent::Warning`, or carried on the
+/// response (`RouteSearchResponse::warnings`) when it concerns part of it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Warning {
+    /// A result, or some of its fields, could not be parsed and was skipped
+    Parse { message: String },
+    /// The Routes API refused the key (403), so the search followed a
+    /// straight line between the endpoints
+    StraightLineFallback,
+    /// The search near one route waypoint failed; the waypoint is listed
+    /// without places
+    WaypointSearch {
+        waypoint_index: usize,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        label: Option<String>,
+        error: String,
+    },
+    /// IP geolocation failed, so no location bias was applied
+    AutoLocate { error: String },
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Warning::Parse { message } => write!(f, "{}", message),
+            Warning::StraightLineFallback => write!(
+                f,
+                "Routes API returned 403; searched along an approximate straight-line route instead"
+            ),
+            Warning::WaypointSearch {
+                waypoint_index,
+                label,
+                error,
+            } => {
+                write!(f, "search near waypoint {}", waypoint_index + 1)?;
+                if let Some(label) = label {
+                    write!(f, " ({})", label)?;
+                }
+                write!(f, " failed, so it has no places: {}", error)
+            }
+            Warning::AutoLocate { error } => write!(f, "auto-locate failed: {}", error),
+        }
+    }
+}
+
 // ─── Place (unified response type) ─────────────────────────────────────────
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -22,95 +132,273 @@ pub struct Circle {
 pub struct Place {
     #[serde(default)]
     pub id: String,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub display_name: Option<DisplayName>,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub formatted_address: Option<String>,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub short_formatted_address: Option<String>,
-    #[serde(default)]
+    /// Structured address; zupo only reads its region code
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub postal_address: Option<PostalAddress>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub types: Option<Vec<String>>,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub primary_type: Option<String>,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub primary_type_display_name: Option<DisplayName>,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub location: Option<LatLng>,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub rating: Option<f64>,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub user_rating_count: Option<u32>,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub price_level: Option<String>,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub website_uri: Option<String>,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub google_maps_uri: Option<String>,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub national_phone_number: Option<String>,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub international_phone_number: Option<String>,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub current_opening_hours: Option<OpeningHours>,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub regular_opening_hours: Option<OpeningHours>,
-    #[serde(default)]
+    /// Offset of the place's local time from UTC, needed to evaluate `periods`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub utc_offset_minutes: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub business_status: Option<String>,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub editorial_summary: Option<EditorialSummary>,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub reviews: Option<Vec<Review>>,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub photos: Option<Vec<Photo>>,
+    /// Top-level fields zupo doesn't model, kept so `--json` output passes
+    /// them through unchanged
+    #[serde(flatten, default, skip_serializing_if = "Map::is_empty")]
+    pub extra: Map<String, Value>,
+    /// The JSON this place was parsed from, as received; only set by clients
+    /// built `with_raw_places` (the TUI's raw JSON popup)
+    #[serde(skip)]
+    pub raw: Option<Arc<Value>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct DisplayName {
     pub text: String,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub language_code: Option<String>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PostalAddress {
+    /// CLDR region code of the address's country, e.g. "AT"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub region_code: Option<String>,
+    /// The lines, locality, postal code, and so on, passed through to `--json`
+    #[serde(flatten, default, skip_serializing_if = "Map::is_empty")]
+    pub rest: Map<String, Value>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct EditorialSummary {
     pub text: Option<String>,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub language_code: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct OpeningHours {
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub open_now: Option<bool>,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub weekday_descriptions: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub periods: Option<Vec<OpeningPeriod>>,
 }
 
+pub const MINUTES_PER_DAY: i64 = 24 * 60;
+pub const MINUTES_PER_WEEK: i64 = 7 * MINUTES_PER_DAY;
+
+/// One opening interval; `close` is absent for places open 24/7
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct Review {
+pub struct OpeningPeriod {
+    pub open: DayTime,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub close: Option<DayTime>,
+}
+
+impl OpeningPeriod {
+    /// The API's 24/7 form: an open point with no close
+    pub fn is_always_open(&self) -> bool {
+        self.close.is_none()
+    }
+
+    /// (open, close) minutes of the week, with close after open even when the
+    /// period crosses midnight or the end of the week. None for 24/7 periods.
+    pub fn interval(&self) -> Option<(i64, i64)> {
+        let open = self.open.minute_of_week();
+        let mut close = self.close.as_ref()?.minute_of_week();
+        if close <= open {
+            close += MINUTES_PER_WEEK;
+        }
+        Some((open, close))
+    }
+
+    /// Minutes from minute-of-week `t` (any integer; taken modulo a week)
+    /// until this period closes, when `t` is in [open, close). None outside
+    /// the period and for 24/7 periods.
+    pub fn minutes_until_close(&self, t: i64) -> Option<i64> {
+        let (open, close) = self.interval()?;
+        let t = t.rem_euclid(MINUTES_PER_WEEK);
+        // The next-week copy covers periods that wrap past Saturday night
+        [t, t + MINUTES_PER_WEEK]
+            .into_iter()
+            .find(|&t| t >= open && t < close)
+            .map(|t| close - t)
+    }
+}
+
+/// A weekly point in place-local time. `hour` 24 (with minute 0) is the end
+/// of `day`, which some places use as a closing time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", try_from = "RawDayTime")]
+pub struct DayTime {
+    pub day: Weekday,
+    pub hour: u8,
+    pub minute: u8,
+}
+
+/// `DayTime` as sent, before range checks
+#[derive(Deserialize)]
+struct RawDayTime {
+    day: Weekday,
     #[serde(default)]
-    pub author_attribution: Option<AuthorAttribution>,
+    hour: u8,
     #[serde(default)]
+    minute: u8,
+}
+
+impl TryFrom<RawDayTime> for DayTime {
+    type Error = String;
+
+    fn try_from(raw: RawDayTime) -> Result<Self, String> {
+        match (raw.hour, raw.minute) {
+            (0..=23, 0..=59) | (24, 0) => Ok(DayTime {
+                day: raw.day,
+                hour: raw.hour,
+                minute: raw.minute,
+            }),
+            (hour, minute) => Err(format!("{:02}:{:02} is not a time of day", hour, minute)),
+        }
+    }
+}
+
+impl DayTime {
+    /// Minutes since Sunday 00:00, 0 to `MINUTES_PER_WEEK` (Saturday 24:00)
+    pub fn minute_of_week(&self) -> i64 {
+        self.day.index() as i64 * MINUTES_PER_DAY + self.hour as i64 * 60 + self.minute as i64
+    }
+}
+
+/// "Mon 08:00"
+impl std::fmt::Display for DayTime {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {:02}:{:02}", self.day.short_name(), self.hour, self.minute)
+    }
+}
+
+/// Day of the week; the API numbers them 0 (Sunday) to 6 (Saturday)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "u8", into = "u8")]
+pub enum Weekday {
+    Sunday,
+    Monday,
+    Tuesday,
+    Wednesday,
+    Thursday,
+    Friday,
+    Saturday,
+}
+
+impl Weekday {
+    const ALL: [Weekday; 7] = [
+        Weekday::Sunday,
+        Weekday::Monday,
+        Weekday::Tuesday,
+        Weekday::Wednesday,
+        Weekday::Thursday,
+        Weekday::Friday,
+        Weekday::Saturday,
+    ];
+
+    /// 0 (Sunday) to 6 (Saturday)
+    pub fn index(self) -> u8 {
+        self as u8
+    }
+
+    /// The weekday `n` days after Sunday; any integer, wrapping around the week
+    pub fn from_days_after_sunday(n: i64) -> Self {
+        Self::ALL[n.rem_euclid(7) as usize]
+    }
+
+    pub fn short_name(self) -> &'static str {
+        ["Sun", "Mon", "Tue", "Wed", "Thu", "Fri", "Sat"][self as usize]
+    }
+}
+
+impl TryFrom<u8> for Weekday {
+    type Error = String;
+
+    fn try_from(day: u8) -> Result<Self, String> {
+        Self::ALL
+            .get(day as usize)
+            .copied()
+            .ok_or_else(|| format!("day {} is not 0 (Sunday) to 6 (Saturday)", day))
+    }
+}
+
+impl From<Weekday> for u8 {
+    fn from(day: Weekday) -> u8 {
+        day.index()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Review {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub author_attribution: Option<AuthorAttribution>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub rating: Option<f64>,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub relative_publish_time_description: Option<String>,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub text: Option<LocalizedText>,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub original_text: Option<LocalizedText>,
+    /// RFC 3339 timestamp, e.g. "2024-05-01T17:03:22.456789Z"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub publish_time: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct AuthorAttribution {
     pub display_name: String,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub uri: Option<String>,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub photo_uri: Option<String>,
 }
 
@@ -118,7 +406,7 @@ pub struct AuthorAttribution {
 #[serde(rename_all = "camelCase")]
 pub struct LocalizedText {
     pub text: String,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub language_code: Option<String>,
 }
 
@@ -126,27 +414,35 @@ pub struct LocalizedText {
 #[serde(rename_all = "camelCase")]
 pub struct Photo {
     pub name: String,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub width_px: Option<u32>,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub height_px: Option<u32>,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub author_attributions: Option<Vec<AuthorAttribution>>,
 }
 
 // ─── Search ─────────────────────────────────────────────────────────────────
 
-#[derive(Debug, Clone)]
+/// Also deserializes from `zupo serve` request bodies
+/// (camelCase; omitted lists and flags are empty/false)
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct SearchRequest {
     pub query: String,
     pub included_type: Option<String>,
     pub min_rating: Option<f64>,
+    #[serde(default)]
     pub price_levels: Vec<String>,
+    #[serde(default)]
     pub open_now: bool,
     pub location: Option<Circle>,
     pub limit: Option<u32>,
     pub language: Option<String>,
     pub region: Option<String>,
+    /// Also request photo references (a pricier SKU)
+    #[serde(default)]
+    pub include_photos: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -156,9 +452,24 @@ pub struct SearchResponse {
     pub places: Vec<Place>,
 }
 
+/// Result of a count-only search (`--count`), serialized as `{"count": N}`
+#[derive(Debug, Clone, Serialize)]
+pub struct PlaceCount {
+    pub count: usize,
+    /// The API's result cap was reached, so the real number may be higher
+    #[serde(skip)]
+    pub capped: bool,
+    /// Field mask sent with each request
+    #[serde(skip)]
+    pub field_mask: &'static str,
+    #[serde(skip)]
+    pub requests: usize,
+}
+
 // ─── Autocomplete ───────────────────────────────────────────────────────────
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct AutocompleteRequest {
     pub input: String,
     pub session_token: Option<String>,
@@ -166,6 +477,31 @@ pub struct AutocompleteRequest {
     pub limit: Option<u32>,
     pub language: Option<String>,
     pub region: Option<String>,
+    /// Restrict suggestions to these primary types (`includedPrimaryTypes`)
+    #[serde(default)]
+    pub included_primary_types: Vec<String>,
+}
+
+/// Convenience groups of autocomplete primary types (`--only`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum PlaceTypeGroup {
+    Addresses,
+    Establishments,
+    Regions,
+    Cities,
+}
+
+impl PlaceTypeGroup {
+    /// The `includedPrimaryTypes` values this group expands to.
+    /// `(regions)` and `(cities)` are API type collections and must be sent alone.
+    pub fn primary_types(&self) -> &'static [&'static str] {
+        match self {
+            PlaceTypeGroup::Addresses => &["street_address", "premise", "subpremise", "route"],
+            PlaceTypeGroup::Establishments => &["establishment"],
+            PlaceTypeGroup::Regions => &["(regions)"],
+            PlaceTypeGroup::Cities => &["(cities)"],
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -218,6 +554,54 @@ pub struct TextMatch {
     pub end_offset: Option<u32>,
 }
 
+impl FormattedText {
+    /// Split the text into `(segment, matched)` pieces using the match offsets.
+    /// Offsets are Unicode code points, so they are mapped to byte positions
+    /// via `char_indices`; out-of-range or overlapping matches are clamped.
+    pub fn segments(&self) -> Vec<(&str, bool)> {
+        let char_count = self.text.chars().count();
+        let mut ranges: Vec<(usize, usize)> = self
+            .matches
+            .iter()
+            .flatten()
+            .filter_map(|m| {
+                let start = (m.start_offset.unwrap_or(0) as usize).min(char_count);
+                let end = (m.end_offset? as usize).min(char_count);
+                (start < end).then_some((start, end))
+            })
+            .collect();
+        ranges.sort_unstable();
+
+        let byte_at = |ci: usize| char_to_byte(&self.text, ci);
+
+        let mut out = Vec::new();
+        let mut pos = 0;
+        for (start, end) in ranges {
+            let start = start.max(pos);
+            if start >= end {
+                continue;
+            }
+            if start > pos {
+                out.push((&self.text[byte_at(pos)..byte_at(start)], false));
+            }
+            out.push((&self.text[byte_at(start)..byte_at(end)], true));
+            pos = end;
+        }
+        if pos < char_count {
+            out.push((&self.text[byte_at(pos)..], false));
+        }
+        out
+    }
+}
+
+/// Byte position of the code point at `char_index` in `text`, or its length
+/// past the last one
+fn char_to_byte(text: &str, char_index: usize) -> usize {
+    text.char_indices()
+        .nth(char_index)
+        .map_or(text.len(), |(byte, _)| byte)
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct StructuredFormat {
@@ -227,16 +611,47 @@ pub struct StructuredFormat {
 
 // ─── Nearby Search ──────────────────────────────────────────────────────────
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct NearbySearchRequest {
     pub lat: f64,
     pub lng: f64,
     pub radius: f64,
+    #[serde(default)]
     pub included_types: Vec<String>,
+    #[serde(default)]
     pub excluded_types: Vec<String>,
+    /// Match only places whose *primary* type is one of these
+    #[serde(default)]
+    pub included_primary_types: Vec<String>,
+    /// Drop places whose *primary* type is one of these
+    #[serde(default)]
+    pub excluded_primary_types: Vec<String>,
     pub limit: Option<u32>,
     pub language: Option<String>,
     pub region: Option<String>,
+    #[serde(default)]
+    pub rank_preference: NearbyRankPreference,
+}
+
+/// searchNearby `rankPreference`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum NearbyRankPreference {
+    /// Most popular first (the API default)
+    #[default]
+    Popularity,
+    /// Closest to the center first
+    Distance,
+}
+
+impl NearbyRankPreference {
+    pub fn as_api_str(&self) -> &'static str {
+        match self {
+            NearbyRankPreference::Popularity => "POPULARITY",
+            NearbyRankPreference::Distance => "DISTANCE",
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -246,12 +661,44 @@ pub struct NearbySearchResponse {
     pub places: Vec<Place>,
 }
 
+/// A nearby search made of one request per ring
+#[derive(Debug, Clone)]
+pub struct RingSearch {
+    pub response: NearbySearchResponse,
+    /// Radius of each request, innermost first
+    pub radii: Vec<f64>,
+    /// Places each request returned that no earlier request had
+    pub added: Vec<usize>,
+}
+
+/// Nearby results around one named location (`nearby --at`)
+#[derive(Debug, Clone, Serialize)]
+pub struct LocationGroup {
+    pub label: String,
+    pub center: LatLng,
+    pub radius: f64,
+    pub places: Vec<GroupedPlace>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GroupedPlace {
+    #[serde(flatten)]
+    pub place: Place,
+    /// Labels of the other locations this place was also found near
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub also_near: Vec<String>,
+}
+
 // ─── Place Details ──────────────────────────────────────────────────────────
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct DetailsRequest {
     pub place_id: String,
+    #[serde(default)]
     pub include_reviews: bool,
+    #[serde(default)]
     pub include_photos: bool,
     pub language: Option<String>,
     pub region: Option<String>,
@@ -259,6 +706,14 @@ pub struct DetailsRequest {
 
 // Details response is just a Place
 
+/// A details request for just a place's reviews (`zupo reviews`)
+#[derive(Debug, Clone)]
+pub struct ReviewsRequest {
+    pub place_id: String,
+    pub language: Option<String>,
+    pub region: Option<String>,
+}
+
 // ─── Photo Media ────────────────────────────────────────────────────────────
 
 #[derive(Debug, Clone)]
@@ -275,6 +730,48 @@ pub struct PhotoMediaResponse {
     pub name: String,
     #[serde(default)]
     pub photo_uri: String,
+    /// Not part of the media response; filled in from the place's photo list
+    #[serde(flatten)]
+    pub context: PhotoContext,
+}
+
+/// A photo's size and authors from its place's `photos` list, carried along
+/// to the media lookup and download, which don't return them. Google's terms
+/// require showing the authors wherever the photo is shown.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PhotoContext {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub width_px: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub height_px: Option<u32>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub author_attributions: Vec<AuthorAttribution>,
+}
+
+impl PhotoContext {
+    /// "Photo by Jane D. via Google", or None without any authors
+    pub fn attribution(&self) -> Option<String> {
+        if self.author_attributions.is_empty() {
+            return None;
+        }
+        let names: Vec<&str> = self
+            .author_attributions
+            .iter()
+            .map(|a| a.display_name.as_str())
+            .collect();
+        Some(format!("Photo by {} via Google", names.join(", ")))
+    }
+}
+
+impl From<&Photo> for PhotoContext {
+    fn from(photo: &Photo) -> Self {
+        PhotoContext {
+            width_px: photo.width_px,
+            height_px: photo.height_px,
+            author_attributions: photo.author_attributions.clone().unwrap_or_default(),
+        }
+    }
 }
 
 // ─── Location Resolve ───────────────────────────────────────────────────────
@@ -302,10 +799,60 @@ pub struct RouteRequest {
     pub results_per_waypoint: u32,
     pub language: Option<String>,
     pub region: Option<String>,
+    pub units: Units,
+    /// Ask the Routes API for alternative routes
+    pub alternatives: bool,
+    /// Which returned route to search along (0 = primary)
+    pub route_index: usize,
+    /// Reverse-resolve each waypoint to a locality name
+    pub label_waypoints: bool,
+    /// Skip the Routes API and use a straight line between the endpoints
+    pub straight_line: bool,
+    /// Include the encoded route polyline in the response
+    pub include_polyline: bool,
+    /// Include the decoded route points in the response
+    pub include_path: bool,
+    /// Douglas–Peucker tolerance in meters applied to the returned geometry
+    pub simplify_tolerance: Option<f64>,
+    /// Origin resolved ahead of routing; routed by place ID when set
+    pub from_resolved: Option<ResolvedEndpoint>,
+    /// Destination resolved ahead of routing; routed by place ID when set
+    pub to_resolved: Option<ResolvedEndpoint>,
 }
 
-#[derive(Debug, Clone, Copy)]
+/// A route endpoint matched to a place before routing (`route --confirm-endpoints`)
+#[derive(Debug, Clone)]
+pub struct ResolvedEndpoint {
+    pub place_id: String,
+    /// Name and address of the matched place, reported as the route's from/to
+    pub label: String,
+    pub location: Option<LatLng>,
+}
+
+/// Unit system for distances (Routes API `units` and all distance display)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum Units {
+    #[default]
+    Metric,
+    Imperial,
+}
+
+impl Units {
+    pub fn as_api_str(&self) -> &'static str {
+        match self {
+            Units::Metric => "METRIC",
+            Units::Imperial => "IMPERIAL",
+        }
+    }
+}
+
+/// Routes API travel mode; serializes to the API strings (`DRIVE`, `TWO_WHEELER`, ...)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+#[value(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum TravelMode {
+    #[default]
     Drive,
     Walk,
     Bicycle,
@@ -325,6 +872,12 @@ impl TravelMode {
     }
 }
 
+impl std::fmt::Display for TravelMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_api_str())
+    }
+}
+
 impl std::str::FromStr for TravelMode {
     type Err = String;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
@@ -342,19 +895,198 @@ impl std::str::FromStr for TravelMode {
     }
 }
 
+/// Origin of a route's geometry
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RouteSource {
+    /// Computed by the Routes API
+    #[default]
+    RoutesApi,
+    /// Approximate great-circle line between the resolved endpoints
+    StraightLine,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RouteWaypointResult {
     pub waypoint: LatLng,
     pub waypoint_index: usize,
+    /// Distance along the route from the origin, in meters
+    #[serde(default)]
+    pub distance_from_origin: f64,
+    /// Share of the route's length (0–1) behind this waypoint
+    #[serde(default)]
+    pub route_fraction: f64,
+    /// Nearby locality name (with --label-waypoints)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
     pub places: Vec<Place>,
 }
 
+/// A place found along a route, positioned at its nearest waypoint
+/// (`route --group-by place`)
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RoutePlace {
+    #[serde(flatten)]
+    pub place: Place,
+    pub waypoint_index: usize,
+    /// Distance along the route from the origin to that waypoint, in meters
+    pub distance_from_origin: f64,
+    pub route_fraction: f64,
+}
+
+/// Distance and travel time of a single route, without geometry
+#[derive(Debug, Clone, Copy)]
+pub struct RouteSummary {
+    pub distance_meters: Option<u64>,
+    pub duration_seconds: Option<u64>,
+}
+
+/// One route returned by computeRoutes
+#[derive(Debug, Clone)]
+pub struct ComputedRoute {
+    pub encoded_polyline: String,
+    pub distance_meters: Option<u64>,
+    /// Duration as returned by the API, e.g. "1823s"
+    pub duration: Option<String>,
+    /// Transit details of each transit step, in order (TRANSIT mode only)
+    pub transit_steps: Vec<TransitDetails>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransitDetails {
+    #[serde(default)]
+    pub stop_details: Option<TransitStopDetails>,
+    #[serde(default)]
+    pub localized_values: Option<TransitLocalizedValues>,
+    #[serde(default)]
+    pub headsign: Option<String>,
+    #[serde(default)]
+    pub stop_count: Option<u32>,
+    #[serde(default)]
+    pub transit_line: Option<TransitLine>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransitStopDetails {
+    #[serde(default)]
+    pub departure_stop: Option<TransitStop>,
+    #[serde(default)]
+    pub arrival_stop: Option<TransitStop>,
+    #[serde(default)]
+    pub departure_time: Option<String>,
+    #[serde(default)]
+    pub arrival_time: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransitStop {
+    #[serde(default)]
+    pub name: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransitLocalizedValues {
+    #[serde(default)]
+    pub departure_time: Option<LocalizedTime>,
+    #[serde(default)]
+    pub arrival_time: Option<LocalizedTime>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LocalizedTime {
+    #[serde(default)]
+    pub time: Option<LocalizedText>,
+    #[serde(default)]
+    pub time_zone: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransitLine {
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub name_short: Option<String>,
+    #[serde(default)]
+    pub vehicle: Option<TransitVehicle>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransitVehicle {
+    #[serde(default)]
+    pub name: Option<LocalizedText>,
+    #[serde(default, rename = "type")]
+    pub vehicle_type: Option<String>,
+}
+
+impl TransitDetails {
+    /// Short line name if available ("U4"), else the full name
+    pub fn line_name(&self) -> Option<&str> {
+        let line = self.transit_line.as_ref()?;
+        line.name_short.as_deref().or(line.name.as_deref())
+    }
+
+    /// Departure time for display: the localized text if present, else the raw timestamp
+    pub fn departure_display(&self) -> Option<&str> {
+        self.localized_values
+            .as_ref()
+            .and_then(|v| v.departure_time.as_ref())
+            .and_then(|t| t.time.as_ref())
+            .map(|t| t.text.as_str())
+            .or_else(|| {
+                self.stop_details
+                    .as_ref()
+                    .and_then(|d| d.departure_time.as_deref())
+            })
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RouteSearchResponse {
     pub from: String,
     pub to: String,
-    pub travel_mode: String,
+    pub travel_mode: TravelMode,
+    /// Where the route geometry came from
+    #[serde(default)]
+    pub route_source: RouteSource,
+    /// Index of the route searched along (0 = primary, >0 = alternative)
+    #[serde(default)]
+    pub route_index: usize,
+    #[serde(default)]
+    pub distance_meters: Option<u64>,
+    /// Duration as returned by the API, e.g. "1823s"
+    #[serde(default)]
+    pub duration: Option<String>,
+    /// `duration` parsed to whole seconds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duration_seconds: Option<u64>,
+    /// Transit legs of the route (empty unless travel mode is TRANSIT)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub transit_legs: Vec<TransitDetails>,
+    /// Encoded route geometry (with --show-polyline)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub polyline: Option<String>,
+    /// Decoded route geometry (with --show-path)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<Vec<LatLng>>,
     pub waypoints: Vec<RouteWaypointResult>,
+    /// Fallbacks and failed waypoint searches
+    #[serde(rename = "_warnings", default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<Warning>,
+}
+
+impl RouteSearchResponse {
+    /// All places found along the route (across waypoints, may repeat)
+    pub fn places(&self) -> impl Iterator<Item = &Place> {
+        self.waypoints.iter().flat_map(|wp| wp.places.iter())
+    }
 }
 
 // ─── Price level helpers ────────────────────────────────────────────────────
@@ -370,6 +1102,86 @@ pub fn price_level_to_api(level: u8) -> Option<&'static str> {
     }
 }
 
+/// A price filter: the sorted set of levels 0-4 (0=Free, 4=$$$$) it allows.
+///
+/// Parses comma-separated terms, each one of: a level (`2`, `$$`, `free`),
+/// a range (`1-3`, `$-$$$`), or a bound (`<=2`, `<3`, `>=$$`, `>1`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PriceSet(Vec<u8>);
+
+impl PriceSet {
+    pub fn levels(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl std::str::FromStr for PriceSet {
+    type Err = String;
+    fn from_str(spec: &str) -> Result<Self, Self::Err> {
+        let mut levels = [false; 5];
+        for term in spec.split(',').map(str::trim) {
+            if term.is_empty() {
+                return Err(format!("empty price term in '{}'", spec));
+            }
+            let (lo, hi) = parse_price_term(term)?;
+            if lo > hi {
+                return Err(format!("price range '{}' is empty", term));
+            }
+            for level in lo..=hi {
+                levels[level as usize] = true;
+            }
+        }
+        Ok(PriceSet((0..5u8).filter(|&l| levels[l as usize]).collect()))
+    }
+}
+
+/// Inclusive (low, high) bounds for one price term
+fn parse_price_term(term: &str) -> Result<(u8, u8), String> {
+    if let Some(rest) = term.strip_prefix("<=") {
+        return Ok((0, parse_price_value(rest)?));
+    }
+    if let Some(rest) = term.strip_prefix(">=") {
+        return Ok((parse_price_value(rest)?, 4));
+    }
+    if let Some(rest) = term.strip_prefix('<') {
+        let v = parse_price_value(rest)?;
+        let hi = v
+            .checked_sub(1)
+            .ok_or_else(|| format!("no price level is below '{}'", rest.trim()))?;
+        return Ok((0, hi));
+    }
+    if let Some(rest) = term.strip_prefix('>') {
+        let v = parse_price_value(rest)?;
+        if v >= 4 {
+            return Err(format!("no price level is above '{}'", rest.trim()));
+        }
+        return Ok((v + 1, 4));
+    }
+    if let Some((a, b)) = term.split_once('-') {
+        return Ok((parse_price_value(a)?, parse_price_value(b)?));
+    }
+    let v = parse_price_value(term)?;
+    Ok((v, v))
+}
+
+/// A single price level: `0`-`4`, `free`, or `$` through `$$$$`
+fn parse_price_value(text: &str) -> Result<u8, String> {
+    let text = text.trim();
+    if text.eq_ignore_ascii_case("free") {
+        return Ok(0);
+    }
+    if !text.is_empty() && text.chars().all(|c| c == '$') && text.len() <= 4 {
+        return Ok(text.len() as u8);
+    }
+    match text.parse::<u8>() {
+        Ok(v) if v <= 4 => Ok(v),
+        _ => Err(format!(
+            "invalid price level '{}' (use 0-4, free, or $ to $$$$)",
+            text
+        )),
+    }
+}
+
 pub fn price_level_display(level: &str) -> &str {
     match level {
         "PRICE_LEVEL_FREE" => "Free",
@@ -380,3 +1192,173 @@ pub fn price_level_display(level: &str) -> &str {
         _ => level,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn prices(spec: &str) -> Result<Vec<u8>, String> {
+        spec.parse::<PriceSet>().map(|set| set.levels().to_vec())
+    }
+
+    #[test]
+    fn price_single_levels() {
+        assert_eq!(prices("0"), Ok(vec![0]));
+        assert_eq!(prices("4"), Ok(vec![4]));
+        assert_eq!(prices("free"), Ok(vec![0]));
+        assert_eq!(prices("FREE"), Ok(vec![0]));
+        assert_eq!(prices("$"), Ok(vec![1]));
+        assert_eq!(prices("$$$$"), Ok(vec![4]));
+        assert_eq!(prices(" 2 "), Ok(vec![2]));
+    }
+
+    #[test]
+    fn price_ranges() {
+        assert_eq!(prices("1-3"), Ok(vec![1, 2, 3]));
+        assert_eq!(prices("$-$$$"), Ok(vec![1, 2, 3]));
+        assert_eq!(prices("free-$"), Ok(vec![0, 1]));
+        assert_eq!(prices("2-2"), Ok(vec![2]));
+        assert_eq!(prices("1 - 2"), Ok(vec![1, 2]));
+    }
+
+    #[test]
+    fn price_bounds() {
+        assert_eq!(prices("<=2"), Ok(vec![0, 1, 2]));
+        assert_eq!(prices("<2"), Ok(vec![0, 1]));
+        assert_eq!(prices(">=$$"), Ok(vec![2, 3, 4]));
+        assert_eq!(prices(">3"), Ok(vec![4]));
+        assert_eq!(prices("<=4"), Ok(vec![0, 1, 2, 3, 4]));
+        assert_eq!(prices(">=0"), Ok(vec![0, 1, 2, 3, 4]));
+    }
+
+    #[test]
+    fn price_terms_combine_sorted_without_duplicates() {
+        assert_eq!(prices("4,1"), Ok(vec![1, 4]));
+        assert_eq!(prices("1-2, 2-3"), Ok(vec![1, 2, 3]));
+        assert_eq!(prices("free,>=$$$"), Ok(vec![0, 3, 4]));
+    }
+
+    #[test]
+    fn price_rejects_out_of_range_levels() {
+        assert!(prices("5").is_err());
+        assert!(prices("$$$$$").is_err());
+        assert!(prices("-1").is_err());
+        assert!(prices("1-5").is_err());
+    }
+
+    #[test]
+    fn price_rejects_empty_ranges_and_bounds() {
+        assert_eq!(prices("3-1"), Err("price range '3-1' is empty".into()));
+        assert_eq!(prices("<0"), Err("no price level is below '0'".into()));
+        assert_eq!(prices("<free"), Err("no price level is below 'free'".into()));
+        assert_eq!(prices(">4"), Err("no price level is above '4'".into()));
+    }
+
+    #[test]
+    fn price_rejects_malformed_terms() {
+        assert_eq!(prices(""), Err("empty price term in ''".into()));
+        assert_eq!(prices("1,,2"), Err("empty price term in '1,,2'".into()));
+        assert_eq!(prices("1,"), Err("empty price term in '1,'".into()));
+        assert!(prices("cheap").is_err());
+        assert!(prices("$2").is_err());
+        assert!(prices("<=").is_err());
+        assert!(prices("1-").is_err());
+        assert!(prices("1-2-3").is_err());
+        assert!(prices("1.5").is_err());
+    }
+
+    fn text(text: &str, matches: &[(Option<u32>, Option<u32>)]) -> FormattedText {
+        FormattedText {
+            text: text.into(),
+            matches: Some(
+                matches
+                    .iter()
+                    .map(|&(start_offset, end_offset)| TextMatch { start_offset, end_offset })
+                    .collect(),
+            ),
+        }
+    }
+
+    #[test]
+    fn char_to_byte_counts_code_points() {
+        assert_eq!(char_to_byte("cafe", 2), 2);
+        // é is two bytes
+        assert_eq!(char_to_byte("café bar", 4), 5);
+        // 東 and 京 are three bytes each
+        assert_eq!(char_to_byte("東京タワー", 2), 6);
+        // ☕ is three bytes
+        assert_eq!(char_to_byte("☕ Coffee", 1), 3);
+        // e plus a combining acute accent (two bytes) is two code points
+        assert_eq!(char_to_byte("Cafe\u{301} Central", 5), 6);
+        assert_eq!(char_to_byte("café", 4), 5);
+        assert_eq!(char_to_byte("café", 99), 5);
+    }
+
+    #[test]
+    fn ascii_match() {
+        let t = text("Cafe Central", &[(Some(0), Some(4))]);
+        assert_eq!(t.segments(), vec![("Cafe", true), (" Central", false)]);
+    }
+
+    #[test]
+    fn offsets_after_multibyte_characters_are_code_points() {
+        // Byte offsets would cut "é" in half and panic
+        let t = text("Café Central", &[(Some(5), Some(12))]);
+        assert_eq!(t.segments(), vec![("Café ", false), ("Central", true)]);
+
+        let t = text("Café Central", &[(Some(0), Some(4))]);
+        assert_eq!(t.segments(), vec![("Café", true), (" Central", false)]);
+    }
+
+    #[test]
+    fn cjk_and_emoji() {
+        let t = text("東京タワー", &[(Some(0), Some(2))]);
+        assert_eq!(t.segments(), vec![("東京", true), ("タワー", false)]);
+
+        let t = text("☕ Coffee", &[(Some(2), Some(5))]);
+        assert_eq!(t.segments(), vec![("☕ ", false), ("Cof", true), ("fee", false)]);
+    }
+
+    #[test]
+    fn combining_characters() {
+        let decomposed = "Cafe\u{301} Central";
+        let t = text(decomposed, &[(Some(0), Some(5))]);
+        assert_eq!(t.segments(), vec![("Cafe\u{301}", true), (" Central", false)]);
+
+        // An offset between a letter and its accent still slices on a char boundary
+        let t = text(decomposed, &[(Some(0), Some(4))]);
+        assert_eq!(t.segments(), vec![("Cafe", true), ("\u{301} Central", false)]);
+    }
+
+    #[test]
+    fn several_matches() {
+        let t = text("Zürich Café", &[(Some(7), Some(11)), (Some(0), Some(2))]);
+        assert_eq!(t.segments(), vec![("Zü", true), ("rich ", false), ("Café", true)]);
+    }
+
+    #[test]
+    fn bad_offsets_are_clamped_or_skipped() {
+        // Past the end
+        let t = text("Café", &[(Some(2), Some(40))]);
+        assert_eq!(t.segments(), vec![("Ca", false), ("fé", true)]);
+        // Missing start means 0; missing end is skipped
+        let t = text("Café", &[(None, Some(2)), (Some(3), None)]);
+        assert_eq!(t.segments(), vec![("Ca", true), ("fé", false)]);
+        // Overlapping and empty
+        let t = text("Café Central", &[(Some(0), Some(4)), (Some(2), Some(6)), (Some(8), Some(8))]);
+        assert_eq!(t.segments(), vec![("Café", true), (" C", true), ("entral", false)]);
+        // Start past the end
+        let t = text("Café", &[(Some(10), Some(12))]);
+        assert_eq!(t.segments(), vec![("Café", false)]);
+    }
+
+    #[test]
+    fn no_matches() {
+        let t = FormattedText {
+            text: "Café".into(),
+            matches: None,
+        };
+        assert_eq!(t.segments(), vec![("Café", false)]);
+        assert!(text("", &[(Some(0), Some(3))]).segments().is_empty());
+    }
+}
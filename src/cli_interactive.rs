@@ -0,0 +1,224 @@
+use std::future::Future;
+use std::io::{self, IsTerminal, Write};
+use std::pin::Pin;
+use std::time::Duration;
+
+use crossterm::cursor::{MoveToColumn, MoveUp};
+use crossterm::event::{Event, EventStream, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use crossterm::queue;
+use crossterm::style::{Attribute, Print, SetAttribute};
+use crossterm::terminal::{self, Clear, ClearType};
+use futures::StreamExt;
+
+use crate::api::client::Client;
+use crate::api::errors::Error;
+use crate::api::types::{AutocompleteRequest, AutocompleteResponse, Suggestion};
+use crate::format::{display_width, sanitize_display, truncate_to_width};
+
+/// Quiet time after a keystroke before suggestions are requested
+const DEBOUNCE: Duration = Duration::from_millis(250);
+const PROMPT: &str = "› ";
+/// Marks the selected suggestion; the others are indented to match
+const SELECTED_MARK: &str = "❯ ";
+
+/// How `pick_place` ended
+pub enum Outcome {
+    /// A place suggestion was chosen with Enter
+    Picked(Box<Suggestion>),
+    /// Esc or Ctrl+C
+    Aborted,
+}
+
+/// Raw mode for as long as it lives, so every exit path restores the terminal
+struct RawMode;
+
+impl RawMode {
+    fn enable() -> io::Result<Self> {
+        terminal::enable_raw_mode()?;
+        Ok(RawMode)
+    }
+}
+
+impl Drop for RawMode {
+    fn drop(&mut self) {
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+type Pending<'a> = Pin<Box<dyn Future<Output = Result<AutocompleteResponse, Error>> + 'a>>;
+
+struct Prompt {
+    input: String,
+    suggestions: Vec<Suggestion>,
+    selected: usize,
+    /// Shown dimmed under the suggestions, e.g. a failed request
+    note: Option<String>,
+}
+
+/// Prompt for a place on stderr, starting from `req.input`, with suggestions
+/// for the current input listed below and refreshed as the user types. Up and
+/// Down move the selection; Enter on a place returns it, and on a query
+/// suggestion takes over its text. Request errors are shown rather than
+/// ending the prompt. Needs stderr to be a terminal; stdout stays free for
+/// the caller's result.
+pub async fn pick_place(client: &Client, req: AutocompleteRequest) -> io::Result<Outcome> {
+    if !io::stderr().is_terminal() {
+        return Err(io::Error::other("--interactive needs a terminal on stderr"));
+    }
+    let mut prompt = Prompt {
+        input: req.input.clone(),
+        suggestions: Vec::new(),
+        selected: 0,
+        note: None,
+    };
+    let mut debounce: Option<Pin<Box<tokio::time::Sleep>>> =
+        (!prompt.input.is_empty()).then(|| Box::pin(tokio::time::sleep(Duration::ZERO)));
+    let mut in_flight: Option<Pending> = None;
+
+    let _raw = RawMode::enable()?;
+    let mut events = EventStream::new();
+    let mut err = io::stderr();
+    let outcome = loop {
+        draw(&mut err, &prompt)?;
+        tokio::select! {
+            event = events.next() => {
+                let key = match event {
+                    Some(Ok(Event::Key(key))) if key.kind != KeyEventKind::Release => key,
+                    Some(Ok(_)) => continue,
+                    Some(Err(e)) => return Err(e),
+                    None => break Outcome::Aborted,
+                };
+                let before = prompt.input.clone();
+                if let Some(outcome) = handle_key(&mut prompt, key) {
+                    break outcome;
+                }
+                if prompt.input != before {
+                    // Drop the stale request; a new one follows the pause
+                    in_flight = None;
+                    if prompt.input.is_empty() {
+                        debounce = None;
+                        prompt.suggestions.clear();
+                        prompt.note = None;
+                    } else {
+                        debounce = Some(Box::pin(tokio::time::sleep(DEBOUNCE)));
+                    }
+                }
+            }
+            _ = async { debounce.as_mut().unwrap().await }, if debounce.is_some() => {
+                debounce = None;
+                let req = AutocompleteRequest {
+                    input: prompt.input.clone(),
+                    ..req.clone()
+                };
+                in_flight = Some(Box::pin(async move { client.autocomplete(&req).await }));
+            }
+            result = async { in_flight.as_mut().unwrap().await }, if in_flight.is_some() => {
+                in_flight = None;
+                match result {
+                    Ok(resp) => {
+                        prompt.note = resp.suggestions.is_empty().then(|| "no suggestions".to_string());
+                        prompt.suggestions = resp.suggestions;
+                        prompt.selected = 0;
+                    }
+                    Err(e) => prompt.note = Some(e.to_string()),
+                }
+            }
+        }
+    };
+
+    queue!(err, MoveToColumn(0), Clear(ClearType::FromCursorDown))?;
+    err.flush()?;
+    Ok(outcome)
+}
+
+/// Apply one key press; Some when it ends the prompt
+fn handle_key(prompt: &mut Prompt, key: KeyEvent) -> Option<Outcome> {
+    let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+    let last = prompt.suggestions.len().saturating_sub(1);
+    match key.code {
+        KeyCode::Esc => return Some(Outcome::Aborted),
+        KeyCode::Char('c') if ctrl => return Some(Outcome::Aborted),
+        KeyCode::Char('u') if ctrl => prompt.input.clear(),
+        KeyCode::Up => prompt.selected = prompt.selected.saturating_sub(1),
+        KeyCode::Char('p') if ctrl => prompt.selected = prompt.selected.saturating_sub(1),
+        KeyCode::Down => prompt.selected = (prompt.selected + 1).min(last),
+        KeyCode::Char('n') if ctrl => prompt.selected = (prompt.selected + 1).min(last),
+        KeyCode::Backspace => {
+            prompt.input.pop();
+        }
+        KeyCode::Char(c) if !ctrl => prompt.input.push(c),
+        KeyCode::Enter => {
+            let suggestion = prompt.suggestions.get(prompt.selected)?;
+            if suggestion.place_prediction.as_ref().is_some_and(|p| p.place_id.is_some()) {
+                return Some(Outcome::Picked(Box::new(suggestion.clone())));
+            }
+            if let Some(text) = suggestion.query_prediction.as_ref().and_then(|q| q.text.as_ref()) {
+                prompt.input = text.text.clone();
+            }
+        }
+        _ => {}
+    }
+    None
+}
+
+/// Redraw the prompt line and the suggestions under it, leaving the cursor
+/// at the end of the input. Lines are cut to the terminal width so none wraps.
+fn draw(w: &mut impl Write, prompt: &Prompt) -> io::Result<()> {
+    let cols = terminal::size().map_or(80, |(cols, _)| cols as usize);
+    let room = cols.saturating_sub(display_width(PROMPT) + 1);
+    let input = truncate_to_width(&prompt.input, room);
+    queue!(
+        w,
+        MoveToColumn(0),
+        Clear(ClearType::FromCursorDown),
+        SetAttribute(Attribute::Bold),
+        Print(PROMPT),
+        SetAttribute(Attribute::Reset),
+        Print(&input)
+    )?;
+
+    let mut below = 0u16;
+    for (i, suggestion) in prompt.suggestions.iter().enumerate() {
+        let mark = if i == prompt.selected { SELECTED_MARK } else { "  " };
+        let line = truncate_to_width(&suggestion_label(suggestion), cols.saturating_sub(display_width(mark)));
+        queue!(w, Print("\r\n"))?;
+        if i == prompt.selected {
+            queue!(w, SetAttribute(Attribute::Reverse), Print(mark), Print(line), SetAttribute(Attribute::Reset))?;
+        } else {
+            queue!(w, Print(mark), Print(line))?;
+        }
+        below += 1;
+    }
+    if let Some(ref note) = prompt.note {
+        let line = truncate_to_width(note, cols);
+        queue!(w, Print("\r\n"), SetAttribute(Attribute::Dim), Print(line), SetAttribute(Attribute::Reset))?;
+        below += 1;
+    }
+
+    if below > 0 {
+        queue!(w, MoveUp(below))?;
+    }
+    let column = display_width(PROMPT) + display_width(&input);
+    queue!(w, MoveToColumn(column as u16))?;
+    w.flush()
+}
+
+/// "Main text — secondary text" for places, "🔍 text" for query suggestions
+fn suggestion_label(suggestion: &Suggestion) -> String {
+    if let Some(ref place) = suggestion.place_prediction {
+        let format = place.structured_format.as_ref();
+        let main = format
+            .and_then(|f| f.main_text.as_ref())
+            .or(place.text.as_ref())
+            .map_or("?", |t| t.text.as_str());
+        match format.and_then(|f| f.secondary_text.as_ref()) {
+            Some(secondary) => format!("{} — {}", sanitize_display(main), sanitize_display(&secondary.text)),
+            None => sanitize_display(main).into_owned(),
+        }
+    } else if let Some(ref query) = suggestion.query_prediction {
+        let text = query.text.as_ref().map_or("?", |t| t.text.as_str());
+        format!("🔍 {}", sanitize_display(text))
+    } else {
+        String::new()
+    }
+}
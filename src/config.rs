@@ -1,14 +1,52 @@
+use std::collections::BTreeMap;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, SystemTime};
 
 use serde::{Deserialize, Serialize};
 
-const APP_NAME: &str = "zupo";
+use crate::api::connections::PoolSettings;
+use crate::api::limits::{MAX_AUTOCOMPLETE_SUGGESTIONS, MAX_SEARCH_RESULTS};
+use crate::api::types::{radius_problem, Units};
+use crate::format::{parse_coord, AddressStyle};
+use crate::locale;
+use crate::paths;
+use crate::render::PhotoSize;
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct Config {
     #[serde(default)]
     pub location: LocationConfig,
+    #[serde(default)]
+    pub defaults: DefaultsConfig,
+    #[serde(default)]
+    pub paths: PathsConfig,
+    #[serde(default)]
+    pub ranking: RankingConfig,
+    #[serde(default)]
+    pub tui: TuiConfig,
+    #[serde(default)]
+    pub filters: FiltersConfig,
+    #[serde(default)]
+    pub output: OutputConfig,
+    #[serde(default)]
+    pub network: NetworkConfig,
+    /// Named places to search around, e.g. `[locations.work]` for `nearby --at work`
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub locations: BTreeMap<String, NamedLocation>,
+    /// Per-command flag defaults, e.g. `[commands.search]` with `limit = 15`;
+    /// keys are flag names in snake_case and explicit flags always win
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub commands: BTreeMap<String, toml::Table>,
+    /// USD per 1,000 requests by endpoint label (e.g. `searchText = 32.0`),
+    /// overriding the built-in prices used by `zupo stats`
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub pricing: BTreeMap<String, f64>,
+    /// Sections as they were on disk at load time, so `save` can tell which
+    /// sections this process changed
+    #[serde(skip)]
+    loaded: Option<toml::Table>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -19,8 +57,149 @@ pub struct LocationConfig {
     pub label: Option<String>,
 }
 
+/// A saved place under `[locations.<name>]`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NamedLocation {
+    pub lat: f64,
+    pub lng: f64,
+    /// Search radius in meters (default: `location.default_radius`)
+    pub radius: Option<f64>,
+}
+
+/// Defaults applied when the corresponding flag is not given
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct DefaultsConfig {
+    pub units: Option<Units>,
+    /// Language code (BCP-47) used when `--lang` is omitted
+    pub language: Option<String>,
+    /// Region code (CLDR) used when `--region` is omitted
+    pub region: Option<String>,
+}
+
+/// Overrides for on-disk locations (see `paths`); `~/` is expanded
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct PathsConfig {
+    pub cache_dir: Option<String>,
+    pub state_dir: Option<String>,
+    pub log_dir: Option<String>,
+}
+
+/// Interactive TUI preferences
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct TuiConfig {
+    /// Wrap list navigation from the last item to the first and back (default off)
+    pub wrap_navigation: Option<bool>,
+    /// Request photo references with each search so results show a photo
+    /// count and `p` can preview one (default off: a pricier SKU)
+    pub request_photos: Option<bool>,
+    /// Results per search, 1-20 (default 10)
+    pub result_limit: Option<u32>,
+    /// Suggestions per autocomplete request, 1-5 (default 5)
+    pub autocomplete_limit: Option<u32>,
+    /// Run a debounced background search while typing and show its results
+    /// as a live preview (default off: every preview is a billed search)
+    pub instant_results: Option<bool>,
+    /// Text searches this month above which live previews turn themselves
+    /// off (default 1000)
+    pub instant_results_monthly_limit: Option<u64>,
+    /// Save the filter panel on exit and restore it on start (default on)
+    pub persist_filters: Option<bool>,
+    /// The filter panel as saved on exit (`[tui.filters]`)
+    #[serde(default, skip_serializing_if = "TuiFilters::is_empty")]
+    pub filters: TuiFilters,
+}
+
+/// TUI filter panel values saved between sessions. A hand-edited file can
+/// hold anything here, so the TUI checks each value when restoring it and
+/// skips the ones out of range.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct TuiFilters {
+    pub included_type: Option<String>,
+    /// Meters
+    pub radius: Option<f64>,
+    pub min_rating: Option<f64>,
+    pub min_reviews: Option<u32>,
+    /// Selected price levels, 0 (free) to 4 ($$$$)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub price_levels: Vec<i64>,
+    pub open_now: Option<bool>,
+}
+
+impl TuiFilters {
+    pub fn is_empty(&self) -> bool {
+        *self == TuiFilters::default()
+    }
+}
+
+/// Text output preferences
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct OutputConfig {
+    /// List opening hours starting from today instead of Monday (default on)
+    pub hours_start_today: Option<bool>,
+    /// Box inline photos are fit into, as "COLSxROWS" (default: a share of
+    /// the terminal size)
+    pub photo_size: Option<String>,
+    /// Address shown in place lists: short, full, or auto (default full)
+    pub address_style: Option<AddressStyle>,
+}
+
+/// Limits on requests to Google and how connections to it are kept
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct NetworkConfig {
+    /// Most API requests per second, across concurrent requests (default:
+    /// unlimited); `--qps` overrides it
+    pub max_qps: Option<f64>,
+    /// Seconds an idle connection is kept for reuse (default 120)
+    pub pool_idle_timeout: Option<u64>,
+    /// Idle connections kept per host (default 8; 0 disables reuse)
+    pub pool_max_idle_per_host: Option<usize>,
+    /// Seconds between TCP keepalive probes (default 30; 0 disables them)
+    pub tcp_keepalive: Option<u64>,
+}
+
+impl NetworkConfig {
+    /// Connection pool settings, with defaults for unset fields
+    pub fn pool_settings(&self) -> PoolSettings {
+        let defaults = PoolSettings::default();
+        PoolSettings {
+            idle_timeout: self
+                .pool_idle_timeout
+                .map(Duration::from_secs)
+                .unwrap_or(defaults.idle_timeout),
+            max_idle_per_host: self.pool_max_idle_per_host.unwrap_or(defaults.max_idle_per_host),
+            tcp_keepalive: match self.tcp_keepalive {
+                Some(0) => None,
+                Some(secs) => Some(Duration::from_secs(secs)),
+                None => defaults.tcp_keepalive,
+            },
+        }
+    }
+}
+
+/// Handling of results far from the location bias
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct FiltersConfig {
+    /// Flag results further than this many times the bias radius from the
+    /// bias center (default 3)
+    pub far_factor: Option<f64>,
+    /// Show the "⚠ N km from your location" line on far results (default on)
+    pub far_warning: Option<bool>,
+    /// Default for `search --max-distance`, in meters
+    pub max_distance: Option<f64>,
+}
+
+/// Weights and priors for `--rank smart` (see `ranking`)
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct RankingConfig {
+    pub rating_weight: Option<f64>,
+    pub reviews_weight: Option<f64>,
+    pub distance_weight: Option<f64>,
+    pub prior_rating: Option<f64>,
+    pub prior_reviews: Option<f64>,
+}
+
 impl Config {
-    /// Load config from ~/.config/zupo/config.toml (returns default if missing)
+    /// Load config from the config dir, usually ~/.config/zupo/config.toml (returns default if missing)
     pub fn load() -> Self {
         let path = match config_path() {
             Some(p) => p,
@@ -36,11 +215,109 @@ impl Config {
             Err(_) => return Config::default(),
         };
 
-        toml::from_str(&contents).unwrap_or_default()
+        match toml::from_str::<Config>(&contents) {
+            Ok(mut cfg) => {
+                cfg.loaded = cfg.to_table().ok();
+                cfg
+            }
+            Err(_) => Config::default(),
+        }
+    }
+
+    /// Layer `ZUPO_DEFAULT_LAT`/`ZUPO_DEFAULT_LNG`, `ZUPO_DEFAULT_RADIUS`,
+    /// `ZUPO_LANG`, and `ZUPO_REGION` over the file's values, for env-only
+    /// setups such as containers. Flags still win over these. Not for configs
+    /// that get saved, or the environment would be written to the file.
+    pub fn apply_env(&mut self) -> Result<(), String> {
+        let var = |name: &str| std::env::var(name).ok().filter(|v| !v.trim().is_empty());
+
+        match (var("ZUPO_DEFAULT_LAT"), var("ZUPO_DEFAULT_LNG")) {
+            (Some(lat), Some(lng)) => {
+                let lat = parse_coord(&lat).map_err(|e| format!("ZUPO_DEFAULT_LAT: {}", e))?;
+                let lng = parse_coord(&lng).map_err(|e| format!("ZUPO_DEFAULT_LNG: {}", e))?;
+                if !(-90.0..=90.0).contains(&lat) {
+                    return Err(format!("ZUPO_DEFAULT_LAT out of range: {}", lat));
+                }
+                if !(-180.0..=180.0).contains(&lng) {
+                    return Err(format!("ZUPO_DEFAULT_LNG out of range: {}", lng));
+                }
+                self.location.default_lat = Some(lat);
+                self.location.default_lng = Some(lng);
+                // The file's label names the file's location, not this one
+                self.location.label = Some("environment".into());
+            }
+            (None, None) => {}
+            (Some(_), None) => return Err("ZUPO_DEFAULT_LAT is set but ZUPO_DEFAULT_LNG is not; set both or neither".into()),
+            (None, Some(_)) => return Err("ZUPO_DEFAULT_LNG is set but ZUPO_DEFAULT_LAT is not; set both or neither".into()),
+        }
+        if let Some(radius) = var("ZUPO_DEFAULT_RADIUS") {
+            match radius.trim().parse::<f64>() {
+                Ok(r) if r.is_finite() && r > 0.0 => self.location.default_radius = Some(r),
+                _ => return Err(format!("ZUPO_DEFAULT_RADIUS must be a positive number of meters: {}", radius)),
+            }
+        }
+        if let Some(lang) = var("ZUPO_LANG") {
+            if !locale::is_language_code(&lang) {
+                return Err(format!("ZUPO_LANG is not a language code (e.g. en, pt-BR): {}", lang));
+            }
+            self.defaults.language = Some(lang);
+        }
+        if let Some(region) = var("ZUPO_REGION") {
+            if !locale::is_region_code(&region) {
+                return Err(format!("ZUPO_REGION is not a two-letter region code (e.g. us): {}", region));
+            }
+            self.defaults.region = Some(region);
+        }
+        Ok(())
     }
 
-    /// Save config to ~/.config/zupo/config.toml
+    /// Load and validate the config file, reporting problems instead of
+    /// falling back to defaults like `load` does; None when there is no file
+    pub fn load_checked() -> Result<Option<Self>, String> {
+        let path = match config_path() {
+            Some(p) if p.exists() => p,
+            _ => return Ok(None),
+        };
+        let contents = fs::read_to_string(&path)
+            .map_err(|e| format!("could not read {}: {}", path.display(), e))?;
+        Config::from_toml(&contents).map(Some)
+    }
+
+    /// Store the TUI filter panel in `[tui.filters]`, leaving every other
+    /// section as it is on disk. A config file that doesn't parse is left
+    /// alone rather than replaced by defaults plus the filters.
+    pub fn save_tui_filters(filters: &TuiFilters) -> Result<(), String> {
+        if let Some(path) = config_path().filter(|p| p.exists()) {
+            let contents = fs::read_to_string(&path)
+                .map_err(|e| format!("could not read {}: {}", path.display(), e))?;
+            toml::from_str::<Config>(&contents)
+                .map_err(|e| format!("{} doesn't parse, not changing it: {}", path.display(), e))?;
+        }
+        let mut cfg = Config::load();
+        if cfg.tui.filters == *filters {
+            return Ok(());
+        }
+        cfg.tui.filters = filters.clone();
+        cfg.save()
+    }
+
+    /// Save config to ~/.config/zupo/config.toml.
+    ///
+    /// Concurrent writers are handled by holding a lock file, re-reading the
+    /// file, and only writing the sections this process changed since `load`;
+    /// other sections keep their on-disk values. The write is atomic
+    /// (temp file + rename).
     pub fn save(&self) -> Result<(), String> {
+        self.write(true)
+    }
+
+    /// Save the whole config in place of what is on disk, with no merge
+    /// (`config import --replace`). Still locked and atomic.
+    pub fn save_replacing(&self) -> Result<(), String> {
+        self.write(false)
+    }
+
+    fn write(&self, merge: bool) -> Result<(), String> {
         let path = config_path().ok_or("could not determine config directory")?;
 
         if let Some(parent) = path.parent() {
@@ -48,14 +325,295 @@ impl Config {
                 .map_err(|e| format!("could not create config directory: {}", e))?;
         }
 
-        let contents =
-            toml::to_string_pretty(self).map_err(|e| format!("could not serialize config: {}", e))?;
+        let _lock = LockFile::acquire(&path.with_extension("toml.lock"))?;
 
-        fs::write(&path, contents).map_err(|e| format!("could not write config file: {}", e))?;
+        // A missing or unreadable file means there is nothing to preserve
+        let on_disk = fs::read_to_string(&path)
+            .ok()
+            .and_then(|c| toml::from_str::<toml::Table>(&c).ok())
+            .filter(|_| merge);
+        let table = match on_disk {
+            Some(on_disk) => merge_sections(on_disk, self.to_table()?, self.loaded.as_ref()),
+            None => self.to_table()?,
+        };
+        let contents = toml::to_string_pretty(&table)
+            .map_err(|e| format!("could not serialize config: {}", e))?;
+
+        write_atomic(&path, &contents)
+    }
 
+    fn to_table(&self) -> Result<toml::Table, String> {
+        toml::Table::try_from(self).map_err(|e| format!("could not serialize config: {}", e))
+    }
+
+    /// Serialize the full config as TOML
+    pub fn to_toml(&self) -> Result<String, String> {
+        toml::to_string_pretty(self).map_err(|e| format!("could not serialize config: {}", e))
+    }
+
+    /// Parse and validate config TOML (used by `config import`)
+    pub fn from_toml(contents: &str) -> Result<Self, String> {
+        let cfg: Config =
+            toml::from_str(contents).map_err(|e| format!("invalid config TOML: {}", e))?;
+        cfg.validate()?;
+        Ok(cfg)
+    }
+
+    /// Check that all values are within their valid ranges
+    pub fn validate(&self) -> Result<(), String> {
+        let loc = &self.location;
+        match (loc.default_lat, loc.default_lng) {
+            (Some(lat), Some(lng)) => {
+                if !(-90.0..=90.0).contains(&lat) {
+                    return Err(format!("location.default_lat out of range: {}", lat));
+                }
+                if !(-180.0..=180.0).contains(&lng) {
+                    return Err(format!("location.default_lng out of range: {}", lng));
+                }
+            }
+            (None, None) => {}
+            _ => {
+                return Err(
+                    "location.default_lat and location.default_lng must be set together".into(),
+                )
+            }
+        }
+        if let Some(r) = loc.default_radius {
+            if let Some(problem) = radius_problem(r) {
+                return Err(format!("location.default_radius: {}", problem));
+            }
+        }
+        for (name, place) in &self.locations {
+            if !(-90.0..=90.0).contains(&place.lat) {
+                return Err(format!("locations.{}.lat out of range: {}", name, place.lat));
+            }
+            if !(-180.0..=180.0).contains(&place.lng) {
+                return Err(format!("locations.{}.lng out of range: {}", name, place.lng));
+            }
+            if let Some(r) = place.radius {
+                if let Some(problem) = radius_problem(r) {
+                    return Err(format!("locations.{}.radius: {}", name, problem));
+                }
+            }
+        }
+        if let Some(lang) = &self.defaults.language {
+            if !locale::is_language_code(lang) {
+                return Err(format!("defaults.language is not a language code (e.g. en, pt-BR): {}", lang));
+            }
+        }
+        if let Some(region) = &self.defaults.region {
+            if !locale::is_region_code(region) {
+                return Err(format!("defaults.region is not a two-letter region code (e.g. us): {}", region));
+            }
+        }
+        let dirs = [
+            ("paths.cache_dir", &self.paths.cache_dir),
+            ("paths.state_dir", &self.paths.state_dir),
+            ("paths.log_dir", &self.paths.log_dir),
+        ];
+        for (key, dir) in dirs {
+            if dir.as_deref().is_some_and(|d| d.trim().is_empty()) {
+                return Err(format!("{} must not be empty", key));
+            }
+        }
+        let rank = &self.ranking;
+        let non_negative = [
+            ("ranking.rating_weight", rank.rating_weight),
+            ("ranking.reviews_weight", rank.reviews_weight),
+            ("ranking.distance_weight", rank.distance_weight),
+            ("ranking.prior_reviews", rank.prior_reviews),
+        ];
+        for (key, value) in non_negative {
+            if let Some(v) = value {
+                if !v.is_finite() || v < 0.0 {
+                    return Err(format!("{} must be a non-negative number: {}", key, v));
+                }
+            }
+        }
+        if let Some(r) = rank.prior_rating {
+            if !(1.0..=5.0).contains(&r) {
+                return Err(format!("ranking.prior_rating must be between 1 and 5: {}", r));
+            }
+        }
+        if let Some(limit) = self.tui.result_limit {
+            if !(1..=MAX_SEARCH_RESULTS).contains(&limit) {
+                return Err(format!(
+                    "tui.result_limit must be between 1 and {}: {}",
+                    MAX_SEARCH_RESULTS, limit
+                ));
+            }
+        }
+        if let Some(limit) = self.tui.autocomplete_limit {
+            if !(1..=MAX_AUTOCOMPLETE_SUGGESTIONS).contains(&limit) {
+                return Err(format!(
+                    "tui.autocomplete_limit must be between 1 and {}: {}",
+                    MAX_AUTOCOMPLETE_SUGGESTIONS, limit
+                ));
+            }
+        }
+        let saved = &self.tui.filters;
+        if let Some(t) = &saved.included_type {
+            if !crate::place_types::is_known_type(t) {
+                return Err(format!("tui.filters.included_type is not a known place type: {}", t));
+            }
+        }
+        if let Some(problem) = saved.radius.and_then(radius_problem) {
+            return Err(format!("tui.filters.radius: {}", problem));
+        }
+        if let Some(r) = saved.min_rating {
+            if !(0.0..=5.0).contains(&r) {
+                return Err(format!("tui.filters.min_rating must be between 0 and 5: {}", r));
+            }
+        }
+        if let Some(level) = saved.price_levels.iter().find(|l| !(0..=4).contains(*l)) {
+            return Err(format!("tui.filters.price_levels must be between 0 and 4: {}", level));
+        }
+        let filters = [
+            ("filters.far_factor", self.filters.far_factor),
+            ("filters.max_distance", self.filters.max_distance),
+        ];
+        for (key, value) in filters {
+            if let Some(v) = value {
+                if !v.is_finite() || v <= 0.0 {
+                    return Err(format!("{} must be a positive number: {}", key, v));
+                }
+            }
+        }
+        if let Some(size) = &self.output.photo_size {
+            PhotoSize::parse(size).map_err(|e| format!("output.photo_size: {}", e))?;
+        }
+        if let Some(qps) = self.network.max_qps {
+            if !qps.is_finite() || qps <= 0.0 {
+                return Err(format!("network.max_qps must be a positive number: {}", qps));
+            }
+        }
+        for (endpoint, price) in &self.pricing {
+            if !price.is_finite() || *price < 0.0 {
+                return Err(format!("pricing.{} must be a non-negative number: {}", endpoint, price));
+            }
+        }
         Ok(())
     }
 
+    /// Merge another config into this one; values set in `other` win
+    pub fn merge(&mut self, other: Config) {
+        let loc = other.location;
+        if loc.default_lat.is_some() && loc.default_lng.is_some() {
+            self.location.default_lat = loc.default_lat;
+            self.location.default_lng = loc.default_lng;
+        }
+        if loc.default_radius.is_some() {
+            self.location.default_radius = loc.default_radius;
+        }
+        if loc.label.is_some() {
+            self.location.label = loc.label;
+        }
+        if other.defaults.units.is_some() {
+            self.defaults.units = other.defaults.units;
+        }
+        if other.defaults.language.is_some() {
+            self.defaults.language = other.defaults.language;
+        }
+        if other.defaults.region.is_some() {
+            self.defaults.region = other.defaults.region;
+        }
+        if other.paths.cache_dir.is_some() {
+            self.paths.cache_dir = other.paths.cache_dir;
+        }
+        if other.paths.state_dir.is_some() {
+            self.paths.state_dir = other.paths.state_dir;
+        }
+        if other.paths.log_dir.is_some() {
+            self.paths.log_dir = other.paths.log_dir;
+        }
+        let rank = other.ranking;
+        if rank.rating_weight.is_some() {
+            self.ranking.rating_weight = rank.rating_weight;
+        }
+        if rank.reviews_weight.is_some() {
+            self.ranking.reviews_weight = rank.reviews_weight;
+        }
+        if rank.distance_weight.is_some() {
+            self.ranking.distance_weight = rank.distance_weight;
+        }
+        if rank.prior_rating.is_some() {
+            self.ranking.prior_rating = rank.prior_rating;
+        }
+        if rank.prior_reviews.is_some() {
+            self.ranking.prior_reviews = rank.prior_reviews;
+        }
+        if other.tui.wrap_navigation.is_some() {
+            self.tui.wrap_navigation = other.tui.wrap_navigation;
+        }
+        if other.tui.request_photos.is_some() {
+            self.tui.request_photos = other.tui.request_photos;
+        }
+        if other.tui.result_limit.is_some() {
+            self.tui.result_limit = other.tui.result_limit;
+        }
+        if other.tui.autocomplete_limit.is_some() {
+            self.tui.autocomplete_limit = other.tui.autocomplete_limit;
+        }
+        if other.tui.instant_results.is_some() {
+            self.tui.instant_results = other.tui.instant_results;
+        }
+        if other.tui.instant_results_monthly_limit.is_some() {
+            self.tui.instant_results_monthly_limit = other.tui.instant_results_monthly_limit;
+        }
+        if other.tui.persist_filters.is_some() {
+            self.tui.persist_filters = other.tui.persist_filters;
+        }
+        if !other.tui.filters.is_empty() {
+            self.tui.filters = other.tui.filters;
+        }
+        if other.filters.far_factor.is_some() {
+            self.filters.far_factor = other.filters.far_factor;
+        }
+        if other.filters.far_warning.is_some() {
+            self.filters.far_warning = other.filters.far_warning;
+        }
+        if other.filters.max_distance.is_some() {
+            self.filters.max_distance = other.filters.max_distance;
+        }
+        if other.output.hours_start_today.is_some() {
+            self.output.hours_start_today = other.output.hours_start_today;
+        }
+        if other.output.photo_size.is_some() {
+            self.output.photo_size = other.output.photo_size;
+        }
+        if other.output.address_style.is_some() {
+            self.output.address_style = other.output.address_style;
+        }
+        if other.network.max_qps.is_some() {
+            self.network.max_qps = other.network.max_qps;
+        }
+        if other.network.pool_idle_timeout.is_some() {
+            self.network.pool_idle_timeout = other.network.pool_idle_timeout;
+        }
+        if other.network.pool_max_idle_per_host.is_some() {
+            self.network.pool_max_idle_per_host = other.network.pool_max_idle_per_host;
+        }
+        if other.network.tcp_keepalive.is_some() {
+            self.network.tcp_keepalive = other.network.tcp_keepalive;
+        }
+        self.locations.extend(other.locations);
+        for (command, defaults) in other.commands {
+            self.commands.entry(command).or_default().extend(defaults);
+        }
+        self.pricing.extend(other.pricing);
+    }
+
+    /// Copy the current config file to config.toml.bak (no-op if missing)
+    pub fn backup() -> Result<Option<PathBuf>, String> {
+        let path = match config_path() {
+            Some(p) if p.exists() => p,
+            _ => return Ok(None),
+        };
+        let backup = path.with_extension("toml.bak");
+        fs::copy(&path, &backup).map_err(|e| format!("could not back up config: {}", e))?;
+        Ok(Some(backup))
+    }
+
     /// Set default location
     pub fn set_location(
         &mut self,
@@ -83,14 +641,197 @@ impl Config {
         }
     }
 
-    /// Get default radius (or fallback)
+    /// A `[locations]` entry by name, falling back to the default location
+    /// when `name` matches its label (case-insensitive): (lat, lng, radius)
+    pub fn named_location(&self, name: &str) -> Option<(f64, f64, f64)> {
+        if let Some(place) = self.locations.get(name) {
+            return Some((place.lat, place.lng, place.radius.unwrap_or_else(|| self.default_radius())));
+        }
+        let label = self.location.label.as_deref()?;
+        let (lat, lng) = self.default_location()?;
+        label
+            .eq_ignore_ascii_case(name)
+            .then(|| (lat, lng, self.default_radius()))
+    }
+
+    /// Names `named_location` accepts, for error messages
+    pub fn location_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.locations.keys().cloned().collect();
+        if let (Some(label), Some(_)) = (&self.location.label, self.default_location()) {
+            if !self.locations.contains_key(label) {
+                names.push(label.clone());
+            }
+        }
+        names
+    }
+
+    /// Unit system for distance display (or metric)
+    pub fn units(&self) -> Units {
+        self.defaults.units.unwrap_or_default()
+    }
+
+    /// Whether TUI lists wrap around at either end
+    pub fn wrap_navigation(&self) -> bool {
+        self.tui.wrap_navigation.unwrap_or(false)
+    }
+
+    /// Whether TUI searches request photo references
+    pub fn request_photos(&self) -> bool {
+        self.tui.request_photos.unwrap_or(false)
+    }
+
+    /// Results requested per TUI search
+    pub fn result_limit(&self) -> u32 {
+        self.tui.result_limit.unwrap_or(10)
+    }
+
+    /// Suggestions requested per TUI autocomplete
+    pub fn autocomplete_limit(&self) -> u32 {
+        self.tui.autocomplete_limit.unwrap_or(MAX_AUTOCOMPLETE_SUGGESTIONS)
+    }
+
+    /// Whether the TUI previews results while typing
+    pub fn instant_results(&self) -> bool {
+        self.tui.instant_results.unwrap_or(false)
+    }
+
+    /// Whether the TUI saves and restores its filter panel
+    pub fn persist_filters(&self) -> bool {
+        self.tui.persist_filters.unwrap_or(true)
+    }
+
+    /// Monthly text searches beyond which TUI live previews are skipped
+    pub fn instant_results_monthly_limit(&self) -> u64 {
+        self.tui.instant_results_monthly_limit.unwrap_or(1000)
+    }
+
+    /// Multiple of the bias radius beyond which a result counts as far
+    pub fn far_factor(&self) -> f64 {
+        self.filters.far_factor.unwrap_or(3.0)
+    }
+
+    /// Whether far results get a distance warning line
+    pub fn far_warning(&self) -> bool {
+        self.filters.far_warning.unwrap_or(true)
+    }
+
+    /// Whether opening hours are listed starting from today
+    pub fn hours_start_today(&self) -> bool {
+        self.output.hours_start_today.unwrap_or(true)
+    }
+
+    /// Address shown in place lists
+    pub fn address_style(&self) -> AddressStyle {
+        self.output.address_style.unwrap_or_default()
+    }
+
+    /// Box for inline photos: `output.photo_size`, else sized to the terminal
+    pub fn photo_size(&self) -> PhotoSize {
+        self.output
+            .photo_size
+            .as_deref()
+            .and_then(|s| PhotoSize::parse(s).ok())
+            .unwrap_or_else(PhotoSize::for_terminal)
+    }
+
+    /// Get default radius (or fallback, also used when the configured one
+    /// isn't a valid radius)
     pub fn default_radius(&self) -> f64 {
-        self.location.default_radius.unwrap_or(1000.0)
+        self.location
+            .default_radius
+            .filter(|&r| radius_problem(r).is_none())
+            .unwrap_or(1000.0)
+    }
+}
+
+/// Section-level merge: take a section from `mine` if it differs from what was
+/// loaded (or nothing was loaded), otherwise keep the on-disk version. A
+/// section emptied by this process (say `[tui]` once its filters are cleared)
+/// is dropped rather than left as a bare header, and so is one that was loaded
+/// but no longer serializes at all (`[locations]` once the last is removed).
+fn merge_sections(
+    on_disk: toml::Table,
+    mine: toml::Table,
+    loaded: Option<&toml::Table>,
+) -> toml::Table {
+    let mut merged = on_disk;
+    for section in loaded.into_iter().flat_map(|l| l.keys()) {
+        if !mine.contains_key(section) {
+            merged.remove(section);
+        }
+    }
+    for (section, value) in mine {
+        let unchanged = loaded.is_some_and(|l| l.get(&section) == Some(&value));
+        if unchanged {
+            continue;
+        }
+        if value.as_table().is_some_and(toml::Table::is_empty) {
+            merged.remove(&section);
+        } else {
+            merged.insert(section, value);
+        }
+    }
+    merged
+}
+
+/// Write via a temp file in the same directory, then rename over the target
+fn write_atomic(path: &Path, contents: &str) -> Result<(), String> {
+    let tmp = path.with_extension(format!("toml.tmp.{}", std::process::id()));
+    fs::write(&tmp, contents).map_err(|e| format!("could not write config file: {}", e))?;
+    fs::rename(&tmp, path).map_err(|e| {
+        let _ = fs::remove_file(&tmp);
+        format!("could not write config file: {}", e)
+    })
+}
+
+/// Advisory lock held while saving; removed on drop
+struct LockFile {
+    path: PathBuf,
+}
+
+impl LockFile {
+    const RETRY: Duration = Duration::from_millis(50);
+    const TIMEOUT: Duration = Duration::from_secs(5);
+    /// A lock older than this is assumed to belong to a crashed process
+    const STALE_AFTER: Duration = Duration::from_secs(30);
+
+    fn acquire(path: &Path) -> Result<Self, String> {
+        let start = SystemTime::now();
+        loop {
+            match fs::OpenOptions::new().write(true).create_new(true).open(path) {
+                Ok(_) => return Ok(LockFile { path: path.to_path_buf() }),
+                Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {
+                    let stale = fs::metadata(path)
+                        .and_then(|m| m.modified())
+                        .ok()
+                        .and_then(|m| m.elapsed().ok())
+                        .is_some_and(|age| age > Self::STALE_AFTER);
+                    if stale {
+                        let _ = fs::remove_file(path);
+                        continue;
+                    }
+                    if start.elapsed().unwrap_or_default() > Self::TIMEOUT {
+                        return Err(format!(
+                            "config is locked by another zupo process (remove {} if stale)",
+                            path.display()
+                        ));
+                    }
+                    thread::sleep(Self::RETRY);
+                }
+                Err(e) => return Err(format!("could not lock config file: {}", e)),
+            }
+        }
+    }
+}
+
+impl Drop for LockFile {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
     }
 }
 
 fn config_path() -> Option<PathBuf> {
-    dirs::config_dir().map(|d| d.join(APP_NAME).join("config.toml"))
+    paths::config_dir().map(|d| d.join("config.toml"))
 }
 
 pub fn config_file_path() -> String {
@@ -98,3 +839,86 @@ pub fn config_file_path() -> String {
         .map(|p| p.display().to_string())
         .unwrap_or_else(|| "~/.config/zupo/config.toml".to_string())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A config as `load` returns it: parsed, with the loaded sections recorded
+    fn loaded(contents: &str) -> Config {
+        let mut cfg: Config = toml::from_str(contents).unwrap();
+        cfg.loaded = cfg.to_table().ok();
+        cfg
+    }
+
+    fn save_onto(on_disk: &toml::Table, cfg: &Config) -> toml::Table {
+        merge_sections(on_disk.clone(), cfg.to_table().unwrap(), cfg.loaded.as_ref())
+    }
+
+    const BASE: &str = r#"
+[location]
+default_lat = 48.2
+default_lng = 16.37
+
+[locations.home]
+lat = 48.2
+lng = 16.37
+"#;
+
+    #[test]
+    fn interleaved_writers_keep_both_changes() {
+        let disk: toml::Table = toml::from_str(BASE).unwrap();
+        let mut a = loaded(BASE);
+        let mut b = loaded(BASE);
+
+        a.defaults.language = Some("de".into());
+        b.locations.insert(
+            "work".into(),
+            NamedLocation {
+                lat: 48.19,
+                lng: 16.4,
+                radius: None,
+            },
+        );
+        let disk = save_onto(&disk, &a);
+        let disk = save_onto(&disk, &b);
+
+        let result: Config = toml::from_str(&toml::to_string(&disk).unwrap()).unwrap();
+        assert_eq!(result.defaults.language.as_deref(), Some("de"));
+        assert!(result.locations.contains_key("home"));
+        assert!(result.locations.contains_key("work"));
+    }
+
+    #[test]
+    fn unchanged_sections_keep_the_on_disk_value() {
+        let mut disk: toml::Table = toml::from_str(BASE).unwrap();
+        let mine = loaded(BASE);
+        disk.insert("pricing".into(), toml::Value::Table(toml::from_str("searchText = 40.0").unwrap()));
+
+        let merged = save_onto(&disk, &mine);
+        assert_eq!(merged.get("pricing"), disk.get("pricing"));
+    }
+
+    #[test]
+    fn emptied_map_sections_are_removed() {
+        let disk: toml::Table = toml::from_str(BASE).unwrap();
+        let mut cfg = loaded(BASE);
+        cfg.locations.clear();
+
+        let merged = save_onto(&disk, &cfg);
+        assert!(!merged.contains_key("locations"));
+        assert!(merged.contains_key("location"));
+    }
+
+    #[test]
+    fn sections_unknown_to_this_process_are_kept() {
+        let mut disk: toml::Table = toml::from_str(BASE).unwrap();
+        disk.insert("commands".into(), toml::Value::Table(toml::from_str("[search]\nlimit = 5").unwrap()));
+        let mut cfg = loaded(BASE);
+        cfg.defaults.region = Some("at".into());
+
+        let merged = save_onto(&disk, &cfg);
+        assert!(merged.contains_key("commands"));
+        assert!(merged.contains_key("locations"));
+    }
+}
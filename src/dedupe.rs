@@ -0,0 +1,210 @@
+use std::collections::HashMap;
+
+use crate::api::types::{Circle, GroupedPlace, LocationGroup, Place, RoutePlace, RouteWaypointResult};
+use crate::geo::haversine_distance;
+
+/// One place folded into another by `dedupe_places`
+#[derive(Debug, Clone)]
+pub struct Merge {
+    pub kept: String,
+    pub dropped: String,
+    pub meters: f64,
+}
+
+/// Collapse places within `max_meters` of each other whose names are
+/// near-identical (see `names_match`), keeping the better-rated one in the
+/// position of the first. Places without a location or name are never merged.
+pub fn dedupe_places(places: Vec<Place>, max_meters: f64) -> (Vec<Place>, Vec<Merge>) {
+    let mut kept: Vec<(Place, String)> = Vec::with_capacity(places.len());
+    let mut merges = Vec::new();
+
+    for place in places {
+        let name = normalize_name(display_name(&place));
+        let duplicate = kept.iter().enumerate().find_map(|(i, (other, other_name))| {
+            let meters = haversine_distance(place.location.as_ref()?, other.location.as_ref()?);
+            (meters <= max_meters && names_match(&name, other_name)).then_some((i, meters))
+        });
+
+        let Some((i, meters)) = duplicate else {
+            kept.push((place, name));
+            continue;
+        };
+        let slot = &mut kept[i];
+        let (winner, loser) = if better_rated(&place, &slot.0) {
+            let loser = std::mem::replace(slot, (place, name));
+            (&slot.0, loser.0)
+        } else {
+            (&slot.0, place)
+        };
+        merges.push(Merge {
+            kept: display_name(winner).to_string(),
+            dropped: display_name(&loser).to_string(),
+            meters,
+        });
+    }
+
+    (kept.into_iter().map(|(p, _)| p).collect(), merges)
+}
+
+/// Group per-location results, listing each place (by ID) only under the
+/// first location that found it, with the labels of the others in `also_near`
+pub fn group_by_location(results: Vec<(String, Circle, Vec<Place>)>) -> Vec<LocationGroup> {
+    let mut found_near: HashMap<String, Vec<String>> = HashMap::new();
+    for (label, _, places) in &results {
+        for place in places {
+            let labels = found_near.entry(place.id.clone()).or_default();
+            if !labels.contains(label) {
+                labels.push(label.clone());
+            }
+        }
+    }
+
+    results
+        .into_iter()
+        .map(|(label, circle, places)| {
+            let places = places
+                .into_iter()
+                .filter_map(|place| {
+                    let near = found_near.get(&place.id)?;
+                    // Listed where it was found first; later groups skip it
+                    if near.first() != Some(&label) {
+                        return None;
+                    }
+                    Some(GroupedPlace {
+                        also_near: near[1..].to_vec(),
+                        place,
+                    })
+                })
+                .collect();
+            LocationGroup {
+                label,
+                center: circle.center,
+                radius: circle.radius,
+                places,
+            }
+        })
+        .collect()
+}
+
+/// Every place found along a route, once (by ID), at the waypoint nearest to
+/// it, ordered by position along the route. A place without a location stays
+/// at the first waypoint that found it.
+pub fn places_along_route(waypoints: &[RouteWaypointResult]) -> Vec<RoutePlace> {
+    let mut seen: HashMap<&str, usize> = HashMap::new();
+    // Meters from each listed place to its waypoint
+    let mut offsets: Vec<f64> = Vec::new();
+    let mut places: Vec<RoutePlace> = Vec::new();
+    for wp in waypoints {
+        for place in &wp.places {
+            let offset = place
+                .location
+                .as_ref()
+                .map_or(f64::INFINITY, |loc| haversine_distance(&wp.waypoint, loc));
+            let at_waypoint = || RoutePlace {
+                place: place.clone(),
+                waypoint_index: wp.waypoint_index,
+                distance_from_origin: wp.distance_from_origin,
+                route_fraction: wp.route_fraction,
+            };
+            match seen.get(place.id.as_str()) {
+                Some(&i) if offset < offsets[i] => {
+                    places[i] = at_waypoint();
+                    offsets[i] = offset;
+                }
+                Some(_) => {}
+                None => {
+                    seen.insert(&place.id, places.len());
+                    places.push(at_waypoint());
+                    offsets.push(offset);
+                }
+            }
+        }
+    }
+    places.sort_by(|a, b| a.distance_from_origin.total_cmp(&b.distance_from_origin));
+    places
+}
+
+/// Lowercase, fold common Latin diacritics, and reduce punctuation to single
+/// spaces: "Café  X-Rooftop" → "cafe x rooftop"
+pub fn normalize_name(name: &str) -> String {
+    let mut folded = String::with_capacity(name.len());
+    for c in name.chars().flat_map(char::to_lowercase) {
+        match fold_diacritic(c) {
+            Some(s) => folded.push_str(s),
+            None if c.is_alphanumeric() => folded.push(c),
+            None => folded.push(' '),
+        }
+    }
+    folded.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Whether two normalized names refer to the same venue: one is a word-prefix
+/// of the other ("cafe x" / "cafe x rooftop"), or they differ by at most one
+/// edit per five characters of the shorter name
+pub fn names_match(a: &str, b: &str) -> bool {
+    if a.is_empty() || b.is_empty() {
+        return false;
+    }
+    let (short, long) = if a.len() <= b.len() { (a, b) } else { (b, a) };
+    if long == short || long.starts_with(&format!("{} ", short)) {
+        return true;
+    }
+    let allowed = (short.chars().count() / 5).max(1);
+    levenshtein(short, long) <= allowed
+}
+
+/// Edit distance in characters (insertions, deletions, substitutions)
+pub fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    let mut cur = vec![0; b.len() + 1];
+    for (i, ca) in a.chars().enumerate() {
+        cur[0] = i + 1;
+        for (j, &cb) in b.iter().enumerate() {
+            let substitute = prev[j] + usize::from(ca != cb);
+            cur[j + 1] = substitute.min(prev[j + 1] + 1).min(cur[j] + 1);
+        }
+        std::mem::swap(&mut prev, &mut cur);
+    }
+    prev[b.len()]
+}
+
+/// Higher rating wins; equal ratings go to the place with more reviews
+fn better_rated(a: &Place, b: &Place) -> bool {
+    let key = |p: &Place| (p.rating.unwrap_or(0.0), p.user_rating_count.unwrap_or(0));
+    let (ra, ca) = key(a);
+    let (rb, cb) = key(b);
+    ra > rb || (ra == rb && ca > cb)
+}
+
+fn display_name(place: &Place) -> &str {
+    place
+        .display_name
+        .as_ref()
+        .map(|n| n.text.as_str())
+        .unwrap_or("")
+}
+
+fn fold_diacritic(c: char) -> Option<&'static str> {
+    Some(match c {
+        'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' | 'ā' | 'ă' | 'ą' => "a",
+        'ç' | 'ć' | 'č' => "c",
+        'ď' | 'đ' => "d",
+        'è' | 'é' | 'ê' | 'ë' | 'ē' | 'ė' | 'ę' | 'ě' => "e",
+        'ğ' => "g",
+        'ì' | 'í' | 'î' | 'ï' | 'ī' | 'ı' => "i",
+        'ł' | 'ľ' => "l",
+        'ñ' | 'ń' | 'ň' => "n",
+        'ò' | 'ó' | 'ô' | 'õ' | 'ö' | 'ø' | 'ō' | 'ő' => "o",
+        'ř' => "r",
+        'ś' | 'š' | 'ş' => "s",
+        'ť' | 'ţ' => "t",
+        'ù' | 'ú' | 'û' | 'ü' | 'ū' | 'ů' | 'ű' => "u",
+        'ý' | 'ÿ' => "y",
+        'ź' | 'ż' | 'ž' => "z",
+        'ß' => "ss",
+        'æ' => "ae",
+        'œ' => "oe",
+        _ => return None,
+    })
+}
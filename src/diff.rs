@@ -0,0 +1,103 @@
+use std::collections::HashMap;
+
+use serde::Serialize;
+
+use crate::api::types::Place;
+
+/// Difference between a previous and a fresh list of places, keyed by place ID
+#[derive(Debug, Clone, Serialize)]
+pub struct PlacesDiff {
+    pub added: Vec<Place>,
+    pub removed: Vec<Place>,
+    pub changed: Vec<PlaceChange>,
+}
+
+/// A before/after pair for one field
+#[derive(Debug, Clone, Serialize)]
+pub struct Change<T> {
+    pub before: Option<T>,
+    pub after: Option<T>,
+}
+
+/// A place present in both lists whose tracked fields differ
+#[derive(Debug, Clone, Serialize)]
+pub struct PlaceChange {
+    pub id: String,
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rating: Option<Change<f64>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_rating_count: Option<Change<u32>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub business_status: Option<Change<String>>,
+}
+
+impl PlacesDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+/// Compare two place lists by ID. Order follows `current` for added/changed
+/// places and `previous` for removed ones.
+pub fn diff_places(previous: &[Place], current: &[Place]) -> PlacesDiff {
+    let prev_by_id: HashMap<&str, &Place> = previous.iter().map(|p| (p.id.as_str(), p)).collect();
+    let curr_by_id: HashMap<&str, &Place> = current.iter().map(|p| (p.id.as_str(), p)).collect();
+
+    let mut added = Vec::new();
+    let mut changed = Vec::new();
+    for place in current {
+        match prev_by_id.get(place.id.as_str()) {
+            None => added.push(place.clone()),
+            Some(old) => {
+                if let Some(change) = compare_place(old, place) {
+                    changed.push(change);
+                }
+            }
+        }
+    }
+
+    let removed = previous
+        .iter()
+        .filter(|p| !curr_by_id.contains_key(p.id.as_str()))
+        .cloned()
+        .collect();
+
+    PlacesDiff {
+        added,
+        removed,
+        changed,
+    }
+}
+
+fn compare_place(old: &Place, new: &Place) -> Option<PlaceChange> {
+    let rating = field_change(&old.rating, &new.rating);
+    let user_rating_count = field_change(&old.user_rating_count, &new.user_rating_count);
+    let business_status = field_change(&old.business_status, &new.business_status);
+
+    if rating.is_none() && user_rating_count.is_none() && business_status.is_none() {
+        return None;
+    }
+
+    let name = new
+        .display_name
+        .as_ref()
+        .or(old.display_name.as_ref())
+        .map(|n| n.text.clone())
+        .unwrap_or_else(|| new.id.clone());
+
+    Some(PlaceChange {
+        id: new.id.clone(),
+        name,
+        rating,
+        user_rating_count,
+        business_status,
+    })
+}
+
+fn field_change<T: Clone + PartialEq>(before: &Option<T>, after: &Option<T>) -> Option<Change<T>> {
+    (before != after).then(|| Change {
+        before: before.clone(),
+        after: after.clone(),
+    })
+}
@@ -0,0 +1,228 @@
+/// One example invocation for a subcommand's `--help` and `zupo examples`
+pub struct Example {
+    pub description: &'static str,
+    /// Shell command line; may pipe into other tools after the zupo part
+    pub command: &'static str,
+}
+
+const fn ex(description: &'static str, command: &'static str) -> Example {
+    Example { description, command }
+}
+
+const SEARCH: &[Example] = &[
+    ex("Coffee near your saved location", "zupo search -q \"coffee\""),
+    ex(
+        "Well-rated, affordable ramen open now, around explicit coordinates",
+        "zupo search -q \"ramen\" --lat 40.7128 --lng=-74.0060 --radius 2000 --min-rating 4.2 --price \"<=2\" --open-now",
+    ),
+    ex(
+        "Rank by rating, review count, and distance instead of relevance",
+        "zupo search -q \"bakery\" --rank smart --min-reviews 50",
+    ),
+    ex(
+        "Just the names, as JSON piped to jq",
+        "zupo search -q \"museum in Vienna\" --json | jq -r '.places[].displayName.text'",
+    ),
+];
+
+const AUTOCOMPLETE: &[Example] = &[
+    ex("Suggestions for a partial name", "zupo autocomplete -i \"cafe cen\""),
+    ex("Only cities, in German", "zupo autocomplete -i \"wie\" --only cities --lang de"),
+    ex(
+        "Biased to a circle, more suggestions",
+        "zupo autocomplete -i \"pizza\" --lat 48.2082 --lng 16.3738 --radius 1500 --limit 10",
+    ),
+];
+
+const NEARBY: &[Example] = &[
+    ex("Cafés around your saved location", "zupo nearby --include-type cafe"),
+    ex(
+        "Closest pharmacies first, within 500 m of explicit coordinates",
+        "zupo nearby --lat 48.2082 --lng 16.3738 --radius 500 --include-type pharmacy --rank-by distance",
+    ),
+    ex(
+        "Restaurants around two saved locations at once",
+        "zupo nearby --at home --at work --include-type restaurant",
+    ),
+    ex(
+        "How many parks are within 2 km",
+        "zupo nearby --include-type park --radius 2000 --count",
+    ),
+];
+
+const ROUTE: &[Example] = &[
+    ex("Coffee along a drive", "zupo route -q \"coffee\" --from \"Vienna\" --to \"Salzburg\""),
+    ex(
+        "Gas stations along the best of several routes, more waypoints",
+        "zupo route -q \"gas station\" --from \"Munich\" --to \"Zurich\" --alternatives --compare-routes --max-waypoints 8",
+    ),
+    ex(
+        "A bike ride between coordinates",
+        "zupo route -q \"bike shop\" --from \"48.2082,16.3738\" --to \"48.1850,16.3200\" --mode bicycle --radius 300",
+    ),
+    ex(
+        "Waypoint names as JSON",
+        "zupo route -q \"rest area\" --from \"Berlin\" --to \"Hamburg\" --json | jq '.waypoints[].places[].displayName.text'",
+    ),
+];
+
+const DETAILS: &[Example] = &[
+    ex("Full details of a place", "zupo details --place-id ChIJ84iU6DOBhYARHXonh3NuCNo"),
+    ex(
+        "With reviews and inline photos",
+        "zupo details --place-id ChIJ84iU6DOBhYARHXonh3NuCNo --reviews --show-photos",
+    ),
+    ex(
+        "As Markdown, for pasting into notes",
+        "zupo details --place-id ChIJ84iU6DOBhYARHXonh3NuCNo --format markdown",
+    ),
+    ex(
+        "Re-render a saved search result without calling the API",
+        "zupo search -q \"coffee\" --json | zupo details --from-json -",
+    ),
+];
+
+const REVIEWS: &[Example] = &[
+    ex("A place's reviews", "zupo reviews --place-id ChIJ84iU6DOBhYARHXonh3NuCNo"),
+    ex(
+        "Newest first, only 4 stars and up",
+        "zupo reviews --place-id ChIJ84iU6DOBhYARHXonh3NuCNo --sort newest --min-rating 4",
+    ),
+    ex(
+        "Translated to English, with the original text",
+        "zupo reviews --place-id ChIJ84iU6DOBhYARHXonh3NuCNo --lang en --full",
+    ),
+];
+
+const PHOTO: &[Example] = &[
+    ex(
+        "URL of a photo listed by details --photos",
+        "zupo photo --name \"places/ChIJ84iU6DOBhYARHXonh3NuCNo/photos/AUc7tXX\"",
+    ),
+    ex(
+        "Show it inline, 60 columns wide",
+        "zupo photo --name \"places/ChIJ84iU6DOBhYARHXonh3NuCNo/photos/AUc7tXX\" --show --photo-width 60",
+    ),
+    ex(
+        "A larger download, URL as JSON",
+        "zupo photo --name \"places/ChIJ84iU6DOBhYARHXonh3NuCNo/photos/AUc7tXX\" --max-width 1600 --json | jq -r .photoUri",
+    ),
+];
+
+const RESOLVE: &[Example] = &[
+    ex("Candidates for an address", "zupo resolve -l \"1600 Amphitheatre Parkway\""),
+    ex("An ambiguous name, up to 10 candidates", "zupo resolve -l \"Springfield\" --limit 10"),
+    ex("Coordinates of the best match", "zupo resolve -l \"Stephansplatz, Wien\" --json | jq '.places[0].location'"),
+];
+
+const MONITOR: &[Example] = &[
+    ex("Report new ramen places every hour", "zupo monitor -q \"ramen\" --notify-on new"),
+    ex(
+        "One check per cron run, logging changes as JSON lines",
+        "zupo monitor -q \"coffee\" --once --json >> coffee-changes.jsonl",
+    ),
+    ex(
+        "Desktop notification on closures and big rating moves",
+        "zupo monitor -q \"bakery\" --notify-on rating,closed --rating-threshold 0.3 --exec 'notify-send \"zupo\" \"$(jq -r .query)\"'",
+    ),
+];
+
+const CONFIG: &[Example] = &[
+    ex(
+        "Save a default location",
+        "zupo config set-location --lat 48.2082 --lng 16.3738 --label \"Vienna\"",
+    ),
+    ex("Show the config and where it lives", "zupo config show"),
+    ex("Check the config file for mistakes", "zupo config validate"),
+    ex("Copy the config to another machine", "zupo config export --output zupo-config.toml"),
+];
+
+const NOTE: &[Example] = &[
+    ex(
+        "Remember something about a place",
+        "zupo note add --place-id ChIJ84iU6DOBhYARHXonh3NuCNo \"great patio, cash only\"",
+    ),
+    ex("Every note, numbered per place", "zupo note list"),
+    ex(
+        "Drop a place's second note",
+        "zupo note remove --place-id ChIJ84iU6DOBhYARHXonh3NuCNo 2",
+    ),
+];
+
+const STATS: &[Example] = &[
+    ex("This month's requests and estimated cost", "zupo stats"),
+    ex("As JSON", "zupo stats --json"),
+    ex("Clear the counters", "zupo stats reset"),
+];
+
+const TYPES: &[Example] = &[
+    ex("Every place type, by category", "zupo types"),
+    ex("Types mentioning food", "zupo types --filter food"),
+];
+
+const TUI: &[Example] = &[
+    ex("Interactive search", "zupo tui"),
+    ex("Interactive search around other coordinates", "ZUPO_DEFAULT_LAT=48.2082 ZUPO_DEFAULT_LNG=16.3738 zupo tui"),
+];
+
+const SERVE: &[Example] = &[
+    ex("Serve on the default port, 127.0.0.1:7777", "zupo serve"),
+    ex(
+        "Require a bearer token",
+        "ZUPO_SERVE_TOKEN=s3cret zupo serve --listen 127.0.0.1:8080",
+    ),
+    ex(
+        "Query it",
+        "curl -s localhost:7777/search -H 'Content-Type: application/json' -d '{\"query\": \"coffee\", \"limit\": 5}'",
+    ),
+];
+
+/// Subcommands with examples, in `--help` order
+pub const COMMANDS: [&str; 15] = [
+    "search",
+    "autocomplete",
+    "nearby",
+    "route",
+    "details",
+    "reviews",
+    "photo",
+    "resolve",
+    "monitor",
+    "config",
+    "note",
+    "stats",
+    "types",
+    "tui",
+    "serve",
+];
+
+/// Examples for a subcommand, by name
+pub fn for_command(name: &str) -> Option<&'static [Example]> {
+    Some(match name {
+        "search" => SEARCH,
+        "autocomplete" => AUTOCOMPLETE,
+        "nearby" => NEARBY,
+        "route" => ROUTE,
+        "details" => DETAILS,
+        "reviews" => REVIEWS,
+        "photo" => PHOTO,
+        "resolve" => RESOLVE,
+        "monitor" => MONITOR,
+        "config" => CONFIG,
+        "note" => NOTE,
+        "stats" => STATS,
+        "types" => TYPES,
+        "tui" => TUI,
+        "serve" => SERVE,
+        _ => return None,
+    })
+}
+
+/// Plain-text "Examples:" block for clap's `after_help`
+pub fn help_text(examples: &[Example]) -> String {
+    let mut out = String::from("Examples:");
+    for example in examples {
+        out.push_str(&format!("\n  # {}\n  {}\n", example.description, example.command));
+    }
+    out.trim_end().to_string()
+}
@@ -0,0 +1,118 @@
+use serde::Serialize;
+
+use crate::api::types::{Circle, Place, SearchRequest};
+use crate::geo::haversine_distance;
+
+/// Outcome of one check against a place
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Verdict {
+    Pass,
+    Fail,
+    /// The place lacks the field the check needs
+    Unknown,
+}
+
+impl Verdict {
+    fn from_option(check: Option<bool>) -> Self {
+        match check {
+            Some(true) => Verdict::Pass,
+            Some(false) => Verdict::Fail,
+            None => Verdict::Unknown,
+        }
+    }
+}
+
+/// What `search --explain` can say about one result. Checks for criteria the
+/// search didn't use are None (and left out of JSON).
+#[derive(Debug, Clone, Serialize)]
+pub struct Explanation {
+    /// 1-based position in the printed list
+    pub rank: usize,
+    /// Meters from the bias center, rounded
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub distance_meters: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub within_radius: Option<bool>,
+    /// `--type` appears in the place's types
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub type_match: Option<Verdict>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_rating: Option<Verdict>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub price_level: Option<Verdict>,
+    /// From the place's `openNow`, so it reflects the time of the request
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub open_now: Option<Verdict>,
+}
+
+/// The search criteria results are explained against
+#[derive(Debug, Clone, Copy)]
+pub struct Criteria<'a> {
+    pub bias: Option<&'a Circle>,
+    pub included_type: Option<&'a str>,
+    pub min_rating: Option<f64>,
+    /// API price level names (`PRICE_LEVEL_MODERATE`); empty for any
+    pub price_levels: &'a [String],
+    pub open_now: bool,
+}
+
+impl<'a> Criteria<'a> {
+    pub fn from_request(req: &'a SearchRequest) -> Self {
+        Criteria {
+            bias: req.location.as_ref(),
+            included_type: req.included_type.as_deref(),
+            min_rating: req.min_rating,
+            price_levels: &req.price_levels,
+            open_now: req.open_now,
+        }
+    }
+}
+
+/// Explain one place at 1-based position `rank`
+pub fn explain_place(place: &Place, rank: usize, criteria: &Criteria) -> Explanation {
+    let distance = criteria
+        .bias
+        .zip(place.location.as_ref())
+        .map(|(bias, loc)| (haversine_distance(&bias.center, loc), bias.radius));
+
+    let type_match = criteria.included_type.map(|wanted| {
+        let listed = place.types.as_ref().map(|types| types.iter().any(|t| t == wanted));
+        let primary = place.primary_type.as_deref().map(|t| t == wanted);
+        match (listed, primary) {
+            (Some(true), _) | (_, Some(true)) => Verdict::Pass,
+            (None, None) => Verdict::Unknown,
+            _ => Verdict::Fail,
+        }
+    });
+
+    Explanation {
+        rank,
+        distance_meters: distance.map(|(d, _)| d.round()),
+        within_radius: distance.map(|(d, radius)| d <= radius),
+        type_match,
+        min_rating: criteria
+            .min_rating
+            .map(|min| Verdict::from_option(place.rating.map(|r| r >= min))),
+        price_level: (!criteria.price_levels.is_empty()).then(|| {
+            Verdict::from_option(
+                place
+                    .price_level
+                    .as_ref()
+                    .map(|level| criteria.price_levels.contains(level)),
+            )
+        }),
+        open_now: criteria.open_now.then(|| {
+            Verdict::from_option(place.current_opening_hours.as_ref().and_then(|h| h.open_now))
+        }),
+    }
+}
+
+/// Explain every place in printed order
+pub fn explain_places<'p>(places: impl IntoIterator<Item = &'p Place>, criteria: &Criteria) -> Vec<Explanation> {
+    places
+        .into_iter()
+        .enumerate()
+        .map(|(i, place)| explain_place(place, i + 1, criteria))
+        .collect()
+}
@@ -0,0 +1,548 @@
+use std::borrow::Cow;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+use crate::api::types::{Place, Units};
+use crate::locale;
+
+const METERS_PER_MILE: f64 = 1609.344;
+const FEET_PER_METER: f64 = 3.28084;
+
+/// Digit grouping and decimal mark of a language's numbers, e.g. "1,204.5"
+/// in English and "1.204,5" in German
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NumberFormat {
+    pub group: &'static str,
+    pub decimal: char,
+}
+
+impl NumberFormat {
+    pub const ENGLISH: NumberFormat = NumberFormat {
+        group: ",",
+        decimal: '.',
+    };
+    const DOT_GROUPS: NumberFormat = NumberFormat {
+        group: ".",
+        decimal: ',',
+    };
+    /// Grouped with a narrow no-break space, as in French "1 204,5"
+    const SPACE_GROUPS: NumberFormat = NumberFormat {
+        group: "\u{202F}",
+        decimal: ',',
+    };
+    const SWISS: NumberFormat = NumberFormat {
+        group: "’",
+        decimal: '.',
+    };
+
+    /// Conventions for a BCP-47 language code, following CLDR for common
+    /// languages; unknown codes and `None` get English formatting
+    pub fn for_language(code: Option<&str>) -> Self {
+        let Some(code) = code else {
+            return Self::ENGLISH;
+        };
+        let code = code.to_ascii_lowercase().replace('_', "-");
+        match code.as_str() {
+            "de-ch" | "de-li" | "it-ch" => return Self::SWISS,
+            "es-mx" | "es-us" | "es-419" | "pt-ao" => return Self::ENGLISH,
+            _ => {}
+        }
+        match code.split('-').next().unwrap_or_default() {
+            "de" | "es" | "it" | "nl" | "pt" | "id" | "tr" | "da" | "el" | "ro" | "hr" | "sl"
+            | "sr" | "vi" => Self::DOT_GROUPS,
+            "fr" | "ru" | "pl" | "cs" | "sk" | "sv" | "fi" | "nb" | "no" | "uk" | "hu" | "bg"
+            | "lt" | "lv" | "et" => Self::SPACE_GROUPS,
+            _ => Self::ENGLISH,
+        }
+    }
+}
+
+/// Which address place lists show (`output.address_style`, `--address-style`);
+/// detail views always show the full one
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum AddressStyle {
+    /// `shortFormattedAddress`, e.g. "Herrengasse 14, Wien"
+    Short,
+    /// `formattedAddress`, with postal code and country
+    #[default]
+    Full,
+    /// Short for places in your region (`defaults.region`), full elsewhere
+    Auto,
+}
+
+/// The address style of a place list, with the home region (`defaults.region`)
+/// that `AddressStyle::Auto` compares each place against
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct AddressChoice<'a> {
+    pub style: AddressStyle,
+    pub region: Option<&'a str>,
+}
+
+/// A place's address as lists show it in `choice`, falling back to the
+/// other form when the preferred one is missing
+pub fn list_address<'p>(place: &'p Place, choice: AddressChoice) -> Option<&'p str> {
+    let short = match choice.style {
+        AddressStyle::Short => true,
+        AddressStyle::Full => false,
+        AddressStyle::Auto => choice.region.is_some_and(|r| locale::in_region(place, r)),
+    };
+    let (first, second) = if short {
+        (&place.short_formatted_address, &place.formatted_address)
+    } else {
+        (&place.formatted_address, &place.short_formatted_address)
+    };
+    first.as_deref().or(second.as_deref())
+}
+
+/// A whole number with digit grouping, e.g. "1,204" / "1.204"
+pub fn format_count(n: u64, nf: NumberFormat) -> String {
+    group_digits(&n.to_string(), nf.group)
+}
+
+/// A rating to one decimal place, e.g. "4.5" / "4,5"
+pub fn format_rating(rating: f64, nf: NumberFormat) -> String {
+    format_decimal(rating, 1, nf)
+}
+
+/// `value` to `places` decimals with the grouping and decimal mark of `nf`
+pub fn format_decimal(value: f64, places: usize, nf: NumberFormat) -> String {
+    let text = format!("{:.*}", places, value.abs());
+    let (int, frac) = text.split_once('.').unwrap_or((&text, ""));
+    let mut out = String::new();
+    if value < 0.0 && text.chars().any(|c| c.is_ascii_digit() && c != '0') {
+        out.push('-');
+    }
+    out.push_str(&group_digits(int, nf.group));
+    if !frac.is_empty() {
+        out.push(nf.decimal);
+        out.push_str(frac);
+    }
+    out
+}
+
+fn group_digits(digits: &str, group: &str) -> String {
+    let mut out = String::with_capacity(digits.len() + digits.len() / 3 * group.len());
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            out.push_str(group);
+        }
+        out.push(c);
+    }
+    out
+}
+
+/// Format a distance in meters for display, e.g. "850 m", "1.2 km", "500 ft", "3.4 mi"
+/// (with the decimal mark and grouping of `nf`)
+pub fn format_distance(meters: f64, units: Units, nf: NumberFormat) -> String {
+    match units {
+        Units::Metric => {
+            if meters < 1000.0 {
+                format!("{} m", format_decimal(meters, 0, nf))
+            } else if meters < 10_000.0 {
+                format!("{} km", format_decimal(meters / 1000.0, 1, nf))
+            } else {
+                format!("{} km", format_decimal(meters / 1000.0, 0, nf))
+            }
+        }
+        Units::Imperial => {
+            let miles = meters / METERS_PER_MILE;
+            if miles < 0.1 {
+                format!("{} ft", format_decimal(meters * FEET_PER_METER, 0, nf))
+            } else if miles < 10.0 {
+                format!("{} mi", format_decimal(miles, 1, nf))
+            } else {
+                format!("{} mi", format_decimal(miles, 0, nf))
+            }
+        }
+    }
+}
+
+/// Default decimal places for coordinates; 5 places is about 1 m
+pub const DEFAULT_COORD_PRECISION: usize = 5;
+
+/// Format a latitude or longitude with a fixed number of decimal places
+pub fn format_coord(value: f64, precision: usize) -> String {
+    format!("{:.*}", precision, value)
+}
+
+/// Parse a single coordinate, accepting `48.2` or the locale style `48,2`.
+/// A comma is only taken as the decimal separator when it is the sole separator.
+pub fn parse_coord(text: &str) -> Result<f64, String> {
+    let text = text.trim();
+    let normalized = if !text.contains('.') && text.matches(',').count() == 1 {
+        text.replace(',', ".")
+    } else {
+        text.to_string()
+    };
+    normalized
+        .parse::<f64>()
+        .map_err(|_| format!("'{}' is not a coordinate (e.g. 48.2082 or 48,2082)", text))
+}
+
+/// Parse free-form location text (an address or a combined `lat,lng`).
+/// A coordinate pair written with decimal commas (`48,2,16,37`) is rejected
+/// because the separators are ambiguous.
+pub fn parse_location(text: &str) -> Result<String, String> {
+    let numeric = text
+        .chars()
+        .all(|c| c.is_ascii_digit() || matches!(c, ',' | '-' | '+' | ' '));
+    if numeric && text.matches(',').count() > 1 {
+        return Err(format!(
+            "'{}' is ambiguous: use '.' as the decimal separator in a combined lat,lng (e.g. 48.2,16.37)",
+            text.trim()
+        ));
+    }
+    Ok(text.to_string())
+}
+
+/// Parse a protobuf-style duration string as returned by the Routes API,
+/// e.g. "1823s" or "0.5s"; None for anything malformed or negative
+pub fn parse_duration_s(text: &str) -> Option<Duration> {
+    let secs: f64 = text.trim().strip_suffix('s')?.parse().ok()?;
+    if !secs.is_finite() || secs < 0.0 {
+        return None;
+    }
+    Some(Duration::from_secs_f64(secs))
+}
+
+/// Format a travel time to the nearest minute, e.g. "12 min", "1 h 12 min", "2 h";
+/// durations under 30 seconds read "< 1 min" (or "0 min" when zero)
+pub fn humanize_duration(duration: Duration) -> String {
+    let secs = duration.as_secs_f64();
+    if secs == 0.0 {
+        return "0 min".to_string();
+    }
+    let minutes = (secs / 60.0).round() as u64;
+    if minutes == 0 {
+        "< 1 min".to_string()
+    } else if minutes < 60 {
+        format!("{} min", minutes)
+    } else if minutes.is_multiple_of(60) {
+        format!("{} h", minutes / 60)
+    } else {
+        format!("{} h {} min", minutes / 60, minutes % 60)
+    }
+}
+
+/// Format a request latency, e.g. "412 ms", "1.9 s"
+pub fn format_elapsed(elapsed: Duration) -> String {
+    let ms = elapsed.as_millis();
+    if ms < 1000 {
+        format!("{} ms", ms)
+    } else {
+        format!("{:.1} s", elapsed.as_secs_f64())
+    }
+}
+
+/// Make API-sourced text safe to print inline: C0/C1 control characters,
+/// zero-width spaces, and bidi embeddings, overrides, isolates, and marks are
+/// dropped, and every whitespace run (newlines and tabs included) becomes a
+/// single space. Borrows when there is nothing to change.
+pub fn sanitize_display(text: &str) -> Cow<'_, str> {
+    let mut prev_space = false;
+    let dirty = text.chars().any(|c| {
+        let bad = is_hidden(c) || (c.is_whitespace() && (c != ' ' || prev_space));
+        prev_space = c == ' ';
+        bad
+    });
+    if !dirty {
+        return Cow::Borrowed(text);
+    }
+
+    let mut out = String::with_capacity(text.len());
+    let mut prev_space = false;
+    for c in text.chars() {
+        if c.is_whitespace() {
+            if !prev_space {
+                out.push(' ');
+            }
+            prev_space = true;
+        } else if !is_hidden(c) {
+            out.push(c);
+            prev_space = false;
+        }
+    }
+    Cow::Owned(out)
+}
+
+/// Characters of a place ID shown when nothing better names the place
+const PLACE_ID_TAIL: usize = 8;
+
+/// What to call a place in a result list: its display name, or for sparse
+/// responses (`--count`, a narrow field mask) its short address, full
+/// address, coordinates, or the tail of its ID, in that order. The flag is
+/// set for those fallbacks so renderers can dim them.
+pub fn place_label(place: &Place) -> (String, bool) {
+    if let Some(name) = place.display_name.as_ref().filter(|n| !n.text.trim().is_empty()) {
+        return (sanitize_display(&name.text).into_owned(), false);
+    }
+    let address = [&place.short_formatted_address, &place.formatted_address]
+        .into_iter()
+        .flatten()
+        .find(|a| !a.trim().is_empty());
+    let label = if let Some(address) = address {
+        sanitize_display(address).into_owned()
+    } else if let Some(loc) = &place.location {
+        format!(
+            "{}, {}",
+            format_coord(loc.latitude, DEFAULT_COORD_PRECISION),
+            format_coord(loc.longitude, DEFAULT_COORD_PRECISION)
+        )
+    } else if !place.id.is_empty() {
+        let id = sanitize_display(&place.id);
+        let skip = id.chars().count().saturating_sub(PLACE_ID_TAIL);
+        let tail: String = id.chars().skip(skip).collect();
+        format!("place {}{}", if skip > 0 { "…" } else { "" }, tail)
+    } else {
+        "Unknown".to_string()
+    };
+    (label, true)
+}
+
+/// Terminal columns `text` takes up: wide (CJK, most emoji) characters count
+/// two, combining marks none
+pub fn display_width(text: &str) -> usize {
+    UnicodeWidthStr::width(text)
+}
+
+/// `text` cut to at most `width` terminal columns, ending in "…" when shortened
+pub fn truncate_to_width(text: &str, width: usize) -> String {
+    if display_width(text) <= width {
+        return text.to_string();
+    }
+    let budget = width.saturating_sub(1);
+    let mut used = 0;
+    let mut out = String::new();
+    for c in text.chars() {
+        let w = UnicodeWidthChar::width(c).unwrap_or(0);
+        if used + w > budget {
+            break;
+        }
+        used += w;
+        out.push(c);
+    }
+    if width > 0 {
+        out.push('…');
+    }
+    out
+}
+
+/// Greedy word wrap to lines of at most `width` characters; a word longer
+/// than `width` gets a line of its own. Whitespace runs collapse to one space.
+pub fn wrap_text(text: &str, width: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut line = String::new();
+    let mut len = 0;
+    for word in text.split_whitespace() {
+        let word_len = word.chars().count();
+        if len > 0 && len + 1 + word_len > width {
+            lines.push(std::mem::take(&mut line));
+            len = 0;
+        }
+        if len > 0 {
+            line.push(' ');
+            len += 1;
+        }
+        line.push_str(word);
+        len += word_len;
+    }
+    if !line.is_empty() {
+        lines.push(line);
+    }
+    lines
+}
+
+/// Invisible characters that can garble or spoof terminal output. Zero-width
+/// (non-)joiners are kept: emoji sequences and some scripts need them.
+fn is_hidden(c: char) -> bool {
+    (c.is_control() && !c.is_whitespace())
+        || matches!(
+            c,
+            '\u{00AD}'
+                | '\u{061C}'
+                | '\u{180E}'
+                | '\u{200B}'
+                | '\u{200E}'
+                | '\u{200F}'
+                | '\u{202A}'..='\u{202E}'
+                | '\u{2060}'..='\u{2064}'
+                | '\u{2066}'..='\u{2069}'
+                | '\u{FEFF}'
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn humanized(text: &str) -> Option<String> {
+        parse_duration_s(text).map(humanize_duration)
+    }
+
+    fn en() -> NumberFormat {
+        NumberFormat::for_language(Some("en"))
+    }
+
+    fn de() -> NumberFormat {
+        NumberFormat::for_language(Some("de"))
+    }
+
+    fn ja() -> NumberFormat {
+        NumberFormat::for_language(Some("ja"))
+    }
+
+    #[test]
+    fn counts_group_digits_by_language() {
+        for nf in [en(), ja()] {
+            assert_eq!(format_count(0, nf), "0");
+            assert_eq!(format_count(999, nf), "999");
+            assert_eq!(format_count(1204, nf), "1,204");
+            assert_eq!(format_count(1_234_567, nf), "1,234,567");
+        }
+        assert_eq!(format_count(999, de()), "999");
+        assert_eq!(format_count(1204, de()), "1.204");
+        assert_eq!(format_count(1_234_567, de()), "1.234.567");
+    }
+
+    #[test]
+    fn ratings_use_the_language_decimal_mark() {
+        for nf in [en(), ja()] {
+            assert_eq!(format_rating(4.5, nf), "4.5");
+            assert_eq!(format_rating(4.0, nf), "4.0");
+            assert_eq!(format_rating(4.56, nf), "4.6");
+        }
+        assert_eq!(format_rating(4.5, de()), "4,5");
+        assert_eq!(format_rating(4.0, de()), "4,0");
+        assert_eq!(format_rating(4.56, de()), "4,6");
+    }
+
+    #[test]
+    fn distances_follow_the_language_and_units() {
+        for nf in [en(), ja()] {
+            assert_eq!(format_distance(850.0, Units::Metric, nf), "850 m");
+            assert_eq!(format_distance(1234.0, Units::Metric, nf), "1.2 km");
+            assert_eq!(format_distance(12_345.0, Units::Metric, nf), "12 km");
+            assert_eq!(format_distance(1_234_567.0, Units::Metric, nf), "1,235 km");
+            assert_eq!(format_distance(100.0, Units::Imperial, nf), "328 ft");
+            assert_eq!(format_distance(5470.0, Units::Imperial, nf), "3.4 mi");
+        }
+        assert_eq!(format_distance(850.0, Units::Metric, de()), "850 m");
+        assert_eq!(format_distance(1234.0, Units::Metric, de()), "1,2 km");
+        assert_eq!(format_distance(12_345.0, Units::Metric, de()), "12 km");
+        assert_eq!(format_distance(1_234_567.0, Units::Metric, de()), "1.235 km");
+        assert_eq!(format_distance(5470.0, Units::Imperial, de()), "3,4 mi");
+    }
+
+    const SHORT: &str = "Herrengasse 14, Wien";
+    const FULL: &str = "Herrengasse 14, 1010 Wien, Austria";
+
+    /// Cafe Central, with both address forms and an Austrian postal address
+    fn cafe_central() -> Place {
+        serde_json::from_value(serde_json::json!({
+            "id": "ChIJ-central",
+            "formattedAddress": FULL,
+            "shortFormattedAddress": SHORT,
+            "postalAddress": {"regionCode": "AT", "locality": "Wien"},
+        }))
+        .unwrap()
+    }
+
+    fn choice(style: AddressStyle, region: Option<&str>) -> AddressChoice<'_> {
+        AddressChoice { style, region }
+    }
+
+    #[test]
+    fn short_and_full_styles_ignore_the_region() {
+        let place = cafe_central();
+        for region in [None, Some("at"), Some("de")] {
+            assert_eq!(list_address(&place, choice(AddressStyle::Short, region)), Some(SHORT));
+            assert_eq!(list_address(&place, choice(AddressStyle::Full, region)), Some(FULL));
+        }
+        assert_eq!(list_address(&place, AddressChoice::default()), Some(FULL));
+    }
+
+    #[test]
+    fn auto_is_short_in_the_home_region() {
+        let place = cafe_central();
+        assert_eq!(list_address(&place, choice(AddressStyle::Auto, Some("AT"))), Some(SHORT));
+        assert_eq!(list_address(&place, choice(AddressStyle::Auto, Some("at"))), Some(SHORT));
+    }
+
+    #[test]
+    fn auto_is_full_outside_the_home_region() {
+        let place = cafe_central();
+        assert_eq!(list_address(&place, choice(AddressStyle::Auto, Some("de"))), Some(FULL));
+        assert_eq!(list_address(&place, choice(AddressStyle::Auto, None)), Some(FULL));
+
+        // Without a postal address the place's region is unknown
+        let mut unplaced = cafe_central();
+        unplaced.postal_address = None;
+        assert_eq!(list_address(&unplaced, choice(AddressStyle::Auto, Some("at"))), Some(FULL));
+    }
+
+    #[test]
+    fn missing_forms_fall_back_to_the_other() {
+        let mut short_only = cafe_central();
+        short_only.formatted_address = None;
+        assert_eq!(list_address(&short_only, choice(AddressStyle::Full, None)), Some(SHORT));
+
+        let mut full_only = cafe_central();
+        full_only.short_formatted_address = None;
+        assert_eq!(list_address(&full_only, choice(AddressStyle::Short, None)), Some(FULL));
+        assert_eq!(list_address(&full_only, choice(AddressStyle::Auto, Some("at"))), Some(FULL));
+
+        assert_eq!(list_address(&Place::default(), choice(AddressStyle::Short, None)), None);
+    }
+
+    #[test]
+    fn unknown_and_missing_languages_format_as_english() {
+        assert_eq!(NumberFormat::for_language(None), NumberFormat::ENGLISH);
+        assert_eq!(NumberFormat::for_language(Some("xx")), NumberFormat::ENGLISH);
+        assert_eq!(NumberFormat::for_language(Some("DE_at")), de());
+        assert_eq!(format_count(1204, NumberFormat::for_language(Some("de-CH"))), "1’204");
+    }
+
+    #[test]
+    fn parses_routes_durations() {
+        assert_eq!(parse_duration_s("0s"), Some(Duration::ZERO));
+        assert_eq!(parse_duration_s("1823s"), Some(Duration::from_secs(1823)));
+        assert_eq!(parse_duration_s("0.5s"), Some(Duration::from_millis(500)));
+        assert_eq!(parse_duration_s(" 45s "), Some(Duration::from_secs(45)));
+    }
+
+    #[test]
+    fn rejects_malformed_durations() {
+        for text in ["", "s", "abc", "12", "12 s", "-5s", "1e999s", "NaNs", "infs", "5m", "1h2s"] {
+            assert_eq!(parse_duration_s(text), None, "{:?}", text);
+        }
+    }
+
+    #[test]
+    fn humanizes_zero_and_sub_minute() {
+        assert_eq!(humanized("0s").as_deref(), Some("0 min"));
+        assert_eq!(humanized("20s").as_deref(), Some("< 1 min"));
+        assert_eq!(humanized("29s").as_deref(), Some("< 1 min"));
+        assert_eq!(humanized("45s").as_deref(), Some("1 min"));
+    }
+
+    #[test]
+    fn humanizes_minutes_and_hours() {
+        assert_eq!(humanized("600s").as_deref(), Some("10 min"));
+        assert_eq!(humanized("3569s").as_deref(), Some("59 min"));
+        assert_eq!(humanized("3600s").as_deref(), Some("1 h"));
+        assert_eq!(humanized("4320s").as_deref(), Some("1 h 12 min"));
+        assert_eq!(humanized("7200s").as_deref(), Some("2 h"));
+        assert_eq!(humanized("90061s").as_deref(), Some("25 h 1 min"));
+    }
+
+    #[test]
+    fn rounds_to_the_nearest_minute() {
+        assert_eq!(humanized("89s").as_deref(), Some("1 min"));
+        assert_eq!(humanized("90s").as_deref(), Some("2 min"));
+        // Rounding up can reach a whole hour
+        assert_eq!(humanized("3590s").as_deref(), Some("1 h"));
+    }
+}
@@ -0,0 +1,419 @@
+use serde_json::Value;
+
+use crate::api::types::{Circle, LatLng, Place, Units};
+use crate::format::{format_distance, NumberFormat};
+
+const EARTH_RADIUS_M: f64 = 6_371_000.0;
+
+/// Haversine distance in meters between two points
+pub fn haversine_distance(a: &LatLng, b: &LatLng) -> f64 {
+    let d_lat = (b.latitude - a.latitude).to_radians();
+    let d_lng = (b.longitude - a.longitude).to_radians();
+    let lat1 = a.latitude.to_radians();
+    let lat2 = b.latitude.to_radians();
+
+    let a_val = (d_lat / 2.0).sin().powi(2) + lat1.cos() * lat2.cos() * (d_lng / 2.0).sin().powi(2);
+    let c = 2.0 * a_val.sqrt().asin();
+    EARTH_RADIUS_M * c
+}
+
+/// Initial great-circle bearing from `a` to `b`, in degrees clockwise from north (0..360)
+pub fn initial_bearing(a: &LatLng, b: &LatLng) -> f64 {
+    let lat1 = a.latitude.to_radians();
+    let lat2 = b.latitude.to_radians();
+    let d_lng = (b.longitude - a.longitude).to_radians();
+
+    let y = d_lng.sin() * lat2.cos();
+    let x = lat1.cos() * lat2.sin() - lat1.sin() * lat2.cos() * d_lng.cos();
+    y.atan2(x).to_degrees().rem_euclid(360.0)
+}
+
+/// Eight-point compass direction for a bearing in degrees, e.g. 50.0 -> "NE"
+pub fn compass_point(bearing: f64) -> &'static str {
+    const POINTS: [&str; 8] = ["N", "NE", "E", "SE", "S", "SW", "W", "NW"];
+    POINTS[((bearing.rem_euclid(360.0) + 22.5) / 45.0) as usize % 8]
+}
+
+/// "2.4 km NE" from `origin` to `place`, when the place has a location
+pub fn distance_and_direction(origin: &LatLng, place: &Place, units: Units, nf: NumberFormat) -> Option<String> {
+    let loc = place.location.as_ref()?;
+    let meters = haversine_distance(origin, loc);
+    let distance = format_distance(meters, units, nf);
+    // A bearing means nothing when you're standing at the place
+    if meters < 1.0 {
+        return Some(distance);
+    }
+    Some(format!("{} {}", distance, compass_point(initial_bearing(origin, loc))))
+}
+
+/// `n` points (n >= 2) evenly spaced along the great circle from `a` to `b`, inclusive
+pub fn great_circle_points(a: &LatLng, b: &LatLng, n: usize) -> Vec<LatLng> {
+    let n = n.max(2);
+    let delta = haversine_distance(a, b) / EARTH_RADIUS_M; // angular distance
+    if delta == 0.0 {
+        return vec![a.clone(), b.clone()];
+    }
+
+    let to_vec = |p: &LatLng| {
+        let (lat, lng) = (p.latitude.to_radians(), p.longitude.to_radians());
+        (lat.cos() * lng.cos(), lat.cos() * lng.sin(), lat.sin())
+    };
+    let (va, vb) = (to_vec(a), to_vec(b));
+
+    (0..n)
+        .map(|i| {
+            let f = i as f64 / (n - 1) as f64;
+            let wa = ((1.0 - f) * delta).sin() / delta.sin();
+            let wb = (f * delta).sin() / delta.sin();
+            let x = wa * va.0 + wb * vb.0;
+            let y = wa * va.1 + wb * vb.1;
+            let z = wa * va.2 + wb * vb.2;
+            LatLng {
+                latitude: z.atan2((x * x + y * y).sqrt()).to_degrees(),
+                longitude: y.atan2(x).to_degrees(),
+            }
+        })
+        .collect()
+}
+
+/// Flags results a location-biased search returned well outside the bias
+/// circle: further than `factor` × its radius from the center
+#[derive(Debug, Clone)]
+pub struct FarCheck {
+    pub center: LatLng,
+    pub threshold: f64,
+}
+
+impl FarCheck {
+    pub fn new(bias: &Circle, factor: f64) -> Self {
+        FarCheck {
+            center: bias.center.clone(),
+            threshold: bias.radius * factor,
+        }
+    }
+
+    /// Distance in meters from the center when `place` is beyond the threshold
+    pub fn far(&self, place: &Place) -> Option<f64> {
+        let meters = haversine_distance(&self.center, place.location.as_ref()?);
+        (meters > self.threshold).then_some(meters)
+    }
+}
+
+/// A polygon as rings of (lng, lat) positions: the outer ring first, then holes
+#[derive(Debug, Clone)]
+struct Polygon {
+    rings: Vec<Vec<(f64, f64)>>,
+}
+
+/// An area made of one or more polygons (GeoJSON Polygon or MultiPolygon)
+#[derive(Debug, Clone)]
+pub struct Area {
+    polygons: Vec<Polygon>,
+    /// When set, longitudes are stored shifted into 0..360 so areas that cross
+    /// the antimeridian (one ring or polygons split at ±180) stay contiguous
+    wraps: bool,
+}
+
+impl Area {
+    /// Parse a GeoJSON Polygon, MultiPolygon, Feature, or FeatureCollection
+    pub fn from_geojson(value: &Value) -> Result<Area, String> {
+        let mut polygons = Vec::new();
+        collect_polygons(value, &mut polygons)?;
+        if polygons.is_empty() {
+            return Err("GeoJSON contains no polygons".into());
+        }
+
+        // Crossing the antimeridian shows as a narrower longitude span in 0..360
+        let lngs = || polygons.iter().flat_map(|p| &p.rings).flatten().map(|pos| pos.0);
+        let wraps = lng_span(lngs().map(wrap_lng)) < lng_span(lngs());
+        if wraps {
+            for ring in polygons.iter_mut().flat_map(|p| p.rings.iter_mut()) {
+                for pos in ring.iter_mut() {
+                    pos.0 = wrap_lng(pos.0);
+                }
+            }
+        }
+
+        Ok(Area { polygons, wraps })
+    }
+
+    /// Whether a point lies inside the area (inside an outer ring and outside its holes)
+    pub fn contains(&self, point: &LatLng) -> bool {
+        let lng = if self.wraps {
+            wrap_lng(point.longitude)
+        } else {
+            point.longitude
+        };
+        let p = (lng, point.latitude);
+
+        self.polygons.iter().any(|poly| {
+            let mut rings = poly.rings.iter();
+            match rings.next() {
+                Some(outer) => ring_contains(outer, p) && !rings.any(|hole| ring_contains(hole, p)),
+                None => false,
+            }
+        })
+    }
+
+    /// Smallest circle around the bounding-box center that covers every vertex
+    pub fn bounding_circle(&self) -> Circle {
+        let outer = self.polygons.iter().filter_map(|p| p.rings.first()).flatten();
+
+        let (mut min_lng, mut max_lng) = (f64::MAX, f64::MIN);
+        let (mut min_lat, mut max_lat) = (f64::MAX, f64::MIN);
+        for &(lng, lat) in outer.clone() {
+            min_lng = min_lng.min(lng);
+            max_lng = max_lng.max(lng);
+            min_lat = min_lat.min(lat);
+            max_lat = max_lat.max(lat);
+        }
+
+        let mut center_lng = (min_lng + max_lng) / 2.0;
+        if center_lng > 180.0 {
+            center_lng -= 360.0;
+        }
+        let center = LatLng {
+            latitude: (min_lat + max_lat) / 2.0,
+            longitude: center_lng,
+        };
+
+        let radius = outer
+            .map(|&(lng, lat)| {
+                haversine_distance(
+                    &center,
+                    &LatLng {
+                        latitude: lat,
+                        longitude: lng,
+                    },
+                )
+            })
+            .fold(0.0, f64::max)
+            .ceil();
+
+        Circle { center, radius }
+    }
+}
+
+fn lng_span(lngs: impl Iterator<Item = f64>) -> f64 {
+    let (min, max) = lngs.fold((f64::MAX, f64::MIN), |(min, max), lng| (min.min(lng), max.max(lng)));
+    max - min
+}
+
+fn wrap_lng(lng: f64) -> f64 {
+    if lng < 0.0 {
+        lng + 360.0
+    } else {
+        lng
+    }
+}
+
+/// Even-odd ray casting test against one ring
+fn ring_contains(ring: &[(f64, f64)], (x, y): (f64, f64)) -> bool {
+    let mut inside = false;
+    let n = ring.len();
+    let mut j = n.wrapping_sub(1);
+    for i in 0..n {
+        let (xi, yi) = ring[i];
+        let (xj, yj) = ring[j];
+        if (yi > y) != (yj > y) && x < (xj - xi) * (y - yi) / (yj - yi) + xi {
+            inside = !inside;
+        }
+        j = i;
+    }
+    inside
+}
+
+fn collect_polygons(value: &Value, out: &mut Vec<Polygon>) -> Result<(), String> {
+    let kind = value
+        .get("type")
+        .and_then(Value::as_str)
+        .ok_or("GeoJSON object has no \"type\"")?;
+
+    match kind {
+        "FeatureCollection" => {
+            let features = value
+                .get("features")
+                .and_then(Value::as_array)
+                .ok_or("FeatureCollection has no \"features\" array")?;
+            for feature in features {
+                collect_polygons(feature, out)?;
+            }
+        }
+        "Feature" => {
+            let geometry = value.get("geometry").ok_or("Feature has no \"geometry\"")?;
+            collect_polygons(geometry, out)?;
+        }
+        "Polygon" => {
+            out.push(parse_polygon(value.get("coordinates"))?);
+        }
+        "MultiPolygon" => {
+            let polys = value
+                .get("coordinates")
+                .and_then(Value::as_array)
+                .ok_or("MultiPolygon has no \"coordinates\" array")?;
+            for poly in polys {
+                out.push(parse_polygon(Some(poly))?);
+            }
+        }
+        other => {
+            return Err(format!(
+                "unsupported GeoJSON type '{}' (expected Polygon or MultiPolygon)",
+                other
+            ))
+        }
+    }
+    Ok(())
+}
+
+fn parse_polygon(coords: Option<&Value>) -> Result<Polygon, String> {
+    let rings = coords
+        .and_then(Value::as_array)
+        .ok_or("Polygon has no \"coordinates\" array")?;
+
+    let rings = rings
+        .iter()
+        .map(|ring| {
+            let positions = ring.as_array().ok_or("polygon ring is not an array")?;
+            let ring = positions
+                .iter()
+                .map(|pos| match pos.as_array().map(|a| a.as_slice()) {
+                    Some([lng, lat, ..]) => match (lng.as_f64(), lat.as_f64()) {
+                        (Some(lng), Some(lat)) => Ok((lng, lat)),
+                        _ => Err("position is not [lng, lat] numbers".to_string()),
+                    },
+                    _ => Err("position is not a [lng, lat] array".to_string()),
+                })
+                .collect::<Result<Vec<_>, String>>()?;
+            if ring.len() < 3 {
+                return Err("polygon ring needs at least 3 positions".to_string());
+            }
+            Ok(ring)
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+
+    if rings.is_empty() {
+        return Err("Polygon has no rings".into());
+    }
+    Ok(Polygon { rings })
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn at(latitude: f64, longitude: f64) -> LatLng {
+        LatLng { latitude, longitude }
+    }
+
+    fn area(value: Value) -> Area {
+        Area::from_geojson(&value).unwrap()
+    }
+
+    /// A 0.2° square around (0, 0) with a 0.1° hole in the middle
+    fn square_with_hole() -> Area {
+        area(json!({
+            "type": "Polygon",
+            "coordinates": [
+                [[-0.1, -0.1], [0.1, -0.1], [0.1, 0.1], [-0.1, 0.1], [-0.1, -0.1]],
+                [[-0.05, -0.05], [0.05, -0.05], [0.05, 0.05], [-0.05, 0.05], [-0.05, -0.05]]
+            ]
+        }))
+    }
+
+    #[test]
+    fn holes_are_outside() {
+        let square = square_with_hole();
+        assert!(square.contains(&at(0.08, 0.08)));
+        assert!(!square.contains(&at(0.0, 0.0)));
+        assert!(!square.contains(&at(0.2, 0.0)));
+    }
+
+    #[test]
+    fn bounding_circle_covers_every_vertex() {
+        let circle = square_with_hole().bounding_circle();
+        assert!(circle.center.latitude.abs() < 1e-9);
+        assert!(circle.center.longitude.abs() < 1e-9);
+        let corner = haversine_distance(&at(0.0, 0.0), &at(0.1, 0.1));
+        assert!(circle.radius >= corner && circle.radius < corner + 1.0);
+    }
+
+    #[test]
+    fn ring_crossing_the_antimeridian() {
+        let fiji = area(json!({
+            "type": "Polygon",
+            "coordinates": [[[179.9, -17.1], [-179.9, -17.1], [-179.9, -16.9], [179.9, -16.9], [179.9, -17.1]]]
+        }));
+        assert!(fiji.contains(&at(-17.0, 179.95)));
+        assert!(fiji.contains(&at(-17.0, -179.95)));
+        assert!(!fiji.contains(&at(-17.0, 0.0)));
+
+        let circle = fiji.bounding_circle();
+        assert!((circle.center.longitude.abs() - 180.0).abs() < 1e-9);
+        assert!(circle.radius < 20_000.0);
+    }
+
+    #[test]
+    fn multipolygon_split_at_the_antimeridian() {
+        let fiji = area(json!({
+            "type": "MultiPolygon",
+            "coordinates": [
+                [[[179.9, -17.1], [180.0, -17.1], [180.0, -16.9], [179.9, -16.9], [179.9, -17.1]]],
+                [[[-180.0, -17.1], [-179.9, -17.1], [-179.9, -16.9], [-180.0, -16.9], [-180.0, -17.1]]]
+            ]
+        }));
+        assert!(fiji.contains(&at(-17.0, 179.95)));
+        assert!(fiji.contains(&at(-17.0, -179.95)));
+        assert!(!fiji.contains(&at(-17.0, 179.0)));
+
+        let circle = fiji.bounding_circle();
+        assert!((circle.center.latitude + 17.0).abs() < 1e-9);
+        assert!((circle.center.longitude.abs() - 180.0).abs() < 1e-9);
+        assert!(circle.radius < 20_000.0);
+    }
+
+    #[test]
+    fn multipolygon_far_apart_spans_the_gap() {
+        let islands = area(json!({
+            "type": "MultiPolygon",
+            "coordinates": [
+                [[[0.0, 0.0], [0.01, 0.0], [0.01, 0.01], [0.0, 0.01], [0.0, 0.0]]],
+                [[[1.0, 0.0], [1.01, 0.0], [1.01, 0.01], [1.0, 0.01], [1.0, 0.0]]]
+            ]
+        }));
+        assert!(!islands.contains(&at(0.005, 0.5)));
+        assert!(islands.bounding_circle().radius > 50_000.0);
+    }
+
+    #[test]
+    fn features_and_collections_are_unwrapped() {
+        let polygon = json!({
+            "type": "Polygon",
+            "coordinates": [[[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 0.0]]]
+        });
+        let collection = json!({
+            "type": "FeatureCollection",
+            "features": [{ "type": "Feature", "geometry": polygon }]
+        });
+        assert!(area(collection).contains(&at(0.2, 0.8)));
+    }
+
+    #[test]
+    fn invalid_geojson_is_rejected() {
+        let error = |value: Value| Area::from_geojson(&value).unwrap_err();
+        assert_eq!(
+            error(json!({ "type": "Point", "coordinates": [0.0, 0.0] })),
+            "unsupported GeoJSON type 'Point' (expected Polygon or MultiPolygon)"
+        );
+        assert_eq!(error(json!({ "type": "FeatureCollection", "features": [] })), "GeoJSON contains no polygons");
+        assert_eq!(
+            error(json!({ "type": "Polygon", "coordinates": [[[0.0, 0.0], [1.0, 0.0]]] })),
+            "polygon ring needs at least 3 positions"
+        );
+        assert_eq!(
+            error(json!({ "type": "Polygon", "coordinates": [[[0.0, "a"], [1.0, 0.0], [1.0, 1.0]]] })),
+            "position is not [lng, lat] numbers"
+        );
+    }
+}
@@ -1,6 +1,8 @@
 use serde::Deserialize;
 
-const IP_API_URL: &str = "http://ip-api.com/json/?fields=status,lat,lon,city,regionName,country";
+use crate::format::{format_coord, DEFAULT_COORD_PRECISION};
+
+const IP_API_URL: &str = "http://ip-api.com/json/?fields=status,lat,lon,city,regionName,country,proxy,hosting";
 
 #[derive(Debug, Deserialize)]
 struct IpApiResponse {
@@ -11,6 +13,10 @@ struct IpApiResponse {
     #[serde(rename = "regionName")]
     region_name: Option<String>,
     country: Option<String>,
+    #[serde(default)]
+    proxy: bool,
+    #[serde(default)]
+    hosting: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -18,6 +24,9 @@ pub struct GeoLocation {
     pub lat: f64,
     pub lng: f64,
     pub description: String,
+    /// The address belongs to a proxy, VPN, or hosting provider, so the
+    /// location is likely the exit point's rather than the user's
+    pub via_proxy: bool,
 }
 
 /// Geolocate via IP address using ip-api.com (free, no key required)
@@ -53,7 +62,11 @@ pub async fn geolocate_by_ip() -> Result<GeoLocation, String> {
     .collect();
 
     let description = if parts.is_empty() {
-        format!("{:.4}, {:.4}", lat, lng)
+        format!(
+            "{}, {}",
+            format_coord(lat, DEFAULT_COORD_PRECISION),
+            format_coord(lng, DEFAULT_COORD_PRECISION)
+        )
     } else {
         parts.join(", ")
     };
@@ -62,5 +75,6 @@ pub async fn geolocate_by_ip() -> Result<GeoLocation, String> {
         lat,
         lng,
         description,
+        via_proxy: resp.proxy || resp.hosting,
     })
 }
@@ -0,0 +1,136 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::api::types::{OpeningHours, OpeningPeriod, Place, Weekday, MINUTES_PER_DAY, MINUTES_PER_WEEK};
+
+/// Threshold for the "closes in N min" hint in result lists
+pub const CLOSING_SOON_MINUTES: i64 = 60;
+
+/// Whether a place is open at a given moment, from its opening periods
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OpenState {
+    /// Open with no closing time (24/7)
+    AlwaysOpen,
+    /// Open, closing in this many minutes
+    OpenFor(i64),
+    Closed,
+    /// No period data or UTC offset to evaluate
+    Unknown,
+}
+
+impl OpenState {
+    /// Open for at least `minutes` more
+    pub fn open_for_at_least(self, minutes: i64) -> bool {
+        match self {
+            OpenState::AlwaysOpen => true,
+            OpenState::OpenFor(left) => left >= minutes,
+            OpenState::Closed | OpenState::Unknown => false,
+        }
+    }
+
+    /// Minutes until closing when that is within `CLOSING_SOON_MINUTES`
+    pub fn closing_soon(self) -> Option<i64> {
+        match self {
+            OpenState::OpenFor(left) if left <= CLOSING_SOON_MINUTES => Some(left),
+            _ => None,
+        }
+    }
+}
+
+/// Evaluate a place's current opening hours at the current time
+pub fn open_state_now(place: &Place) -> OpenState {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0);
+    open_state_at(place, now)
+}
+
+/// Evaluate a place's current opening hours at `unix_secs`, in the place's
+/// local time (from `utc_offset_minutes`)
+pub fn open_state_at(place: &Place, unix_secs: i64) -> OpenState {
+    let periods = match place
+        .current_opening_hours
+        .as_ref()
+        .and_then(|h: &OpeningHours| h.periods.as_deref())
+    {
+        Some(p) if !p.is_empty() => p,
+        _ => return OpenState::Unknown,
+    };
+    let Some(offset) = place.utc_offset_minutes else {
+        return OpenState::Unknown;
+    };
+
+    week_state(periods, local_minute_of_week(unix_secs, offset))
+}
+
+/// Minute of the week (0 = Sunday 00:00) at `unix_secs` in a time zone
+/// `offset_minutes` from UTC
+pub fn local_minute_of_week(unix_secs: i64, offset_minutes: i32) -> i64 {
+    let local_minutes = unix_secs.div_euclid(60) + offset_minutes as i64;
+    // 1970-01-01 was a Thursday
+    (local_minutes + Weekday::Thursday.index() as i64 * MINUTES_PER_DAY).rem_euclid(MINUTES_PER_WEEK)
+}
+
+/// Today's weekday for a place: in its local time when it has a UTC offset,
+/// otherwise in UTC
+pub fn weekday_now(place: &Place) -> Weekday {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0);
+    weekday_at(place, now)
+}
+
+/// Weekday at `unix_secs`, shifted by the place's UTC offset when known
+pub fn weekday_at(place: &Place, unix_secs: i64) -> Weekday {
+    let minute = local_minute_of_week(unix_secs, place.utc_offset_minutes.unwrap_or(0));
+    Weekday::from_days_after_sunday(minute / MINUTES_PER_DAY)
+}
+
+/// Position of `day` in `weekdayDescriptions`, which the Places API documents
+/// as starting on Monday. Going by position keeps this independent of the
+/// language the day names are in.
+pub fn description_index(day: Weekday) -> usize {
+    (day.index() as usize + 6) % 7
+}
+
+/// Weekday descriptions reordered to start with `today`; lists that don't
+/// have one entry per day are returned in API order
+pub fn descriptions_from(descriptions: &[String], today: Weekday) -> Vec<&String> {
+    if descriptions.len() != 7 {
+        return descriptions.iter().collect();
+    }
+    let start = description_index(today);
+    descriptions[start..].iter().chain(&descriptions[..start]).collect()
+}
+
+/// State at minute-of-week `now` (0 = Sunday 00:00)
+fn week_state(periods: &[OpeningPeriod], now: i64) -> OpenState {
+    if periods.iter().any(OpeningPeriod::is_always_open) {
+        return OpenState::AlwaysOpen;
+    }
+
+    // Overlapping periods: the latest close counts
+    let Some(left) = periods.iter().filter_map(|p| p.minutes_until_close(now)).max() else {
+        return OpenState::Closed;
+    };
+    let mut close = now + left;
+
+    let intervals: Vec<(i64, i64)> = periods.iter().filter_map(OpeningPeriod::interval).collect();
+
+    // Follow back-to-back periods, e.g. one closing at midnight and the next
+    // opening at 00:00, so the place counts as continuously open
+    while close - now < MINUTES_PER_WEEK {
+        let next = intervals.iter().find_map(|&(open, end)| {
+            ((open - close).rem_euclid(MINUTES_PER_WEEK) == 0).then(|| close + (end - open))
+        });
+        match next {
+            Some(extended) if extended > close => close = extended,
+            _ => break,
+        }
+    }
+    if close - now >= MINUTES_PER_WEEK {
+        return OpenState::AlwaysOpen;
+    }
+    OpenState::OpenFor(close - now)
+}
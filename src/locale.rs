@@ -0,0 +1,53 @@
+use crate::api::types::Place;
+
+/// Language codes offered by the TUI picker (a subset of what the API accepts)
+pub const COMMON_LANGUAGES: &[&str] = &[
+    "ar", "cs", "da", "de", "el", "en", "en-AU", "en-GB", "es", "es-419", "fi", "fr", "he", "hi",
+    "hu", "id", "it", "ja", "ko", "nl", "no", "pl", "pt", "pt-BR", "pt-PT", "ro", "ru", "sv", "th",
+    "tr", "uk", "vi", "zh-CN", "zh-TW",
+];
+
+/// CLDR region codes offered by the TUI picker
+pub const COMMON_REGIONS: &[&str] = &[
+    "ae", "ar", "at", "au", "be", "br", "ca", "ch", "cn", "de", "dk", "es", "fi", "fr", "gb", "hk",
+    "ie", "in", "it", "jp", "kr", "mx", "nl", "no", "nz", "pl", "pt", "se", "sg", "tw", "us", "za",
+];
+
+/// Plausible BCP-47 language tag: a 2-3 letter language, then optional
+/// 2-8 character alphanumeric subtags (e.g. "en", "pt-BR", "zh-Hant-TW", "es-419")
+pub fn is_language_code(code: &str) -> bool {
+    let mut subtags = code.split('-');
+    let primary = subtags.next().unwrap_or("");
+    (2..=3).contains(&primary.len())
+        && primary.chars().all(|c| c.is_ascii_alphabetic())
+        && subtags.all(|s| (2..=8).contains(&s.len()) && s.chars().all(|c| c.is_ascii_alphanumeric()))
+}
+
+/// Plausible CLDR region code: two letters (e.g. "us", "GB")
+pub fn is_region_code(code: &str) -> bool {
+    code.len() == 2 && code.chars().all(|c| c.is_ascii_alphabetic())
+}
+
+/// Whether the place's address is in `region` (a CLDR code, any case); false
+/// when the response didn't include its postal address
+pub fn in_region(place: &Place, region: &str) -> bool {
+    place
+        .postal_address
+        .as_ref()
+        .and_then(|a| a.region_code.as_deref())
+        .is_some_and(|code| code.eq_ignore_ascii_case(region))
+}
+
+/// Codes from `codes` starting with `query` (case-insensitive)
+pub fn filter_codes(codes: &[&'static str], query: &str, max: usize) -> Vec<&'static str> {
+    if query.is_empty() {
+        return Vec::new();
+    }
+    let query = query.to_lowercase();
+    codes
+        .iter()
+        .filter(|c| c.to_lowercase().starts_with(&query))
+        .take(max)
+        .copied()
+        .collect()
+}
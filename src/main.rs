@@ -1,28 +1,68 @@
 mod api;
+mod cli_interactive;
 mod config;
+mod dedupe;
+mod diff;
+mod examples;
+mod explain;
+mod format;
+mod geo;
 mod geolocate;
+mod hours;
+mod locale;
+mod monitor;
+mod notes;
+mod pager;
+mod paths;
+mod place_types;
+mod ranking;
 mod render;
+mod serve;
 mod tui;
+mod usage;
 
+use std::io::IsTerminal;
 use std::process;
-use std::time::Duration;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
-use clap::{Parser, Subcommand};
+use clap::parser::ValueSource;
+use clap::{CommandFactory, FromArgMatches, Parser, Subcommand, ValueEnum};
 use colored::Colorize;
-
-use api::client::Client;
+use futures::stream::{FuturesUnordered, StreamExt};
+use tracing::Instrument;
+use tracing_subscriber::EnvFilter;
+
+use api::client::{Client, ClientEvent, SLOW_REQUEST_THRESHOLD};
+use api::connections::ConnectionStats;
+use api::limits::{
+    self, MAX_AUTOCOMPLETE_SUGGESTIONS, MAX_NEARBY_LIMIT, MAX_RESOLVE_RESULTS, MAX_SEARCH_RESULTS,
+};
 use api::types::*;
 use config::Config;
+use format::{format_coord, format_elapsed, humanize_duration, AddressChoice, AddressStyle, NumberFormat};
+use monitor::NotifyOn;
+use render::PhotoSize;
 
 #[derive(Parser)]
 #[command(
     name = "zupo",
     about = "A Rust CLI for Google Places API (New)",
     version,
-    after_help = "Environment:\n  GOOGLE_PLACES_API_KEY    API key for Google Places (required)\n\n\
+    after_help = "Environment:\n  GOOGLE_PLACES_API_KEY    API key for Google Places (required)\n  \
+    ZUPO_LOG                 Enable tracing on stderr (e.g. debug, info, zupo=trace)\n  \
+    ZUPO_DEFAULT_LAT/_LNG    Default location (set both); overrides the config's\n  \
+    ZUPO_DEFAULT_RADIUS      Default search radius in meters\n  \
+    ZUPO_LANG, ZUPO_REGION   Default language and region codes\n  \
+    ZUPO_FORMAT              Default --format (text, markdown, csv, or table)\n  \
+    ZUPO_BASE_URL            Default --base-url\n  \
+    ZUPO_TIMEOUT             Default --timeout in seconds\n\n\
+    Precedence: explicit flags > ZUPO_* variables > [commands.<name>] and other\n\
+    config file values > built-in defaults\n\n\
     Location resolution (for commands that use --lat/--lng):\n  \
     1. Explicit --lat/--lng flags (highest priority)\n  \
-    2. Default location from config (~/.config/zupo/config.toml)\n  \
+    2. ZUPO_DEFAULT_LAT/ZUPO_DEFAULT_LNG, else the location from config\n     \
+    (~/.config/zupo/config.toml)\n  \
     3. IP-based geolocation via --auto-locate flag"
 )]
 struct Cli {
@@ -39,32 +79,171 @@ struct Cli {
     no_color: bool,
 
     /// HTTP timeout in seconds
-    #[arg(long, default_value = "10", global = true)]
+    #[arg(long, env = "ZUPO_TIMEOUT", default_value = "10", global = true)]
     timeout: u64,
 
+    /// Send at most this many API requests per second (default from config
+    /// `network.max_qps`, else unlimited)
+    #[arg(long, value_name = "N", value_parser = api::throttle::parse_qps, global = true)]
+    qps: Option<f64>,
+
     /// Auto-detect location via IP geolocation (fallback if no --lat/--lng or config)
     #[arg(long, global = true)]
     auto_locate: bool,
 
     /// Override Places API base URL
-    #[arg(long, global = true)]
+    #[arg(long, env = "ZUPO_BASE_URL", global = true)]
     base_url: Option<String>,
 
     /// Override Routes API base URL
     #[arg(long, global = true)]
     routes_base_url: Option<String>,
 
+    /// Unit system for distances (default from config `defaults.units`, else metric)
+    #[arg(long, value_enum, global = true)]
+    units: Option<Units>,
+
+    /// Address shown in place lists; `auto` shortens it for places in
+    /// `defaults.region` (default from config `output.address_style`, else full)
+    #[arg(long, value_enum, global = true)]
+    address_style: Option<AddressStyle>,
+
+    /// Emit tracing logs on stderr in this format (level from ZUPO_LOG, default info)
+    #[arg(long, value_enum, global = true)]
+    log_format: Option<LogFormat>,
+
+    /// Order search and nearby results by this field, after local filters
+    /// (JSON output too)
+    #[arg(long, value_enum, global = true)]
+    sort_by: Option<ranking::SortKey>,
+
+    /// With --sort-by, sort the other way (places missing the field stay last)
+    #[arg(long, global = true, requires = "sort_by")]
+    reverse: bool,
+
+    /// Accept an auto-detected location without asking, even when it looks wrong
+    #[arg(short = 'y', long, global = true)]
+    yes: bool,
+
+    /// Print per-request timings and a total to stderr
+    #[arg(long, global = true)]
+    timings: bool,
+
+    /// Decimal places for printed coordinates (JSON output keeps raw numbers)
+    #[arg(long, default_value_t = format::DEFAULT_COORD_PRECISION, global = true)]
+    precision: usize,
+
+    /// Print extra request details (such as the field mask) to stderr, and
+    /// every populated place field in text output
+    #[arg(short, long, global = true)]
+    verbose: bool,
+
+    /// Exit with code 3 when `search`, `nearby`, `resolve`, or `route` finds no places
+    #[arg(long, global = true)]
+    fail_on_empty: bool,
+
     #[command(subcommand)]
     command: Commands,
 }
 
+/// Output format for commands that support more than colored text
+#[derive(Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+enum OutputFormat {
+    /// Colored terminal output
+    #[default]
+    Text,
+    /// Plain CommonMark, for pasting into chat or notes
+    Markdown,
+    /// One row per place with a header row, for spreadsheets
+    Csv,
+    /// Column-aligned table, narrowed to fit the terminal
+    Table,
+}
+
+/// Result ordering for search and nearby
+#[derive(Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+enum RankMode {
+    /// Keep the API's order
+    #[default]
+    Relevance,
+    /// Composite of Bayesian-adjusted rating, review count, and distance (see `ranking`)
+    Smart,
+}
+
+/// What `autocomplete --interactive` does with the chosen place
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum PickThen {
+    /// Print its details, as `zupo details` would
+    Details,
+}
+
+/// How `route` lists the places it finds
+#[derive(Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+enum RouteGrouping {
+    /// Under each waypoint; a place near two waypoints shows up twice
+    #[default]
+    Waypoint,
+    /// One list of unique places, in trip order unless --sort-by says otherwise
+    Place,
+}
+
+/// Review ordering for `reviews`
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum ReviewSort {
+    /// Most recently published first
+    Newest,
+    /// Highest rating first
+    Highest,
+    /// Lowest rating first
+    Lowest,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum LogFormat {
+    /// JSON lines, one event per line
+    Json,
+    /// Human-readable multi-line output
+    Pretty,
+}
+
+/// Process exit codes, the contract scripts rely on. Every error path exits
+/// through `ExitCode::exit`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum ExitCode {
+    /// An API or auth error, or a local I/O failure
+    Failure = 1,
+    /// Invalid input: a bad flag value, a missing API key, an unreadable file
+    Usage = 2,
+    /// `--fail-on-empty` was given and the command found no places
+    Empty = 3,
+    /// The API could not be reached: no connection, or the request timed out
+    Offline = 4,
+    /// An interactive prompt was cancelled with Esc or Ctrl+C (as for SIGINT)
+    Interrupted = 130,
+}
+
+impl ExitCode {
+    fn for_error(e: &api::errors::Error) -> Self {
+        match e {
+            api::errors::Error::Validation { .. } | api::errors::Error::MissingApiKey => ExitCode::Usage,
+            api::errors::Error::Http(e) if e.is_connect() || e.is_timeout() => ExitCode::Offline,
+            api::errors::Error::Api { .. } | api::errors::Error::Http(_) => ExitCode::Failure,
+        }
+    }
+
+    fn exit(self) -> ! {
+        process::exit(self as i32)
+    }
+}
+
 #[derive(Subcommand)]
 enum Commands {
     /// Search for places by text query
     Search {
-        /// Search query (e.g., "coffee shops in Vienna")
+        /// Search query (e.g., "coffee shops in Vienna"); `-`, or leaving it out
+        /// with piped input, reads it from stdin
         #[arg(short, long)]
-        query: String,
+        query: Option<String>,
 
         /// Filter by place type (e.g., restaurant, cafe)
         #[arg(long, name = "type")]
@@ -78,16 +257,20 @@ enum Commands {
         #[arg(long, value_delimiter = ',')]
         price_level: Vec<u8>,
 
+        /// Price filter with ranges: 1-3, <=2, >=$$, $$, or comma-separated terms
+        #[arg(long, value_name = "SPEC", conflicts_with = "price_level")]
+        price: Option<PriceSet>,
+
         /// Only return places that are currently open
         #[arg(long)]
         open_now: bool,
 
         /// Latitude for location bias
-        #[arg(long)]
+        #[arg(long, value_parser = format::parse_coord, allow_hyphen_values = true)]
         lat: Option<f64>,
 
         /// Longitude for location bias
-        #[arg(long)]
+        #[arg(long, value_parser = format::parse_coord, allow_hyphen_values = true)]
         lng: Option<f64>,
 
         /// Radius in meters for location bias
@@ -98,31 +281,79 @@ enum Commands {
         #[arg(short, long, default_value = "10")]
         limit: u32,
 
-        /// BCP-47 language code (e.g., en, de, ja)
+        /// BCP-47 language code, e.g. en, de, ja (default from config `defaults.language`)
         #[arg(long)]
         lang: Option<String>,
 
-        /// CLDR region code (e.g., US, AT, JP)
+        /// CLDR region code, e.g. US, AT, JP (default from config `defaults.region`)
         #[arg(long)]
         region: Option<String>,
+
+        /// Keep only results inside this GeoJSON Polygon/MultiPolygon (also biases the search)
+        #[arg(long, value_name = "GEOJSON_FILE", conflicts_with_all = ["lat", "lng", "radius"])]
+        polygon: Option<String>,
+
+        /// Compare against a previous `--json` output and show new, disappeared, and changed places
+        #[arg(long, value_name = "PREVIOUS_JSON")]
+        diff: Option<String>,
+
+        /// Output format (markdown is ignored with --json; csv can't be combined with it)
+        #[arg(long, value_enum, env = "ZUPO_FORMAT", default_value_t)]
+        format: OutputFormat,
+
+        /// Drop places with fewer than N reviews (applied locally after the search)
+        #[arg(long, value_name = "N")]
+        min_reviews: Option<u32>,
+
+        /// Keep only places open for at least this many more minutes (uses opening periods)
+        #[arg(long, value_name = "MINUTES")]
+        open_for: Option<u32>,
+
+        /// With --open-for, keep places without opening-hours data (with a warning)
+        #[arg(long, requires = "open_for")]
+        open_for_lenient: bool,
+
+        /// Merge results with near-identical names within this many meters,
+        /// keeping the better-rated one (merges listed with --verbose)
+        #[arg(long, value_name = "METERS")]
+        dedupe_nearby: Option<f64>,
+
+        /// Drop results further than this many meters from the bias center
+        /// (default from config `filters.max_distance`)
+        #[arg(long, value_name = "METERS")]
+        max_distance: Option<f64>,
+
+        /// Result ordering; `smart` re-ranks by rating, review count, and distance
+        #[arg(long, value_enum, default_value_t)]
+        rank: RankMode,
+
+        /// Only print the number of matching places (requests just place IDs)
+        #[arg(long, conflicts_with_all = ["polygon", "diff", "min_reviews", "rank", "dedupe_nearby", "max_distance"])]
+        count: bool,
+
+        /// Annotate each result with its rank, distance from the bias center,
+        /// and whether it passes the type, rating, price, and open-now filters
+        #[arg(long, conflicts_with_all = ["diff", "count"])]
+        explain: bool,
     },
 
     /// Get autocomplete suggestions
     Autocomplete {
-        /// Input text for autocomplete
+        /// Input text for autocomplete; `-`, or leaving it out with piped
+        /// input, reads it from stdin
         #[arg(short, long)]
-        input: String,
+        input: Option<String>,
 
         /// Session token for billing optimization
         #[arg(long)]
         session_token: Option<String>,
 
         /// Latitude for location bias
-        #[arg(long)]
+        #[arg(long, value_parser = format::parse_coord, allow_hyphen_values = true)]
         lat: Option<f64>,
 
         /// Longitude for location bias
-        #[arg(long)]
+        #[arg(long, value_parser = format::parse_coord, allow_hyphen_values = true)]
         lng: Option<f64>,
 
         /// Radius in meters for location bias
@@ -133,30 +364,47 @@ enum Commands {
         #[arg(short, long, default_value = "5")]
         limit: u32,
 
-        /// BCP-47 language code
+        /// BCP-47 language code (default from config `defaults.language`)
         #[arg(long)]
         lang: Option<String>,
 
-        /// CLDR region code
+        /// CLDR region code (default from config `defaults.region`)
         #[arg(long)]
         region: Option<String>,
+
+        /// Restrict suggestions to these primary types (comma-separated, max 5)
+        #[arg(long = "type", value_delimiter = ',')]
+        types: Vec<String>,
+
+        /// Restrict suggestions to a group of types (added to --type)
+        #[arg(long, value_enum)]
+        only: Option<PlaceTypeGroup>,
+
+        /// Type with live suggestions below the prompt and print the chosen
+        /// place's ID (--input, if given, is the starting text)
+        #[arg(long)]
+        interactive: bool,
+
+        /// With --interactive, show the chosen place's details instead of its ID
+        #[arg(long, value_enum, value_name = "COMMAND", requires = "interactive")]
+        then: Option<PickThen>,
     },
 
     /// Search for places near a location
     Nearby {
         /// Latitude (uses config/auto-locate if omitted)
-        #[arg(long)]
+        #[arg(long, value_parser = format::parse_coord, allow_hyphen_values = true)]
         lat: Option<f64>,
 
         /// Longitude (uses config/auto-locate if omitted)
-        #[arg(long)]
+        #[arg(long, value_parser = format::parse_coord, allow_hyphen_values = true)]
         lng: Option<f64>,
 
         /// Search radius in meters
         #[arg(long)]
         radius: Option<f64>,
 
-        /// Include only these place types
+        /// Include only these place types (matches any of a place's types)
         #[arg(long = "include-type", value_delimiter = ',')]
         include_types: Vec<String>,
 
@@ -164,17 +412,81 @@ enum Commands {
         #[arg(long = "exclude-type", value_delimiter = ',')]
         exclude_types: Vec<String>,
 
-        /// Maximum number of results (1-20)
+        /// Include only places whose primary type is one of these. Unlike
+        /// --include-type, a hotel with a restaurant won't match `restaurant`
+        #[arg(long = "include-primary-type", value_delimiter = ',')]
+        include_primary_types: Vec<String>,
+
+        /// Exclude places whose primary type is one of these
+        #[arg(long = "exclude-primary-type", value_delimiter = ',')]
+        exclude_primary_types: Vec<String>,
+
+        /// Maximum number of results (1-60; above 20 searches concentric rings)
         #[arg(short, long, default_value = "10")]
         limit: u32,
 
-        /// BCP-47 language code
+        /// BCP-47 language code (default from config `defaults.language`)
         #[arg(long)]
         lang: Option<String>,
 
-        /// CLDR region code
+        /// CLDR region code (default from config `defaults.region`)
         #[arg(long)]
         region: Option<String>,
+
+        /// Search the bounding circle of this GeoJSON Polygon/MultiPolygon and keep only results inside it
+        #[arg(long, value_name = "GEOJSON_FILE", conflicts_with_all = ["lat", "lng", "radius"])]
+        polygon: Option<String>,
+
+        /// Compare against a previous `--json` output and show new, disappeared, and changed places
+        #[arg(long, value_name = "PREVIOUS_JSON")]
+        diff: Option<String>,
+
+        /// Output format (markdown is ignored with --json; csv can't be combined with it)
+        #[arg(long, value_enum, env = "ZUPO_FORMAT", default_value_t)]
+        format: OutputFormat,
+
+        /// Drop places with fewer than N reviews (applied locally after the search)
+        #[arg(long, value_name = "N")]
+        min_reviews: Option<u32>,
+
+        /// Keep only places open for at least this many more minutes (uses opening periods)
+        #[arg(long, value_name = "MINUTES")]
+        open_for: Option<u32>,
+
+        /// With --open-for, keep places without opening-hours data (with a warning)
+        #[arg(long, requires = "open_for")]
+        open_for_lenient: bool,
+
+        /// Merge results with near-identical names within this many meters,
+        /// keeping the better-rated one (merges listed with --verbose)
+        #[arg(long, value_name = "METERS")]
+        dedupe_nearby: Option<f64>,
+
+        /// Result ordering; `smart` re-ranks by rating, review count, and distance
+        #[arg(long, value_enum, default_value_t)]
+        rank: RankMode,
+
+        /// How the API orders (and, with a full radius, picks) results
+        #[arg(long, value_enum, default_value_t)]
+        rank_by: NearbyRankPreference,
+
+        /// Only print the number of matching places (requests just place IDs)
+        #[arg(long, conflicts_with_all = ["polygon", "diff", "min_reviews", "rank", "dedupe_nearby"])]
+        count: bool,
+
+        /// Search around a named location from config `[locations]` (or the saved
+        /// location's label) instead; repeat to search several at once
+        #[arg(
+            long,
+            value_name = "NAME",
+            conflicts_with_all = ["lat", "lng", "polygon", "diff", "count", "rank", "fallback_search"]
+        )]
+        at: Vec<String>,
+
+        /// When nothing is found, run a text search for the first included type
+        /// biased to the same circle (may return places just outside it)
+        #[arg(long, conflicts_with = "count")]
+        fallback_search: bool,
     },
 
     /// Search for places along a route
@@ -184,16 +496,16 @@ enum Commands {
         query: String,
 
         /// Origin address or place name
-        #[arg(long)]
+        #[arg(long, value_parser = format::parse_location)]
         from: String,
 
         /// Destination address or place name
-        #[arg(long)]
+        #[arg(long, value_parser = format::parse_location)]
         to: String,
 
-        /// Travel mode: DRIVE, WALK, BICYCLE, TWO_WHEELER, TRANSIT
-        #[arg(long, default_value = "DRIVE")]
-        mode: String,
+        /// Travel mode
+        #[arg(long, value_enum, ignore_case = true, default_value_t = TravelMode::Drive)]
+        mode: TravelMode,
 
         /// Search radius around each waypoint in meters
         #[arg(long, default_value = "1000")]
@@ -207,11 +519,52 @@ enum Commands {
         #[arg(short, long, default_value = "5")]
         limit: u32,
 
-        /// BCP-47 language code
+        /// Request alternative routes from the Routes API
+        #[arg(long)]
+        alternatives: bool,
+
+        /// Search along this route (0 = primary; implies --alternatives when > 0)
+        #[arg(long, default_value = "0")]
+        route_index: usize,
+
+        /// Search along every returned route and show a per-route summary
+        #[arg(long, conflicts_with = "route_index")]
+        compare_routes: bool,
+
+        /// List places per waypoint, or once each with how far into the trip they are
+        #[arg(long, value_enum, default_value_t, conflicts_with = "compare_routes")]
+        group_by: RouteGrouping,
+
+        /// Label each waypoint with its nearest locality (one extra request per waypoint)
+        #[arg(long)]
+        label_waypoints: bool,
+
+        /// Skip the Routes API and search along a straight line between the endpoints
+        #[arg(long)]
+        straight_line: bool,
+
+        /// Resolve --from and --to to places first, print the matches, and route
+        /// between those places (asks which one when a name is ambiguous and stdin is a terminal)
+        #[arg(long)]
+        confirm_endpoints: bool,
+
+        /// Include the encoded route polyline in the output
+        #[arg(long)]
+        show_polyline: bool,
+
+        /// Include the decoded route points in JSON output
+        #[arg(long)]
+        show_path: bool,
+
+        /// Simplify the output geometry, dropping points within this many meters of the line
+        #[arg(long, value_name = "METERS")]
+        simplify: Option<f64>,
+
+        /// BCP-47 language code (default from config `defaults.language`)
         #[arg(long)]
         lang: Option<String>,
 
-        /// CLDR region code
+        /// CLDR region code (default from config `defaults.region`)
         #[arg(long)]
         region: Option<String>,
     },
@@ -219,8 +572,17 @@ enum Commands {
     /// Get detailed information about a place
     Details {
         /// Place ID (from search results)
-        #[arg(long)]
-        place_id: String,
+        #[arg(long, required_unless_present = "from_json")]
+        place_id: Option<String>,
+
+        /// Render a saved place (or array of places) from a `--json` file, or - for
+        /// stdin, instead of calling the API
+        #[arg(
+            long,
+            value_name = "FILE",
+            conflicts_with_all = ["place_id", "reviews", "photos", "show_photos", "force_show_photos", "compare_lang"]
+        )]
+        from_json: Option<String>,
 
         /// Include reviews in the response
         #[arg(long)]
@@ -230,15 +592,70 @@ enum Commands {
         #[arg(long)]
         photos: bool,
 
-        /// Download and display photos inline in the terminal
+        /// Download and display photos inline in the terminal (only listed by
+        /// size when stdout isn't a terminal that can draw them)
         #[arg(long)]
         show_photos: bool,
 
-        /// BCP-47 language code
+        /// Like --show-photos, but draw them even when stdout isn't a terminal
+        #[arg(long)]
+        force_show_photos: bool,
+
+        /// Width in terminal columns of inline photos (default from config
+        /// `output.photo_size`, else 3/5 of the terminal)
+        #[arg(long, value_name = "COLS", value_parser = clap::value_parser!(u32).range(1..))]
+        photo_width: Option<u32>,
+
+        /// Height in terminal rows of inline photos (default from config
+        /// `output.photo_size`, else half the terminal)
+        #[arg(long, value_name = "ROWS", value_parser = clap::value_parser!(u32).range(1..))]
+        photo_height: Option<u32>,
+
+        /// BCP-47 language code (default from config `defaults.language`)
+        #[arg(long)]
+        lang: Option<String>,
+
+        /// CLDR region code (default from config `defaults.region`)
+        #[arg(long)]
+        region: Option<String>,
+
+        /// Fetch the place in a second language and compare it with --lang
+        #[arg(long, value_name = "CODE")]
+        compare_lang: Option<String>,
+
+        /// Output format (markdown is ignored with --json; csv can't be combined with it)
+        #[arg(long, value_enum, env = "ZUPO_FORMAT", default_value_t)]
+        format: OutputFormat,
+    },
+
+    /// Read a place's reviews in full
+    #[command(alias = "review")]
+    Reviews {
+        /// Place ID (from search results)
+        #[arg(long)]
+        place_id: String,
+
+        /// Review order (default: the API's, most relevant first)
+        #[arg(long, value_enum)]
+        sort: Option<ReviewSort>,
+
+        /// Keep only reviews rated at least this (1-5)
+        #[arg(long, value_name = "RATING")]
+        min_rating: Option<f64>,
+
+        /// Maximum number of reviews to show
+        #[arg(long, default_value = "10")]
+        max: usize,
+
+        /// Also show each review's original text when it was translated
+        #[arg(long)]
+        full: bool,
+
+        /// BCP-47 language code (default from config `defaults.language`)
         #[arg(long)]
         lang: Option<String>,
 
-        /// CLDR region code
+        /// CLDR region code (default from config `defaults.region`)
         #[arg(long)]
         region: Option<String>,
     },
@@ -257,28 +674,107 @@ enum Commands {
         #[arg(long)]
         max_height: Option<u32>,
 
-        /// Display the photo inline in the terminal
+        /// Display the photo inline in the terminal (prints its pixel size
+        /// instead when stdout isn't a terminal that can draw it)
         #[arg(long)]
         show: bool,
+
+        /// Like --show, but draw the photo even when stdout isn't a terminal,
+        /// for tools that understand the escape codes
+        #[arg(long)]
+        force_show: bool,
+
+        /// Width in terminal columns of the inline photo (default from config
+        /// `output.photo_size`, else 3/5 of the terminal)
+        #[arg(long, value_name = "COLS", value_parser = clap::value_parser!(u32).range(1..))]
+        photo_width: Option<u32>,
+
+        /// Height in terminal rows of the inline photo (default from config
+        /// `output.photo_size`, else half the terminal)
+        #[arg(long, value_name = "ROWS", value_parser = clap::value_parser!(u32).range(1..))]
+        photo_height: Option<u32>,
     },
 
     /// Resolve an address or location name to place candidates
     Resolve {
         /// Location text to resolve (address, place name, etc.)
-        #[arg(short, long)]
+        #[arg(short, long, value_parser = format::parse_location)]
         location: String,
 
-        /// Maximum number of results
-        #[arg(short, long, default_value = "5")]
+        /// Maximum number of results (1-10)
+        #[arg(long, default_value = "5")]
+        limit: u32,
+
+        /// BCP-47 language code (default from config `defaults.language`)
+        #[arg(long)]
+        lang: Option<String>,
+
+        /// CLDR region code (default from config `defaults.region`)
+        #[arg(long)]
+        region: Option<String>,
+
+        /// Output format (markdown is ignored with --json; csv can't be combined with it)
+        #[arg(long, value_enum, env = "ZUPO_FORMAT", default_value_t)]
+        format: OutputFormat,
+    },
+
+    /// Re-run a search periodically and report new places, rating shifts, and closures
+    Monitor {
+        /// Search query to monitor
+        #[arg(short, long)]
+        query: String,
+
+        /// Filter by place type (e.g., restaurant, cafe)
+        #[arg(long, name = "type")]
+        included_type: Option<String>,
+
+        /// Latitude for location bias
+        #[arg(long, value_parser = format::parse_coord, allow_hyphen_values = true)]
+        lat: Option<f64>,
+
+        /// Longitude for location bias
+        #[arg(long, value_parser = format::parse_coord, allow_hyphen_values = true)]
+        lng: Option<f64>,
+
+        /// Radius in meters for location bias
+        #[arg(long)]
+        radius: Option<f64>,
+
+        /// Number of results to track (1-20)
+        #[arg(short, long, default_value = "20")]
         limit: u32,
 
-        /// BCP-47 language code
+        /// BCP-47 language code (default from config `defaults.language`)
         #[arg(long)]
         lang: Option<String>,
 
-        /// CLDR region code
+        /// CLDR region code (default from config `defaults.region`)
         #[arg(long)]
         region: Option<String>,
+
+        /// Seconds between checks (at least 60); each check is one search request
+        #[arg(long, value_name = "SECS", default_value = "3600", value_parser = parse_interval)]
+        interval: u64,
+
+        /// Check once and exit, for cron or another external scheduler
+        #[arg(long)]
+        once: bool,
+
+        /// Changes to report
+        #[arg(long, value_enum, value_delimiter = ',', default_values_t = [NotifyOn::New, NotifyOn::Rating, NotifyOn::Closed])]
+        notify_on: Vec<NotifyOn>,
+
+        /// Smallest rating change to report
+        #[arg(long, value_name = "STARS", default_value = "0.2")]
+        rating_threshold: f64,
+
+        /// Run this shell command with the changes as JSON on stdin whenever there are any
+        #[arg(long, value_name = "CMD")]
+        exec: Option<String>,
+
+        /// Snapshot file (default: one per query and location in the state directory)
+        #[arg(long, value_name = "FILE")]
+        snapshot: Option<String>,
     },
 
     /// Manage zupo configuration
@@ -287,8 +783,156 @@ enum Commands {
         action: ConfigAction,
     },
 
+    /// Personal notes on places, kept locally and never sent to any API
+    Note {
+        #[command(subcommand)]
+        action: NoteAction,
+    },
+
+    /// Show this month's cumulative API usage with estimated costs
+    Stats {
+        #[command(subcommand)]
+        action: Option<StatsAction>,
+    },
+
+    /// List supported place types by category (for --type and nearby's --include-type,
+    /// --exclude-type, and their --*-primary-type variants)
+    Types {
+        /// Only show types containing this text (or whole categories whose name does)
+        #[arg(short, long)]
+        filter: Option<String>,
+    },
+
     /// Launch interactive TUI mode
     Tui,
+
+    /// Serve search, nearby, details, and autocomplete as JSON over local HTTP
+    Serve {
+        /// Loopback address and port to listen on
+        #[arg(long, default_value = serve::DEFAULT_LISTEN, value_parser = serve::parse_listen)]
+        listen: std::net::SocketAddr,
+
+        /// Require `Authorization: Bearer <TOKEN>` on every request
+        #[arg(long, env = "ZUPO_SERVE_TOKEN", hide_env_values = true)]
+        token: Option<String>,
+    },
+
+    /// Print example invocations of a command (all commands without one)
+    #[command(hide = true)]
+    Examples {
+        /// Command to show examples for, e.g. search
+        command: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+enum NoteAction {
+    /// Attach a note to a place
+    Add {
+        /// Place ID (from search results)
+        #[arg(long)]
+        place_id: String,
+
+        /// Note text, e.g. "great patio, cash only"
+        text: String,
+    },
+
+    /// List notes on every place, or on one
+    List {
+        /// Only this place's notes
+        #[arg(long)]
+        place_id: Option<String>,
+    },
+
+    /// Remove a note by its number in `note list`, or all of a place's notes
+    Remove {
+        /// Place ID the note is attached to
+        #[arg(long)]
+        place_id: String,
+
+        /// Note number, as shown by `note list`
+        #[arg(required_unless_present = "all", conflicts_with = "all")]
+        number: Option<usize>,
+
+        /// Remove every note on the place
+        #[arg(long)]
+        all: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum StatsAction {
+    /// Clear the usage counters
+    Reset,
+}
+
+impl Commands {
+    fn name(&self) -> &'static str {
+        match self {
+            Commands::Search { .. } => "search",
+            Commands::Autocomplete { .. } => "autocomplete",
+            Commands::Nearby { .. } => "nearby",
+            Commands::Route { .. } => "route",
+            Commands::Details { .. } => "details",
+            Commands::Reviews { .. } => "reviews",
+            Commands::Photo { .. } => "photo",
+            Commands::Resolve { .. } => "resolve",
+            Commands::Monitor { .. } => "monitor",
+            Commands::Config { .. } => "config",
+            Commands::Note { .. } => "note",
+            Commands::Stats { .. } => "stats",
+            Commands::Types { .. } => "types",
+            Commands::Tui => "tui",
+            Commands::Serve { .. } => "serve",
+            Commands::Examples { .. } => "examples",
+        }
+    }
+
+    /// The `--format` value of commands that take one, unless a mode that
+    /// prints its own output ignores it
+    fn output_format(&self) -> Option<OutputFormat> {
+        if self.format_ignored_with().is_some() {
+            return None;
+        }
+        self.requested_format()
+    }
+
+    /// `--format` as given (or from ZUPO_FORMAT), whether or not it applies
+    fn requested_format(&self) -> Option<OutputFormat> {
+        match self {
+            Commands::Search { format, .. }
+            | Commands::Nearby { format, .. }
+            | Commands::Details { format, .. }
+            | Commands::Resolve { format, .. } => Some(*format),
+            _ => None,
+        }
+    }
+
+    /// The flag whose output replaces `--format`. These don't conflict with
+    /// `--format` in clap, since ZUPO_FORMAT counts as giving it.
+    fn format_ignored_with(&self) -> Option<&'static str> {
+        match self {
+            Commands::Search { count: true, .. } | Commands::Nearby { count: true, .. } => Some("--count"),
+            Commands::Search { explain: true, .. } => Some("--explain"),
+            Commands::Nearby { at, .. } if !at.is_empty() => Some("--at"),
+            _ => None,
+        }
+    }
+
+    /// The `--lang` value of commands that take one
+    fn language(&self) -> Option<&str> {
+        match self {
+            Commands::Search { lang, .. }
+            | Commands::Autocomplete { lang, .. }
+            | Commands::Nearby { lang, .. }
+            | Commands::Route { lang, .. }
+            | Commands::Details { lang, .. }
+            | Commands::Monitor { lang, .. }
+            | Commands::Reviews { lang, .. }
+            | Commands::Resolve { lang, .. } => lang.as_deref(),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Subcommand)]
@@ -296,11 +940,11 @@ enum ConfigAction {
     /// Set your default location
     SetLocation {
         /// Latitude
-        #[arg(long)]
+        #[arg(long, value_parser = format::parse_coord, allow_hyphen_values = true)]
         lat: f64,
 
         /// Longitude
-        #[arg(long)]
+        #[arg(long, value_parser = format::parse_coord, allow_hyphen_values = true)]
         lng: f64,
 
         /// Default search radius in meters
@@ -315,11 +959,35 @@ enum ConfigAction {
     /// Show current configuration
     Show,
 
+    /// Check the config file: value ranges and `[commands.*]` flag names
+    Validate,
+
     /// Detect location via IP and save as default
     AutoDetect,
 
     /// Clear saved location
     ClearLocation,
+
+    /// Print the full config as TOML (or write it to a file)
+    Export {
+        /// Write to this file instead of stdout
+        #[arg(short, long)]
+        output: Option<String>,
+    },
+
+    /// Load config from a TOML file (use - for stdin)
+    Import {
+        /// Config file to import, or - to read from stdin
+        file: String,
+
+        /// Merge imported values into the existing config (default)
+        #[arg(long, conflicts_with = "replace")]
+        merge: bool,
+
+        /// Replace the existing config entirely
+        #[arg(long)]
+        replace: bool,
+    },
 }
 
 #[tokio::main]
@@ -327,15 +995,49 @@ async fn main() {
     // Load .env file if present (ignore errors)
     let _ = dotenvy::dotenv();
 
-    let cli = Cli::parse();
+    let cli = Cli::from_arg_matches(&cli_command().get_matches()).unwrap_or_else(|e| e.exit());
 
     if cli.no_color {
         colored::control::set_override(false);
     }
 
+    // The TUI installs its own file logger
+    if !matches!(cli.command, Commands::Tui) {
+        init_logging(cli.log_format);
+    }
+
     // Handle config commands first (don't need API key)
     if let Commands::Config { ref action } = cli.command {
-        handle_config_command(action).await;
+        handle_config_command(action, cli.precision, cli.yes).await;
+        return;
+    }
+    if let Commands::Note { ref action } = cli.command {
+        handle_note_command(action, cli.json);
+        return;
+    }
+    if let Commands::Stats { ref action } = cli.command {
+        handle_stats_command(action.as_ref(), cli.json);
+        return;
+    }
+    if let Commands::Types { ref filter } = cli.command {
+        handle_types_command(filter.as_deref(), cli.json);
+        return;
+    }
+    if let Commands::Examples { ref command } = cli.command {
+        handle_examples_command(command.as_deref());
+        return;
+    }
+    if let Commands::Details {
+        from_json: Some(ref source),
+        format,
+        ..
+    } = cli.command
+    {
+        let verbosity = render::Verbosity::from_verbose(cli.verbose);
+        if let Err(e) = render_details_from_json(source, format, cli.json, cli.precision, verbosity, cli.command.language()) {
+            eprintln!("Error: {}", e);
+            ExitCode::Usage.exit();
+        }
         return;
     }
 
@@ -343,7 +1045,7 @@ async fn main() {
         Some(ref key) => key.clone(),
         None => {
             eprintln!("Error: missing API key. Set GOOGLE_PLACES_API_KEY or use --api-key");
-            process::exit(2);
+            ExitCode::Usage.exit();
         }
     };
 
@@ -351,96 +1053,1404 @@ async fn main() {
         Ok(c) => c,
         Err(e) => {
             eprintln!("Error: {}", e);
-            process::exit(2);
+            ExitCode::Usage.exit();
         }
     };
 
     client = client.with_timeout(Duration::from_secs(cli.timeout));
 
-    if let Some(url) = cli.base_url {
+    if let Some(url) = cli.base_url.clone() {
         client = client.with_places_base_url(url);
     }
-    if let Some(url) = cli.routes_base_url {
+    if let Some(url) = cli.routes_base_url.clone() {
         client = client.with_routes_base_url(url);
     }
 
-    let cfg = Config::load();
+    let mut cfg = load_config_with_env();
+    if let Some(qps) = cli.qps.or(cfg.network.max_qps) {
+        client = client.with_max_qps(qps);
+    }
+    client = client.with_pool(cfg.network.pool_settings());
+    let cli = with_command_defaults(cli, &cfg);
 
     // Handle TUI mode
     if let Commands::Tui = cli.command {
+        if cli.address_style.is_some() {
+            cfg.output.address_style = cli.address_style;
+        }
         if let Err(e) = tui::run(client, cfg).await {
             eprintln!("Error: {}", e);
-            process::exit(1);
+            ExitCode::Failure.exit();
         }
         return;
     }
 
-    let result = run_command(&client, &cli.command, cli.json, cli.auto_locate, &cfg).await;
-    if let Err(e) = result {
-        eprintln!("Error: {}", e);
-        match e {
-            api::errors::Error::Validation { .. } => process::exit(2),
-            _ => process::exit(1),
+    let tally = Arc::new(usage::Tally::default());
+    let client = client.with_observer({
+        let tally = tally.clone();
+        let timings = cli.timings;
+        let verbose = cli.verbose;
+        move |event| report_client_event(event, timings, verbose, &tally)
+    });
+
+    if let Commands::Monitor { .. } = cli.command {
+        let result = run_monitor(&client, &cli, &cfg, &tally).await;
+        tally.flush(&cfg);
+        if let Err(e) = result {
+            eprintln!("Error: {}", e);
+            ExitCode::for_error(&e).exit();
         }
+        return;
     }
-}
-
-/// Resolve lat/lng from: explicit flags > config > auto-locate
-async fn resolve_location(
-    explicit_lat: Option<f64>,
-    explicit_lng: Option<f64>,
-    auto_locate: bool,
-    cfg: &Config,
-) -> Option<(f64, f64)> {
-    // 1. Explicit flags
-    if let (Some(lat), Some(lng)) = (explicit_lat, explicit_lng) {
-        return Some((lat, lng));
+    if let Commands::Serve { listen, ref token } = cli.command {
+        let result = serve::run(client, cfg.clone(), listen, token.clone()).await;
+        tally.persist(&cfg);
+        if let Err(e) = result {
+            eprintln!("Error: could not serve on {}: {}", listen, e);
+            ExitCode::Failure.exit();
+        }
+        return;
     }
 
-    // 2. Config default
-    if let Some((lat, lng)) = cfg.default_location() {
-        let label = cfg.location.label.as_deref().unwrap_or("config");
+    let started = Instant::now();
+    let span = tracing::info_span!("command", command = cli.command.name());
+    let result = run_command(&client, &cli, &cfg)
+        .instrument(span)
+        .await;
+    tally.persist(&cfg);
+    if cli.timings {
         eprintln!(
             "{}",
-            format!("Using saved location ({}) [{:.4}, {:.4}]", label, lat, lng).dimmed()
+            format!(
+                "total {} ({} requests, {})",
+                format_elapsed(started.elapsed()),
+                tally.total(),
+                connections_summary(client.connection_stats())
+            )
+            .dimmed()
         );
-        return Some((lat, lng));
     }
+    match result {
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            ExitCode::for_error(&e).exit();
+        }
+        Ok(Some(0)) if cli.fail_on_empty => ExitCode::Empty.exit(),
+        Ok(_) => {}
+    }
+}
 
-    // 3. IP-based auto-locate
-    if auto_locate {
+/// "2 new connections, 180ms connecting" for the --timings total; the rest
+/// of the total is time spent on requests over open connections
+fn connections_summary(stats: &ConnectionStats) -> String {
+    match stats.opened() {
+        0 => "no new connections".to_string(),
+        n => format!(
+            "{} new connection{}, {} connecting",
+            n,
+            if n == 1 { "" } else { "s" },
+            format_elapsed(stats.connect_time())
+        ),
+    }
+}
+
+/// A non-fatal problem, as a yellow "Warning:" line on stderr
+fn print_warning(warning: &Warning) {
+    eprintln!("{} {}", "Warning:".yellow(), warning);
+}
+
+/// Client observer: print per-request timings (--timings), always warn on
+/// slow requests, surface partial-parse warnings, and note rate limiting
+/// with --verbose
+fn report_client_event(event: &ClientEvent, timings: bool, verbose: bool, tally: &usage::Tally) {
+    match event {
+        ClientEvent::RequestFinished {
+            endpoint,
+            elapsed,
+            ok,
+        } => {
+            tally.record(endpoint);
+
+            if timings {
+                let status = if *ok { "" } else { " (failed)" };
+                eprintln!(
+                    "{}",
+                    format!("{} {}{}", endpoint, format_elapsed(*elapsed), status).dimmed()
+                );
+            }
+            if *elapsed > SLOW_REQUEST_THRESHOLD {
+                eprintln!(
+                    "{} {} took {}",
+                    "Warning: slow request:".yellow(),
+                    endpoint,
+                    format_elapsed(*elapsed)
+                );
+            }
+        }
+        ClientEvent::Warning(warning) => print_warning(warning),
+        ClientEvent::Coalesced { endpoint } => {
+            tally.record_coalesced(endpoint);
+            if timings {
+                eprintln!("{}", format!("{} shared an in-flight request", endpoint).dimmed());
+            }
+        }
+        ClientEvent::Throttled { delay, qps } => {
+            if verbose {
+                eprintln!(
+                    "{}",
+                    format!(
+                        "Rate limit: waited {} to stay under {} requests per second",
+                        format_elapsed(*delay),
+                        qps
+                    )
+                    .dimmed()
+                );
+            }
+        }
+    }
+}
+
+/// Initialize stderr tracing when ZUPO_LOG or --log-format is set.
+/// Without either, nothing is installed so normal stderr stays clean.
+fn init_logging(format: Option<LogFormat>) {
+    let env_filter = std::env::var("ZUPO_LOG").ok();
+    if env_filter.is_none() && format.is_none() {
+        return;
+    }
+
+    let filter = EnvFilter::try_new(env_filter.as_deref().unwrap_or("info"))
+        .unwrap_or_else(|_| EnvFilter::new("info"));
+    let builder = tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_writer(std::io::stderr);
+
+    match format.unwrap_or(LogFormat::Json) {
+        LogFormat::Json => builder
+            .json()
+            .with_current_span(true)
+            .with_span_list(true)
+            .init(),
+        LogFormat::Pretty => builder.pretty().init(),
+    }
+}
+
+/// Read a GeoJSON polygon file for --polygon. Its bounding circle becomes the
+/// search circle, so it must fit the API's radius limit.
+fn load_polygon(path: &str) -> Result<geo::Area, api::errors::Error> {
+    let invalid = |message: String| api::errors::Error::Validation {
+        field: "polygon".into(),
+        message,
+    };
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| invalid(format!("failed to read {}: {}", path, e)))?;
+    let value: serde_json::Value = serde_json::from_str(&content)
+        .map_err(|e| invalid(format!("invalid JSON in {}: {}", path, e)))?;
+    let area = geo::Area::from_geojson(&value).map_err(invalid)?;
+    let radius = area.bounding_circle().radius;
+    if radius > MAX_CIRCLE_RADIUS {
+        return Err(invalid(format!(
+            "polygon in {} is too large: its bounding circle has a {:.1} km radius, max {:.0} km",
+            path,
+            radius / 1000.0,
+            MAX_CIRCLE_RADIUS / 1000.0
+        )));
+    }
+    Ok(area)
+}
+
+/// Drop places outside the polygon (or without a location), noting the count on stderr
+fn trim_to_polygon(places: &mut Vec<Place>, area: &geo::Area) {
+    let before = places.len();
+    places.retain(|p| p.location.as_ref().is_some_and(|loc| area.contains(loc)));
+    let trimmed = before - places.len();
+    if trimmed > 0 {
+        eprintln!(
+            "{}",
+            format!("Polygon trimmed {} of {} results", trimmed, before).dimmed()
+        );
+    }
+}
+
+/// Print a place list in a non-text `--format`; `origin` adds a distance
+/// column to tables
+fn print_places_as(format: OutputFormat, places: &[Place], origin: Option<(&LatLng, Units)>, nf: NumberFormat) {
+    match format {
+        OutputFormat::Markdown => print!("{}", render::markdown::places_table(places)),
+        OutputFormat::Csv => render::render_places_csv(places),
+        OutputFormat::Table => render::render_places_table(places, origin, table_width(), nf),
+        OutputFormat::Text => unreachable!("text output has its own renderers"),
+    }
+}
+
+/// Width `--format table` fits into: the terminal's, or unlimited when
+/// stdout isn't one
+fn table_width() -> usize {
+    if !std::io::stdout().is_terminal() {
+        return usize::MAX;
+    }
+    crossterm::terminal::size().map_or(usize::MAX, |(cols, _)| cols as usize)
+}
+
+fn csv_with_json() -> api::errors::Error {
+    api::errors::Error::Validation {
+        field: "format".into(),
+        message: "--format csv can't be combined with --json; pick one".into(),
+    }
+}
+
+/// Apply `--sort-by` (and `--reverse`), measuring distances from `center`
+fn sort_results(places: &mut [Place], cli: &Cli, center: Option<&LatLng>) -> Result<(), api::errors::Error> {
+    let Some(key) = cli.sort_by else {
+        return Ok(());
+    };
+    if key == ranking::SortKey::Distance && center.is_none() {
+        return Err(api::errors::Error::Validation {
+            field: "sort-by".into(),
+            message: "distance needs a bias center: use --lat/--lng, a default location, or --auto-locate".into(),
+        });
+    }
+    ranking::sort_places(places, key, center, cli.reverse);
+    Ok(())
+}
+
+/// Drop places with fewer than `min` reviews (the API has no such filter)
+fn drop_below_min_reviews(places: &mut Vec<Place>, min: u32) {
+    let before = places.len();
+    places.retain(|p| p.user_rating_count.unwrap_or(0) >= min);
+    let dropped = before - places.len();
+    if dropped > 0 {
+        eprintln!(
+            "{}",
+            format!(
+                "--min-reviews {} dropped {} of {} results",
+                min, dropped, before
+            )
+            .dimmed()
+        );
+    }
+}
+
+/// Drop places further than `meters` from `center`; places without a
+/// location are kept
+fn drop_beyond_distance(places: &mut Vec<Place>, center: &LatLng, meters: f64) -> Result<(), api::errors::Error> {
+    if !meters.is_finite() || meters <= 0.0 {
+        return Err(api::errors::Error::Validation {
+            field: "max-distance".into(),
+            message: format!("must be a positive distance in meters, got {}", meters),
+        });
+    }
+    let before = places.len();
+    places.retain(|p| {
+        p.location
+            .as_ref()
+            .is_none_or(|loc| geo::haversine_distance(center, loc) <= meters)
+    });
+    let dropped = before - places.len();
+    if dropped > 0 {
+        eprintln!(
+            "{}",
+            format!(
+                "--max-distance {} dropped {} of {} results",
+                meters, dropped, before
+            )
+            .dimmed()
+        );
+    }
+    Ok(())
+}
+
+/// Merge near-duplicate places within `meters`, reporting merges on stderr
+fn dedupe_places(places: Vec<Place>, meters: f64, verbose: bool) -> Result<Vec<Place>, api::errors::Error> {
+    if !meters.is_finite() || meters <= 0.0 {
+        return Err(api::errors::Error::Validation {
+            field: "dedupe-nearby".into(),
+            message: format!("must be a positive distance in meters, got {}", meters),
+        });
+    }
+    let before = places.len();
+    let (places, merges) = dedupe::dedupe_places(places, meters);
+    if !merges.is_empty() {
+        eprintln!(
+            "{}",
+            format!(
+                "--dedupe-nearby {} merged {} of {} results",
+                meters,
+                merges.len(),
+                before
+            )
+            .dimmed()
+        );
+    }
+    if verbose {
+        for m in &merges {
+            eprintln!(
+                "{}",
+                format!("  merged \"{}\" into \"{}\" ({:.0} m apart)", m.dropped, m.kept, m.meters).dimmed()
+            );
+        }
+    }
+    Ok(places)
+}
+
+/// Keep places open for at least `minutes` more. Places without period data
+/// are dropped (and counted), or kept with a warning when `lenient`.
+fn keep_open_for(places: &mut Vec<Place>, minutes: u32, lenient: bool) {
+    let before = places.len();
+    let mut unknown = 0;
+    places.retain(|p| match hours::open_state_now(p) {
+        hours::OpenState::Unknown => {
+            unknown += 1;
+            lenient
+        }
+        state => state.open_for_at_least(minutes as i64),
+    });
+
+    let closing = before - places.len() - if lenient { 0 } else { unknown };
+    if closing > 0 {
+        eprintln!(
+            "{}",
+            format!(
+                "--open-for {} dropped {} of {} results closing sooner",
+                minutes, closing, before
+            )
+            .dimmed()
+        );
+    }
+    if unknown > 0 {
+        let message = if lenient {
+            format!("{} result(s) have no opening-hours data and were kept unchecked", unknown)
+        } else {
+            format!(
+                "{} result(s) have no opening-hours data and were dropped (use --open-for-lenient to keep them)",
+                unknown
+            )
+        };
+        eprintln!("{} {}", "Warning:".yellow(), message);
+    }
+}
+
+/// JSON for `--rank smart`: the usual `{"places": [...]}` shape, in ranked
+/// order, with each place's score under the namespaced `zupo:rank` key
+/// `fallback_used` is added to the output when set (`nearby --fallback-search`)
+fn print_ranked_json(
+    ranked: &[(Place, ranking::Score)],
+    fallback_used: Option<bool>,
+    explanations: Option<&[explain::Explanation]>,
+) {
+    let places: Vec<serde_json::Value> = ranked
+        .iter()
+        .enumerate()
+        .map(|(i, (place, score))| {
+            let mut value = serde_json::to_value(place).unwrap();
+            if let Some(obj) = value.as_object_mut() {
+                obj.insert("zupo:rank".into(), serde_json::to_value(score).unwrap());
+                if let Some(explanation) = explanations.and_then(|e| e.get(i)) {
+                    obj.insert("zupo:explain".into(), serde_json::to_value(explanation).unwrap());
+                }
+            }
+            value
+        })
+        .collect();
+    let mut out = serde_json::json!({ "places": places });
+    if let Some(used) = fallback_used {
+        out["fallback_used"] = used.into();
+    }
+    println!("{}", serde_json::to_string_pretty(&out).unwrap());
+}
+
+/// `search --explain --json`: places with a `zupo:explain` object each
+fn print_explained_json(places: &[Place], explanations: &[explain::Explanation]) {
+    let places: Vec<serde_json::Value> = places
+        .iter()
+        .zip(explanations)
+        .map(|(place, explanation)| {
+            let mut value = serde_json::to_value(place).unwrap();
+            if let Some(obj) = value.as_object_mut() {
+                obj.insert("zupo:explain".into(), serde_json::to_value(explanation).unwrap());
+            }
+            value
+        })
+        .collect();
+    println!("{}", serde_json::to_string_pretty(&serde_json::json!({ "places": places })).unwrap());
+}
+
+/// Text query for `nearby --fallback-search`: the first included type, as words
+fn nearby_fallback_query(include_types: &[String], include_primary_types: &[String]) -> Option<String> {
+    include_types
+        .iter()
+        .chain(include_primary_types)
+        .next()
+        .map(|t| t.replace('_', " "))
+}
+
+/// Candidates looked up per endpoint by `route --confirm-endpoints`
+const ENDPOINT_CANDIDATES: u32 = 5;
+/// Candidates named like the best match but further from it than this make
+/// an endpoint ambiguous ("Springfield")
+const AMBIGUOUS_ENDPOINT_METERS: f64 = 10_000.0;
+
+/// Resolve both route endpoints to places and pin the request to them,
+/// reporting the matches on stderr
+async fn confirm_route_endpoints(client: &Client, req: &mut RouteRequest) -> Result<(), api::errors::Error> {
+    let (from, to) = client.resolve_route_endpoints(req, ENDPOINT_CANDIDATES).await?;
+    let interactive = std::io::stdin().is_terminal() && std::io::stderr().is_terminal();
+    req.from_resolved = Some(choose_endpoint("From", &req.from, from, interactive));
+    req.to_resolved = Some(choose_endpoint("To", &req.to, to, interactive));
+    Ok(())
+}
+
+/// Pick the endpoint for `text` from its candidates: the best match, unless
+/// same-named places elsewhere make it ambiguous and the user picks another
+fn choose_endpoint(role: &str, text: &str, candidates: Vec<Place>, interactive: bool) -> ResolvedEndpoint {
+    let best = &candidates[0];
+    let best_name = dedupe::normalize_name(endpoint_name(best));
+    let mut choices = vec![best];
+    choices.extend(candidates[1..].iter().filter(|p| {
+        let elsewhere = match (&best.location, &p.location) {
+            (Some(a), Some(b)) => geo::haversine_distance(a, b) > AMBIGUOUS_ENDPOINT_METERS,
+            _ => false,
+        };
+        elsewhere && dedupe::names_match(&best_name, &dedupe::normalize_name(endpoint_name(p)))
+    }));
+
+    let chosen = if choices.len() < 2 {
+        best
+    } else if interactive {
+        eprintln!("{} '{}' matches {} places:", "?".yellow(), format::sanitize_display(text), choices.len());
+        for (i, p) in choices.iter().enumerate() {
+            eprintln!("  {} {}", format!("{}.", i + 1).dimmed(), format::sanitize_display(&endpoint_label(p)));
+        }
+        choices[prompt_choice(&format!("{} [1-{}, Enter = 1]: ", role, choices.len()), choices.len())]
+    } else {
+        eprintln!(
+            "{} '{}' matches {} places; using the first (run in a terminal to choose)",
+            "Warning:".yellow(),
+            format::sanitize_display(text),
+            choices.len()
+        );
+        best
+    };
+
+    let label = endpoint_label(chosen);
+    eprintln!("{} {}", format!("{}:", role).dimmed(), format::sanitize_display(&label));
+    ResolvedEndpoint {
+        place_id: chosen.id.clone(),
+        label,
+        location: chosen.location.clone(),
+    }
+}
+
+/// Text of `search --query` or `autocomplete --input`: the flag's value, or
+/// stdin for `-` or when the flag is left out and stdin isn't a terminal
+fn query_text(value: Option<&str>, flag: &str) -> Result<String, api::errors::Error> {
+    let invalid = |message: String| api::errors::Error::Validation {
+        field: flag.into(),
+        message,
+    };
+    match value {
+        Some("-") => {}
+        Some(text) => return Ok(text.to_string()),
+        None if std::io::stdin().is_terminal() => {
+            return Err(invalid(format!("missing: pass --{} TEXT, or pipe the text on stdin", flag)))
+        }
+        None => {}
+    }
+    let mut buf = String::new();
+    std::io::Read::read_to_string(&mut std::io::stdin(), &mut buf)
+        .map_err(|e| invalid(format!("failed to read stdin: {}", e)))?;
+    let text = buf.trim_end_matches(['\n', '\r']);
+    if text.trim().is_empty() {
+        return Err(invalid(format!("stdin was empty (pass --{} TEXT instead)", flag)));
+    }
+    Ok(text.to_string())
+}
+
+/// Zero-based index read from stdin; an empty line, end of input, or a read
+/// error picks the first choice
+fn prompt_choice(prompt: &str, count: usize) -> usize {
+    loop {
+        eprint!("{}", prompt);
+        let mut line = String::new();
+        match std::io::stdin().read_line(&mut line) {
+            Ok(0) | Err(_) => return 0,
+            Ok(_) => {}
+        }
+        let line = line.trim();
+        if line.is_empty() {
+            return 0;
+        }
+        match line.parse::<usize>() {
+            Ok(n) if (1..=count).contains(&n) => return n - 1,
+            _ => eprintln!("Enter a number from 1 to {}", count),
+        }
+    }
+}
+
+/// Yes/no answer read from stdin; anything but y or yes, including end of
+/// input or a read error, is no
+fn prompt_yes(prompt: &str) -> bool {
+    eprint!("{}", prompt);
+    let mut line = String::new();
+    match std::io::stdin().read_line(&mut line) {
+        Ok(0) | Err(_) => false,
+        Ok(_) => matches!(line.trim().to_ascii_lowercase().as_str(), "y" | "yes"),
+    }
+}
+
+fn endpoint_name(place: &Place) -> &str {
+    place.display_name.as_ref().map(|n| n.text.as_str()).unwrap_or("")
+}
+
+/// "Name, address", or whichever of the two is present
+fn endpoint_label(place: &Place) -> String {
+    let name = endpoint_name(place);
+    match place.formatted_address.as_deref() {
+        Some(addr) if name.is_empty() || addr.starts_with(name) => addr.to_string(),
+        Some(addr) => format!("{}, {}", name, addr),
+        None if name.is_empty() => place.id.clone(),
+        None => name.to_string(),
+    }
+}
+
+/// Print a `--count` result: the bare number, or `{"count": N}` with --json
+fn print_count(counted: &PlaceCount, json_output: bool, verbose: bool) {
+    if verbose {
+        eprintln!(
+            "{} {} ({} request{})",
+            "field mask:".dimmed(),
+            counted.field_mask,
+            counted.requests,
+            if counted.requests == 1 { "" } else { "s" }
+        );
+    }
+    if counted.capped {
+        eprintln!(
+            "{}",
+            "Note: reached the API's result limit; there may be more matching places".yellow()
+        );
+    }
+
+    if json_output {
+        println!("{}", serde_json::to_string_pretty(counted).unwrap());
+    } else {
+        println!("{}", counted.count);
+    }
+}
+
+/// How place lists show addresses: `--address-style`, else the config's,
+/// with `auto` comparing places against `defaults.region`
+fn address_choice<'a>(cli: &Cli, cfg: &'a Config) -> AddressChoice<'a> {
+    AddressChoice {
+        style: cli.address_style.unwrap_or_else(|| cfg.address_style()),
+        region: cfg.defaults.region.as_deref(),
+    }
+}
+
+/// Clamp `--limit` to an endpoint's maximum, warning on stderr when it is lowered
+fn effective_limit(requested: u32, endpoint_max: u32) -> u32 {
+    let (limit, warning) = limits::clamp_limit(requested, endpoint_max);
+    if let Some(w) = warning {
+        eprintln!("{} --limit: {}", "Warning:".yellow(), w);
+    }
+    limit
+}
+
+/// Note on stderr when a nearby search was split into rings (each one listed with `verbose`)
+fn report_rings(rings: &RingSearch, units: Units, verbose: bool, nf: NumberFormat) {
+    if rings.radii.len() < 2 {
+        return;
+    }
+    eprintln!(
+        "{}",
+        format!(
+            "--limit above 20: split into {} ring searches, one request each; results are approximate",
+            rings.radii.len()
+        )
+        .dimmed()
+    );
+    if verbose {
+        for (i, (radius, added)) in rings.radii.iter().zip(&rings.added).enumerate() {
+            eprintln!(
+                "{}",
+                format!(
+                    "  ring {}: radius {}, {} new place{}",
+                    i + 1,
+                    format::format_distance(*radius, units, nf),
+                    added,
+                    if *added == 1 { "" } else { "s" }
+                )
+                .dimmed()
+            );
+        }
+    }
+}
+
+/// `details --from-json`: render saved places without an API request
+fn render_details_from_json(
+    source: &str,
+    format: OutputFormat,
+    json_output: bool,
+    precision: usize,
+    verbosity: render::Verbosity,
+    language: Option<&str>,
+) -> Result<(), api::errors::Error> {
+    let invalid = |message: String| api::errors::Error::Validation {
+        field: "from-json".into(),
+        message,
+    };
+    let content = if source == "-" {
+        let mut buf = String::new();
+        std::io::Read::read_to_string(&mut std::io::stdin(), &mut buf).map(|_| buf)
+    } else {
+        std::fs::read_to_string(source)
+    }
+    .map_err(|e| invalid(format!("failed to read {}: {}", source, e)))?;
+    let value: serde_json::Value = serde_json::from_str(&content)
+        .map_err(|e| invalid(format!("invalid JSON in {}: {}", source, e)))?;
+    let places = api::parse::places_from_json(value).map_err(invalid)?;
+    let cfg = load_config_with_env();
+    let nf = NumberFormat::for_language(language.or(cfg.defaults.language.as_deref()));
+
+    if json_output && format == OutputFormat::Csv {
+        return Err(csv_with_json());
+    }
+    if matches!(format, OutputFormat::Csv | OutputFormat::Table) {
+        print_places_as(format, &places, None, nf);
+        return Ok(());
+    }
+    if json_output {
+        let out = match places.as_slice() {
+            [place] => serde_json::to_string_pretty(place),
+            _ => serde_json::to_string_pretty(&places),
+        };
+        println!("{}", out.unwrap());
+        return Ok(());
+    }
+    let hours_from_today = cfg.hours_start_today();
+    let notes = notes::PlaceNotes::load_or_default(&cfg);
+    for (i, place) in places.iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+        let place_notes = notes.for_place(&place.id);
+        if format == OutputFormat::Markdown {
+            print!("{}", render::markdown::place_details(place, place_notes));
+        } else {
+            render::render_place_details(place, precision, hours_from_today, verbosity, None, place_notes, nf);
+        }
+    }
+    Ok(())
+}
+
+/// Load a previous `--json` result file (`{"places": [...]}` or a bare array)
+/// and print its diff against the fresh places
+fn print_places_diff(
+    path: &str,
+    current: &[Place],
+    json_output: bool,
+    nf: NumberFormat,
+    addresses: AddressChoice,
+) -> Result<(), api::errors::Error> {
+    let invalid = |message: String| api::errors::Error::Validation {
+        field: "diff".into(),
+        message,
+    };
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| invalid(format!("failed to read {}: {}", path, e)))?;
+    let value: serde_json::Value = serde_json::from_str(&content)
+        .map_err(|e| invalid(format!("invalid JSON in {}: {}", path, e)))?;
+    let places_value = match value {
+        serde_json::Value::Array(_) => value,
+        mut obj => obj["places"].take(),
+    };
+    let previous: Vec<Place> = serde_json::from_value(places_value)
+        .map_err(|e| invalid(format!("{} is not a zupo places result: {}", path, e)))?;
+
+    let result = diff::diff_places(&previous, current);
+    if json_output {
+        println!("{}", serde_json::to_string_pretty(&result).unwrap());
+    } else {
+        render::render_places_diff(&result, nf, addresses);
+    }
+    Ok(())
+}
+
+/// Resolve lat/lng from: explicit flags > config > auto-locate
+async fn resolve_location(
+    explicit_lat: Option<f64>,
+    explicit_lng: Option<f64>,
+    auto_locate: bool,
+    assume_yes: bool,
+    cfg: &Config,
+    precision: usize,
+) -> Option<(f64, f64)> {
+    // 1. Explicit flags
+    if let (Some(lat), Some(lng)) = (explicit_lat, explicit_lng) {
+        return Some((lat, lng));
+    }
+
+    // 2. Config default
+    if let Some((lat, lng)) = cfg.default_location() {
+        let label = cfg.location.label.as_deref().unwrap_or("config");
+        eprintln!(
+            "{}",
+            format!(
+                "Using saved location ({}) [{}, {}]",
+                label,
+                format_coord(lat, precision),
+                format_coord(lng, precision)
+            )
+            .dimmed()
+        );
+        return Some((lat, lng));
+    }
+
+    // 3. IP-based auto-locate
+    if auto_locate {
         eprintln!("{}", "Auto-detecting location via IP...".dimmed());
         match geolocate::geolocate_by_ip().await {
             Ok(geo) => {
                 eprintln!(
                     "{}",
                     format!(
-                        "Detected: {} [{:.4}, {:.4}]",
-                        geo.description, geo.lat, geo.lng
+                        "Detected: {} [{}, {}]",
+                        geo.description,
+                        format_coord(geo.lat, precision),
+                        format_coord(geo.lng, precision)
                     )
                     .dimmed()
                 );
-                return Some((geo.lat, geo.lng));
+                if accept_detected_location(&geo, cfg, assume_yes) {
+                    return Some((geo.lat, geo.lng));
+                }
+                eprintln!("{}", "Not using the detected location".dimmed());
+            }
+            Err(e) => print_warning(&Warning::AutoLocate { error: e.to_string() }),
+        }
+    }
+
+    None
+}
+
+/// An IP-detected location further than this from the saved one is suspect
+const AUTO_LOCATE_DRIFT_METERS: f64 = 100_000.0;
+
+/// Whether to use an IP-detected location that looks wrong: behind a VPN or
+/// proxy, or far from the saved location. On a terminal the user decides
+/// (default no); otherwise zupo warns and keeps the saved location, or uses
+/// the detected one when there's none. `--yes` accepts it outright.
+fn accept_detected_location(geo: &geolocate::GeoLocation, cfg: &Config, assume_yes: bool) -> bool {
+    let nf = NumberFormat::for_language(cfg.defaults.language.as_deref());
+    let detected = LatLng {
+        latitude: geo.lat,
+        longitude: geo.lng,
+    };
+    let saved = cfg.default_location().map(|(latitude, longitude)| LatLng { latitude, longitude });
+    let drift = saved.as_ref().map(|s| geo::haversine_distance(s, &detected));
+    let doubt = match drift {
+        Some(meters) if meters > AUTO_LOCATE_DRIFT_METERS => {
+            let label = cfg.location.label.as_deref().unwrap_or("saved location");
+            format!(
+                "your saved location is {} ({} km away)",
+                format::sanitize_display(label),
+                format::format_decimal(meters / 1000.0, 0, nf)
+            )
+        }
+        _ if geo.via_proxy => "your IP address looks like a VPN or proxy".to_string(),
+        _ => return true,
+    };
+    if assume_yes {
+        return true;
+    }
+
+    let detected = format::sanitize_display(&geo.description);
+    if std::io::stdin().is_terminal() && std::io::stderr().is_terminal() {
+        return prompt_yes(&format!("Detected {}, but {} — use detected? [y/N] ", detected, doubt));
+    }
+    let outcome = if saved.is_some() {
+        "keeping the saved location (pass --yes to use it)"
+    } else {
+        "using it anyway"
+    };
+    eprintln!("{} detected {}, but {}; {}", "Warning:".yellow(), detected, doubt, outcome);
+    saved.is_none()
+}
+
+/// The config file with `ZUPO_*` environment overrides on top; exits with a
+/// usage error when one of the variables is invalid
+fn load_config_with_env() -> Config {
+    let mut cfg = Config::load();
+    if let Err(e) = cfg.apply_env() {
+        eprintln!("Error: {}", e);
+        ExitCode::Usage.exit();
+    }
+    cfg
+}
+
+/// Resolve radius from: explicit flag > config default > fallback. A config
+/// default that isn't a valid radius (say `default_radius = 0.0`) is skipped
+/// with a warning rather than failing every command; an explicit one is
+/// checked by the request.
+fn resolve_radius(explicit: Option<f64>, cfg: &Config, fallback: f64) -> f64 {
+    if let Some(radius) = explicit {
+        return radius;
+    }
+    let Some(radius) = cfg.location.default_radius else {
+        return fallback;
+    };
+    match api::types::radius_problem(radius) {
+        None => radius,
+        Some(problem) => {
+            eprintln!(
+                "{} ignoring location.default_radius in the config ({}); using {} m",
+                "Warning:".yellow(),
+                problem,
+                fallback
+            );
+            fallback
+        }
+    }
+}
+
+fn handle_stats_command(action: Option<&StatsAction>, json_output: bool) {
+    let cfg = Config::load();
+
+    if let Some(StatsAction::Reset) = action {
+        match usage::reset(&cfg) {
+            Ok(()) => println!("Usage counters cleared"),
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                ExitCode::Failure.exit();
+            }
+        }
+        return;
+    }
+
+    let usage = usage::MonthlyUsage::load_current(&cfg);
+    let rows = usage.rows(&cfg);
+    if json_output {
+        let total_requests: u64 = rows.iter().map(|r| r.requests).sum();
+        let total_cost: f64 = rows.iter().filter_map(|r| r.estimated_cost_usd).sum();
+        let total_coalesced: u64 = rows.iter().map(|r| r.coalesced).sum();
+        let out = serde_json::json!({
+            "month": usage.month,
+            "endpoints": rows,
+            "totalRequests": total_requests,
+            "totalCoalesced": total_coalesced,
+            "estimatedCostUsd": total_cost,
+            "disclaimer": usage::DISCLAIMER,
+        });
+        println!("{}", serde_json::to_string_pretty(&out).unwrap());
+    } else {
+        render::render_usage(&usage.month, &rows);
+    }
+}
+
+fn handle_note_command(action: &NoteAction, json_output: bool) {
+    let cfg = Config::load();
+    let fail = |message: String| -> ! {
+        eprintln!("Error: {}", message);
+        ExitCode::Failure.exit()
+    };
+    let usage = |message: &str| -> ! {
+        eprintln!("Error: {}", message);
+        ExitCode::Usage.exit()
+    };
+    // The same ID forms `details` accepts, so notes line up with its output
+    let place_id = |raw: &str| api::normalize_place_id(raw).unwrap_or_else(|e| usage(&e.to_string()));
+    let mut notes = notes::PlaceNotes::load(&cfg).unwrap_or_else(|e| fail(e));
+
+    match action {
+        NoteAction::Add { place_id: raw, text } => {
+            let (place_id, text) = (place_id(raw), text.trim());
+            if text.is_empty() {
+                usage("note text must not be empty");
+            }
+            notes.add(&place_id, text);
+            notes.save(&cfg).unwrap_or_else(|e| fail(e));
+            let count = notes.for_place(&place_id).len();
+            println!("Note {} added to {}", count, place_id);
+        }
+        NoteAction::List { place_id: raw } => {
+            let id = raw.as_deref().map(place_id);
+            let places: Vec<(&str, &[notes::Note])> = match id {
+                Some(ref id) => vec![(id.as_str(), notes.for_place(id))],
+                None => notes.places.iter().map(|(id, n)| (id.as_str(), n.as_slice())).collect(),
+            };
+            let places: Vec<_> = places.into_iter().filter(|(_, n)| !n.is_empty()).collect();
+            if json_output {
+                let map: serde_json::Map<String, serde_json::Value> = places
+                    .iter()
+                    .map(|(id, n)| (id.to_string(), serde_json::to_value(n).unwrap()))
+                    .collect();
+                println!("{}", serde_json::to_string_pretty(&map).unwrap());
+            } else if places.is_empty() {
+                println!("{}", "No notes yet. Add one with `zupo note add --place-id <ID> \"text\"`".dimmed());
+            } else {
+                render::render_note_list(&places);
+            }
+        }
+        NoteAction::Remove { place_id: raw, number, all } => {
+            let place_id = &place_id(raw);
+            if *all {
+                let removed = notes.remove_all(place_id);
+                if removed == 0 {
+                    usage(&format!("no notes on {}", place_id));
+                }
+                notes.save(&cfg).unwrap_or_else(|e| fail(e));
+                println!("Removed {} note(s) from {}", removed, place_id);
+            } else if let Some(number) = number {
+                let count = notes.for_place(place_id).len();
+                if notes.remove(place_id, *number).is_none() {
+                    usage(&match count {
+                        0 => format!("no notes on {}", place_id),
+                        _ => format!("{} has notes 1-{}, not {}", place_id, count, number),
+                    });
+                }
+                notes.save(&cfg).unwrap_or_else(|e| fail(e));
+                println!("Removed note {} from {}", number, place_id);
+            }
+        }
+    }
+}
+
+/// Parse `monitor --interval`: whole seconds, at least `monitor::MIN_INTERVAL_SECS`
+fn parse_interval(text: &str) -> Result<u64, String> {
+    match text.trim().parse::<u64>() {
+        Ok(secs) if secs >= monitor::MIN_INTERVAL_SECS => Ok(secs),
+        Ok(_) => Err(format!("must be at least {} seconds", monitor::MIN_INTERVAL_SECS)),
+        Err(_) => Err(format!("'{}' is not a whole number of seconds", text)),
+    }
+}
+
+/// `zupo monitor`: check the search every `--interval` (or once), report
+/// notable changes against the stored snapshot, and write usage after each
+/// check. In the loop, a failed check is reported and retried at the next
+/// interval; with `--once` it is the command's error.
+async fn run_monitor(
+    client: &Client,
+    cli: &Cli,
+    cfg: &Config,
+    tally: &usage::Tally,
+) -> Result<(), api::errors::Error> {
+    let Commands::Monitor {
+        query,
+        included_type,
+        lat,
+        lng,
+        radius,
+        limit,
+        lang,
+        region,
+        interval,
+        once,
+        snapshot,
+        ..
+    } = &cli.command
+    else {
+        unreachable!()
+    };
+    let location = resolve_location(*lat, *lng, cli.auto_locate, cli.yes, cfg, cli.precision)
+        .await
+        .map(|(latitude, longitude)| Circle {
+            center: LatLng { latitude, longitude },
+            radius: resolve_radius(*radius, cfg, 5000.0),
+        });
+    let req = SearchRequest {
+        query: query.clone(),
+        included_type: included_type.clone(),
+        min_rating: None,
+        price_levels: Vec::new(),
+        open_now: false,
+        location,
+        limit: Some(effective_limit(*limit, MAX_SEARCH_RESULTS)),
+        language: lang.clone().or_else(|| cfg.defaults.language.clone()),
+        region: region.clone().or_else(|| cfg.defaults.region.clone()),
+        include_photos: false,
+    };
+    let nf = NumberFormat::for_language(req.language.as_deref());
+
+    let path = match snapshot {
+        Some(path) => std::path::PathBuf::from(path),
+        None => {
+            let center = req
+                .location
+                .as_ref()
+                .map(|c| format!("{:.4},{:.4},{}", c.center.latitude, c.center.longitude, c.radius))
+                .unwrap_or_default();
+            let params = format!(
+                "{}|{}|{}|{}|{}|{}",
+                query,
+                included_type.as_deref().unwrap_or(""),
+                center,
+                req.limit.unwrap_or(0),
+                req.language.as_deref().unwrap_or(""),
+                req.region.as_deref().unwrap_or("")
+            );
+            monitor::snapshot_path(cfg, query, &params).ok_or_else(|| api::errors::Error::Validation {
+                field: "snapshot".into(),
+                message: "could not determine the state directory; pass --snapshot".into(),
+            })?
+        }
+    };
+
+    if !*once {
+        let checks_per_month = 30 * 86_400 / *interval;
+        let cost = usage::estimated_cost("searchText", checks_per_month, cfg)
+            .map(|usd| format!(" (about ${:.2})", usd))
+            .unwrap_or_default();
+        eprintln!(
+            "{}",
+            format!(
+                "Checking \"{}\" every {}: {} search requests a month{}. Ctrl+C to stop.",
+                query,
+                humanize_duration(Duration::from_secs(*interval)),
+                format::format_count(checks_per_month, nf),
+                cost
+            )
+            .dimmed()
+        );
+    }
+
+    let ctrl_c = tokio::signal::ctrl_c();
+    tokio::pin!(ctrl_c);
+    loop {
+        let result = monitor_check(client, cli, cfg, &req, &path).await;
+        tally.flush(cfg);
+        match result {
+            Err(e) if *once => return Err(e),
+            Err(e) => eprintln!(
+                "{} check failed, retrying in {}: {}",
+                "Warning:".yellow(),
+                humanize_duration(Duration::from_secs(*interval)),
+                e
+            ),
+            Ok(()) => {}
+        }
+        if *once {
+            return Ok(());
+        }
+        tokio::select! {
+            _ = tokio::time::sleep(Duration::from_secs(*interval)) => {}
+            _ = &mut ctrl_c => return Ok(()),
+        }
+    }
+}
+
+/// One `monitor` check: search, diff against the snapshot, print and hand the
+/// notable changes to `--exec`, and store the new snapshot
+async fn monitor_check(
+    client: &Client,
+    cli: &Cli,
+    cfg: &Config,
+    req: &SearchRequest,
+    path: &std::path::Path,
+) -> Result<(), api::errors::Error> {
+    let Commands::Monitor {
+        notify_on,
+        rating_threshold,
+        exec,
+        ..
+    } = &cli.command
+    else {
+        unreachable!()
+    };
+    let snapshot_error = |message: String| api::errors::Error::Validation {
+        field: "snapshot".into(),
+        message,
+    };
+    let previous = monitor::Snapshot::load(path).map_err(snapshot_error)?;
+    let resp = client.search(req).await?;
+    let mut current = monitor::Snapshot::new(&req.query, resp.places);
+
+    let Some(previous) = previous else {
+        current.save(path).map_err(snapshot_error)?;
+        eprintln!(
+            "{}",
+            format!(
+                "Saved a first snapshot of {} places to {}; changes are reported from the next check.",
+                current.places.len(),
+                path.display()
+            )
+            .dimmed()
+        );
+        return Ok(());
+    };
+
+    let changes = monitor::notable_changes(
+        diff::diff_places(&previous.places, &current.places),
+        notify_on,
+        *rating_threshold,
+    );
+    monitor::carry_unreported_ratings(&mut current.places, &previous.places, &changes);
+    current.save(path).map_err(snapshot_error)?;
+
+    let checked_at = monitor::format_timestamp(current.taken_at);
+    if changes.is_empty() {
+        if cli.verbose {
+            eprintln!("{}", format!("{} no changes", checked_at).dimmed());
+        }
+        return Ok(());
+    }
+
+    let changes_json = serde_json::json!({
+        "query": req.query,
+        "checkedAt": checked_at,
+        "added": changes.added,
+        "removed": changes.removed,
+        "changed": changes.changed,
+    });
+    if cli.json {
+        // One line per check, so the output can be appended to a log
+        println!("{}", changes_json);
+    } else {
+        println!("{}", format!("{} \"{}\"", checked_at, req.query).bold());
+        let nf = NumberFormat::for_language(req.language.as_deref());
+        render::render_places_diff(&changes, nf, address_choice(cli, cfg));
+    }
+    if let Some(command) = exec.as_deref() {
+        if let Err(e) = monitor::run_hook(command, &changes_json.to_string(), &req.query).await {
+            eprintln!("{} {}", "Warning:".yellow(), e);
+        }
+    }
+    Ok(())
+}
+
+/// Widest line `reviews` wraps text to, however wide the terminal
+const REVIEW_TEXT_WIDTH: usize = 100;
+
+/// Commands whose flags can be given defaults under `[commands.<name>]`
+const DEFAULTABLE_COMMANDS: [&str; 9] = [
+    "search",
+    "autocomplete",
+    "nearby",
+    "route",
+    "details",
+    "reviews",
+    "photo",
+    "resolve",
+    "monitor",
+];
+
+/// Re-parse the command line with the `[commands.<name>]` defaults from the
+/// config appended for every flag not given explicitly. Each default goes
+/// through clap's own parsing; one that doesn't parse, or conflicts with an
+/// explicit flag, is skipped with a warning.
+fn with_command_defaults(cli: Cli, cfg: &Config) -> Cli {
+    let name = cli.command.name();
+    let Some(defaults) = cfg.commands.get(name) else {
+        return cli;
+    };
+    let command = Cli::command();
+    let Some(subcommand) = command
+        .find_subcommand(name)
+        .filter(|_| DEFAULTABLE_COMMANDS.contains(&name))
+    else {
+        return cli;
+    };
+
+    let mut argv: Vec<std::ffi::OsString> = std::env::args_os().collect();
+    let matches = Cli::command().get_matches_from(&argv);
+    let Some((_, explicit)) = matches.subcommand() else {
+        return cli;
+    };
+
+    let mut cli = cli;
+    for (key, value) in defaults {
+        let warn = |message: String| {
+            eprintln!("{} ignoring config commands.{}.{}: {}", "Warning:".yellow(), name, key, message)
+        };
+        let Some(arg) = find_flag(subcommand, key) else {
+            warn(unknown_flag_message(subcommand, key));
+            continue;
+        };
+        let given = matches!(
+            explicit.value_source(arg.get_id().as_str()),
+            Some(ValueSource::CommandLine | ValueSource::EnvVariable)
+        );
+        if given {
+            continue;
+        }
+        let args = match default_args(arg, value) {
+            Ok(args) => args,
+            Err(e) => {
+                warn(e);
+                continue;
+            }
+        };
+        let mut candidate = argv.clone();
+        candidate.extend(args.into_iter().map(Into::into));
+        match Cli::try_parse_from(&candidate) {
+            Ok(parsed) => {
+                argv = candidate;
+                cli = parsed;
             }
             Err(e) => {
-                eprintln!("{}", format!("Auto-locate failed: {}", e).yellow());
+                let rendered = e.to_string();
+                let first = rendered.lines().next().unwrap_or_default();
+                warn(first.trim_start_matches("error: ").to_string());
             }
         }
     }
+    cli
+}
 
-    None
+/// Problems with `[commands.*]`: unknown commands or flags (with the closest
+/// match as a suggestion) and values no flag can take
+fn check_command_defaults(cfg: &Config) -> Vec<String> {
+    let command = Cli::command();
+    let mut problems = Vec::new();
+    for (name, defaults) in &cfg.commands {
+        let subcommand = command
+            .find_subcommand(name)
+            .filter(|_| DEFAULTABLE_COMMANDS.contains(&name.as_str()));
+        let Some(subcommand) = subcommand else {
+            let hint = closest(name, DEFAULTABLE_COMMANDS.iter().copied())
+                .map(|c| format!(" (did you mean `{}`?)", c))
+                .unwrap_or_default();
+            problems.push(format!("commands.{}: not a command with flag defaults{}", name, hint));
+            continue;
+        };
+        for (key, value) in defaults {
+            let problem = match find_flag(subcommand, key) {
+                None => Some(unknown_flag_message(subcommand, key)),
+                Some(arg) => default_args(arg, value)
+                    .and_then(|args| check_default_values(subcommand, &args))
+                    .err(),
+            };
+            if let Some(problem) = problem {
+                problems.push(format!("commands.{}.{}: {}", name, key, problem));
+            }
+        }
+    }
+    problems
 }
 
-/// Resolve radius from: explicit flag > config default > fallback
-fn resolve_radius(explicit: Option<f64>, cfg: &Config, fallback: f64) -> f64 {
-    explicit.unwrap_or_else(|| {
-        cfg.location
-            .default_radius
-            .unwrap_or(fallback)
+/// Parse a default's arguments on their own against the subcommand, so bad
+/// values are caught by the flag's own parser (missing required flags aside)
+fn check_default_values(subcommand: &clap::Command, args: &[String]) -> Result<(), String> {
+    let result = subcommand
+        .clone()
+        .mut_args(|a| a.required(false))
+        .try_get_matches_from(std::iter::once(subcommand.get_name().to_string()).chain(args.iter().cloned()));
+    match result {
+        Err(e) if e.kind() != clap::error::ErrorKind::MissingRequiredArgument => {
+            let rendered = e.to_string();
+            let first = rendered.lines().next().unwrap_or_default();
+            Err(first.trim_start_matches("error: ").to_string())
+        }
+        _ => Ok(()),
+    }
+}
+
+/// The flag a `[commands.*]` key names, by argument id or long name
+/// (`min_rating` and `min-rating` both find `--min-rating`)
+fn find_flag<'a>(subcommand: &'a clap::Command, key: &str) -> Option<&'a clap::Arg> {
+    let long = key.replace('_', "-");
+    subcommand
+        .get_arguments()
+        .filter(|a| a.get_long().is_some() && a.get_id() != "help")
+        .find(|a| a.get_id() == key || a.get_long() == Some(long.as_str()))
+}
+
+fn unknown_flag_message(subcommand: &clap::Command, key: &str) -> String {
+    let keys: Vec<String> = subcommand
+        .get_arguments()
+        .filter_map(|a| a.get_long())
+        .filter(|l| *l != "help")
+        .map(|l| l.replace('-', "_"))
+        .collect();
+    match closest(key, keys.iter().map(String::as_str)) {
+        Some(suggestion) => format!("unknown flag (did you mean `{}`?)", suggestion),
+        None => "unknown flag".to_string(),
+    }
+}
+
+/// The candidate nearest to `input` by edit distance, if it's close enough
+/// to be a plausible typo
+fn closest<'a>(input: &str, candidates: impl Iterator<Item = &'a str>) -> Option<&'a str> {
+    let max = (input.chars().count() / 3).max(2);
+    candidates
+        .map(|c| (dedupe::levenshtein(input, c), c))
+        .filter(|(d, _)| *d <= max)
+        .min_by_key(|(d, _)| *d)
+        .map(|(_, c)| c)
+}
+
+/// Command-line arguments for a config default: `--flag` for a `true`
+/// switch, `--flag=value` (repeated for arrays) for flags that take values
+fn default_args(arg: &clap::Arg, value: &toml::Value) -> Result<Vec<String>, String> {
+    let long = arg.get_long().unwrap_or_default();
+    if !arg.get_action().takes_values() {
+        return match value {
+            toml::Value::Boolean(true) => Ok(vec![format!("--{}", long)]),
+            toml::Value::Boolean(false) => Ok(Vec::new()),
+            _ => Err("expects true or false".into()),
+        };
+    }
+    let scalar = |v: &toml::Value| match v {
+        toml::Value::String(s) => Ok(s.clone()),
+        toml::Value::Integer(i) => Ok(i.to_string()),
+        toml::Value::Float(f) => Ok(f.to_string()),
+        toml::Value::Boolean(b) => Ok(b.to_string()),
+        _ => Err("expects a string, number, or boolean (or an array of them)".to_string()),
+    };
+    match value {
+        toml::Value::Array(items) => items
+            .iter()
+            .map(|v| Ok(format!("--{}={}", long, scalar(v)?)))
+            .collect(),
+        v => Ok(vec![format!("--{}={}", long, scalar(v)?)]),
+    }
+}
+
+/// The clap command with each subcommand's examples appended to its help
+fn cli_command() -> clap::Command {
+    examples::COMMANDS.iter().fold(Cli::command(), |cmd, &name| {
+        let help = examples::for_command(name).map(examples::help_text).unwrap_or_default();
+        cmd.mut_subcommand(name, |sub| sub.after_help(help))
     })
 }
 
-async fn handle_config_command(action: &ConfigAction) {
+fn handle_examples_command(command: Option<&str>) {
+    let names: Vec<&str> = match command {
+        Some(name) if examples::for_command(name).is_some() => vec![name],
+        Some(name) => {
+            eprintln!(
+                "Error: no examples for '{}'; commands: {}",
+                name,
+                examples::COMMANDS.join(", ")
+            );
+            ExitCode::Usage.exit();
+        }
+        None => examples::COMMANDS.to_vec(),
+    };
+    for (i, name) in names.iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+        render::render_examples(name, examples::for_command(name).unwrap_or_default());
+    }
+}
+
+fn handle_types_command(filter: Option<&str>, json_output: bool) {
+    let groups = place_types::grouped_types(filter);
+    if json_output {
+        let out: Vec<serde_json::Value> = groups
+            .iter()
+            .flat_map(|(category, types)| {
+                types
+                    .iter()
+                    .map(move |t| serde_json::json!({ "type": t, "category": category }))
+            })
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&out).unwrap());
+    } else {
+        render::render_place_types(&groups);
+    }
+}
+
+async fn handle_config_command(action: &ConfigAction, precision: usize, assume_yes: bool) {
     match action {
         ConfigAction::SetLocation {
             lat,
@@ -453,8 +2463,8 @@ async fn handle_config_command(action: &ConfigAction) {
             match cfg.save() {
                 Ok(()) => {
                     println!("Location saved to {}", config::config_file_path());
-                    println!("  Lat: {}", lat);
-                    println!("  Lng: {}", lng);
+                    println!("  Lat: {}", format_coord(*lat, precision));
+                    println!("  Lng: {}", format_coord(*lng, precision));
                     if let Some(r) = radius {
                         println!("  Radius: {}m", r);
                     }
@@ -464,7 +2474,7 @@ async fn handle_config_command(action: &ConfigAction) {
                 }
                 Err(e) => {
                     eprintln!("Error: {}", e);
-                    process::exit(1);
+                    ExitCode::Failure.exit();
                 }
             }
         }
@@ -479,8 +2489,8 @@ async fn handle_config_command(action: &ConfigAction) {
                 if let Some(ref label) = cfg.location.label {
                     println!("    {} {}", "Label:".dimmed(), label);
                 }
-                println!("    {} {}", "Lat:".dimmed(), lat);
-                println!("    {} {}", "Lng:".dimmed(), lng);
+                println!("    {} {}", "Lat:".dimmed(), format_coord(lat, precision));
+                println!("    {} {}", "Lng:".dimmed(), format_coord(lng, precision));
                 println!(
                     "    {} {}m",
                     "Radius:".dimmed(),
@@ -492,6 +2502,55 @@ async fn handle_config_command(action: &ConfigAction) {
                     "No default location set. Use `zupo config set-location` or `zupo config auto-detect`.".dimmed()
                 );
             }
+            println!();
+            println!("  {}", "Paths".bold());
+            let dirs = [
+                ("Cache:", paths::cache_dir(&cfg.paths)),
+                ("State:", paths::state_dir(&cfg.paths)),
+                ("Logs:", paths::log_dir(&cfg.paths)),
+            ];
+            for (label, dir) in dirs {
+                let dir = dir
+                    .map(|d| d.display().to_string())
+                    .unwrap_or_else(|| "(unavailable)".to_string());
+                println!("    {} {}", label.dimmed(), dir);
+            }
+            if !cfg.commands.is_empty() {
+                println!();
+                println!("  {}", "Command Defaults".bold());
+                for (command, defaults) in &cfg.commands {
+                    let values: Vec<String> = defaults
+                        .iter()
+                        .map(|(key, value)| format!("{} = {}", key, value))
+                        .collect();
+                    println!("    {} {}", format!("{}:", command).dimmed(), values.join(", "));
+                }
+            }
+        }
+
+        ConfigAction::Validate => {
+            let path = config::config_file_path();
+            let cfg = match Config::load_checked() {
+                Ok(Some(cfg)) => cfg,
+                Ok(None) => {
+                    println!("No config file at {}; defaults are in use.", path);
+                    return;
+                }
+                Err(e) => {
+                    eprintln!("Error: {}", e);
+                    ExitCode::Failure.exit();
+                }
+            };
+            let problems = check_command_defaults(&cfg);
+            if problems.is_empty() {
+                println!("{} {}", "Config OK:".green(), path);
+                return;
+            }
+            eprintln!("{} {}", "Config problems in".red(), path);
+            for problem in &problems {
+                eprintln!("  {}", problem);
+            }
+            ExitCode::Failure.exit();
         }
 
         ConfigAction::AutoDetect => {
@@ -499,6 +2558,14 @@ async fn handle_config_command(action: &ConfigAction) {
             match geolocate::geolocate_by_ip().await {
                 Ok(geo) => {
                     let mut cfg = Config::load();
+                    if !accept_detected_location(&geo, &cfg, assume_yes) {
+                        if cfg.default_location().is_some() {
+                            println!("Kept the saved location.");
+                        } else {
+                            println!("Location not saved.");
+                        }
+                        return;
+                    }
                     cfg.set_location(
                         geo.lat,
                         geo.lng,
@@ -509,8 +2576,8 @@ async fn handle_config_command(action: &ConfigAction) {
                         Ok(()) => {
                             println!("Location auto-detected and saved:");
                             println!("  {} {}", "Location:".bold(), geo.description);
-                            println!("  {} {}", "Lat:".dimmed(), geo.lat);
-                            println!("  {} {}", "Lng:".dimmed(), geo.lng);
+                            println!("  {} {}", "Lat:".dimmed(), format_coord(geo.lat, precision));
+                            println!("  {} {}", "Lng:".dimmed(), format_coord(geo.lng, precision));
                             println!(
                                 "  {}",
                                 "Note: IP geolocation is approximate. Use `zupo config set-location` for exact coords."
@@ -519,13 +2586,13 @@ async fn handle_config_command(action: &ConfigAction) {
                         }
                         Err(e) => {
                             eprintln!("Error saving config: {}", e);
-                            process::exit(1);
+                            ExitCode::Failure.exit();
                         }
                     }
                 }
                 Err(e) => {
                     eprintln!("Error: {}", e);
-                    process::exit(1);
+                    ExitCode::Failure.exit();
                 }
             }
         }
@@ -537,26 +2604,135 @@ async fn handle_config_command(action: &ConfigAction) {
                 Ok(()) => println!("Default location cleared."),
                 Err(e) => {
                     eprintln!("Error: {}", e);
-                    process::exit(1);
+                    ExitCode::Failure.exit();
+                }
+            }
+        }
+
+        ConfigAction::Export { output } => {
+            let contents = match Config::load().to_toml() {
+                Ok(c) => c,
+                Err(e) => {
+                    eprintln!("Error: {}", e);
+                    ExitCode::Failure.exit();
+                }
+            };
+            match output {
+                Some(path) => {
+                    if let Err(e) = std::fs::write(path, contents) {
+                        eprintln!("Error: could not write {}: {}", path, e);
+                        ExitCode::Failure.exit();
+                    }
+                    eprintln!("Config exported to {}", path);
+                }
+                None => print!("{}", contents),
+            }
+        }
+
+        ConfigAction::Import { file, replace, .. } => {
+            let contents = if file == "-" {
+                let mut buf = String::new();
+                std::io::Read::read_to_string(&mut std::io::stdin(), &mut buf).map(|_| buf)
+            } else {
+                std::fs::read_to_string(file)
+            };
+            let contents = match contents {
+                Ok(c) => c,
+                Err(e) => {
+                    eprintln!("Error: could not read {}: {}", file, e);
+                    ExitCode::Failure.exit();
+                }
+            };
+
+            let imported = match Config::from_toml(&contents) {
+                Ok(c) => c,
+                Err(e) => {
+                    eprintln!("Error: {}", e);
+                    ExitCode::Usage.exit();
+                }
+            };
+
+            let cfg = if *replace {
+                imported
+            } else {
+                let mut cfg = Config::load();
+                cfg.merge(imported);
+                cfg
+            };
+
+            match Config::backup() {
+                Ok(Some(backup)) => eprintln!("Previous config backed up to {}", backup.display()),
+                Ok(None) => {}
+                Err(e) => {
+                    eprintln!("Error: {}", e);
+                    ExitCode::Failure.exit();
+                }
+            }
+
+            let saved = if *replace { cfg.save_replacing() } else { cfg.save() };
+            match saved {
+                Ok(()) => println!("Config imported to {}", config::config_file_path()),
+                Err(e) => {
+                    eprintln!("Error: {}", e);
+                    ExitCode::Failure.exit();
                 }
             }
         }
     }
 }
 
-async fn run_command(
-    client: &Client,
-    command: &Commands,
-    json_output: bool,
-    auto_locate: bool,
-    cfg: &Config,
-) -> Result<(), api::errors::Error> {
-    match command {
+/// Run an API command. Returns how many places it found, for the commands
+/// `--fail-on-empty` applies to
+async fn run_command(client: &Client, cli: &Cli, cfg: &Config) -> Result<Option<usize>, api::errors::Error> {
+    let json_output = cli.json;
+    let auto_locate = cli.auto_locate;
+    let assume_yes = cli.yes;
+    let units = cli.units.unwrap_or_else(|| cfg.units());
+    let precision = cli.precision;
+    let verbosity = render::Verbosity::from_verbose(cli.verbose);
+    let nf = NumberFormat::for_language(cli.command.language().or(cfg.defaults.language.as_deref()));
+    let addresses = address_choice(cli, cfg);
+    let mut found = None;
+
+    if json_output && cli.command.output_format() == Some(OutputFormat::Csv) {
+        return Err(csv_with_json());
+    }
+    if let (Some(flag), Some(format)) = (cli.command.format_ignored_with(), cli.command.requested_format()) {
+        if format != OutputFormat::Text {
+            let name = format.to_possible_value().map(|v| v.get_name().to_string()).unwrap_or_default();
+            eprintln!("{}", format!("--format {} is ignored with {}", name, flag).dimmed());
+        }
+    }
+
+    if cli.sort_by.is_some() {
+        match &cli.command {
+            Commands::Search { rank: RankMode::Smart, .. } | Commands::Nearby { rank: RankMode::Smart, .. } => {
+                return Err(api::errors::Error::Validation {
+                    field: "sort-by".into(),
+                    message: "can't be combined with --rank smart, which orders results itself".into(),
+                });
+            }
+            Commands::Search { .. }
+            | Commands::Nearby { .. }
+            | Commands::Route { group_by: RouteGrouping::Place, .. } => {}
+            other => eprintln!(
+                "{}",
+                format!(
+                    "--sort-by only applies to search, nearby, and route --group-by place; ignored for {}",
+                    other.name()
+                )
+                .dimmed()
+            ),
+        }
+    }
+
+    match &cli.command {
         Commands::Search {
             query,
             included_type,
             min_rating,
             price_level,
+            price,
             open_now,
             lat,
             lng,
@@ -564,39 +2740,142 @@ async fn run_command(
             limit,
             lang,
             region,
+            polygon,
+            diff,
+            format,
+            min_reviews,
+            open_for,
+            open_for_lenient,
+            dedupe_nearby,
+            max_distance,
+            rank,
+            count,
+            explain,
         } => {
-            let resolved = resolve_location(*lat, *lng, auto_locate, cfg).await;
-            let location = resolved.map(|(la, ln)| Circle {
-                center: LatLng {
-                    latitude: la,
-                    longitude: ln,
-                },
-                radius: resolve_radius(*radius, cfg, 5000.0),
-            });
+            // --explain replaces --format (which may come from ZUPO_FORMAT)
+            let format = if *explain { &OutputFormat::Text } else { format };
+            let area = polygon.as_deref().map(load_polygon).transpose()?;
+            let location = match area {
+                Some(ref area) => Some(area.bounding_circle()),
+                None => {
+                    let resolved = resolve_location(*lat, *lng, auto_locate, assume_yes, cfg, precision).await;
+                    resolved.map(|(la, ln)| Circle {
+                        center: LatLng {
+                            latitude: la,
+                            longitude: ln,
+                        },
+                        radius: resolve_radius(*radius, cfg, 5000.0),
+                    })
+                }
+            };
 
-            let price_levels: Vec<String> = price_level
+            let price_levels: Vec<String> = price
+                .as_ref()
+                .map_or(price_level.as_slice(), PriceSet::levels)
                 .iter()
                 .filter_map(|&p| price_level_to_api(p).map(String::from))
                 .collect();
 
             let req = SearchRequest {
-                query: query.clone(),
+                query: query_text(query.as_deref(), "query")?,
                 included_type: included_type.clone(),
                 min_rating: *min_rating,
                 price_levels,
-                open_now: *open_now,
+                open_now: *open_now || open_for.is_some(),
                 location,
-                limit: Some(*limit),
-                language: lang.clone(),
-                region: region.clone(),
+                limit: Some(if *count { *limit } else { effective_limit(*limit, MAX_SEARCH_RESULTS) }),
+                language: lang.clone().or_else(|| cfg.defaults.language.clone()),
+                region: region.clone().or_else(|| cfg.defaults.region.clone()),
+                include_photos: false,
             };
 
-            let resp = client.search(&req).await?;
+            if *count {
+                let counted = client.search_count(&req).await?;
+                print_count(&counted, json_output, cli.verbose);
+                return Ok(Some(counted.count));
+            }
 
-            if json_output {
+            let mut resp = client.search(&req).await?;
+            if let Some(ref area) = area {
+                trim_to_polygon(&mut resp.places, area);
+            }
+            if let Some(min) = min_reviews {
+                drop_below_min_reviews(&mut resp.places, *min);
+            }
+            if let Some(minutes) = open_for {
+                keep_open_for(&mut resp.places, *minutes, *open_for_lenient);
+            }
+            if let Some(meters) = dedupe_nearby {
+                resp.places = dedupe_places(std::mem::take(&mut resp.places), *meters, cli.verbose)?;
+            }
+            match (req.location.as_ref(), max_distance) {
+                (Some(bias), _) => {
+                    if let Some(meters) = max_distance.or(cfg.filters.max_distance) {
+                        drop_beyond_distance(&mut resp.places, &bias.center, meters)?;
+                    }
+                }
+                (None, Some(_)) => {
+                    return Err(api::errors::Error::Validation {
+                        field: "max-distance".into(),
+                        message: "needs a bias center: use --lat/--lng, a default location, or --auto-locate".into(),
+                    });
+                }
+                (None, None) => {}
+            }
+            sort_results(&mut resp.places, cli, req.location.as_ref().map(|c| &c.center))?;
+            found = Some(resp.places.len());
+            let far_check = req
+                .location
+                .as_ref()
+                .filter(|_| cfg.far_warning())
+                .map(|bias| geo::FarCheck::new(bias, cfg.far_factor()));
+            let far = far_check.as_ref().map(|check| (check, units));
+
+            if let Some(path) = diff {
+                print_places_diff(path, &resp.places, json_output, nf, addresses)?;
+            } else if *rank == RankMode::Smart {
+                let origin = req.location.as_ref().map(|c| ranking::Origin {
+                    center: c.center.clone(),
+                    decay_meters: c.radius,
+                });
+                let ranked = ranking::rank(
+                    resp.places,
+                    origin.as_ref(),
+                    &ranking::RankingParams::from_config(&cfg.ranking),
+                );
+                let explanations = explain.then(|| {
+                    explain::explain_places(ranked.iter().map(|(p, _)| p), &explain::Criteria::from_request(&req))
+                });
+                if json_output {
+                    print_ranked_json(&ranked, None, explanations.as_deref());
+                } else if *format != OutputFormat::Text {
+                    let places: Vec<Place> = ranked.into_iter().map(|(p, _)| p).collect();
+                    print_places_as(*format, &places, req.location.as_ref().map(|c| (&c.center, units)), nf);
+                } else {
+                    let explained = explanations.as_deref().map(|e| (e, units));
+                    render::render_ranked_places(&ranked, "Search Results", verbosity, far, explained, nf, addresses);
+                }
+            } else if *explain {
+                let explanations = explain::explain_places(&resp.places, &explain::Criteria::from_request(&req));
+                if json_output {
+                    print_explained_json(&resp.places, &explanations);
+                } else {
+                    render::render_explained_places(
+                        &resp.places,
+                        (&explanations, units),
+                        "Search Results",
+                        far,
+                        verbosity,
+                        nf,
+                        addresses,
+                    );
+                }
+            } else if json_output {
                 println!("{}", serde_json::to_string_pretty(&resp).unwrap());
+            } else if *format != OutputFormat::Text {
+                print_places_as(*format, &resp.places, req.location.as_ref().map(|c| (&c.center, units)), nf);
             } else {
-                render::render_places(&resp.places, "Search Results");
+                render::render_places(&resp.places, "Search Results", far, verbosity, nf, addresses);
             }
         }
 
@@ -609,8 +2888,12 @@ async fn run_command(
             limit,
             lang,
             region,
+            types,
+            only,
+            interactive,
+            then,
         } => {
-            let resolved = resolve_location(*lat, *lng, auto_locate, cfg).await;
+            let resolved = resolve_location(*lat, *lng, auto_locate, assume_yes, cfg, precision).await;
             let location = resolved.map(|(la, ln)| Circle {
                 center: LatLng {
                     latitude: la,
@@ -619,13 +2902,37 @@ async fn run_command(
                 radius: resolve_radius(*radius, cfg, 5000.0),
             });
 
+            let mut included_primary_types = types.clone();
+            if let Some(group) = only {
+                for t in group.primary_types() {
+                    if !included_primary_types.iter().any(|x| x == t) {
+                        included_primary_types.push(t.to_string());
+                    }
+                }
+            }
+
+            if *interactive {
+                let req = AutocompleteRequest {
+                    input: input.clone().unwrap_or_default(),
+                    // One session for the typing and the details lookup
+                    session_token: Some(session_token.clone().unwrap_or_else(|| uuid::Uuid::new_v4().to_string())),
+                    location,
+                    limit: Some(effective_limit(*limit, MAX_AUTOCOMPLETE_SUGGESTIONS)),
+                    language: lang.clone().or_else(|| cfg.defaults.language.clone()),
+                    region: region.clone().or_else(|| cfg.defaults.region.clone()),
+                    included_primary_types,
+                };
+                return pick_interactively(client, req, *then, cli, cfg).await;
+            }
+
             let req = AutocompleteRequest {
-                input: input.clone(),
+                input: query_text(input.as_deref(), "input")?,
                 session_token: session_token.clone(),
                 location,
-                limit: Some(*limit),
-                language: lang.clone(),
-                region: region.clone(),
+                limit: Some(effective_limit(*limit, MAX_AUTOCOMPLETE_SUGGESTIONS)),
+                language: lang.clone().or_else(|| cfg.defaults.language.clone()),
+                region: region.clone().or_else(|| cfg.defaults.region.clone()),
+                included_primary_types,
             };
 
             let resp = client.autocomplete(&req).await?;
@@ -643,16 +2950,108 @@ async fn run_command(
             radius,
             include_types,
             exclude_types,
+            include_primary_types,
+            exclude_primary_types,
             limit,
             lang,
             region,
+            polygon,
+            diff,
+            format,
+            min_reviews,
+            open_for,
+            open_for_lenient,
+            dedupe_nearby,
+            rank,
+            rank_by,
+            count,
+            at,
+            fallback_search,
         } => {
-            let resolved = resolve_location(*lat, *lng, auto_locate, cfg).await;
-            let (rlat, rlng) = resolved.ok_or_else(|| api::errors::Error::Validation {
-                field: "lat/lng".into(),
-                message: "location required: use --lat/--lng, set a default with `zupo config set-location`, or use --auto-locate".into(),
-            })?;
-            let rradius = resolve_radius(*radius, cfg, 1000.0);
+            if !at.is_empty() {
+                // Center and radius are filled in per location
+                let req = NearbySearchRequest {
+                    lat: 0.0,
+                    lng: 0.0,
+                    radius: resolve_radius(*radius, cfg, 1000.0),
+                    included_types: include_types.clone(),
+                    excluded_types: exclude_types.clone(),
+                    included_primary_types: include_primary_types.clone(),
+                    excluded_primary_types: exclude_primary_types.clone(),
+                    limit: Some(effective_limit(*limit, MAX_NEARBY_LIMIT)),
+                    language: lang.clone().or_else(|| cfg.defaults.language.clone()),
+                    region: region.clone().or_else(|| cfg.defaults.region.clone()),
+                    rank_preference: *rank_by,
+                };
+                let mut locations = Vec::new();
+                for name in at {
+                    if locations.iter().any(|(label, _)| label == name) {
+                        continue;
+                    }
+                    let (lat, lng, saved_radius) =
+                        cfg.named_location(name).ok_or_else(|| api::errors::Error::Validation {
+                            field: "at".into(),
+                            message: match cfg.location_names() {
+                                names if names.is_empty() => format!(
+                                    "no saved location named '{}'; add one under [locations.{}] in the config",
+                                    name, name
+                                ),
+                                names => format!("no saved location named '{}' (known: {})", name, names.join(", ")),
+                            },
+                        })?;
+                    let circle = Circle {
+                        center: LatLng {
+                            latitude: lat,
+                            longitude: lng,
+                        },
+                        radius: radius.unwrap_or(saved_radius),
+                    };
+                    locations.push((name.clone(), circle));
+                }
+
+                let circles: Vec<Circle> = locations.iter().map(|(_, c)| c.clone()).collect();
+                let results = client.nearby_search_at(&req, &circles).await?;
+                let mut labeled = Vec::with_capacity(results.len());
+                for ((label, circle), mut found_here) in locations.into_iter().zip(results) {
+                    if let Some(min) = min_reviews {
+                        drop_below_min_reviews(&mut found_here, *min);
+                    }
+                    if let Some(minutes) = open_for {
+                        keep_open_for(&mut found_here, *minutes, *open_for_lenient);
+                    }
+                    if let Some(meters) = dedupe_nearby {
+                        found_here = dedupe_places(found_here, *meters, cli.verbose)?;
+                    }
+                    sort_results(&mut found_here, cli, Some(&circle.center))?;
+                    labeled.push((label, circle, found_here));
+                }
+                let groups = dedupe::group_by_location(labeled);
+                found = Some(groups.iter().map(|g| g.places.len()).sum());
+
+                if json_output {
+                    let out = serde_json::json!({ "locations": groups });
+                    println!("{}", serde_json::to_string_pretty(&out).unwrap());
+                } else {
+                    render::render_location_groups(&groups, units, verbosity, nf, addresses);
+                }
+                return Ok(found);
+            }
+
+            let area = polygon.as_deref().map(load_polygon).transpose()?;
+            let (rlat, rlng, rradius) = match area {
+                Some(ref area) => {
+                    let circle = area.bounding_circle();
+                    (circle.center.latitude, circle.center.longitude, circle.radius)
+                }
+                None => {
+                    let resolved = resolve_location(*lat, *lng, auto_locate, assume_yes, cfg, precision).await;
+                    let (rlat, rlng) = resolved.ok_or_else(|| api::errors::Error::Validation {
+                        field: "lat/lng".into(),
+                        message: "location required: use --lat/--lng, set a default with `zupo config set-location`, or use --auto-locate".into(),
+                    })?;
+                    (rlat, rlng, resolve_radius(*radius, cfg, 1000.0))
+                }
+            };
 
             let req = NearbySearchRequest {
                 lat: rlat,
@@ -660,17 +3059,116 @@ async fn run_command(
                 radius: rradius,
                 included_types: include_types.clone(),
                 excluded_types: exclude_types.clone(),
-                limit: Some(*limit),
-                language: lang.clone(),
-                region: region.clone(),
+                included_primary_types: include_primary_types.clone(),
+                excluded_primary_types: exclude_primary_types.clone(),
+                limit: Some(if *count { *limit } else { effective_limit(*limit, MAX_NEARBY_LIMIT) }),
+                language: lang.clone().or_else(|| cfg.defaults.language.clone()),
+                region: region.clone().or_else(|| cfg.defaults.region.clone()),
+                rank_preference: *rank_by,
             };
 
-            let resp = client.nearby_search(&req).await?;
+            if *count {
+                let counted = client.nearby_count(&req).await?;
+                print_count(&counted, json_output, cli.verbose);
+                return Ok(Some(counted.count));
+            }
 
-            if json_output {
-                println!("{}", serde_json::to_string_pretty(&resp).unwrap());
+            let rings = client.nearby_search_rings(&req).await?;
+            report_rings(&rings, units, cli.verbose, nf);
+            let mut resp = rings.response;
+            let mut fallback_used = false;
+            if *fallback_search && resp.places.is_empty() {
+                match nearby_fallback_query(include_types, include_primary_types) {
+                    Some(query) => {
+                        eprintln!(
+                            "{}",
+                            format!("Nearby search found nothing; searching for \"{}\" instead", query).dimmed()
+                        );
+                        let search = SearchRequest {
+                            query,
+                            included_type: None,
+                            min_rating: None,
+                            price_levels: Vec::new(),
+                            open_now: false,
+                            location: Some(Circle {
+                                center: LatLng {
+                                    latitude: rlat,
+                                    longitude: rlng,
+                                },
+                                radius: rradius,
+                            }),
+                            limit: Some(req.limit.unwrap_or(MAX_SEARCH_RESULTS).min(MAX_SEARCH_RESULTS)),
+                            language: req.language.clone(),
+                            region: req.region.clone(),
+                            include_photos: false,
+                        };
+                        resp.places = client.search(&search).await?.places;
+                        fallback_used = true;
+                    }
+                    None => eprintln!(
+                        "{}",
+                        "--fallback-search needs --include-type or --include-primary-type; skipped".dimmed()
+                    ),
+                }
+            }
+            let label = if fallback_used {
+                "Nearby Places (expanded via text search)"
+            } else {
+                "Nearby Places"
+            };
+            if let Some(ref area) = area {
+                trim_to_polygon(&mut resp.places, area);
+            }
+            if let Some(min) = min_reviews {
+                drop_below_min_reviews(&mut resp.places, *min);
+            }
+            if let Some(minutes) = open_for {
+                keep_open_for(&mut resp.places, *minutes, *open_for_lenient);
+            }
+            if let Some(meters) = dedupe_nearby {
+                resp.places = dedupe_places(std::mem::take(&mut resp.places), *meters, cli.verbose)?;
+            }
+            let center = LatLng {
+                latitude: rlat,
+                longitude: rlng,
+            };
+            sort_results(&mut resp.places, cli, Some(&center))?;
+
+            found = Some(resp.places.len());
+
+            if let Some(path) = diff {
+                print_places_diff(path, &resp.places, json_output, nf, addresses)?;
+            } else if *rank == RankMode::Smart {
+                let origin = ranking::Origin {
+                    center: LatLng {
+                        latitude: rlat,
+                        longitude: rlng,
+                    },
+                    decay_meters: rradius,
+                };
+                let ranked = ranking::rank(
+                    resp.places,
+                    Some(&origin),
+                    &ranking::RankingParams::from_config(&cfg.ranking),
+                );
+                if json_output {
+                    print_ranked_json(&ranked, fallback_search.then_some(fallback_used), None);
+                } else if *format != OutputFormat::Text {
+                    let places: Vec<Place> = ranked.into_iter().map(|(p, _)| p).collect();
+                    print_places_as(*format, &places, Some((&center, units)), nf);
+                } else {
+                    render::render_ranked_places(&ranked, label, verbosity, None, None, nf, addresses);
+                }
+            } else if json_output {
+                let mut out = serde_json::to_value(&resp).unwrap();
+                if let (true, Some(obj)) = (*fallback_search, out.as_object_mut()) {
+                    obj.insert("fallback_used".into(), fallback_used.into());
+                }
+                println!("{}", serde_json::to_string_pretty(&out).unwrap());
+            } else if *format != OutputFormat::Text {
+                print_places_as(*format, &resp.places, Some((&center, units)), nf);
             } else {
-                render::render_places(&resp.places, "Nearby Places");
+                render::render_places(&resp.places, label, None, verbosity, nf, addresses);
             }
         }
 
@@ -682,65 +3180,224 @@ async fn run_command(
             radius,
             max_waypoints,
             limit,
+            alternatives,
+            route_index,
+            compare_routes,
+            group_by,
+            label_waypoints,
+            straight_line,
+            confirm_endpoints,
+            show_polyline,
+            show_path,
+            simplify,
             lang,
             region,
         } => {
-            let travel_mode: TravelMode = mode.parse().map_err(|msg: String| {
-                api::errors::Error::Validation {
-                    field: "mode".into(),
-                    message: msg,
-                }
-            })?;
-
-            let req = RouteRequest {
+            let mut req = RouteRequest {
                 query: query.clone(),
                 from: from.clone(),
                 to: to.clone(),
-                travel_mode,
+                travel_mode: *mode,
                 search_radius: *radius,
                 max_waypoints: *max_waypoints,
-                results_per_waypoint: *limit,
-                language: lang.clone(),
-                region: region.clone(),
+                results_per_waypoint: effective_limit(*limit, MAX_SEARCH_RESULTS),
+                language: lang.clone().or_else(|| cfg.defaults.language.clone()),
+                region: region.clone().or_else(|| cfg.defaults.region.clone()),
+                units,
+                alternatives: *alternatives || *compare_routes || *route_index > 0,
+                route_index: *route_index,
+                label_waypoints: *label_waypoints,
+                straight_line: *straight_line,
+                include_polyline: *show_polyline,
+                include_path: *show_path,
+                simplify_tolerance: *simplify,
+                from_resolved: None,
+                to_resolved: None,
             };
 
+            if *confirm_endpoints {
+                confirm_route_endpoints(client, &mut req).await?;
+            }
+
+            if *compare_routes {
+                let resps = client.route_compare(&req).await?;
+                found = Some(resps.iter().map(|r| r.places().count()).sum());
+                for resp in &resps {
+                    for warning in &resp.warnings {
+                        eprintln!("{} route {}: {}", "Warning:".yellow(), resp.route_index + 1, warning);
+                    }
+                }
+                if json_output {
+                    println!("{}", serde_json::to_string_pretty(&resps).unwrap());
+                } else {
+                    render::render_route_comparison(&resps, units, nf);
+                }
+                return Ok(found);
+            }
+
             let resp = client.route_search(&req).await?;
+            found = Some(resp.places().count());
+            resp.warnings.iter().for_each(print_warning);
+
+            if *group_by == RouteGrouping::Place {
+                let mut places = dedupe::places_along_route(&resp.waypoints);
+                match cli.sort_by {
+                    // Distance along the route: already in trip order
+                    None | Some(ranking::SortKey::Distance) => {
+                        if cli.reverse {
+                            places.reverse();
+                        }
+                    }
+                    Some(key) => ranking::sort_by_place(&mut places, |p| &p.place, key, None, cli.reverse),
+                }
+                found = Some(places.len());
+                if json_output {
+                    let mut out = serde_json::to_value(&resp).unwrap();
+                    out["places"] = serde_json::to_value(&places).unwrap();
+                    println!("{}", serde_json::to_string_pretty(&out).unwrap());
+                } else {
+                    render::render_route_places(&resp, &places, units, nf, addresses);
+                }
+                return Ok(found);
+            }
 
             if json_output {
                 println!("{}", serde_json::to_string_pretty(&resp).unwrap());
             } else {
-                render::render_route(&resp);
+                render::render_route(&resp, units, precision, nf);
             }
         }
 
         Commands::Details {
             place_id,
+            from_json: _,
             reviews,
             photos,
             show_photos,
+            force_show_photos,
+            photo_width,
+            photo_height,
             lang,
             region,
+            compare_lang,
+            format,
         } => {
-            let include_photos = *photos || *show_photos;
+            let show_photos = *show_photos || *force_show_photos;
+            let include_photos = *photos || show_photos;
             let req = DetailsRequest {
-                place_id: place_id.clone(),
+                place_id: place_id.clone().unwrap_or_default(),
                 include_reviews: *reviews,
                 include_photos,
-                language: lang.clone(),
-                region: region.clone(),
+                language: lang.clone().or_else(|| cfg.defaults.language.clone()),
+                region: region.clone().or_else(|| cfg.defaults.region.clone()),
             };
 
-            let resp = client.details(&req).await?;
+            if let Some(other) = compare_lang {
+                let other_req = DetailsRequest {
+                    language: Some(other.clone()),
+                    ..req.clone()
+                };
+                let (first, second) =
+                    tokio::join!(client.details(&req), client.details(&other_req));
+                let (first, second) = (first?, second?);
+                let first_lang = req.language.as_deref().unwrap_or("default");
+
+                if json_output {
+                    let mut map = serde_json::Map::new();
+                    map.insert(first_lang.to_string(), serde_json::to_value(&first).unwrap());
+                    map.insert(other.clone(), serde_json::to_value(&second).unwrap());
+                    println!("{}", serde_json::to_string_pretty(&map).unwrap());
+                } else {
+                    render::render_language_comparison(first_lang, &first, other, &second);
+                }
+                return Ok(found);
+            }
+
+            // Only text output shows "N km from you"; locate while the request runs
+            let text_output = !json_output && *format == OutputFormat::Text;
+            let (resp, here) = tokio::join!(client.details(&req), async {
+                if text_output {
+                    resolve_location(None, None, auto_locate, assume_yes, cfg, precision).await
+                } else {
+                    None
+                }
+            });
+            let resp = resp?;
 
+            let notes = notes::PlaceNotes::load_or_default(cfg);
+            // The request succeeded, so the ID normalizes
+            let noted_id = api::normalize_place_id(&req.place_id).unwrap_or_default();
+            let place_notes = notes.for_place(&noted_id);
             if json_output {
                 println!("{}", serde_json::to_string_pretty(&resp).unwrap());
+            } else if *format == OutputFormat::Markdown {
+                print!("{}", render::markdown::place_details(&resp, place_notes));
+            } else if *format != OutputFormat::Text {
+                print_places_as(*format, std::slice::from_ref(&resp), None, nf);
             } else {
-                let photo_images = if *show_photos {
-                    fetch_place_photo_images(client, &resp).await
-                } else {
-                    None
-                };
-                render::render_place_details(&resp, photo_images.as_deref());
+                let here = here.map(|(latitude, longitude)| LatLng { latitude, longitude });
+                let origin = here.as_ref().map(|h| (h, units));
+                // Text first, so a slow photo never holds up the details
+                render::render_place_details(&resp, precision, cfg.hours_start_today(), verbosity, origin, place_notes, nf);
+                if show_photos {
+                    let size = photo_size(*photo_width, *photo_height, cfg);
+                    let unavailable = render::inline_images_unavailable().filter(|_| !*force_show_photos);
+                    show_place_photos(client, &resp, size, unavailable).await;
+                }
+            }
+        }
+
+        Commands::Reviews {
+            place_id,
+            sort,
+            min_rating,
+            max,
+            full,
+            lang,
+            region,
+        } => {
+            if let Some(r) = min_rating {
+                if !(1.0..=5.0).contains(r) {
+                    return Err(api::errors::Error::Validation {
+                        field: "min_rating".into(),
+                        message: format!("must be between 1 and 5, got {}", r),
+                    });
+                }
+            }
+            let req = ReviewsRequest {
+                place_id: place_id.clone(),
+                language: lang.clone().or_else(|| cfg.defaults.language.clone()),
+                region: region.clone().or_else(|| cfg.defaults.region.clone()),
+            };
+
+            let place = client.reviews(&req).await?;
+            let mut reviews = place.reviews.clone().unwrap_or_default();
+            if let Some(min) = min_rating {
+                reviews.retain(|r| r.rating.is_some_and(|rating| rating >= *min));
+            }
+            match sort {
+                Some(ReviewSort::Newest) => {
+                    // RFC 3339 UTC timestamps order correctly as text
+                    reviews.sort_by(|a, b| b.publish_time.cmp(&a.publish_time));
+                }
+                Some(ReviewSort::Highest) => {
+                    reviews.sort_by(|a, b| b.rating.unwrap_or(0.0).total_cmp(&a.rating.unwrap_or(0.0)));
+                }
+                Some(ReviewSort::Lowest) => {
+                    reviews.sort_by(|a, b| a.rating.unwrap_or(0.0).total_cmp(&b.rating.unwrap_or(0.0)));
+                }
+                None => {}
+            }
+            reviews.truncate(*max);
+
+            if json_output {
+                println!("{}", serde_json::to_string_pretty(&reviews).unwrap());
+            } else {
+                let width = crossterm::terminal::size()
+                    .map(|(cols, _)| cols as usize)
+                    .unwrap_or(80)
+                    .min(REVIEW_TEXT_WIDTH);
+                pager::page(&render::format_reviews(&place, &reviews, *full, width, nf));
             }
         }
 
@@ -749,19 +3406,43 @@ async fn run_command(
             max_width,
             max_height,
             show,
+            force_show,
+            photo_width,
+            photo_height,
         } => {
+            let show = *show || *force_show;
+            let unavailable = render::inline_images_unavailable().filter(|_| !*force_show);
+            let size = photo_size(*photo_width, *photo_height, cfg);
+            // Without explicit pixel bounds, download about what will be drawn
+            let (max_width, max_height) = if max_width.is_none() && max_height.is_none() && show && unavailable.is_none() {
+                size.request_px(None)
+            } else {
+                (*max_width, *max_height)
+            };
             let req = PhotoMediaRequest {
                 name: name.clone(),
-                max_width: *max_width,
-                max_height: *max_height,
+                max_width,
+                max_height,
             };
 
-            let resp = client.photo_media(&req).await?;
+            // The media endpoint doesn't return the photo's authors, which
+            // must be shown with it; the place's photo list has them
+            let (resp, context) = tokio::join!(client.photo_media(&req), client.photo_context(name));
+            let mut resp = resp?;
+            match context {
+                Ok(Some(context)) => resp.context = context,
+                Ok(None) => {}
+                Err(e) => eprintln!(
+                    "{} could not look up the photo's attribution: {}",
+                    "Warning:".yellow(),
+                    e
+                ),
+            }
 
             if json_output {
                 println!("{}", serde_json::to_string_pretty(&resp).unwrap());
             } else {
-                let image_bytes = if *show && !resp.photo_uri.is_empty() {
+                let image_bytes = if show && !resp.photo_uri.is_empty() {
                     match client.download_bytes(&resp.photo_uri).await {
                         Ok(bytes) => Some(bytes),
                         Err(e) => {
@@ -772,7 +3453,7 @@ async fn run_command(
                 } else {
                     None
                 };
-                render::render_photo(&resp, image_bytes.as_deref());
+                render::render_photo(&resp, image_bytes.as_deref(), size, unavailable);
             }
         }
 
@@ -781,56 +3462,179 @@ async fn run_command(
             limit,
             lang,
             region,
+            format,
         } => {
             let req = ResolveRequest {
                 location: location.clone(),
-                limit: Some(*limit),
-                language: lang.clone(),
-                region: region.clone(),
+                limit: Some(effective_limit(*limit, MAX_RESOLVE_RESULTS)),
+                language: lang.clone().or_else(|| cfg.defaults.language.clone()),
+                region: region.clone().or_else(|| cfg.defaults.region.clone()),
             };
 
             let resp = client.resolve(&req).await?;
+            found = Some(resp.places.len());
 
             if json_output {
                 println!("{}", serde_json::to_string_pretty(&resp).unwrap());
+            } else if *format != OutputFormat::Text {
+                print_places_as(*format, &resp.places, None, nf);
             } else {
-                render::render_places(&resp.places, "Resolved Places");
+                render::render_places(&resp.places, "Resolved Places", None, verbosity, nf, addresses);
             }
         }
 
-        Commands::Config { .. } | Commands::Tui => unreachable!(),
+        Commands::Monitor { .. }
+        | Commands::Config { .. }
+        | Commands::Note { .. }
+        | Commands::Stats { .. }
+        | Commands::Types { .. }
+        | Commands::Tui
+        | Commands::Serve { .. }
+        | Commands::Examples { .. } => {
+            unreachable!()
+        }
     }
 
-    Ok(())
+    Ok(found)
+}
+
+/// `autocomplete --interactive`: prompt for a place, then print its ID (or
+/// the suggestion with --json), or its details with `--then details`
+async fn pick_interactively(
+    client: &Client,
+    req: AutocompleteRequest,
+    then: Option<PickThen>,
+    cli: &Cli,
+    cfg: &Config,
+) -> Result<Option<usize>, api::errors::Error> {
+    let language = req.language.clone();
+    let nf = NumberFormat::for_language(language.as_deref());
+    let region = req.region.clone();
+    let origin = req.location.as_ref().map(|c| c.center.clone());
+    let picked = cli_interactive::pick_place(client, req)
+        .await
+        .map_err(|e| api::errors::Error::Validation {
+            field: "interactive".into(),
+            message: e.to_string(),
+        })?;
+    let suggestion = match picked {
+        cli_interactive::Outcome::Picked(suggestion) => suggestion,
+        cli_interactive::Outcome::Aborted => ExitCode::Interrupted.exit(),
+    };
+    let place_id = suggestion
+        .place_prediction
+        .as_ref()
+        .and_then(|p| p.place_id.clone())
+        .unwrap_or_default();
+
+    match then {
+        None if cli.json => println!("{}", serde_json::to_string_pretty(&suggestion).unwrap()),
+        None => println!("{}", place_id),
+        Some(PickThen::Details) => {
+            let place = client
+                .details(&DetailsRequest {
+                    place_id: place_id.clone(),
+                    include_reviews: false,
+                    include_photos: false,
+                    language,
+                    region,
+                })
+                .await?;
+            if cli.json {
+                println!("{}", serde_json::to_string_pretty(&place).unwrap());
+            } else {
+                let notes = notes::PlaceNotes::load_or_default(cfg);
+                let units = cli.units.unwrap_or_else(|| cfg.units());
+                render::render_place_details(
+                    &place,
+                    cli.precision,
+                    cfg.hours_start_today(),
+                    render::Verbosity::from_verbose(cli.verbose),
+                    origin.as_ref().map(|o| (o, units)),
+                    notes.for_place(&place_id),
+                    nf,
+                );
+            }
+        }
+    }
+    Ok(Some(1))
 }
 
-/// Fetch up to 3 place photos as raw image bytes for inline display
-async fn fetch_place_photo_images(client: &Client, place: &Place) -> Option<Vec<Vec<u8>>> {
-    let photos = place.photos.as_ref()?;
-    if photos.is_empty() {
-        return None;
+/// Photos previewed by `details --show-photos`
+const PREVIEW_PHOTOS: usize = 3;
+
+/// How long one photo preview may take (URL lookup plus download) before it is skipped
+const PHOTO_PREVIEW_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Inline photo box: `--photo-width`/`--photo-height` over config and the
+/// terminal-sized default
+fn photo_size(width: Option<u32>, height: Option<u32>, cfg: &Config) -> PhotoSize {
+    let base = cfg.photo_size();
+    PhotoSize {
+        width: width.unwrap_or(base.width),
+        height: height.unwrap_or(base.height),
     }
+}
 
-    let mut images = Vec::new();
-    for photo in photos.iter().take(3) {
-        let req = PhotoMediaRequest {
-            name: photo.name.clone(),
-            max_width: Some(400),
-            max_height: None,
-        };
+/// Fetch up to `PREVIEW_PHOTOS` place photos concurrently and print each
+/// preview as soon as it arrives; failed or slow photos are skipped with a
+/// note. When `unavailable` gives a reason images can't be drawn, the photos
+/// are only listed by size and nothing is fetched.
+async fn show_place_photos(client: &Client, place: &Place, size: PhotoSize, unavailable: Option<&str>) {
+    let Some(photos) = place.photos.as_ref().filter(|p| !p.is_empty()) else {
+        return;
+    };
+    let total = photos.len().min(PREVIEW_PHOTOS);
+    if let Some(reason) = unavailable {
+        render::render_photo_previews_heading();
+        render::render_photo_previews_skipped(&photos[..total], reason);
+        return;
+    }
 
-        if let Ok(resp) = client.photo_media(&req).await {
-            if !resp.photo_uri.is_empty() {
-                if let Ok(bytes) = client.download_bytes(&resp.photo_uri).await {
-                    images.push(bytes);
-                }
-            }
+    let mut pending: FuturesUnordered<_> = photos
+        .iter()
+        .take(total)
+        .enumerate()
+        .map(|(i, photo)| async move {
+            let (max_width, max_height) = size.request_px(photo.width_px.zip(photo.height_px));
+            let fetch = fetch_photo_bytes(client, &photo.name, max_width, max_height);
+            let context = PhotoContext::from(photo);
+            let result = tokio::time::timeout(PHOTO_PREVIEW_TIMEOUT, fetch)
+                .await
+                .unwrap_or_else(|_| {
+                    Err(format!("timed out after {}", format_elapsed(PHOTO_PREVIEW_TIMEOUT)))
+                });
+            (i + 1, result, context)
+        })
+        .collect();
+
+    render::render_photo_previews_heading();
+    while let Some((index, result, context)) = pending.next().await {
+        match result {
+            Ok(bytes) => render::render_photo_preview(index, total, &bytes, size, &context),
+            Err(e) => eprintln!("{}", format!("Photo {}/{} skipped: {}", index, total, e).dimmed()),
         }
     }
+}
 
-    if images.is_empty() {
-        None
-    } else {
-        Some(images)
+/// Look up a photo's URL and download it within the given pixel bounds
+async fn fetch_photo_bytes(
+    client: &Client,
+    name: &str,
+    max_width: Option<u32>,
+    max_height: Option<u32>,
+) -> Result<Vec<u8>, String> {
+    let req = PhotoMediaRequest {
+        name: name.to_string(),
+        max_width,
+        max_height,
+    };
+    let resp = client.photo_media(&req).await.map_err(|e| e.to_string())?;
+    if resp.photo_uri.is_empty() {
+        return Err("no photo URL in the response".into());
     }
+    client
+        .download_bytes(&resp.photo_uri)
+        .await
+        .map_err(|e| e.to_string())
 }
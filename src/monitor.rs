@@ -0,0 +1,211 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use tokio::io::AsyncWriteExt;
+
+use crate::api::types::Place;
+use crate::config::Config;
+use crate::diff::{Change, PlacesDiff};
+use crate::paths;
+use crate::usage::civil_from_days;
+
+/// Shortest `--interval`, so a typo can't turn into a request every second
+pub const MIN_INTERVAL_SECS: u64 = 60;
+
+/// Business statuses that count as a closure
+const CLOSED_STATUSES: &[&str] = &["CLOSED_TEMPORARILY", "CLOSED_PERMANENTLY"];
+
+/// Kinds of change `zupo monitor` reports (`--notify-on`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum NotifyOn {
+    /// Places that weren't in the previous results
+    New,
+    /// Places that dropped out of the results
+    Removed,
+    /// Ratings that moved by at least `--rating-threshold`
+    Rating,
+    /// Places that became temporarily or permanently closed
+    Closed,
+}
+
+/// The last results of a monitored query. The `places` key keeps it readable
+/// by `search --diff`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Snapshot {
+    pub query: String,
+    /// Seconds since the Unix epoch
+    pub taken_at: u64,
+    pub places: Vec<Place>,
+}
+
+impl Snapshot {
+    pub fn new(query: &str, places: Vec<Place>) -> Self {
+        Snapshot {
+            query: query.to_string(),
+            taken_at: now_secs(),
+            places,
+        }
+    }
+
+    /// Read a snapshot; Ok(None) when there isn't one yet
+    pub fn load(path: &Path) -> Result<Option<Self>, String> {
+        match fs::read_to_string(path) {
+            Ok(contents) => serde_json::from_str(&contents)
+                .map(Some)
+                .map_err(|e| format!("could not parse snapshot {}: {}", path.display(), e)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(format!("could not read snapshot {}: {}", path.display(), e)),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let write = || -> std::io::Result<()> {
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            let contents = serde_json::to_string_pretty(self).map_err(std::io::Error::other)?;
+            let tmp = path.with_extension(format!("json.tmp.{}", std::process::id()));
+            fs::write(&tmp, contents)?;
+            fs::rename(&tmp, path).inspect_err(|_| {
+                let _ = fs::remove_file(&tmp);
+            })
+        };
+        write().map_err(|e| format!("could not write snapshot {}: {}", path.display(), e))
+    }
+}
+
+/// Default snapshot file for a monitored search: a readable slug of the query
+/// plus a hash of every parameter that changes the results, so two monitors
+/// of the same query in different places don't share a file
+pub fn snapshot_path(cfg: &Config, query: &str, params: &str) -> Option<PathBuf> {
+    let slug: String = query
+        .to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .collect::<Vec<_>>()
+        .join("-")
+        .chars()
+        .take(40)
+        .collect();
+    let name = format!("{}-{:016x}.json", slug, fnv1a(params.as_bytes()));
+    paths::state_dir(&cfg.paths).map(|d| d.join("monitor").join(name))
+}
+
+/// FNV-1a: a stable hash for file names (std's hasher may change between releases)
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &b| {
+        (hash ^ b as u64).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+/// The part of `diff` worth a notification: the kinds in `notify`, rating
+/// moves of at least `rating_threshold`, and status changes into a closure.
+/// Review-count changes are left out; they change on almost every check.
+pub fn notable_changes(diff: PlacesDiff, notify: &[NotifyOn], rating_threshold: f64) -> PlacesDiff {
+    let wants = |kind| notify.contains(&kind);
+    let changed = diff
+        .changed
+        .into_iter()
+        .filter_map(|mut change| {
+            change.user_rating_count = None;
+            change.rating = change
+                .rating
+                .filter(|c| wants(NotifyOn::Rating) && rating_moved(c, rating_threshold));
+            change.business_status = change
+                .business_status
+                .filter(|c| wants(NotifyOn::Closed) && became_closed(c));
+            (change.rating.is_some() || change.business_status.is_some()).then_some(change)
+        })
+        .collect();
+    PlacesDiff {
+        added: if wants(NotifyOn::New) { diff.added } else { Vec::new() },
+        removed: if wants(NotifyOn::Removed) { diff.removed } else { Vec::new() },
+        changed,
+    }
+}
+
+fn rating_moved(change: &Change<f64>, threshold: f64) -> bool {
+    match (change.before, change.after) {
+        (Some(before), Some(after)) => (after - before).abs() >= threshold - f64::EPSILON,
+        _ => false,
+    }
+}
+
+fn became_closed(change: &Change<String>) -> bool {
+    change
+        .after
+        .as_deref()
+        .is_some_and(|status| CLOSED_STATUSES.contains(&status))
+}
+
+/// Keep the previous rating in the new snapshot for places whose rating moved
+/// without being reported, so slow drifts add up until they cross the
+/// threshold instead of being compared one small step at a time
+pub fn carry_unreported_ratings(current: &mut [Place], previous: &[Place], reported: &PlacesDiff) {
+    for place in current.iter_mut() {
+        let was_reported = reported
+            .changed
+            .iter()
+            .any(|c| c.id == place.id && c.rating.is_some());
+        if was_reported {
+            continue;
+        }
+        if let Some(old) = previous.iter().find(|p| p.id == place.id) {
+            if old.rating.is_some() && place.rating.is_some() {
+                place.rating = old.rating;
+            }
+        }
+    }
+}
+
+/// Run `--exec`'s command through the shell with the changes as JSON on
+/// stdin and `ZUPO_MONITOR_QUERY` set. Waits for it to exit.
+pub async fn run_hook(command: &str, changes_json: &str, query: &str) -> Result<(), String> {
+    let (shell, flag) = if cfg!(windows) { ("cmd", "/C") } else { ("sh", "-c") };
+    let mut child = tokio::process::Command::new(shell)
+        .arg(flag)
+        .arg(command)
+        .env("ZUPO_MONITOR_QUERY", query)
+        .stdin(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("could not run --exec command: {}", e))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        // A hook that ignores its input may exit before reading it all
+        let _ = stdin.write_all(changes_json.as_bytes()).await;
+    }
+    let status = child
+        .wait()
+        .await
+        .map_err(|e| format!("--exec command failed: {}", e))?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(format!("--exec command exited with {}", status))
+    }
+}
+
+pub fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// "YYYY-MM-DDTHH:MM:SSZ" for seconds since the Unix epoch
+pub fn format_timestamp(secs: u64) -> String {
+    let (year, month, day) = civil_from_days((secs / 86_400) as i64);
+    let rem = secs % 86_400;
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        year,
+        month,
+        day,
+        rem / 3600,
+        rem % 3600 / 60,
+        rem % 60
+    )
+}
@@ -0,0 +1,109 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::Config;
+use crate::paths;
+use crate::usage::civil_from_days;
+
+/// A personal annotation on a place. Notes stay on this machine; they are
+/// never sent to any API.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Note {
+    pub text: String,
+    /// Seconds since the Unix epoch
+    pub created_at: u64,
+}
+
+impl Note {
+    /// Creation date as "YYYY-MM-DD" (UTC)
+    pub fn date(&self) -> String {
+        let (year, month, day) = civil_from_days((self.created_at / 86_400) as i64);
+        format!("{:04}-{:02}-{:02}", year, month, day)
+    }
+}
+
+/// Notes by place ID, each place's in the order they were added
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PlaceNotes {
+    #[serde(default)]
+    pub places: BTreeMap<String, Vec<Note>>,
+}
+
+impl PlaceNotes {
+    /// Read the notes file; a missing file means no notes yet
+    pub fn load(cfg: &Config) -> Result<Self, String> {
+        let Some(path) = notes_path(cfg) else {
+            return Ok(Self::default());
+        };
+        match fs::read_to_string(&path) {
+            Ok(contents) => serde_json::from_str(&contents)
+                .map_err(|e| format!("could not parse {}: {}", path.display(), e)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(format!("could not read {}: {}", path.display(), e)),
+        }
+    }
+
+    /// Notes for rendering: an unreadable file shows as no notes rather
+    /// than failing the command
+    pub fn load_or_default(cfg: &Config) -> Self {
+        Self::load(cfg).unwrap_or_default()
+    }
+
+    pub fn for_place(&self, place_id: &str) -> &[Note] {
+        self.places.get(place_id).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    pub fn add(&mut self, place_id: &str, text: &str) {
+        let created_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        self.places.entry(place_id.to_string()).or_default().push(Note {
+            text: text.to_string(),
+            created_at,
+        });
+    }
+
+    /// Remove a place's `number`th note (1-based, as `note list` shows it)
+    pub fn remove(&mut self, place_id: &str, number: usize) -> Option<Note> {
+        let notes = self.places.get_mut(place_id)?;
+        if number == 0 || number > notes.len() {
+            return None;
+        }
+        let note = notes.remove(number - 1);
+        if notes.is_empty() {
+            self.places.remove(place_id);
+        }
+        Some(note)
+    }
+
+    /// Remove every note on a place; returns how many there were
+    pub fn remove_all(&mut self, place_id: &str) -> usize {
+        self.places.remove(place_id).map(|n| n.len()).unwrap_or(0)
+    }
+
+    pub fn save(&self, cfg: &Config) -> Result<(), String> {
+        let path = notes_path(cfg).ok_or("could not determine the state directory")?;
+        let write = || -> std::io::Result<()> {
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            let contents = serde_json::to_string_pretty(self).map_err(std::io::Error::other)?;
+            let tmp = path.with_extension(format!("json.tmp.{}", std::process::id()));
+            fs::write(&tmp, contents)?;
+            fs::rename(&tmp, &path).inspect_err(|_| {
+                let _ = fs::remove_file(&tmp);
+            })
+        };
+        write().map_err(|e| format!("could not write {}: {}", path.display(), e))
+    }
+}
+
+pub fn notes_path(cfg: &Config) -> Option<PathBuf> {
+    paths::state_dir(&cfg.paths).map(|d| d.join("notes.json"))
+}
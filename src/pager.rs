@@ -0,0 +1,40 @@
+use std::io::{IsTerminal, Write};
+use std::process::{Command, Stdio};
+
+/// Print `text`, through `$PAGER` (default `less -R`) when stdout is a
+/// terminal and the text is taller than it. Falls back to printing directly
+/// when the pager can't be started.
+pub fn page(text: &str) {
+    let rows = crossterm::terminal::size().map(|(_, rows)| rows as usize).unwrap_or(0);
+    if !std::io::stdout().is_terminal() || rows == 0 || text.lines().count() < rows {
+        print!("{}", text);
+        return;
+    }
+
+    let pager = std::env::var("PAGER")
+        .ok()
+        .filter(|p| !p.trim().is_empty())
+        .unwrap_or_else(|| "less -R".to_string());
+    let mut parts = pager.split_whitespace();
+    let Some(program) = parts.next() else {
+        print!("{}", text);
+        return;
+    };
+
+    let child = Command::new(program)
+        .args(parts)
+        .stdin(Stdio::piped())
+        .spawn();
+    let mut child = match child {
+        Ok(c) => c,
+        Err(_) => {
+            print!("{}", text);
+            return;
+        }
+    };
+    if let Some(mut stdin) = child.stdin.take() {
+        // The reader quitting early closes the pipe; that's not an error
+        let _ = stdin.write_all(text.as_bytes());
+    }
+    let _ = child.wait();
+}
@@ -0,0 +1,81 @@
+use std::path::PathBuf;
+
+use crate::config::PathsConfig;
+
+const APP_NAME: &str = "zupo";
+
+/// Directory holding config.toml.
+/// `ZUPO_CONFIG_DIR` > `$XDG_CONFIG_HOME/zupo` > platform config dir
+pub fn config_dir() -> Option<PathBuf> {
+    resolve("ZUPO_CONFIG_DIR", None, "XDG_CONFIG_HOME", dirs::config_dir())
+}
+
+/// Directory for cached API responses.
+/// `ZUPO_CACHE_DIR` > `paths.cache_dir` > `$XDG_CACHE_HOME/zupo` > platform cache dir
+pub fn cache_dir(cfg: &PathsConfig) -> Option<PathBuf> {
+    resolve(
+        "ZUPO_CACHE_DIR",
+        cfg.cache_dir.as_deref(),
+        "XDG_CACHE_HOME",
+        dirs::cache_dir(),
+    )
+}
+
+/// Directory for history and session state.
+/// `ZUPO_STATE_DIR` > `paths.state_dir` > `$XDG_STATE_HOME/zupo` > platform state
+/// dir (local data dir on macOS/Windows, which have no state dir)
+pub fn state_dir(cfg: &PathsConfig) -> Option<PathBuf> {
+    resolve(
+        "ZUPO_STATE_DIR",
+        cfg.state_dir.as_deref(),
+        "XDG_STATE_HOME",
+        dirs::state_dir().or_else(dirs::data_local_dir),
+    )
+}
+
+/// Directory for log files (the TUI log).
+/// `ZUPO_LOG_DIR` > `paths.log_dir` > the state dir
+pub fn log_dir(cfg: &PathsConfig) -> Option<PathBuf> {
+    if let Some(dir) = env_dir("ZUPO_LOG_DIR") {
+        return Some(dir);
+    }
+    if let Some(dir) = cfg.log_dir.as_deref() {
+        return Some(expand_home(dir));
+    }
+    state_dir(cfg)
+}
+
+/// Precedence: explicit env override > config value > XDG base dir > platform default.
+/// The app name is appended to XDG and platform dirs, not to overrides.
+fn resolve(
+    env_var: &str,
+    configured: Option<&str>,
+    xdg_var: &str,
+    platform: Option<PathBuf>,
+) -> Option<PathBuf> {
+    if let Some(dir) = env_dir(env_var) {
+        return Some(dir);
+    }
+    if let Some(dir) = configured {
+        return Some(expand_home(dir));
+    }
+    // The XDG spec says relative paths must be ignored
+    if let Some(base) = env_dir(xdg_var).filter(|p| p.is_absolute()) {
+        return Some(base.join(APP_NAME));
+    }
+    platform.map(|d| d.join(APP_NAME))
+}
+
+fn env_dir(var: &str) -> Option<PathBuf> {
+    std::env::var_os(var)
+        .filter(|v| !v.is_empty())
+        .map(PathBuf::from)
+}
+
+/// Expand a leading `~/` to the home directory
+fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix("~/"), dirs::home_dir()) {
+        (Some(rest), Some(home)) => home.join(rest),
+        _ => PathBuf::from(path),
+    }
+}
@@ -0,0 +1,409 @@
+/// Google Places API (New) — Table A types valid for `includedType` and the
+/// nearby `includedTypes`/`includedPrimaryTypes` filters, grouped by category.
+/// https://developers.google.com/maps/documentation/places/web-service/place-types
+pub static PLACE_TYPE_CATEGORIES: &[(&str, &[&str])] = &[
+    (
+        "Food and Drink",
+        &[
+            "acai_shop",
+            "afghani_restaurant",
+            "african_restaurant",
+            "american_restaurant",
+            "argentinian_restaurant",
+            "asian_fusion_restaurant",
+            "asian_restaurant",
+            "australian_restaurant",
+            "austrian_restaurant",
+            "bagel_shop",
+            "bakery",
+            "bangladeshi_restaurant",
+            "bar",
+            "bar_and_grill",
+            "barbecue_restaurant",
+            "basque_restaurant",
+            "bavarian_restaurant",
+            "beer_garden",
+            "belgian_restaurant",
+            "bistro",
+            "brazilian_restaurant",
+            "breakfast_restaurant",
+            "brewery",
+            "brewpub",
+            "british_restaurant",
+            "brunch_restaurant",
+            "buffet_restaurant",
+            "burmese_restaurant",
+            "burrito_restaurant",
+            "cafe",
+            "cafeteria",
+            "cajun_restaurant",
+            "cake_shop",
+            "californian_restaurant",
+            "cambodian_restaurant",
+            "candy_store",
+            "cantonese_restaurant",
+            "caribbean_restaurant",
+            "cat_cafe",
+            "chicken_restaurant",
+            "chicken_wings_restaurant",
+            "chilean_restaurant",
+            "chinese_noodle_restaurant",
+            "chinese_restaurant",
+            "chocolate_factory",
+            "chocolate_shop",
+            "cocktail_bar",
+            "coffee_roastery",
+            "coffee_shop",
+            "coffee_stand",
+            "colombian_restaurant",
+            "confectionery",
+            "croatian_restaurant",
+            "cuban_restaurant",
+            "czech_restaurant",
+            "danish_restaurant",
+            "deli",
+            "dessert_restaurant",
+            "dessert_shop",
+            "dim_sum_restaurant",
+            "diner",
+            "dog_cafe",
+            "donut_shop",
+            "dumpling_restaurant",
+            "dutch_restaurant",
+            "eastern_european_restaurant",
+            "ethiopian_restaurant",
+            "european_restaurant",
+            "falafel_restaurant",
+            "family_restaurant",
+            "fast_food_restaurant",
+            "filipino_restaurant",
+            "fine_dining_restaurant",
+            "fish_and_chips_restaurant",
+            "fondue_restaurant",
+            "food_court",
+            "french_restaurant",
+            "fusion_restaurant",
+            "gastropub",
+            "german_restaurant",
+            "greek_restaurant",
+            "gyro_restaurant",
+            "halal_restaurant",
+            "hamburger_restaurant",
+            "hawaiian_restaurant",
+            "hookah_bar",
+            "hot_dog_restaurant",
+            "hot_pot_restaurant",
+            "hungarian_restaurant",
+            "ice_cream_shop",
+            "indian_restaurant",
+            "indonesian_restaurant",
+            "irish_pub",
+            "irish_restaurant",
+            "israeli_restaurant",
+            "italian_restaurant",
+            "japanese_curry_restaurant",
+            "japanese_izakaya_restaurant",
+            "japanese_restaurant",
+            "juice_shop",
+            "kebab_shop",
+            "korean_barbecue_restaurant",
+            "korean_restaurant",
+            "latin_american_restaurant",
+            "lebanese_restaurant",
+            "lounge_bar",
+            "malaysian_restaurant",
+            "meal_delivery",
+            "meal_takeaway",
+            "mediterranean_restaurant",
+            "mexican_restaurant",
+            "middle_eastern_restaurant",
+            "mongolian_barbecue_restaurant",
+            "moroccan_restaurant",
+            "noodle_shop",
+            "north_indian_restaurant",
+            "oyster_bar_restaurant",
+            "pakistani_restaurant",
+            "pastry_shop",
+            "persian_restaurant",
+            "peruvian_restaurant",
+            "pizza_delivery",
+            "pizza_restaurant",
+            "polish_restaurant",
+            "portuguese_restaurant",
+            "pub",
+            "ramen_restaurant",
+            "restaurant",
+            "romanian_restaurant",
+            "russian_restaurant",
+            "salad_shop",
+            "sandwich_shop",
+            "scandinavian_restaurant",
+            "seafood_restaurant",
+            "shawarma_restaurant",
+            "snack_bar",
+            "soul_food_restaurant",
+            "soup_restaurant",
+            "south_american_restaurant",
+            "south_indian_restaurant",
+            "southwestern_us_restaurant",
+            "spanish_restaurant",
+            "sports_bar",
+            "sri_lankan_restaurant",
+            "steak_house",
+            "sushi_restaurant",
+            "swiss_restaurant",
+            "taco_restaurant",
+            "taiwanese_restaurant",
+            "tapas_restaurant",
+            "tea_house",
+            "tex_mex_restaurant",
+            "thai_restaurant",
+            "tibetan_restaurant",
+            "tonkatsu_restaurant",
+            "turkish_restaurant",
+            "ukrainian_restaurant",
+            "vegan_restaurant",
+            "vegetarian_restaurant",
+            "vietnamese_restaurant",
+            "wine_bar",
+            "winery",
+            "yakiniku_restaurant",
+            "yakitori_restaurant",
+        ],
+    ),
+    (
+        "Shopping",
+        &[
+            "farmers_market",
+            "flea_market",
+            "gift_shop",
+            "grocery_store",
+            "hardware_store",
+            "health_food_store",
+            "home_goods_store",
+            "home_improvement_store",
+            "jewelry_store",
+            "liquor_store",
+            "market",
+            "shoe_store",
+            "shopping_mall",
+            "supermarket",
+            "tea_store",
+            "thrift_store",
+        ],
+    ),
+    (
+        "Services",
+        &[
+            "beauty_salon",
+            "cemetery",
+            "hair_care",
+            "insurance_agency",
+            "laundry",
+            "lawyer",
+            "real_estate_agency",
+            "veterinary_care",
+        ],
+    ),
+    (
+        "Health and Wellness",
+        &[
+            "dentist",
+            "doctor",
+            "hospital",
+            "massage",
+            "pharmacy",
+            "physiotherapist",
+            "spa",
+        ],
+    ),
+    (
+        "Automotive",
+        &[
+            "car_dealer",
+            "car_rental",
+            "car_repair",
+            "car_wash",
+            "electric_vehicle_charging_station",
+            "gas_station",
+            "parking",
+        ],
+    ),
+    (
+        "Transportation",
+        &[
+            "airport",
+            "bus_station",
+            "light_rail_station",
+            "subway_station",
+            "taxi_stand",
+            "train_station",
+            "transit_station",
+        ],
+    ),
+    (
+        "Lodging",
+        &[
+            "bed_and_breakfast",
+            "campground",
+            "guest_house",
+            "hostel",
+            "hotel",
+            "lodging",
+            "motel",
+            "resort_hotel",
+            "rv_park",
+        ],
+    ),
+    (
+        "Entertainment and Recreation",
+        &[
+            "amusement_center",
+            "amusement_park",
+            "aquarium",
+            "bowling_alley",
+            "casino",
+            "dog_park",
+            "hiking_area",
+            "marina",
+            "movie_theater",
+            "national_park",
+            "night_club",
+            "park",
+            "playground",
+            "tourist_attraction",
+            "tourist_information_center",
+            "visitor_center",
+            "zoo",
+        ],
+    ),
+    (
+        "Culture",
+        &[
+            "art_gallery",
+            "historical_landmark",
+            "museum",
+            "performing_arts_theater",
+        ],
+    ),
+    (
+        "Sports",
+        &[
+            "fitness_center",
+            "golf_course",
+            "gym",
+            "ice_skating_rink",
+            "stadium",
+            "swimming_pool",
+        ],
+    ),
+    (
+        "Education",
+        &[
+            "library",
+            "primary_school",
+            "school",
+            "secondary_school",
+            "university",
+        ],
+    ),
+    (
+        "Finance",
+        &[
+            "accounting",
+            "atm",
+            "bank",
+        ],
+    ),
+    (
+        "Government",
+        &[
+            "city_hall",
+            "courthouse",
+            "embassy",
+            "fire_station",
+            "police",
+            "post_office",
+        ],
+    ),
+    (
+        "Places of Worship",
+        &[
+            "church",
+            "hindu_temple",
+            "mosque",
+            "synagogue",
+        ],
+    ),
+];
+
+/// Fuzzy-match place types: returns types containing any of the space-separated
+/// query words as substrings, scored by how early/many matches occur.
+pub fn filter_types(query: &str, max: usize) -> Vec<&'static str> {
+    if query.is_empty() {
+        return Vec::new();
+    }
+
+    let query_lower = query.to_lowercase();
+    let terms: Vec<&str> = query_lower.split_whitespace().collect();
+    if terms.is_empty() {
+        return Vec::new();
+    }
+
+    let mut scored: Vec<(usize, &'static str)> = all_types()
+        .filter_map(|t| {
+            // All terms must match somewhere in the type string
+            let all_match = terms.iter().all(|term| t.contains(term));
+            if !all_match {
+                return None;
+            }
+            // Score: prefer prefix matches, then shorter strings
+            let score = if t.starts_with(terms[0]) {
+                0 // best: prefix match on first term
+            } else {
+                1
+            };
+            Some((score * 1000 + t.len(), t))
+        })
+        .collect();
+
+    scored.sort_by_key(|(score, _)| *score);
+    scored.into_iter().take(max).map(|(_, t)| t).collect()
+}
+
+/// Whether `t` is a known Table A place type
+pub fn is_known_type(t: &str) -> bool {
+    category_of(t).is_some()
+}
+
+/// Every place type, category by category
+pub fn all_types() -> impl Iterator<Item = &'static str> {
+    PLACE_TYPE_CATEGORIES
+        .iter()
+        .flat_map(|(_, types)| types.iter().copied())
+}
+
+/// Category name a place type is listed under
+pub fn category_of(t: &str) -> Option<&'static str> {
+    PLACE_TYPE_CATEGORIES
+        .iter()
+        .find(|(_, types)| types.contains(&t))
+        .map(|&(category, _)| category)
+}
+
+/// Categories with the types matching `query` (case-insensitive substring of
+/// the type or its category name); empty categories are dropped
+pub fn grouped_types(query: Option<&str>) -> Vec<(&'static str, Vec<&'static str>)> {
+    let query = query.map(|q| q.trim().to_lowercase().replace(' ', "_"));
+    PLACE_TYPE_CATEGORIES
+        .iter()
+        .filter_map(|&(category, types)| {
+            let types: Vec<&'static str> = match query.as_deref() {
+                None | Some("") => types.to_vec(),
+                Some(q) if category.to_lowercase().replace(' ', "_").contains(q) => types.to_vec(),
+                Some(q) => types.iter().copied().filter(|t| t.contains(q)).collect(),
+            };
+            (!types.is_empty()).then_some((category, types))
+        })
+        .collect()
+}
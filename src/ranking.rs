@@ -0,0 +1,310 @@
+use std::cmp::Ordering;
+
+use serde::Serialize;
+
+use crate::api::types::{LatLng, Place};
+use crate::config::RankingConfig;
+use crate::geo::haversine_distance;
+
+/// Review count at which the review component reaches 1.0
+const REVIEWS_SATURATION: f64 = 10_000.0;
+
+/// Parameters of the `--rank smart` score; defaults can be overridden by `[ranking]`
+#[derive(Debug, Clone, Copy)]
+pub struct RankingParams {
+    pub rating_weight: f64,
+    pub reviews_weight: f64,
+    pub distance_weight: f64,
+    /// Rating a place is pulled toward when it has few reviews
+    pub prior_rating: f64,
+    /// How many reviews the prior is worth
+    pub prior_reviews: f64,
+}
+
+impl Default for RankingParams {
+    fn default() -> Self {
+        RankingParams {
+            rating_weight: 0.5,
+            reviews_weight: 0.3,
+            distance_weight: 0.2,
+            prior_rating: 4.0,
+            prior_reviews: 50.0,
+        }
+    }
+}
+
+impl RankingParams {
+    pub fn from_config(cfg: &RankingConfig) -> Self {
+        let d = RankingParams::default();
+        RankingParams {
+            rating_weight: cfg.rating_weight.unwrap_or(d.rating_weight),
+            reviews_weight: cfg.reviews_weight.unwrap_or(d.reviews_weight),
+            distance_weight: cfg.distance_weight.unwrap_or(d.distance_weight),
+            prior_rating: cfg.prior_rating.unwrap_or(d.prior_rating),
+            prior_reviews: cfg.prior_reviews.unwrap_or(d.prior_reviews),
+        }
+    }
+}
+
+/// A composite score in 0..=1 and its components (each also 0..=1)
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct Score {
+    pub score: f64,
+    /// Bayesian-adjusted rating, scaled from 1-5 stars
+    pub rating: f64,
+    /// Log-scaled review count
+    pub reviews: f64,
+    /// Distance decay from the bias center; None without a center or location
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub distance: Option<f64>,
+}
+
+/// Where distances are measured from: the bias center and the distance at
+/// which the distance component has decayed to 1/e
+#[derive(Debug, Clone)]
+pub struct Origin {
+    pub center: LatLng,
+    pub decay_meters: f64,
+}
+
+/// Score one place.
+///
+/// score = (wr·rating + wv·reviews + wd·distance) / (wr + wv + wd), where
+/// - rating   = (v·R + m·C) / (v + m), mapped from 1..5 to 0..1
+///   (R: rating, v: review count, C: prior rating, m: prior reviews)
+/// - reviews  = ln(1 + v) / ln(1 + 10000), capped at 1
+/// - distance = exp(-d / decay_meters)
+///
+/// Without an origin or a place location, the distance term and its weight drop out.
+pub fn score_place(place: &Place, origin: Option<&Origin>, params: &RankingParams) -> Score {
+    let count = place.user_rating_count.unwrap_or(0) as f64;
+    let adjusted = match place.rating {
+        Some(r) => (count * r + params.prior_reviews * params.prior_rating) / (count + params.prior_reviews),
+        None => params.prior_rating,
+    };
+    let rating = ((adjusted - 1.0) / 4.0).clamp(0.0, 1.0);
+    let reviews = ((1.0 + count).ln() / (1.0 + REVIEWS_SATURATION).ln()).min(1.0);
+
+    let distance = match (origin, place.location.as_ref()) {
+        (Some(o), Some(loc)) if o.decay_meters > 0.0 => {
+            Some((-haversine_distance(&o.center, loc) / o.decay_meters).exp())
+        }
+        _ => None,
+    };
+
+    let mut total = params.rating_weight * rating + params.reviews_weight * reviews;
+    let mut weights = params.rating_weight + params.reviews_weight;
+    if let Some(d) = distance {
+        total += params.distance_weight * d;
+        weights += params.distance_weight;
+    }
+    let score = if weights > 0.0 { total / weights } else { 0.0 };
+
+    Score {
+        score,
+        rating,
+        reviews,
+        distance,
+    }
+}
+
+/// Score every place and sort by descending score (ties keep API order)
+pub fn rank(places: Vec<Place>, origin: Option<&Origin>, params: &RankingParams) -> Vec<(Place, Score)> {
+    let mut ranked: Vec<(Place, Score)> = places
+        .into_iter()
+        .map(|p| {
+            let score = score_place(&p, origin, params);
+            (p, score)
+        })
+        .collect();
+    ranked.sort_by(|a, b| b.1.score.total_cmp(&a.1.score));
+    ranked
+}
+
+/// Client-side orderings for `--sort-by`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum SortKey {
+    /// Highest rated first
+    Rating,
+    /// Most reviewed first
+    Reviews,
+    /// Closest to the bias center first
+    Distance,
+    /// Alphabetical by name
+    Name,
+}
+
+/// Sort places by `key`, or the opposite way with `reverse`. Ties keep API
+/// order, and places without the field (or without a location, or any
+/// `origin`, for distance) go last either way.
+pub fn sort_places(places: &mut [Place], key: SortKey, origin: Option<&LatLng>, reverse: bool) {
+    sort_by_place(places, |p| p, key, origin, reverse);
+}
+
+/// `sort_places` for items that wrap a place
+pub fn sort_by_place<T>(
+    items: &mut [T],
+    place: impl Fn(&T) -> &Place,
+    key: SortKey,
+    origin: Option<&LatLng>,
+    reverse: bool,
+) {
+    let distance = |p: &Place| Some(haversine_distance(origin?, p.location.as_ref()?));
+    let name = |p: &Place| p.display_name.as_ref().map(|n| n.text.to_lowercase());
+
+    items.sort_by(|a, b| {
+        let (a, b) = (place(a), place(b));
+        match key {
+            SortKey::Rating => missing_last(a.rating, b.rating, reverse, |x, y| y.total_cmp(x)),
+            SortKey::Reviews => missing_last(a.user_rating_count, b.user_rating_count, reverse, |x, y| y.cmp(x)),
+            SortKey::Distance => missing_last(distance(a), distance(b), reverse, |x, y| x.total_cmp(y)),
+            SortKey::Name => missing_last(name(a), name(b), reverse, |x, y| x.cmp(y)),
+        }
+    });
+}
+
+fn missing_last<T>(a: Option<T>, b: Option<T>, reverse: bool, cmp: impl Fn(&T, &T) -> Ordering) -> Ordering {
+    match (a, b) {
+        (Some(a), Some(b)) if reverse => cmp(&a, &b).reverse(),
+        (Some(a), Some(b)) => cmp(&a, &b),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CENTER: LatLng = LatLng {
+        latitude: 48.2082,
+        longitude: 16.3738,
+    };
+
+    fn place(id: &str, rating: Option<f64>, reviews: u32) -> Place {
+        Place {
+            id: id.into(),
+            rating,
+            user_rating_count: Some(reviews),
+            ..Place::default()
+        }
+    }
+
+    fn at(mut place: Place, latitude: f64, longitude: f64) -> Place {
+        place.location = Some(LatLng { latitude, longitude });
+        place
+    }
+
+    fn close(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-9
+    }
+
+    #[test]
+    fn few_reviews_are_pulled_toward_the_prior() {
+        let params = RankingParams::default();
+        let rating = |r, reviews| score_place(&place("p", Some(r), reviews), None, &params).rating;
+
+        // (2·5 + 50·4) / 52 stars, not 5
+        assert!(close(rating(5.0, 2), (210.0 / 52.0 - 1.0) / 4.0));
+        assert!(close(rating(1.0, 2), (202.0 / 52.0 - 1.0) / 4.0));
+        // At the prior, the count doesn't matter
+        assert!(close(rating(4.0, 2), 0.75));
+        assert!(close(rating(4.0, 5000), 0.75));
+        // Many reviews outweigh the prior
+        assert!(rating(5.0, 5000) > 0.99);
+        // No rating scores as the prior
+        assert!(close(score_place(&place("p", None, 0), None, &params).rating, 0.75));
+    }
+
+    #[test]
+    fn a_well_reviewed_place_outranks_a_perfect_score_from_two_reviews() {
+        let ranked = rank(
+            vec![place("perfect", Some(5.0), 2), place("proven", Some(4.7), 2400)],
+            None,
+            &RankingParams::default(),
+        );
+        let ids: Vec<&str> = ranked.iter().map(|(p, _)| p.id.as_str()).collect();
+        assert_eq!(ids, ["proven", "perfect"]);
+    }
+
+    #[test]
+    fn review_counts_are_log_scaled_and_capped() {
+        let params = RankingParams::default();
+        let reviews = |count| score_place(&place("p", Some(4.0), count), None, &params).reviews;
+
+        assert_eq!(reviews(0), 0.0);
+        // ln(100) is half of ln(10 000), near enough ln(10 001)
+        assert!((reviews(99) - 0.5).abs() < 1e-4);
+        assert!(close(reviews(10_000), 1.0));
+        assert_eq!(reviews(250_000), 1.0);
+        // Each tenfold increase adds about the same
+        let steps = [reviews(9) - reviews(0), reviews(99) - reviews(9), reviews(999) - reviews(99)];
+        assert!(steps.iter().all(|s| (s - 0.25).abs() < 0.01), "{:?}", steps);
+    }
+
+    #[test]
+    fn distance_decays_exponentially_from_the_origin() {
+        let params = RankingParams::default();
+        let far = at(place("far", Some(4.0), 100), 48.2172, 16.3738);
+        let origin = Origin {
+            center: CENTER,
+            decay_meters: haversine_distance(&CENTER, far.location.as_ref().unwrap()),
+        };
+        let distance = |p: &Place| score_place(p, Some(&origin), &params).distance;
+
+        let here = at(place("here", Some(4.0), 100), CENTER.latitude, CENTER.longitude);
+        assert_eq!(distance(&here), Some(1.0));
+        assert!(close(distance(&far).unwrap(), (-1.0f64).exp()));
+        let twice = at(place("twice", Some(4.0), 100), 48.2262, 16.3738);
+        assert!((distance(&twice).unwrap() - (-2.0f64).exp()).abs() < 1e-3);
+
+        // Without a location, or without an origin, there's no distance term
+        assert_eq!(distance(&place("nowhere", Some(4.0), 100)), None);
+        assert_eq!(score_place(&here, None, &params).distance, None);
+    }
+
+    #[test]
+    fn missing_distance_drops_its_weight() {
+        let params = RankingParams::default();
+        let score = score_place(&place("p", Some(4.0), 99), None, &params);
+        let expected = (0.5 * score.rating + 0.3 * score.reviews) / 0.8;
+        assert!(close(score.score, expected));
+    }
+
+    #[test]
+    fn weights_and_priors_come_from_the_config() {
+        let cfg = RankingConfig {
+            reviews_weight: Some(0.0),
+            distance_weight: Some(0.0),
+            prior_reviews: Some(0.0),
+            ..RankingConfig::default()
+        };
+        let params = RankingParams::from_config(&cfg);
+        assert_eq!(params.rating_weight, 0.5);
+        assert_eq!(params.prior_rating, 4.0);
+
+        // Rating alone, unadjusted: the perfect score now wins
+        let ranked = rank(vec![place("proven", Some(4.7), 2400), place("perfect", Some(5.0), 2)], None, &params);
+        assert_eq!(ranked[0].0.id, "perfect");
+        assert!(close(ranked[0].1.score, 1.0));
+
+        let ratings_ignored = RankingParams {
+            rating_weight: 0.0,
+            ..RankingParams::default()
+        };
+        let ranked = rank(vec![place("few", Some(5.0), 2), place("many", Some(3.0), 900)], None, &ratings_ignored);
+        assert_eq!(ranked[0].0.id, "many");
+    }
+
+    #[test]
+    fn zero_weights_score_zero() {
+        let params = RankingParams {
+            rating_weight: 0.0,
+            reviews_weight: 0.0,
+            distance_weight: 0.0,
+            ..RankingParams::default()
+        };
+        assert_eq!(score_place(&place("p", Some(5.0), 900), None, &params).score, 0.0);
+    }
+}
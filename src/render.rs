@@ -1,13 +1,53 @@
-use std::io::Cursor;
+pub mod markdown;
 
-use colored::Colorize;
+use std::io::{Cursor, IsTerminal};
+use std::time::Duration;
+
+use colored::{ColoredString, Colorize};
 use image::ImageReader;
 use viuer::{print as viuer_print, Config as ViuerConfig};
 
 use crate::api::types::*;
+use crate::diff::PlacesDiff;
+use crate::explain::{Explanation, Verdict};
+use crate::format::{
+    display_width, format_coord, format_count, format_distance, format_rating, humanize_duration, list_address,
+    place_label, sanitize_display, truncate_to_width, wrap_text, AddressChoice, NumberFormat,
+};
+use crate::geo::{distance_and_direction, haversine_distance, FarCheck};
+use crate::hours;
+use crate::notes::Note;
+use crate::ranking::Score;
+
+/// How much of each place the text renderers show
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verbosity {
+    /// The usual summary or details layout
+    Compact,
+    /// Also every populated field, labeled with its API name (`--verbose`)
+    Full,
+}
+
+impl Verbosity {
+    pub fn from_verbose(verbose: bool) -> Self {
+        if verbose {
+            Verbosity::Full
+        } else {
+            Verbosity::Compact
+        }
+    }
+}
 
-/// Render a list of places (used by search, nearby, resolve)
-pub fn render_places(places: &[Place], label: &str) {
+/// Render a list of places (used by search, nearby, resolve); with `far`,
+/// results beyond its threshold get a distance warning
+pub fn render_places(
+    places: &[Place],
+    label: &str,
+    far: Option<(&FarCheck, Units)>,
+    verbosity: Verbosity,
+    nf: NumberFormat,
+    addresses: AddressChoice,
+) {
     if places.is_empty() {
         println!("{}", "No results found.".yellow());
         return;
@@ -22,27 +62,442 @@ pub fn render_places(places: &[Place], label: &str) {
     println!();
 
     for (i, place) in places.iter().enumerate() {
-        render_place_summary(i + 1, place);
+        render_place_summary(i + 1, place, None, far, verbosity, nf, addresses);
     }
 }
 
-/// Render a single place summary (one-line style for lists)
-fn render_place_summary(index: usize, place: &Place) {
-    let name = place
-        .display_name
+/// Names never shrink below this many columns in `--format table`
+const TABLE_MIN_NAME_WIDTH: usize = 12;
+/// Columns `--format table` drops, in order, when the table is too wide
+const TABLE_OPTIONAL_COLUMNS: [&str; 2] = ["Type", "Price"];
+/// Dropped next if the table is still too wide with names shortened
+const TABLE_LAST_RESORT_COLUMNS: [&str; 2] = ["Open", "Reviews"];
+
+struct TableColumn {
+    title: &'static str,
+    right_aligned: bool,
+    cells: Vec<String>,
+}
+
+impl TableColumn {
+    fn width(&self) -> usize {
+        self.cells
+            .iter()
+            .map(|c| display_width(c))
+            .chain([display_width(self.title)])
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+/// Print places as a column-aligned table (`--format table`); see `places_table`
+pub fn render_places_table(places: &[Place], origin: Option<(&LatLng, Units)>, max_width: usize, nf: NumberFormat) {
+    if places.is_empty() {
+        println!("{}", "No results found.".yellow());
+        return;
+    }
+    print!("{}", places_table(places, origin, max_width, nf));
+}
+
+/// Box-drawn table of rank, name, type, rating, reviews, price, open now, and
+/// distance from `origin` when there is one. To fit `max_width` columns it
+/// drops the type column, then price, then shortens names, then drops the
+/// open and review columns.
+pub fn places_table(places: &[Place], origin: Option<(&LatLng, Units)>, max_width: usize, nf: NumberFormat) -> String {
+    let column = |title, right_aligned, cell: &dyn Fn(&Place) -> Option<String>| TableColumn {
+        title,
+        right_aligned,
+        cells: places.iter().map(|p| cell(p).unwrap_or_default()).collect(),
+    };
+    let mut columns = vec![
+        TableColumn {
+            title: "#",
+            right_aligned: true,
+            cells: (1..=places.len()).map(|i| i.to_string()).collect(),
+        },
+        column("Name", false, &|p| Some(sanitize_display(&place_label(p).0).into_owned())),
+        column("Type", false, &|p| {
+            p.primary_type_display_name
+                .as_ref()
+                .map(|t| sanitize_display(&t.text).into_owned())
+        }),
+        column("Rating", true, &|p| p.rating.map(|r| format_rating(r, nf))),
+        column("Reviews", true, &|p| p.user_rating_count.map(|c| format_count(c as u64, nf))),
+        column("Price", false, &|p| p.price_level.as_deref().map(|l| price_level_display(l).to_string())),
+        column("Open", false, &|p| {
+            let open = p.current_opening_hours.as_ref()?.open_now?;
+            Some(if open { "open" } else { "closed" }.to_string())
+        }),
+    ];
+    if let Some((center, units)) = origin {
+        columns.push(column("Distance", true, &|p| {
+            Some(format_distance(haversine_distance(center, p.location.as_ref()?), units, nf))
+        }));
+    }
+
+    // Each column adds its content, a space either side, and a border
+    let table_width = |columns: &[TableColumn]| columns.iter().map(|c| c.width() + 3).sum::<usize>() + 1;
+    let drop_until_fits = |columns: &mut Vec<TableColumn>, titles: [&str; 2]| {
+        for title in titles {
+            if table_width(columns) <= max_width {
+                break;
+            }
+            columns.retain(|c| c.title != title);
+        }
+    };
+    drop_until_fits(&mut columns, TABLE_OPTIONAL_COLUMNS);
+    let over = table_width(&columns).saturating_sub(max_width);
+    if over > 0 {
+        let name = &mut columns[1];
+        let fit = name.width().saturating_sub(over).max(TABLE_MIN_NAME_WIDTH);
+        for cell in &mut name.cells {
+            *cell = truncate_to_width(cell, fit);
+        }
+    }
+    drop_until_fits(&mut columns, TABLE_LAST_RESORT_COLUMNS);
+
+    let widths: Vec<usize> = columns.iter().map(TableColumn::width).collect();
+    let rule = |left: &str, mid: &str, right: &str| {
+        let segments: Vec<String> = widths.iter().map(|w| "─".repeat(w + 2)).collect();
+        format!("{}{}{}\n", left, segments.join(mid), right)
+    };
+    let row = |cells: Vec<(&str, bool)>| {
+        let padded: Vec<String> = cells
+            .iter()
+            .zip(&widths)
+            .map(|((text, right_aligned), width)| {
+                let pad = " ".repeat(width - display_width(text));
+                if *right_aligned {
+                    format!("{}{}", pad, text)
+                } else {
+                    format!("{}{}", text, pad)
+                }
+            })
+            .collect();
+        format!("│ {} │\n", padded.join(" │ "))
+    };
+
+    let mut out = rule("┌", "┬", "┐");
+    out.push_str(&row(columns.iter().map(|c| (c.title, c.right_aligned)).collect()));
+    out.push_str(&rule("├", "┼", "┤"));
+    for i in 0..places.len() {
+        out.push_str(&row(columns.iter().map(|c| (c.cells[i].as_str(), c.right_aligned)).collect()));
+    }
+    out.push_str(&rule("└", "┴", "┘"));
+    out
+}
+
+/// Columns of `--format csv`
+const CSV_HEADER: &str = "id,name,primary_type,rating,user_rating_count,price_level,address,lat,lng,website";
+
+/// Print places as CSV (`--format csv`): a header row, then one row per place.
+/// Missing values are empty fields; numbers are unformatted.
+pub fn render_places_csv(places: &[Place]) {
+    let mut out = String::from(CSV_HEADER);
+    out.push('\n');
+    for place in places {
+        let number = |n: Option<String>| n.unwrap_or_default();
+        let row = [
+            csv_field(&place.id),
+            csv_field(place.display_name.as_ref().map_or("", |n| n.text.as_str())),
+            csv_field(place.primary_type.as_deref().unwrap_or("")),
+            number(place.rating.map(|r| r.to_string())),
+            number(place.user_rating_count.map(|c| c.to_string())),
+            csv_field(place.price_level.as_deref().unwrap_or("")),
+            csv_field(place.formatted_address.as_deref().unwrap_or("")),
+            number(place.location.as_ref().map(|l| l.latitude.to_string())),
+            number(place.location.as_ref().map(|l| l.longitude.to_string())),
+            csv_field(place.website_uri.as_deref().unwrap_or("")),
+        ];
+        out.push_str(&row.join(","));
+        out.push('\n');
+    }
+    print!("{}", out);
+}
+
+/// Quote a CSV field (RFC 4180) when it holds a comma, quote, or line break
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Render `search --explain` results: each place with a line of the checks
+/// behind it (see `explain_line`)
+pub fn render_explained_places(
+    places: &[Place],
+    explained: (&[Explanation], Units),
+    label: &str,
+    far: Option<(&FarCheck, Units)>,
+    verbosity: Verbosity,
+    nf: NumberFormat,
+    addresses: AddressChoice,
+) {
+    if places.is_empty() {
+        println!("{}", "No results found.".yellow());
+        return;
+    }
+
+    println!(
+        "{} {} {}",
+        label.bold(),
+        format!("({})", places.len()).dimmed(),
+        "─".repeat(40).dimmed()
+    );
+    println!();
+
+    let (explanations, units) = explained;
+    for (i, (place, explanation)) in places.iter().zip(explanations).enumerate() {
+        let note = explain_line(explanation, units, nf);
+        render_place_summary(i + 1, place, Some(&note), far, verbosity, nf, addresses);
+    }
+}
+
+/// One dimmed line per `--explain`ed place, e.g.
+/// "why: #2 · 420 m from center, inside radius · type ✓ · rating ✓ · open now ?"
+pub fn explain_line(e: &Explanation, units: Units, nf: NumberFormat) -> String {
+    let mark = |verdict: Verdict| match verdict {
+        Verdict::Pass => "✓",
+        Verdict::Fail => "✗",
+        Verdict::Unknown => "?",
+    };
+
+    let mut parts = vec![format!("#{}", e.rank)];
+    if let (Some(meters), Some(inside)) = (e.distance_meters, e.within_radius) {
+        parts.push(format!(
+            "{} from center, {} radius",
+            format_distance(meters, units, nf),
+            if inside { "inside" } else { "outside" }
+        ));
+    }
+    let checks = [
+        ("type", e.type_match),
+        ("rating", e.min_rating),
+        ("price", e.price_level),
+        ("open now", e.open_now),
+    ];
+    for (name, verdict) in checks {
+        if let Some(v) = verdict {
+            parts.push(format!("{} {}", name, mark(v)));
+        }
+    }
+    format!("why: {}", parts.join(" · ")).dimmed().to_string()
+}
+
+/// Render `nearby --at` results: one section per location, with places
+/// found around several locations noted under the first
+pub fn render_location_groups(
+    groups: &[LocationGroup],
+    units: Units,
+    verbosity: Verbosity,
+    nf: NumberFormat,
+    addresses: AddressChoice,
+) {
+    for (g, group) in groups.iter().enumerate() {
+        if g > 0 {
+            println!();
+        }
+        println!(
+            "{} {} {}",
+            format!("Near {}", sanitize_display(&group.label)).bold(),
+            format!("({}, within {})", group.places.len(), format_distance(group.radius, units, nf)).dimmed(),
+            "─".repeat(30).dimmed()
+        );
+        println!();
+        if group.places.is_empty() {
+            println!("  {}", "No results found.".yellow());
+            continue;
+        }
+        for (i, grouped) in group.places.iter().enumerate() {
+            let note = (!grouped.also_near.is_empty())
+                .then(|| format!("also near {}", sanitize_display(&grouped.also_near.join(", "))).dimmed().to_string());
+            render_place_summary(i + 1, &grouped.place, note.as_deref(), None, verbosity, nf, addresses);
+        }
+    }
+}
+
+/// Render places re-ordered by `--rank smart`, with each score
+/// (and its components at `Verbosity::Full`)
+pub fn render_ranked_places(
+    ranked: &[(Place, Score)],
+    label: &str,
+    verbosity: Verbosity,
+    far: Option<(&FarCheck, Units)>,
+    explained: Option<(&[Explanation], Units)>,
+    nf: NumberFormat,
+    addresses: AddressChoice,
+) {
+    if ranked.is_empty() {
+        println!("{}", "No results found.".yellow());
+        return;
+    }
+
+    println!(
+        "{} {} {}",
+        label.bold(),
+        format!("({}, ranked)", ranked.len()).dimmed(),
+        "─".repeat(40).dimmed()
+    );
+    println!();
+
+    for (i, (place, score)) in ranked.iter().enumerate() {
+        let mut note = format!("{} {}", "score".dimmed(), format!("{:.2}", score.score).green());
+        if verbosity == Verbosity::Full {
+            let mut parts = vec![
+                format!("rating {:.2}", score.rating),
+                format!("reviews {:.2}", score.reviews),
+            ];
+            if let Some(d) = score.distance {
+                parts.push(format!("distance {:.2}", d));
+            }
+            note.push_str(&format!("  {}", format!("({})", parts.join(" · ")).dimmed()));
+        }
+        if let Some((explanation, units)) = explained.and_then(|(all, units)| Some((all.get(i)?, units))) {
+            note.push('\n');
+            note.push_str(&explain_line(explanation, units, nf));
+        }
+        render_place_summary(i + 1, place, Some(&note), far, verbosity, nf, addresses);
+    }
+}
+
+/// Render the changes between a saved result list and fresh results
+pub fn render_places_diff(diff: &PlacesDiff, nf: NumberFormat, addresses: AddressChoice) {
+    if diff.is_empty() {
+        println!("{}", "No changes since the previous results.".green());
+        return;
+    }
+
+    let section = |title: ColoredString, count: usize| {
+        println!(
+            "{} {} {}",
+            title,
+            format!("({})", count).dimmed(),
+            "─".repeat(40).dimmed()
+        );
+        println!();
+    };
+
+    if !diff.added.is_empty() {
+        section("New places".bold().green(), diff.added.len());
+        for (i, place) in diff.added.iter().enumerate() {
+            render_place_summary(i + 1, place, None, None, Verbosity::Compact, nf, addresses);
+        }
+    }
+
+    if !diff.removed.is_empty() {
+        section("Disappeared places".bold().red(), diff.removed.len());
+        for (i, place) in diff.removed.iter().enumerate() {
+            render_place_summary(i + 1, place, None, None, Verbosity::Compact, nf, addresses);
+        }
+    }
+
+    if !diff.changed.is_empty() {
+        section("Changed places".bold().yellow(), diff.changed.len());
+        for (i, change) in diff.changed.iter().enumerate() {
+            println!(
+                "  {} {}",
+                format!("{}.", i + 1).dimmed(),
+                sanitize_display(&change.name).bold().cyan()
+            );
+            if let Some(ref c) = change.rating {
+                let delta = match (c.before, c.after) {
+                    (Some(b), Some(a)) => signed_delta(a - b, format!("{:+.1}", a - b)),
+                    _ => String::new(),
+                };
+                println!(
+                    "     {} {} → {} {}",
+                    "Rating:".dimmed(),
+                    opt_display(&c.before),
+                    opt_display(&c.after),
+                    delta
+                );
+            }
+            if let Some(ref c) = change.user_rating_count {
+                let delta = match (c.before, c.after) {
+                    (Some(b), Some(a)) => {
+                        let d = a as i64 - b as i64;
+                        signed_delta(d as f64, format!("{:+}", d))
+                    }
+                    _ => String::new(),
+                };
+                println!(
+                    "     {} {} → {} {}",
+                    "Reviews:".dimmed(),
+                    opt_display(&c.before),
+                    opt_display(&c.after),
+                    delta
+                );
+            }
+            if let Some(ref c) = change.business_status {
+                let after = match c.after.as_deref() {
+                    Some("OPERATIONAL") => "OPERATIONAL".green().to_string(),
+                    Some(s) => s.red().to_string(),
+                    None => "-".dimmed().to_string(),
+                };
+                println!(
+                    "     {} {} → {}",
+                    "Status:".dimmed(),
+                    opt_display(&c.before),
+                    after
+                );
+            }
+            println!();
+        }
+    }
+}
+
+fn signed_delta(value: f64, text: String) -> String {
+    if value > 0.0 {
+        format!("({})", text).green().to_string()
+    } else if value < 0.0 {
+        format!("({})", text).red().to_string()
+    } else {
+        format!("({})", text).dimmed().to_string()
+    }
+}
+
+fn opt_display<T: std::fmt::Display>(value: &Option<T>) -> String {
+    value
         .as_ref()
-        .map(|n| n.text.as_str())
-        .unwrap_or("Unknown");
+        .map(|v| v.to_string())
+        .unwrap_or_else(|| "-".to_string())
+}
+
+/// Render a single place summary (one-line style for lists)
+fn render_place_summary(
+    index: usize,
+    place: &Place,
+    note: Option<&str>,
+    far: Option<(&FarCheck, Units)>,
+    verbosity: Verbosity,
+    nf: NumberFormat,
+    addresses: AddressChoice,
+) {
+    if let Some((meters, units)) = far.and_then(|(check, units)| Some((check.far(place)?, units))) {
+        println!(
+            "     {}",
+            format!("⚠ {} from your location", format_distance(meters, units, nf)).yellow()
+        );
+    }
+
+    let (name, fallback) = place_label(place);
 
     // Line 1: index + name + type
     let type_str = place
         .primary_type_display_name
         .as_ref()
-        .map(|t| t.text.clone())
+        .map(|t| sanitize_display(&t.text).into_owned())
         .or_else(|| place.primary_type.clone())
         .unwrap_or_default();
 
-    print!("  {} {}", format!("{}.", index).dimmed(), name.bold().cyan());
+    let styled_name = if fallback {
+        name.dimmed()
+    } else {
+        name.bold().cyan()
+    };
+    print!("  {} {}", format!("{}.", index).dimmed(), styled_name);
     if !type_str.is_empty() {
         print!("  {}", type_str.dimmed());
     }
@@ -53,46 +508,205 @@ fn render_place_summary(index: usize, place: &Place) {
     if let Some(rating) = place.rating {
         let stars = star_string(rating);
         let count = place.user_rating_count.unwrap_or(0);
-        meta_parts.push(format!("{} {} ({})", stars, rating, count));
+        meta_parts.push(format!("{} {} ({})", stars, format_rating(rating, nf), format_count(count as u64, nf)));
     }
     if let Some(ref price) = place.price_level {
         meta_parts.push(price_level_display(price).to_string());
     }
     if let Some(ref status) = place.business_status {
         if status != "OPERATIONAL" {
-            meta_parts.push(status.red().to_string());
+            meta_parts.push(sanitize_display(status).red().to_string());
         }
     }
+    if let Some(left) = hours::open_state_now(place).closing_soon() {
+        meta_parts.push(format!("closes in {} min", left).yellow().to_string());
+    }
     if !meta_parts.is_empty() {
         println!("     {}", meta_parts.join("  ·  "));
     }
+    if let Some(note) = note {
+        for line in note.lines() {
+            println!("     {}", line);
+        }
+    }
 
-    // Line 3: address
-    if let Some(ref addr) = place.formatted_address {
-        println!("     {}", addr.dimmed());
+    // Line 3: address, unless it already stands in for the name
+    if let Some(addr) = list_address(place, addresses) {
+        let addr = sanitize_display(addr);
+        if !(fallback && addr == name) {
+            println!("     {}", addr.dimmed());
+        }
     }
 
     // Line 4: ID
     if !place.id.is_empty() {
-        println!("     {} {}", "ID:".dimmed(), place.id.dimmed());
+        println!("     {} {}", "ID:".dimmed(), sanitize_display(&place.id).dimmed());
+    }
+
+    if verbosity == Verbosity::Full {
+        render_place_fields(place, "     ");
     }
 
     println!();
 }
 
-/// Render full place details with optional inline photo previews
-pub fn render_place_details(place: &Place, photo_images: Option<&[Vec<u8>]>) {
-    let name = place
-        .display_name
-        .as_ref()
-        .map(|n| n.text.as_str())
-        .unwrap_or("Unknown");
+/// Every populated field of a place as aligned `apiName  value` rows under
+/// `indent`; enum values show their readable form with the raw value beside it
+fn render_place_fields(place: &Place, indent: &str) {
+    let rows = place_fields(place);
+    let width = rows.iter().map(|(label, _)| label.len()).max().unwrap_or(0);
+    println!("{}{}", indent, "Fields".bold());
+    for (label, value) in rows {
+        println!(
+            "{}  {}  {}",
+            indent,
+            format!("{:width$}", label, width = width).dimmed(),
+            sanitize_display(&value)
+        );
+    }
+}
+
+fn place_fields(place: &Place) -> Vec<(&'static str, String)> {
+    let mut rows: Vec<(&'static str, String)> = Vec::new();
+    let with_lang = |text: &str, lang: &Option<String>| match lang {
+        Some(lang) => format!("{} ({})", text, lang),
+        None => text.to_string(),
+    };
+
+    if !place.id.is_empty() {
+        rows.push(("id", place.id.clone()));
+    }
+    if let Some(ref name) = place.display_name {
+        rows.push(("displayName", with_lang(&name.text, &name.language_code)));
+    }
+    if let Some(ref t) = place.primary_type {
+        let pretty = place.primary_type_display_name.as_ref().map(|d| d.text.as_str());
+        rows.push(("primaryType", match pretty {
+            Some(pretty) => format!("{} ({})", pretty, t),
+            None => t.clone(),
+        }));
+    }
+    if let Some(ref types) = place.types {
+        rows.push(("types", types.join(", ")));
+    }
+    if let Some(ref addr) = place.formatted_address {
+        rows.push(("formattedAddress", addr.clone()));
+    }
+    if let Some(ref addr) = place.short_formatted_address {
+        rows.push(("shortFormattedAddress", addr.clone()));
+    }
+    if let Some(ref loc) = place.location {
+        rows.push(("location", format!("{}, {}", loc.latitude, loc.longitude)));
+    }
+    if let Some(rating) = place.rating {
+        rows.push(("rating", rating.to_string()));
+    }
+    if let Some(count) = place.user_rating_count {
+        rows.push(("userRatingCount", count.to_string()));
+    }
+    if let Some(ref price) = place.price_level {
+        rows.push(("priceLevel", format!("{} ({})", price_level_display(price), price)));
+    }
+    if let Some(ref status) = place.business_status {
+        let pretty = match status.as_str() {
+            "OPERATIONAL" => "Open",
+            "CLOSED_TEMPORARILY" => "Temporarily closed",
+            "CLOSED_PERMANENTLY" => "Permanently closed",
+            other => other,
+        };
+        rows.push(("businessStatus", format!("{} ({})", pretty, status)));
+    }
+    if let Some(ref phone) = place.national_phone_number {
+        rows.push(("nationalPhoneNumber", phone.clone()));
+    }
+    if let Some(ref phone) = place.international_phone_number {
+        rows.push(("internationalPhoneNumber", phone.clone()));
+    }
+    if let Some(ref uri) = place.website_uri {
+        rows.push(("websiteUri", uri.clone()));
+    }
+    if let Some(ref uri) = place.google_maps_uri {
+        rows.push(("googleMapsUri", uri.clone()));
+    }
+    if let Some(offset) = place.utc_offset_minutes {
+        let sign = if offset < 0 { '-' } else { '+' };
+        let abs = offset.unsigned_abs();
+        rows.push((
+            "utcOffsetMinutes",
+            format!("{} (UTC{}{:02}:{:02})", offset, sign, abs / 60, abs % 60),
+        ));
+    }
+    let hours = [
+        ("currentOpeningHours", &place.current_opening_hours),
+        ("regularOpeningHours", &place.regular_opening_hours),
+    ];
+    for (label, hours) in hours {
+        let Some(hours) = hours else { continue };
+        let mut parts = Vec::new();
+        if let Some(open) = hours.open_now {
+            parts.push(format!("openNow {}", open));
+        }
+        if let Some(ref periods) = hours.periods {
+            let periods: Vec<String> = periods.iter().map(period_display).collect();
+            parts.push(format!("periods {}", periods.join(", ")));
+        }
+        if let Some(ref descs) = hours.weekday_descriptions {
+            parts.push(format!("{} weekday descriptions", descs.len()));
+        }
+        rows.push((label, parts.join("; ")));
+    }
+    if let Some(ref summary) = place.editorial_summary {
+        if let Some(ref text) = summary.text {
+            rows.push(("editorialSummary", with_lang(text, &summary.language_code)));
+        }
+    }
+    if let Some(ref reviews) = place.reviews {
+        rows.push(("reviews", reviews.len().to_string()));
+    }
+    if let Some(ref photos) = place.photos {
+        let sizes: Vec<String> = photos
+            .iter()
+            .map(|p| match (p.width_px, p.height_px) {
+                (Some(w), Some(h)) => format!("{}x{}", w, h),
+                _ => "?".to_string(),
+            })
+            .collect();
+        rows.push(("photos", format!("{} ({})", photos.len(), sizes.join(", "))));
+    }
+    rows
+}
+
+/// "Mon 08:00–Mon 21:00", or "Sun 00:00–" for a period with no close
+fn period_display(period: &OpeningPeriod) -> String {
+    let close = period.close.map(|c| c.to_string()).unwrap_or_default();
+    format!("{}–{}", period.open, close)
+}
+
+/// Render full place details; photo previews follow separately (see
+/// `render_photo_preview`) so they can stream in as downloads finish.
+/// With `hours_from_today`, the weekly hours start at today's entry
+pub fn render_place_details(
+    place: &Place,
+    precision: usize,
+    hours_from_today: bool,
+    verbosity: Verbosity,
+    origin: Option<(&LatLng, Units)>,
+    notes: &[Note],
+    nf: NumberFormat,
+) {
+    let name = sanitize_display(
+        place
+            .display_name
+            .as_ref()
+            .map(|n| n.text.as_str())
+            .unwrap_or("Unknown"),
+    );
 
     println!("{}", "━".repeat(60).dimmed());
     println!("  {}", name.bold().cyan());
 
     if let Some(ref primary) = place.primary_type_display_name {
-        println!("  {}", primary.text.dimmed());
+        println!("  {}", sanitize_display(&primary.text).dimmed());
     }
     println!("{}", "━".repeat(60).dimmed());
 
@@ -100,7 +714,13 @@ pub fn render_place_details(place: &Place, photo_images: Option<&[Vec<u8>]>) {
     if let Some(rating) = place.rating {
         let stars = star_string(rating);
         let count = place.user_rating_count.unwrap_or(0);
-        println!("  {} {} {} {}", "Rating:".bold(), stars, rating, format!("({} reviews)", count).dimmed());
+        println!(
+            "  {} {} {} {}",
+            "Rating:".bold(),
+            stars,
+            format_rating(rating, nf),
+            format!("({} reviews)", format_count(count as u64, nf)).dimmed()
+        );
     }
 
     // Price level
@@ -113,14 +733,14 @@ pub fn render_place_details(place: &Place, photo_images: Option<&[Vec<u8>]>) {
         let colored_status = if status == "OPERATIONAL" {
             "Open".green().to_string()
         } else {
-            status.red().to_string()
+            sanitize_display(status).red().to_string()
         };
         println!("  {} {}", "Status:".bold(), colored_status);
     }
 
     // Address
     if let Some(ref addr) = place.formatted_address {
-        println!("  {} {}", "Address:".bold(), addr);
+        println!("  {} {}", "Address:".bold(), sanitize_display(addr));
     }
 
     // Location
@@ -128,26 +748,29 @@ pub fn render_place_details(place: &Place, photo_images: Option<&[Vec<u8>]>) {
         println!(
             "  {} {}, {}",
             "Location:".bold(),
-            loc.latitude,
-            loc.longitude
+            format_coord(loc.latitude, precision),
+            format_coord(loc.longitude, precision)
         );
     }
+    if let Some(away) = origin.and_then(|(from, units)| distance_and_direction(from, place, units, nf)) {
+        println!("  {} {} {}", "Distance:".bold(), away, "from you".dimmed());
+    }
 
     // Phone
     if let Some(ref phone) = place.international_phone_number {
-        println!("  {} {}", "Phone:".bold(), phone);
+        println!("  {} {}", "Phone:".bold(), sanitize_display(phone));
     } else if let Some(ref phone) = place.national_phone_number {
-        println!("  {} {}", "Phone:".bold(), phone);
+        println!("  {} {}", "Phone:".bold(), sanitize_display(phone));
     }
 
     // Website
     if let Some(ref uri) = place.website_uri {
-        println!("  {} {}", "Website:".bold(), uri.underline());
+        println!("  {} {}", "Website:".bold(), sanitize_display(uri).underline());
     }
 
     // Google Maps
     if let Some(ref uri) = place.google_maps_uri {
-        println!("  {} {}", "Maps:".bold(), uri.underline());
+        println!("  {} {}", "Maps:".bold(), sanitize_display(uri).underline());
     }
 
     // Editorial summary
@@ -155,7 +778,7 @@ pub fn render_place_details(place: &Place, photo_images: Option<&[Vec<u8>]>) {
         if let Some(ref text) = summary.text {
             println!();
             println!("  {}", "Summary".bold());
-            println!("  {}", text);
+            println!("  {}", sanitize_display(text));
         }
     }
 
@@ -177,12 +800,27 @@ pub fn render_place_details(place: &Place, photo_images: Option<&[Vec<u8>]>) {
         .or(place.regular_opening_hours.as_ref());
     if let Some(hours) = hours_source {
         if let Some(ref descs) = hours.weekday_descriptions {
+            let descs = if hours_from_today {
+                hours::descriptions_from(descs, hours::weekday_now(place))
+            } else {
+                descs.iter().collect()
+            };
             for desc in descs {
-                println!("    {}", desc.dimmed());
+                println!("    {}", sanitize_display(desc).dimmed());
             }
         }
     }
 
+    // Local notes (`zupo note`)
+    if !notes.is_empty() {
+        println!();
+        println!("  {}", "Your notes".bold());
+        for note in notes {
+            println!("  {} {}", "•".yellow(), sanitize_display(&note.text));
+            println!("    {}", note.date().dimmed());
+        }
+    }
+
     // Reviews
     if let Some(ref reviews) = place.reviews {
         if !reviews.is_empty() {
@@ -214,11 +852,9 @@ pub fn render_place_details(place: &Place, photo_images: Option<&[Vec<u8>]>) {
                 format!("({})", photos.len()).dimmed()
             );
             for photo in photos.iter().take(3) {
-                println!("    {}", photo.name.dimmed());
-                if let Some(ref authors) = photo.author_attributions {
-                    for author in authors {
-                        println!("      by {}", author.display_name.dimmed());
-                    }
+                println!("    {}", sanitize_display(&photo.name).dimmed());
+                if let Some(attribution) = PhotoContext::from(photo).attribution() {
+                    println!("      {}", sanitize_display(&attribution).dimmed());
                 }
             }
             if photos.len() > 3 {
@@ -230,39 +866,67 @@ pub fn render_place_details(place: &Place, photo_images: Option<&[Vec<u8>]>) {
         }
     }
 
-    // Inline photo previews (if provided)
-    if let Some(ref image_data) = photo_images {
-        if !image_data.is_empty() {
-            println!();
-            println!("  {}", "Photo Previews".bold());
-            for (i, bytes) in image_data.iter().enumerate() {
-                println!("  {} {}", format!("Photo {}:", i + 1).dimmed(), "─".repeat(30).dimmed());
-                render_image_bytes(bytes, 60, 15);
-                println!();
-            }
-        }
-    }
-
     // Place ID
     if !place.id.is_empty() {
         println!();
-        println!("  {} {}", "Place ID:".dimmed(), place.id.dimmed());
+        println!("  {} {}", "Place ID:".dimmed(), sanitize_display(&place.id).dimmed());
+    }
+
+    if verbosity == Verbosity::Full {
+        println!();
+        render_place_fields(place, "  ");
+    }
+
+    println!();
+}
+
+/// Heading printed before the first of `details --show-photos`'s previews
+pub fn render_photo_previews_heading() {
+    println!("  {}", "Photo Previews".bold());
+}
+
+/// Photo previews listed by pixel size when they can't be drawn
+pub fn render_photo_previews_skipped(photos: &[Photo], reason: &str) {
+    for (i, photo) in photos.iter().enumerate() {
+        let size = match (photo.width_px, photo.height_px) {
+            (Some(w), Some(h)) => format!("{} × {} px", w, h),
+            _ => "size unknown".to_string(),
+        };
+        println!("  {} {}", format!("Photo {}/{}:", i + 1, photos.len()).dimmed(), size);
+        if let Some(attribution) = PhotoContext::from(photo).attribution() {
+            println!("    {}", sanitize_display(&attribution).dimmed());
+        }
     }
+    println!("  {}", format!("(image preview skipped: {})", reason).dimmed());
+    println!();
+}
 
+/// One inline photo preview, numbered `index` of `total`, printed as soon as
+/// its download completes, with its authors underneath
+pub fn render_photo_preview(index: usize, total: usize, bytes: &[u8], size: PhotoSize, context: &PhotoContext) {
+    println!("  {} {}", format!("Photo {}/{}:", index, total).dimmed(), "─".repeat(30).dimmed());
+    render_image_bytes(bytes, size);
+    if let Some(attribution) = context.attribution() {
+        println!("  {}", sanitize_display(&attribution).dimmed());
+    }
     println!();
 }
 
 fn render_review(index: usize, review: &Review) {
-    let author = review
-        .author_attribution
-        .as_ref()
-        .map(|a| a.display_name.as_str())
-        .unwrap_or("Anonymous");
+    let author = sanitize_display(
+        review
+            .author_attribution
+            .as_ref()
+            .map(|a| a.display_name.as_str())
+            .unwrap_or("Anonymous"),
+    );
     let rating = review.rating.unwrap_or(0.0);
-    let time = review
-        .relative_publish_time_description
-        .as_deref()
-        .unwrap_or("");
+    let time = sanitize_display(
+        review
+            .relative_publish_time_description
+            .as_deref()
+            .unwrap_or(""),
+    );
 
     println!(
         "    {}. {} {} {}",
@@ -273,9 +937,101 @@ fn render_review(index: usize, review: &Review) {
     );
 
     if let Some(ref text) = review.text {
-        let display = truncate(&text.text, 200);
-        println!("       {}", display);
+        let text = sanitize_display(&text.text);
+        println!("       {}", truncate(&text, 200));
+    }
+}
+
+/// Text of `zupo reviews`: a header for the place, then each review with
+/// its full text word-wrapped to `width` columns (and the original-language
+/// text when `show_original` is set and it differs). Returned rather than
+/// printed so the caller can page it.
+pub fn format_reviews(place: &Place, reviews: &[Review], show_original: bool, width: usize, nf: NumberFormat) -> String {
+    use std::fmt::Write;
+
+    let mut out = String::new();
+    let name = place
+        .display_name
+        .as_ref()
+        .map(|n| sanitize_display(&n.text))
+        .unwrap_or_default();
+    let rating = match (place.rating, place.user_rating_count) {
+        (Some(r), count) => format!(
+            "{} {} {}",
+            star_string(r),
+            format_rating(r, nf),
+            format!("({} ratings)", format_count(count.unwrap_or(0) as u64, nf)).dimmed()
+        ),
+        (None, _) => String::new(),
+    };
+    let _ = writeln!(out, "{} {} {}", format!("{} Reviews", name).bold(), rating, "─".repeat(20).dimmed());
+    let _ = writeln!(out);
+
+    if reviews.is_empty() {
+        let _ = writeln!(out, "{}", "No reviews to show.".yellow());
+        return out;
+    }
+
+    let indent = "     ";
+    let text_width = width.saturating_sub(indent.len()).max(20);
+    // Line breaks in the review are kept; runs of blank lines become one
+    let write_text = |out: &mut String, text: &str| {
+        let mut blank = false;
+        for line in text.trim().lines() {
+            let line = sanitize_display(line);
+            if line.trim().is_empty() {
+                if !blank {
+                    let _ = writeln!(out);
+                }
+                blank = true;
+                continue;
+            }
+            blank = false;
+            for wrapped in wrap_text(&line, text_width) {
+                let _ = writeln!(out, "{}{}", indent, wrapped);
+            }
+        }
+    };
+
+    for (i, review) in reviews.iter().enumerate() {
+        let author = sanitize_display(
+            review
+                .author_attribution
+                .as_ref()
+                .map(|a| a.display_name.as_str())
+                .unwrap_or("Anonymous"),
+        );
+        let time = sanitize_display(review.relative_publish_time_description.as_deref().unwrap_or(""));
+        let _ = writeln!(
+            out,
+            "  {}. {} {} {}",
+            i + 1,
+            author.bold(),
+            star_string(review.rating.unwrap_or(0.0)),
+            time.dimmed()
+        );
+
+        match review.text {
+            Some(ref text) => write_text(&mut out, &text.text),
+            None => {
+                let _ = writeln!(out, "{}{}", indent, "(rating only)".dimmed());
+            }
+        }
+
+        if show_original {
+            if let Some(ref original) = review.original_text {
+                let translated = review.text.as_ref().is_some_and(|t| t.text != original.text);
+                if translated {
+                    let lang = original.language_code.as_deref().unwrap_or("original");
+                    let _ = writeln!(out);
+                    let _ = writeln!(out, "{}{}", indent, format!("Original ({}):", sanitize_display(lang)).dimmed());
+                    write_text(&mut out, &original.text);
+                }
+            }
+        }
+        let _ = writeln!(out);
     }
+    out
 }
 
 /// Render autocomplete suggestions
@@ -303,27 +1059,29 @@ pub fn render_autocomplete(response: &AutocompleteResponse) {
             let main = place_pred
                 .structured_format
                 .as_ref()
-                .and_then(|sf| sf.main_text.as_ref())
-                .map(|t| t.text.as_str());
+                .and_then(|sf| sf.main_text.as_ref());
             let secondary = place_pred
                 .structured_format
                 .as_ref()
                 .and_then(|sf| sf.secondary_text.as_ref())
-                .map(|t| t.text.as_str());
+                .map(|t| sanitize_display(&t.text));
 
             print!("  {} ", format!("{}.", i + 1).dimmed());
             if let Some(main_text) = main {
-                print!("{}", main_text.bold().cyan());
+                print!("{}", highlight_matches(main_text));
                 if let Some(sec) = secondary {
                     print!("  {}", sec.dimmed());
                 }
+            } else if let Some(ref full) = place_pred.text {
+                print!("{}", highlight_matches(full));
             } else {
-                print!("{}", text.bold().cyan());
+                print!("{}", sanitize_display(text).bold().cyan());
             }
 
             // Show types if available
             if let Some(ref types) = place_pred.types {
-                let type_str = types.iter().take(2).cloned().collect::<Vec<_>>().join(", ");
+                let type_str = sanitize_display(&types.iter().take(2).cloned().collect::<Vec<_>>().join(", "))
+                    .into_owned();
                 if !type_str.is_empty() {
                     print!("  [{}]", type_str.dimmed());
                 }
@@ -333,7 +1091,7 @@ pub fn render_autocomplete(response: &AutocompleteResponse) {
 
             // Show place ID
             if let Some(ref pid) = place_pred.place_id {
-                println!("     {} {}", "ID:".dimmed(), pid.dimmed());
+                println!("     {} {}", "ID:".dimmed(), sanitize_display(pid).dimmed());
             }
         } else if let Some(ref query_pred) = suggestion.query_prediction {
             let text = query_pred
@@ -345,27 +1103,232 @@ pub fn render_autocomplete(response: &AutocompleteResponse) {
                 "  {} {} {}",
                 format!("{}.", i + 1).dimmed(),
                 "🔍".dimmed(),
-                text.bold()
+                sanitize_display(text).bold()
             );
         }
         println!();
     }
 }
 
+/// How a field differs between two localized versions of a place
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FieldStatus {
+    Identical,
+    Translated,
+    MissingIn(usize),
+    Absent,
+}
+
+fn compare_field(a: Option<&str>, b: Option<&str>) -> FieldStatus {
+    match (a, b) {
+        (Some(x), Some(y)) if x == y => FieldStatus::Identical,
+        (Some(_), Some(_)) => FieldStatus::Translated,
+        (Some(_), None) => FieldStatus::MissingIn(1),
+        (None, Some(_)) => FieldStatus::MissingIn(0),
+        (None, None) => FieldStatus::Absent,
+    }
+}
+
+fn weekday_text(place: &Place) -> Option<String> {
+    place
+        .current_opening_hours
+        .as_ref()
+        .or(place.regular_opening_hours.as_ref())
+        .and_then(|h| h.weekday_descriptions.as_ref())
+        .filter(|d| !d.is_empty())
+        .map(|d| d.join("\n"))
+}
+
+/// Render a field-by-field comparison of one place fetched in two languages
+pub fn render_language_comparison(lang_a: &str, a: &Place, lang_b: &str, b: &Place) {
+    let langs = [lang_a, lang_b];
+    let fields: [(&str, Option<String>, Option<String>); 4] = [
+        (
+            "Name",
+            a.display_name.as_ref().map(|n| n.text.clone()),
+            b.display_name.as_ref().map(|n| n.text.clone()),
+        ),
+        ("Address", a.formatted_address.clone(), b.formatted_address.clone()),
+        (
+            "Summary",
+            a.editorial_summary.as_ref().and_then(|s| s.text.clone()),
+            b.editorial_summary.as_ref().and_then(|s| s.text.clone()),
+        ),
+        ("Hours", weekday_text(a), weekday_text(b)),
+    ];
+
+    println!(
+        "{} {} {}",
+        "Language comparison".bold(),
+        format!("({} ↔ {})", lang_a, lang_b).dimmed(),
+        "─".repeat(30).dimmed()
+    );
+
+    for (label, va, vb) in &fields {
+        let status = compare_field(va.as_deref(), vb.as_deref());
+        let tag = match status {
+            FieldStatus::Identical => "identical".dimmed().to_string(),
+            FieldStatus::Translated => "translated".green().to_string(),
+            FieldStatus::MissingIn(i) => format!("missing in {}", langs[i]).yellow().to_string(),
+            FieldStatus::Absent => "absent".dimmed().to_string(),
+        };
+        println!();
+        println!("  {} [{}]", format!("{:<8}", label).bold(), tag);
+
+        match status {
+            FieldStatus::Identical => {
+                for line in va.as_deref().unwrap_or_default().lines() {
+                    println!("      {}", sanitize_display(line));
+                }
+            }
+            FieldStatus::Absent => {}
+            _ => {
+                for (lang, value) in langs.iter().zip([va, vb]) {
+                    let Some(value) = value else { continue };
+                    for line in value.lines() {
+                        println!("    {} {}", format!("{:<4}", lang).cyan(), sanitize_display(line));
+                    }
+                }
+            }
+        }
+    }
+    println!();
+}
+
+/// Bold cyan text with the matched input portion underlined
+fn highlight_matches(text: &FormattedText) -> String {
+    text.segments()
+        .into_iter()
+        .map(|(seg, matched)| {
+            let seg = sanitize_display(seg);
+            if matched {
+                seg.bold().cyan().underline().to_string()
+            } else {
+                seg.bold().cyan().to_string()
+            }
+        })
+        .collect()
+}
+
 /// Render photo media result, optionally displaying the image inline
-pub fn render_photo(response: &PhotoMediaResponse, image_bytes: Option<&[u8]>) {
+/// With `skipped`, the image isn't drawn; its pixel size and the reason are
+/// printed instead
+pub fn render_photo(
+    response: &PhotoMediaResponse,
+    image_bytes: Option<&[u8]>,
+    size: PhotoSize,
+    skipped: Option<&str>,
+) {
     println!("{}", "Photo".bold());
-    println!("  {} {}", "Name:".bold(), response.name);
-    println!("  {} {}", "URL:".bold(), response.photo_uri.underline());
+    println!("  {} {}", "Name:".bold(), sanitize_display(&response.name));
+    println!("  {} {}", "URL:".bold(), sanitize_display(&response.photo_uri).underline());
 
-    if let Some(bytes) = image_bytes {
-        println!();
-        render_image_bytes(bytes, 60, 15);
+    match (image_bytes, skipped) {
+        (None, _) => {}
+        (Some(bytes), Some(reason)) => {
+            let dimensions = ImageReader::new(Cursor::new(bytes))
+                .with_guessed_format()
+                .ok()
+                .and_then(|r| r.into_dimensions().ok());
+            if let Some((width, height)) = dimensions {
+                println!("  {} {} × {} px", "Size:".bold(), width, height);
+            }
+            println!("  {}", format!("(image preview skipped: {})", reason).dimmed());
+        }
+        (Some(bytes), None) => {
+            println!();
+            render_image_bytes(bytes, size);
+        }
+    }
+    // Google's terms require the authors wherever the photo is shown
+    if let Some(attribution) = response.context.attribution() {
+        println!("  {}", sanitize_display(&attribution).dimmed());
+    }
+}
+
+/// Why inline images can't be drawn here, if they can't: stdout isn't a
+/// terminal, or (on Unix) `TERM` is unset or "dumb"
+pub fn inline_images_unavailable() -> Option<&'static str> {
+    if !std::io::stdout().is_terminal() {
+        return Some("not a TTY");
+    }
+    if cfg!(unix) && !std::env::var("TERM").is_ok_and(|t| !t.is_empty() && t != "dumb") {
+        return Some("unsupported terminal");
+    }
+    None
+}
+
+/// Largest pixel size the photo media endpoint accepts on either side
+const MAX_PHOTO_PX: u32 = 4800;
+
+/// Box, in terminal cells, that inline photos are scaled to fit. With
+/// half-block rendering one cell shows one pixel across and two down.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PhotoSize {
+    pub width: u32,
+    pub height: u32,
+}
+
+impl PhotoSize {
+    /// Three fifths of the terminal's width and half its height (80×24 when
+    /// stdout isn't a terminal)
+    pub fn for_terminal() -> Self {
+        let (cols, rows) = crossterm::terminal::size().unwrap_or((80, 24));
+        PhotoSize {
+            width: (u32::from(cols) * 3 / 5).max(10),
+            height: (u32::from(rows) / 2).max(5),
+        }
+    }
+
+    /// Parse "COLSxROWS", e.g. "80x20"
+    pub fn parse(s: &str) -> Result<Self, String> {
+        let (w, h) = s
+            .trim()
+            .split_once(['x', 'X', '×'])
+            .ok_or_else(|| format!("expected COLSxROWS (e.g. 80x20), got '{}'", s))?;
+        let cells = |v: &str| match v.trim().parse::<u32>() {
+            Ok(n) if n > 0 => Ok(n),
+            _ => Err(format!("expected COLSxROWS with positive numbers (e.g. 80x20), got '{}'", s)),
+        };
+        Ok(PhotoSize {
+            width: cells(w)?,
+            height: cells(h)?,
+        })
+    }
+
+    /// Cells an image of `width_px`×`height_px` takes when fit inside this
+    /// box with its aspect ratio kept
+    pub fn fit(&self, width_px: u32, height_px: u32) -> (u32, u32) {
+        if width_px == 0 || height_px == 0 {
+            return (self.width, self.height);
+        }
+        // Compare in pixels: the box is `width` px across and `2 * height` px down
+        let (w, h) = (width_px as f64, height_px as f64);
+        let scale = (self.width as f64 / w).min(2.0 * self.height as f64 / h);
+        let cols = (w * scale).round().max(1.0) as u32;
+        let rows = (h * scale / 2.0).round().max(1.0) as u32;
+        (cols.min(self.width), rows.min(self.height))
+    }
+
+    /// `maxWidthPx`/`maxHeightPx` to request so the download is about the
+    /// size it will be drawn at. Photo dimensions from place details pin the
+    /// exact width; without them both bounds are sent and the API keeps the
+    /// aspect ratio.
+    pub fn request_px(&self, photo_px: Option<(u32, u32)>) -> (Option<u32>, Option<u32>) {
+        let clamp = |px: u32| px.clamp(1, MAX_PHOTO_PX);
+        match photo_px {
+            Some((w, h)) if w > 0 && h > 0 => {
+                let (cols, _) = self.fit(w, h);
+                (Some(clamp(cols)), None)
+            }
+            _ => (Some(clamp(self.width)), Some(clamp(2 * self.height))),
+        }
     }
 }
 
-/// Render image bytes inline using Unicode half-blocks (works in Alacritty + tmux)
-pub fn render_image_bytes(bytes: &[u8], width: u32, height: u32) {
+/// Render image bytes inline using Unicode half-blocks (works in Alacritty + tmux),
+/// scaled to fit `size` with the image's aspect ratio kept
+pub fn render_image_bytes(bytes: &[u8], size: PhotoSize) {
     let cursor = Cursor::new(bytes);
     let reader = match ImageReader::new(cursor).with_guessed_format() {
         Ok(r) => r,
@@ -382,6 +1345,7 @@ pub fn render_image_bytes(bytes: &[u8], width: u32, height: u32) {
         }
     };
 
+    let (width, height) = size.fit(img.width(), img.height());
     let conf = ViuerConfig {
         width: Some(width),
         height: Some(height),
@@ -398,42 +1362,51 @@ pub fn render_image_bytes(bytes: &[u8], width: u32, height: u32) {
 }
 
 /// Render route search results
-pub fn render_route(response: &RouteSearchResponse) {
-    println!(
-        "{} {} {} {} {} {} {}",
-        "Route".bold(),
-        response.from.cyan(),
-        "→".dimmed(),
-        response.to.cyan(),
-        format!("({})", response.travel_mode).dimmed(),
-        "─".repeat(20).dimmed(),
-        format!("{} waypoints", response.waypoints.len()).dimmed()
-    );
-    println!();
+pub fn render_route(response: &RouteSearchResponse, units: Units, precision: usize, nf: NumberFormat) {
+    render_route_header(response, units, nf);
 
+    let mut prev_label: Option<&str> = None;
     for wp_result in &response.waypoints {
+        // Show the locality only when it changes from the previous waypoint
+        let label = wp_result.label.as_deref();
+        let near = match label {
+            Some(l) if label != prev_label => format!(" — near {}", sanitize_display(l)),
+            _ => String::new(),
+        };
+        prev_label = label;
+
         println!(
-            "  {} {} ({:.4}, {:.4})",
-            format!("Waypoint {}:", wp_result.waypoint_index + 1).bold().yellow(),
-            "📍",
-            wp_result.waypoint.latitude,
-            wp_result.waypoint.longitude
+            "  {}{}{} 📍 ({}, {})  {}",
+            format!("Waypoint {}", wp_result.waypoint_index + 1).bold().yellow(),
+            near.bold(),
+            ":".bold().yellow(),
+            format_coord(wp_result.waypoint.latitude, precision),
+            format_coord(wp_result.waypoint.longitude, precision),
+            format!(
+                "{} from origin",
+                format_distance(wp_result.distance_from_origin, units, nf)
+            )
+            .dimmed()
         );
 
         if wp_result.places.is_empty() {
             println!("    {}", "No places found near this waypoint.".dimmed());
         } else {
             for (j, place) in wp_result.places.iter().enumerate() {
-                let name = place
-                    .display_name
-                    .as_ref()
-                    .map(|n| n.text.as_str())
-                    .unwrap_or("Unknown");
-                let addr = place
-                    .short_formatted_address
-                    .as_deref()
-                    .or(place.formatted_address.as_deref())
-                    .unwrap_or("");
+                let name = sanitize_display(
+                    place
+                        .display_name
+                        .as_ref()
+                        .map(|n| n.text.as_str())
+                        .unwrap_or("Unknown"),
+                );
+                let addr = sanitize_display(
+                    place
+                        .short_formatted_address
+                        .as_deref()
+                        .or(place.formatted_address.as_deref())
+                        .unwrap_or(""),
+                );
 
                 print!("    {} {}", format!("{}.", j + 1).dimmed(), name.cyan());
                 if let Some(rating) = place.rating {
@@ -449,6 +1422,285 @@ pub fn render_route(response: &RouteSearchResponse) {
     }
 }
 
+/// Render `route --group-by place`: each place once, with how far into the trip it is
+pub fn render_route_places(
+    response: &RouteSearchResponse,
+    places: &[RoutePlace],
+    units: Units,
+    nf: NumberFormat,
+    addresses: AddressChoice,
+) {
+    render_route_header(response, units, nf);
+
+    if places.is_empty() {
+        println!("  {}", "No places found along this route.".dimmed());
+        return;
+    }
+    for (i, found) in places.iter().enumerate() {
+        let place = &found.place;
+        let name = sanitize_display(
+            place
+                .display_name
+                .as_ref()
+                .map(|n| n.text.as_str())
+                .unwrap_or("Unknown"),
+        );
+        let addr = sanitize_display(list_address(place, addresses).unwrap_or(""));
+
+        print!("  {} {}", format!("{}.", i + 1).dimmed(), name.cyan());
+        if let Some(rating) = place.rating {
+            print!("  {}", star_string(rating));
+        }
+        println!();
+        if !addr.is_empty() {
+            println!("     {}", addr.dimmed());
+        }
+        // Scale to the API's route length; the decoded polyline runs a little short or long
+        let into = response
+            .distance_meters
+            .map_or(found.distance_from_origin, |total| found.route_fraction * total as f64);
+        let mut position = format!("~{} into the trip", format_distance(into, units, nf));
+        if let Some(label) = response
+            .waypoints
+            .get(found.waypoint_index)
+            .and_then(|wp| wp.label.as_deref())
+        {
+            position.push_str(&format!(" (near {})", sanitize_display(label)));
+        }
+        println!("     {}", position.dimmed());
+    }
+    println!();
+}
+
+/// Route title, summary, and geometry lines shared by the route renderers
+fn render_route_header(response: &RouteSearchResponse, units: Units, nf: NumberFormat) {
+    println!(
+        "{} {} {} {} {} {} {}",
+        "Route".bold(),
+        sanitize_display(&response.from).cyan(),
+        "→".dimmed(),
+        sanitize_display(&response.to).cyan(),
+        format!("({})", response.travel_mode).dimmed(),
+        "─".repeat(20).dimmed(),
+        format!("{} waypoints", response.waypoints.len()).dimmed()
+    );
+    if response.route_source == RouteSource::StraightLine {
+        println!("  {}", "approximate straight-line route".yellow());
+    }
+    let summary = route_summary_parts(response, units, nf);
+    if response.route_index > 0 || !summary.is_empty() {
+        let mut parts = summary;
+        if response.route_index > 0 {
+            parts.insert(0, format!("alternative route {}", response.route_index));
+        }
+        println!("  {}", parts.join("  ·  ").dimmed());
+    }
+    for leg in &response.transit_legs {
+        println!("  {} {}", "🚆".dimmed(), transit_step_summary(leg));
+    }
+    if let Some(ref polyline) = response.polyline {
+        println!("  {} {}", "Polyline:".dimmed(), polyline);
+    }
+    if let Some(ref path) = response.path {
+        println!("  {}", format!("Path: {} points (see --json)", path.len()).dimmed());
+    }
+    println!();
+}
+
+/// Render a per-route summary for `route --compare-routes`
+pub fn render_route_comparison(responses: &[RouteSearchResponse], units: Units, nf: NumberFormat) {
+    let Some(first) = responses.first() else {
+        println!("{}", "No routes found.".yellow());
+        return;
+    };
+
+    println!(
+        "{} {} {} {} {} {} {}",
+        "Routes".bold(),
+        sanitize_display(&first.from).cyan(),
+        "→".dimmed(),
+        sanitize_display(&first.to).cyan(),
+        format!("({})", first.travel_mode).dimmed(),
+        "─".repeat(20).dimmed(),
+        format!("{} routes", responses.len()).dimmed()
+    );
+    if first.route_source == RouteSource::StraightLine {
+        println!("  {}", "approximate straight-line route".yellow());
+    }
+    println!();
+
+    for resp in responses {
+        let label = if resp.route_index == 0 {
+            "Route 0 (primary)".to_string()
+        } else {
+            format!("Route {}", resp.route_index)
+        };
+        println!("  {}", label.bold().yellow());
+
+        let summary = route_summary_parts(resp, units, nf);
+        if !summary.is_empty() {
+            println!("     {}", summary.join("  ·  "));
+        }
+
+        let ratings: Vec<f64> = resp.places().filter_map(|p| p.rating).collect();
+        let count = resp.places().count();
+        let mut matches = format!("{} matching places", format_count(count as u64, nf));
+        if !ratings.is_empty() {
+            let avg = ratings.iter().sum::<f64>() / ratings.len() as f64;
+            matches.push_str(&format!("  ·  avg {} {}", star_string(avg), format_rating(avg, nf)));
+        }
+        println!("     {}", matches);
+        println!();
+    }
+
+    println!(
+        "  {}",
+        "Use --route-index N to search along a specific route.".dimmed()
+    );
+}
+
+/// Cumulative monthly API usage with estimated costs (`zupo stats`)
+pub fn render_usage(month: &str, rows: &[crate::usage::EndpointUsage]) {
+    println!(
+        "{} {} {}",
+        "API usage".bold(),
+        month.cyan(),
+        "─".repeat(30).dimmed()
+    );
+    if rows.is_empty() {
+        println!("  {}", "No requests recorded this month.".dimmed());
+        return;
+    }
+
+    println!(
+        "  {:<16} {:>10} {:>12}",
+        "Endpoint".dimmed(),
+        "Requests".dimmed(),
+        "Est. cost".dimmed()
+    );
+    for row in rows {
+        let cost = row
+            .estimated_cost_usd
+            .map(|c| format!("${:.2}", c))
+            .unwrap_or_else(|| "?".to_string());
+        println!("  {:<16} {:>10} {:>12}", row.endpoint, row.requests, cost);
+    }
+
+    let total_requests: u64 = rows.iter().map(|r| r.requests).sum();
+    let total_cost: f64 = rows.iter().filter_map(|r| r.estimated_cost_usd).sum();
+    println!(
+        "  {:<16} {:>10} {:>12}",
+        "Total".bold(),
+        total_requests.to_string().bold(),
+        format!("${:.2}", total_cost).bold()
+    );
+    let coalesced: u64 = rows.iter().map(|r| r.coalesced).sum();
+    if coalesced > 0 {
+        println!(
+            "  {}",
+            format!("{} coalesced (shared an identical in-flight request, not billed)", coalesced).dimmed()
+        );
+    }
+    println!();
+    println!("  {}", format!("ESTIMATE ONLY. {}", crate::usage::DISCLAIMER).yellow());
+}
+
+/// Example invocations for `zupo examples`: descriptions dimmed, the zupo
+/// subcommand highlighted, and flags colored apart from their values
+pub fn render_examples(command: &str, examples: &[crate::examples::Example]) {
+    println!("{} {}", "Examples:".bold(), command.cyan());
+    for example in examples {
+        println!("  {}", format!("# {}", example.description).dimmed());
+        let line: Vec<String> = example
+            .command
+            .split(' ')
+            .map(|word| {
+                if word == "zupo" || word == command {
+                    word.bold().to_string()
+                } else if word.starts_with('-') {
+                    word.yellow().to_string()
+                } else if word == "|" {
+                    word.dimmed().to_string()
+                } else {
+                    word.to_string()
+                }
+            })
+            .collect();
+        println!("  {} {}", "$".dimmed(), line.join(" "));
+    }
+}
+
+/// `zupo note list`: each place's notes, numbered for `note remove`
+pub fn render_note_list(places: &[(&str, &[Note])]) {
+    for (i, (place_id, notes)) in places.iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+        println!("{}", sanitize_display(place_id).bold().cyan());
+        for (n, note) in notes.iter().enumerate() {
+            println!(
+                "  {} {} {}",
+                format!("{}.", n + 1).dimmed(),
+                sanitize_display(&note.text),
+                note.date().dimmed()
+            );
+        }
+    }
+}
+
+/// Place types grouped under category headers
+pub fn render_place_types(groups: &[(&str, Vec<&str>)]) {
+    if groups.is_empty() {
+        println!("{}", "No matching place types.".dimmed());
+        return;
+    }
+    for (i, (category, types)) in groups.iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+        println!("{} {}", category.bold(), format!("({})", types.len()).dimmed());
+        for t in types {
+            println!("  {}", t);
+        }
+    }
+}
+
+/// One-line transit step summary, e.g. "Take U4 toward Heiligenstadt (7 stops, dep 14:32)"
+fn transit_step_summary(step: &TransitDetails) -> String {
+    let mut line = format!(
+        "Take {}",
+        sanitize_display(step.line_name().unwrap_or("transit")).bold()
+    );
+    if let Some(ref headsign) = step.headsign {
+        line.push_str(&format!(" toward {}", sanitize_display(headsign)));
+    }
+
+    let mut extras = Vec::new();
+    if let Some(stops) = step.stop_count {
+        extras.push(format!("{} stop{}", stops, if stops == 1 { "" } else { "s" }));
+    }
+    if let Some(dep) = step.departure_display() {
+        extras.push(format!("dep {}", sanitize_display(dep)));
+    }
+    if !extras.is_empty() {
+        line.push_str(&format!(" ({})", extras.join(", ")));
+    }
+    line
+}
+
+fn route_summary_parts(response: &RouteSearchResponse, units: Units, nf: NumberFormat) -> Vec<String> {
+    let mut parts = Vec::new();
+    if let Some(meters) = response.distance_meters {
+        parts.push(format_distance(meters as f64, units, nf));
+    }
+    match (response.duration_seconds, &response.duration) {
+        (Some(secs), _) => parts.push(humanize_duration(Duration::from_secs(secs))),
+        (None, Some(raw)) => parts.push(raw.clone()),
+        (None, None) => {}
+    }
+    parts
+}
+
 // ─── Helpers ────────────────────────────────────────────────────────────────
 
 pub fn star_string(rating: f64) -> String {
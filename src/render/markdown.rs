@@ -0,0 +1,159 @@
+use crate::api::types::{price_level_display, Place, Review};
+use crate::format::sanitize_display;
+use crate::notes::Note;
+
+/// Escape characters that CommonMark (and table cells) would interpret, after
+/// dropping control characters and folding line breaks (see `sanitize_display`)
+pub fn escape(text: &str) -> String {
+    let text = sanitize_display(text);
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' | '*' | '_' | '|' | '[' | ']' | '`' | '<' | '>' | '#' => {
+                out.push('\\');
+                out.push(c);
+            }
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+/// Place details as a shareable CommonMark snippet (no ANSI)
+pub fn place_details(place: &Place, notes: &[Note]) -> String {
+    let mut out = String::new();
+    let name = escape(place_name(place));
+
+    match place.google_maps_uri {
+        Some(ref uri) => out.push_str(&format!("**[{}]({})**\n", name, sanitize_display(uri))),
+        None => out.push_str(&format!("**{}**\n", name)),
+    }
+    if let Some(ref primary) = place.primary_type_display_name {
+        out.push_str(&format!("_{}_\n", escape(&primary.text)));
+    }
+    out.push('\n');
+
+    if let Some(rating) = place.rating {
+        let count = place.user_rating_count.unwrap_or(0);
+        out.push_str(&format!("- **Rating:** {} ★ ({} reviews)\n", rating, count));
+    }
+    if let Some(ref price) = place.price_level {
+        out.push_str(&format!("- **Price:** {}\n", escape(price_level_display(price))));
+    }
+    if let Some(ref addr) = place.formatted_address {
+        out.push_str(&format!("- **Address:** {}\n", escape(addr)));
+    }
+    if let Some(phone) = place
+        .international_phone_number
+        .as_ref()
+        .or(place.national_phone_number.as_ref())
+    {
+        out.push_str(&format!("- **Phone:** {}\n", escape(phone)));
+    }
+    if let Some(ref uri) = place.website_uri {
+        out.push_str(&format!("- **Website:** <{}>\n", sanitize_display(uri)));
+    }
+    let hours = place
+        .current_opening_hours
+        .as_ref()
+        .or(place.regular_opening_hours.as_ref())
+        .and_then(|h| h.weekday_descriptions.as_ref())
+        .filter(|d| !d.is_empty());
+    if let Some(descs) = hours {
+        out.push_str("- **Hours:**\n");
+        for desc in descs {
+            out.push_str(&format!("  - {}\n", escape(desc)));
+        }
+    }
+
+    if let Some(text) = place.editorial_summary.as_ref().and_then(|s| s.text.as_ref()) {
+        out.push_str(&format!("\n> {}\n", escape(text)));
+    }
+
+    if !notes.is_empty() {
+        out.push_str("\n**Your notes**\n\n");
+        for note in notes {
+            out.push_str(&format!("- {} _{}_\n", escape(&note.text), note.date()));
+        }
+    }
+
+    if let Some(reviews) = place.reviews.as_ref().filter(|r| !r.is_empty()) {
+        out.push_str("\n**Reviews**\n\n");
+        for review in reviews.iter().take(3) {
+            out.push_str(&review_item(review));
+        }
+    }
+
+    out
+}
+
+fn review_item(review: &Review) -> String {
+    let author = review
+        .author_attribution
+        .as_ref()
+        .map(|a| a.display_name.as_str())
+        .unwrap_or("Anonymous");
+    let mut line = format!("- **{}**", escape(author));
+    if let Some(rating) = review.rating {
+        line.push_str(&format!(" {} ★", rating));
+    }
+    if let Some(ref time) = review.relative_publish_time_description {
+        line.push_str(&format!(" _{}_", escape(time)));
+    }
+    if let Some(ref text) = review.text {
+        line.push_str(&format!(": {}", escape(super::truncate(&text.text, 200))));
+    }
+    line.push('\n');
+    line
+}
+
+/// A list of places as a CommonMark table
+pub fn places_table(places: &[Place]) -> String {
+    let mut out = String::from("| # | Name | Type | Rating | Price | Address |\n");
+    out.push_str("|---|---|---|---|---|---|\n");
+
+    for (i, place) in places.iter().enumerate() {
+        let name = match place.google_maps_uri {
+            Some(ref uri) => format!("[{}]({})", escape(place_name(place)), sanitize_display(uri)),
+            None => escape(place_name(place)),
+        };
+        let kind = place
+            .primary_type_display_name
+            .as_ref()
+            .map(|t| escape(&t.text))
+            .unwrap_or_default();
+        let rating = place
+            .rating
+            .map(|r| format!("{} ({})", r, place.user_rating_count.unwrap_or(0)))
+            .unwrap_or_default();
+        let price = place
+            .price_level
+            .as_deref()
+            .map(|p| escape(price_level_display(p)))
+            .unwrap_or_default();
+        let address = place
+            .formatted_address
+            .as_deref()
+            .map(escape)
+            .unwrap_or_default();
+
+        out.push_str(&format!(
+            "| {} | {} | {} | {} | {} | {} |\n",
+            i + 1,
+            name,
+            kind,
+            rating,
+            price,
+            address
+        ));
+    }
+    out
+}
+
+fn place_name(place: &Place) -> &str {
+    place
+        .display_name
+        .as_ref()
+        .map(|n| n.text.as_str())
+        .unwrap_or("Unknown")
+}
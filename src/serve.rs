@@ -0,0 +1,332 @@
+use std::convert::Infallible;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use http_body_util::{BodyExt, Full, Limited};
+use hyper::body::{Bytes, Incoming};
+use hyper::header::{HeaderValue, ALLOW, AUTHORIZATION, CONTENT_TYPE, HOST};
+use hyper::server::conn::http1;
+use hyper::service::service_fn;
+use hyper::{Method, Request, Response, StatusCode};
+use hyper_util::rt::TokioIo;
+use serde::de::DeserializeOwned;
+use serde_json::{json, Value};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::watch;
+use tokio::task::JoinSet;
+use tracing::{debug, info, warn};
+
+use crate::api::client::Client;
+use crate::api::errors::Error;
+use crate::api::types::{AutocompleteRequest, DetailsRequest, NearbySearchRequest, SearchRequest};
+use crate::config::Config;
+
+/// `zupo serve --listen` default
+pub const DEFAULT_LISTEN: &str = "127.0.0.1:7777";
+
+/// Largest request body accepted; real requests are a few hundred bytes
+const MAX_BODY_BYTES: usize = 64 * 1024;
+
+/// How long open connections get to finish their requests after Ctrl+C
+const SHUTDOWN_GRACE: Duration = Duration::from_secs(10);
+
+/// Endpoints, all `POST` with a JSON body
+const ENDPOINTS: [&str; 4] = ["/search", "/nearby", "/details", "/autocomplete"];
+
+/// Parse a `--listen` address. Only loopback addresses are accepted: the
+/// server has no authentication beyond the optional bearer token.
+pub fn parse_listen(text: &str) -> Result<SocketAddr, String> {
+    let addr: SocketAddr = text
+        .parse()
+        .map_err(|_| format!("'{}' is not an address like {}", text, DEFAULT_LISTEN))?;
+    if !addr.ip().is_loopback() {
+        return Err(format!(
+            "{} is not a loopback address; zupo serve only listens on 127.0.0.1 or ::1",
+            addr.ip()
+        ));
+    }
+    Ok(addr)
+}
+
+/// State shared by every connection
+struct Server {
+    client: Client,
+    cfg: Config,
+    token: Option<String>,
+}
+
+/// Why a request failed, as an HTTP status and JSON error body
+enum Rejection {
+    NotFound,
+    MethodNotAllowed,
+    Unauthorized,
+    /// Host header isn't a loopback name (a DNS-rebinding page, say)
+    ForbiddenHost,
+    UnsupportedMediaType,
+    PayloadTooLarge,
+    BadRequest(String),
+    Api(Error),
+}
+
+impl From<Error> for Rejection {
+    fn from(e: Error) -> Self {
+        Rejection::Api(e)
+    }
+}
+
+impl Rejection {
+    fn status(&self) -> StatusCode {
+        match self {
+            Rejection::NotFound => StatusCode::NOT_FOUND,
+            Rejection::MethodNotAllowed => StatusCode::METHOD_NOT_ALLOWED,
+            Rejection::Unauthorized => StatusCode::UNAUTHORIZED,
+            Rejection::ForbiddenHost => StatusCode::FORBIDDEN,
+            Rejection::UnsupportedMediaType => StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            Rejection::PayloadTooLarge => StatusCode::PAYLOAD_TOO_LARGE,
+            Rejection::BadRequest(_) | Rejection::Api(Error::Validation { .. }) => StatusCode::BAD_REQUEST,
+            Rejection::Api(Error::Api { status: 400, .. }) => StatusCode::BAD_REQUEST,
+            Rejection::Api(Error::Api { status: 404, .. }) => StatusCode::NOT_FOUND,
+            Rejection::Api(Error::Api { status: 429, .. }) => StatusCode::TOO_MANY_REQUESTS,
+            Rejection::Api(Error::Http(e)) if e.is_timeout() => StatusCode::GATEWAY_TIMEOUT,
+            Rejection::Api(Error::MissingApiKey) => StatusCode::INTERNAL_SERVER_ERROR,
+            Rejection::Api(_) => StatusCode::BAD_GATEWAY,
+        }
+    }
+
+    /// `{"error": {"message": ..., "field"?: ..., "upstreamStatus"?: ...}}`
+    fn body(&self) -> Value {
+        let message = match self {
+            Rejection::NotFound => format!("unknown endpoint; use one of {}", ENDPOINTS.join(", ")),
+            Rejection::MethodNotAllowed => "use POST with a JSON body".to_string(),
+            Rejection::Unauthorized => "missing or wrong bearer token".to_string(),
+            Rejection::ForbiddenHost => "Host must be localhost, 127.0.0.1, or [::1]".to_string(),
+            Rejection::UnsupportedMediaType => "Content-Type must be application/json".to_string(),
+            Rejection::PayloadTooLarge => format!("request body is over {} bytes", MAX_BODY_BYTES),
+            Rejection::BadRequest(message) => message.clone(),
+            Rejection::Api(e) => e.to_string(),
+        };
+        let mut error = json!({ "message": message });
+        match self {
+            Rejection::Api(Error::Validation { field, .. }) => error["field"] = json!(field),
+            Rejection::Api(Error::Api { status, .. }) => error["upstreamStatus"] = json!(status),
+            _ => {}
+        }
+        json!({ "error": error })
+    }
+}
+
+/// Serve the JSON endpoints on `listen` until Ctrl+C, sharing one `Client`
+/// (and so its connection pool, in-flight coalescing, and rate limiter)
+/// across every request. Open connections get `SHUTDOWN_GRACE` to finish.
+pub async fn run(client: Client, cfg: Config, listen: SocketAddr, token: Option<String>) -> std::io::Result<()> {
+    let listener = TcpListener::bind(listen).await?;
+    eprintln!(
+        "Listening on http://{} ({}); Ctrl+C to stop",
+        listener.local_addr()?,
+        ENDPOINTS.join(", ")
+    );
+
+    let server = Arc::new(Server { client, cfg, token });
+    let (shutdown_tx, shutdown_rx) = watch::channel(false);
+    let mut connections = JoinSet::new();
+    let ctrl_c = tokio::signal::ctrl_c();
+    tokio::pin!(ctrl_c);
+
+    loop {
+        tokio::select! {
+            accepted = listener.accept() => match accepted {
+                Ok((stream, peer)) => {
+                    debug!(%peer, "connection accepted");
+                    connections.spawn(serve_connection(stream, server.clone(), shutdown_rx.clone()));
+                }
+                // Out of file descriptors, say: back off instead of spinning
+                Err(e) => {
+                    warn!(error = %e, "accept failed");
+                    tokio::time::sleep(Duration::from_millis(100)).await;
+                }
+            },
+            Some(_) = connections.join_next(), if !connections.is_empty() => {}
+            _ = &mut ctrl_c => break,
+        }
+    }
+
+    drop(listener);
+    eprintln!("Shutting down");
+    let _ = shutdown_tx.send(true);
+    let drained = tokio::time::timeout(SHUTDOWN_GRACE, async {
+        while connections.join_next().await.is_some() {}
+    })
+    .await;
+    if drained.is_err() {
+        connections.abort_all();
+    }
+    Ok(())
+}
+
+/// Serve one connection's requests; on shutdown, finish the request in
+/// progress and close
+async fn serve_connection(stream: TcpStream, server: Arc<Server>, mut shutdown: watch::Receiver<bool>) {
+    let service = service_fn(move |req| {
+        let server = server.clone();
+        async move { Ok::<_, Infallible>(server.handle(req).await) }
+    });
+    let conn = http1::Builder::new().serve_connection(TokioIo::new(stream), service);
+    tokio::pin!(conn);
+    tokio::select! {
+        result = conn.as_mut() => {
+            if let Err(e) = result {
+                debug!(error = %e, "connection error");
+            }
+            return;
+        }
+        _ = shutdown.changed() => conn.as_mut().graceful_shutdown(),
+    }
+    let _ = conn.await;
+}
+
+impl Server {
+    async fn handle(&self, req: Request<Incoming>) -> Response<Full<Bytes>> {
+        let started = Instant::now();
+        let path = req.uri().path().to_string();
+        let response = match self.route(req).await {
+            Ok(value) => json_response(StatusCode::OK, &value),
+            Err(rejection) => {
+                let mut response = json_response(rejection.status(), &rejection.body());
+                if let Rejection::MethodNotAllowed = rejection {
+                    response.headers_mut().insert(ALLOW, HeaderValue::from_static("POST"));
+                }
+                response
+            }
+        };
+        info!(
+            path,
+            status = response.status().as_u16(),
+            elapsed_ms = started.elapsed().as_millis() as u64,
+            "request"
+        );
+        response
+    }
+
+    async fn route(&self, req: Request<Incoming>) -> Result<Value, Rejection> {
+        self.check_headers(&req)?;
+        let path = req.uri().path().to_string();
+        if !ENDPOINTS.contains(&path.as_str()) {
+            return Err(Rejection::NotFound);
+        }
+        if req.method() != Method::POST {
+            return Err(Rejection::MethodNotAllowed);
+        }
+        let body = read_body(req).await?;
+
+        match path.as_str() {
+            "/search" => {
+                let mut req: SearchRequest = parse_body(&body)?;
+                self.fill_locale(&mut req.language, &mut req.region);
+                Ok(serde_json::to_value(self.client.search(&req).await?).unwrap())
+            }
+            "/nearby" => {
+                let mut req: NearbySearchRequest = parse_body(&body)?;
+                self.fill_locale(&mut req.language, &mut req.region);
+                Ok(serde_json::to_value(self.client.nearby_search_rings(&req).await?.response).unwrap())
+            }
+            "/details" => {
+                let mut req: DetailsRequest = parse_body(&body)?;
+                self.fill_locale(&mut req.language, &mut req.region);
+                Ok(serde_json::to_value(self.client.details(&req).await?).unwrap())
+            }
+            "/autocomplete" => {
+                let mut req: AutocompleteRequest = parse_body(&body)?;
+                self.fill_locale(&mut req.language, &mut req.region);
+                Ok(serde_json::to_value(self.client.autocomplete(&req).await?).unwrap())
+            }
+            _ => Err(Rejection::NotFound),
+        }
+    }
+
+    /// Token, Host, and Content-Type checks. A web page can reach a loopback
+    /// port too, so requests must name a loopback Host (defeating DNS
+    /// rebinding) and send JSON, which browsers won't do cross-origin
+    /// without a CORS preflight this server never answers.
+    fn check_headers(&self, req: &Request<Incoming>) -> Result<(), Rejection> {
+        if let Some(ref token) = self.token {
+            let given = req
+                .headers()
+                .get(AUTHORIZATION)
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.strip_prefix("Bearer "))
+                .unwrap_or("");
+            if !constant_time_eq(given.as_bytes(), token.as_bytes()) {
+                return Err(Rejection::Unauthorized);
+            }
+        }
+
+        let host = req.headers().get(HOST).and_then(|v| v.to_str().ok()).unwrap_or("");
+        if !is_loopback_host(host) {
+            return Err(Rejection::ForbiddenHost);
+        }
+
+        if req.method() == Method::POST {
+            let json = req
+                .headers()
+                .get(CONTENT_TYPE)
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.split(';').next())
+                .is_some_and(|v| v.trim().eq_ignore_ascii_case("application/json"));
+            if !json {
+                return Err(Rejection::UnsupportedMediaType);
+            }
+        }
+        Ok(())
+    }
+
+    /// Config `defaults.language` / `defaults.region` where the body has none,
+    /// as the CLI commands do
+    fn fill_locale(&self, language: &mut Option<String>, region: &mut Option<String>) {
+        if language.is_none() {
+            language.clone_from(&self.cfg.defaults.language);
+        }
+        if region.is_none() {
+            region.clone_from(&self.cfg.defaults.region);
+        }
+    }
+}
+
+async fn read_body(req: Request<Incoming>) -> Result<Bytes, Rejection> {
+    match Limited::new(req.into_body(), MAX_BODY_BYTES).collect().await {
+        Ok(collected) => Ok(collected.to_bytes()),
+        Err(e) if e.is::<http_body_util::LengthLimitError>() => Err(Rejection::PayloadTooLarge),
+        Err(e) => Err(Rejection::BadRequest(format!("could not read the request body: {}", e))),
+    }
+}
+
+fn parse_body<T: DeserializeOwned>(body: &[u8]) -> Result<T, Rejection> {
+    serde_json::from_slice(body).map_err(|e| Rejection::BadRequest(format!("invalid request body: {}", e)))
+}
+
+fn json_response(status: StatusCode, value: &Value) -> Response<Full<Bytes>> {
+    let mut response = Response::new(Full::new(Bytes::from(value.to_string())));
+    *response.status_mut() = status;
+    response
+        .headers_mut()
+        .insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+    response
+}
+
+/// `localhost`, `127.0.0.1`, `[::1]` and friends, with or without a port
+fn is_loopback_host(host: &str) -> bool {
+    let name = match host.strip_prefix('[') {
+        Some(rest) => rest.split(']').next().unwrap_or(""),
+        None => host.split(':').next().unwrap_or(""),
+    };
+    name.eq_ignore_ascii_case("localhost")
+        || name
+            .parse::<std::net::IpAddr>()
+            .is_ok_and(|ip| ip.is_loopback())
+}
+
+/// Compare without returning early, so response timing doesn't reveal how
+/// much of a guessed token matched
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
@@ -1,19 +1,25 @@
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::sync::Arc;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 use ratatui::widgets::ListState;
-use tokio::sync::mpsc::UnboundedSender;
+use tokio::sync::mpsc::Sender;
 use tokio::task::JoinHandle;
 use tracing::info;
 use tui_input::Input;
 
 use crate::api::client::Client;
 use crate::api::types::{
-    price_level_to_api, AutocompleteRequest, Circle, DetailsRequest, LatLng, Place, SearchRequest,
-    Suggestion,
+    price_level_to_api, AutocompleteRequest, Circle, DetailsRequest, LatLng, PhotoMediaRequest,
+    Place, RouteSummary, SearchRequest, Suggestion, TravelMode,
 };
-use crate::config::Config;
+use crate::api::types::radius_problem;
+use crate::config::{Config, TuiFilters};
+use crate::format::{sanitize_display, AddressChoice, NumberFormat};
+use crate::locale;
+use crate::notes::PlaceNotes;
 use crate::tui::event::Action;
+use crate::usage::MonthlyUsage;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Focus {
@@ -22,6 +28,112 @@ pub enum Focus {
     ResultsList,
     FilterPanel,
     FilterEditing, // editing a text field inside the filter panel
+    RouteMode,     // choosing a travel mode after `R` on a result
+    MessageLog,    // the status message history popup
+    RawJson,       // the JSON popup for one place (`J`)
+}
+
+/// How long a typed count prefix (e.g. the `5` in `5j`) stays pending
+pub const COUNT_PREFIX_TIMEOUT: Duration = Duration::from_millis(1500);
+
+/// How many status messages the message log keeps
+pub const STATUS_LOG_CAPACITY: usize = 50;
+
+/// A status bar message as kept in the message log
+#[derive(Debug, Clone)]
+pub struct StatusEntry {
+    pub at: Instant,
+    pub message: String,
+    pub is_error: bool,
+}
+
+/// A place's JSON as shown by the `J` popup
+#[derive(Debug, Clone)]
+pub struct RawJson {
+    pub name: String,
+    /// False when the place was re-serialized because its raw JSON wasn't kept
+    pub as_received: bool,
+    /// Pretty-printed
+    pub text: String,
+}
+
+/// The selected place's photos, shown one at a time: `p` opens the gallery
+/// and `]`/`[` step through it, fetching each photo's URL on first view
+#[derive(Debug, Clone)]
+pub struct GalleryState {
+    pub place_id: String,
+    /// Photo resource names, in the API's order
+    pub photos: Vec<String>,
+    pub index: usize,
+    /// Fetched photo URLs by index, only those within `GALLERY_WINDOW` of `index`
+    pub urls: HashMap<usize, String>,
+    /// Indexes with a request in flight
+    pub pending: HashSet<usize>,
+    /// Index the user is waiting on (the spinner is for it)
+    pub waiting: Option<usize>,
+}
+
+impl GalleryState {
+    pub fn current_url(&self) -> Option<&str> {
+        self.urls.get(&self.index).map(String::as_str)
+    }
+
+    /// Steps from `index` to `i`, either way round (the gallery wraps)
+    pub fn distance(&self, i: usize) -> usize {
+        let d = i.abs_diff(self.index);
+        d.min(self.photos.len() - d)
+    }
+
+    /// Drop URLs that fell out of the window around `index`
+    fn evict(&mut self) {
+        let far: Vec<usize> = self
+            .urls
+            .keys()
+            .copied()
+            .filter(|&i| self.distance(i) > GALLERY_WINDOW)
+            .collect();
+        for i in far {
+            self.urls.remove(&i);
+        }
+    }
+}
+
+/// Photo URLs kept either side of the one shown; others are fetched again when revisited
+pub const GALLERY_WINDOW: usize = 2;
+
+/// How many fetched place details are kept for the session (least recently used evicted)
+pub const DETAILS_CACHE_CAPACITY: usize = 100;
+
+/// How many route summaries are kept for the session (least recently used evicted)
+pub const ROUTE_CACHE_CAPACITY: usize = 200;
+
+/// Reviews kept per fetched place; `v` refetches the selected place with all of them
+pub const KEPT_REVIEWS: usize = 3;
+
+/// How long the "end of list" indicator stays visible
+pub const END_FLASH_DURATION: Duration = Duration::from_millis(800);
+
+/// Typing pause before a live preview search is sent (`tui.instant_results`)
+pub const PREVIEW_DEBOUNCE: Duration = Duration::from_millis(800);
+
+/// Shortest input, in characters, that gets a live preview
+pub const PREVIEW_MIN_CHARS: usize = 3;
+
+/// Live preview searches allowed per session before previews pause
+pub const PREVIEW_SESSION_BUDGET: usize = 50;
+
+/// Move `current` by `delta` within `0..len` (len > 0), wrapping around when
+/// `wrap` is set. Returns the new index and whether the move ran past either end.
+pub fn step_index(current: usize, delta: isize, len: usize, wrap: bool) -> (usize, bool) {
+    let len = len as isize;
+    let target = current as isize + delta;
+    let past_end = target < 0 || target >= len;
+    let next = if wrap {
+        target.rem_euclid(len)
+    } else {
+        target.clamp(0, len - 1)
+    };
+    (next as usize, past_end)
 }
 
 /// Which filter row is selected
@@ -30,17 +142,23 @@ pub enum FilterField {
     Type,      // included_type (text)
     Radius,    // cycle: 500, 1000, 2000, 5000, 10000, 25000, 50000
     MinRating, // cycle: None, 3.0, 3.5, 4.0, 4.5
+    MinReviews, // cycle: None, 50, 200, 1000 (applied locally)
     Price,     // toggle individual price levels 0-4
     OpenNow,   // toggle bool
+    Language,  // language code (text)
+    Region,    // region code (text)
 }
 
 impl FilterField {
-    pub const ALL: [FilterField; 5] = [
+    pub const ALL: [FilterField; 8] = [
         FilterField::Type,
         FilterField::Radius,
         FilterField::MinRating,
+        FilterField::MinReviews,
         FilterField::Price,
         FilterField::OpenNow,
+        FilterField::Language,
+        FilterField::Region,
     ];
 
     pub fn from_index(i: usize) -> Self {
@@ -53,79 +171,312 @@ pub struct App {
     pub focus: Focus,
     pub loading: bool,
     pub status: Option<(String, bool)>, // (message, is_error)
+    pub status_log: VecDeque<StatusEntry>, // newest last, at most STATUS_LOG_CAPACITY
+    pub log_scroll: u16,
     pub last_ctrl_c: Option<Instant>,
+    pub last_quit: Option<Instant>, // first `q` while a request was in flight
+    pub last_request: Option<(String, Duration)>, // (endpoint, latency)
+    pub pending_count: Option<(usize, Instant)>, // vim-style count prefix, last digit time
+    pub end_flash: Option<Instant>, // when navigation last hit the end of the results
+    pub needs_full_redraw: bool, // set on resize: clear the terminal before the next frame
 
     // Search input
     pub input: Input,
     pub autocomplete: Vec<Suggestion>,
     pub ac_selected: usize,
+    pub ac_offset: usize, // first suggestion shown; the dropdown scrolls when it can't fit them all
     pub ac_task: Option<JoinHandle<()>>,
+    pub preview_task: Option<JoinHandle<()>>,
+    pub search_seq: u64, // tags each search and preview; older results are ignored
+    pub previews_sent: usize,
+    pub preview_paused_noted: bool,
+    pub searches_this_month: u64, // text searches in the usage file plus this session's
 
     // Filters
     pub filter_selected: usize, // index into FilterField::ALL
     pub filter_type_input: Input,
-    pub filter_type_matches: Vec<&'static str>,
-    pub filter_type_match_idx: usize,
+    pub filter_matches: Vec<&'static str>, // picker for the text field being edited
+    pub filter_match_idx: usize,
     pub filter_radius: f64,            // meters
     pub filter_min_rating: Option<f64>,
+    pub filter_min_reviews: Option<u32>,
     pub filter_price_levels: [bool; 5], // indices 0-4 (Free, $, $$, $$$, $$$$)
     pub filter_open_now: bool,
+    pub filter_language_input: Input,
+    pub filter_region_input: Input,
 
     // Results
     pub results: Vec<Place>,
     pub results_state: ListState,
+    pub previewing: bool, // results come from a live preview, not Enter
 
     // Details (right pane)
     pub detail: Option<Place>,
     pub detail_scroll: u16,
+    pub details_cache: HashMap<String, Place>, // by cache key (place id + language/region)
+    pub details_lru: VecDeque<String>,         // cache keys, least recently used first
+    pub details_in_flight: HashSet<String>,    // cache keys with a request pending
+    pub photo_gallery: Option<GalleryState>, // selected place only
+    pub all_reviews: Option<String>, // cache key whose pane keeps every review (`v`)
+    pub pinned: Option<Place>, // compare mode: the place pinned with `c`
+    pub comparing: bool,       // details pane shows pinned vs selected
+    pub route_cache: HashMap<(String, TravelMode), RouteSummary>, // (place id, mode), per session
+    pub route_lru: VecDeque<(String, TravelMode)>, // route cache keys, least recently used first
+    pub notes: PlaceNotes, // `zupo note` annotations, read at startup
+    pub raw_json: Option<RawJson>, // open `J` popup
+    pub raw_scroll: u16,
 
     // Shared
     pub client: Arc<Client>,
     pub config: Config,
     pub session_token: String,
-    pub action_tx: UnboundedSender<Action>,
+    pub action_tx: Sender<Action>,
 }
 
 impl App {
-    pub fn new(client: Arc<Client>, config: Config, action_tx: UnboundedSender<Action>) -> Self {
-        Self {
+    pub fn new(client: Arc<Client>, config: Config, action_tx: Sender<Action>) -> Self {
+        let mut app = Self {
             should_quit: false,
             focus: Focus::SearchInput,
             loading: false,
             status: None,
+            status_log: VecDeque::with_capacity(STATUS_LOG_CAPACITY),
+            log_scroll: 0,
             last_ctrl_c: None,
+            last_quit: None,
+            last_request: None,
+            pending_count: None,
+            end_flash: None,
+            needs_full_redraw: false,
 
             input: Input::default(),
             autocomplete: Vec::new(),
             ac_selected: 0,
+            ac_offset: 0,
             ac_task: None,
+            preview_task: None,
+            search_seq: 0,
+            previews_sent: 0,
+            preview_paused_noted: false,
+            searches_this_month: MonthlyUsage::load_current(&config)
+                .requests
+                .get("searchText")
+                .copied()
+                .unwrap_or(0),
 
             filter_selected: 0,
             filter_type_input: Input::default(),
-            filter_type_matches: Vec::new(),
-            filter_type_match_idx: 0,
+            filter_matches: Vec::new(),
+            filter_match_idx: 0,
             filter_radius: config.default_radius(),
             filter_min_rating: None,
+            filter_min_reviews: None,
             filter_price_levels: [false; 5],
             filter_open_now: false,
+            filter_language_input: Input::new(config.defaults.language.clone().unwrap_or_default()),
+            filter_region_input: Input::new(config.defaults.region.clone().unwrap_or_default()),
 
             results: Vec::new(),
             results_state: ListState::default(),
+            previewing: false,
 
             detail: None,
             detail_scroll: 0,
+            details_cache: HashMap::new(),
+            details_lru: VecDeque::with_capacity(DETAILS_CACHE_CAPACITY),
+            details_in_flight: HashSet::new(),
+            photo_gallery: None,
+            all_reviews: None,
+            pinned: None,
+            comparing: false,
+            route_cache: HashMap::new(),
+            route_lru: VecDeque::with_capacity(ROUTE_CACHE_CAPACITY),
+            notes: PlaceNotes::load_or_default(&config),
+            raw_json: None,
+            raw_scroll: 0,
 
             client,
             config,
             session_token: uuid::Uuid::new_v4().to_string(),
             action_tx,
+        };
+        if app.config.persist_filters() {
+            let saved = app.config.tui.filters.clone();
+            app.restore_filters(&saved);
+        }
+        app
+    }
+
+    /// Apply filters saved by an earlier session, skipping values that are
+    /// out of range (the config file may have been edited by hand)
+    fn restore_filters(&mut self, saved: &TuiFilters) {
+        if let Some(t) = saved.included_type.as_deref().filter(|t| crate::place_types::is_known_type(t)) {
+            self.filter_type_input = Input::new(t.to_string());
+        }
+        if let Some(r) = saved.radius.filter(|&r| radius_problem(r).is_none()) {
+            self.filter_radius = r;
+        }
+        self.filter_min_rating = saved.min_rating.filter(|r| (0.0..=5.0).contains(r));
+        self.filter_min_reviews = saved.min_reviews;
+        for &level in &saved.price_levels {
+            if let Some(selected) = usize::try_from(level).ok().and_then(|i| self.filter_price_levels.get_mut(i)) {
+                *selected = true;
+            }
+        }
+        self.filter_open_now = saved.open_now.unwrap_or(false);
+    }
+
+    /// The filter panel as saved on exit; values still at their defaults are left out
+    pub fn saved_filters(&self) -> TuiFilters {
+        TuiFilters {
+            included_type: Some(self.filter_type_input.value().to_string()).filter(|t| !t.is_empty()),
+            radius: Some(self.filter_radius).filter(|&r| r != self.config.default_radius()),
+            min_rating: self.filter_min_rating,
+            min_reviews: self.filter_min_reviews,
+            price_levels: (0..self.filter_price_levels.len() as i64)
+                .filter(|&i| self.filter_price_levels[i as usize])
+                .collect(),
+            open_now: self.filter_open_now.then_some(true),
+        }
+    }
+
+    /// `x` in the filter panel: clear every filter, and the saved copy too
+    pub fn reset_filters(&mut self) {
+        self.filter_type_input.reset();
+        self.filter_radius = self.config.default_radius();
+        self.filter_min_rating = None;
+        self.filter_min_reviews = None;
+        self.filter_price_levels = [false; 5];
+        self.filter_open_now = false;
+        self.filter_language_input = Input::new(self.config.defaults.language.clone().unwrap_or_default());
+        self.filter_region_input = Input::new(self.config.defaults.region.clone().unwrap_or_default());
+
+        if !self.config.persist_filters() {
+            self.set_status("Filters reset.", false);
+            return;
+        }
+        match Config::save_tui_filters(&TuiFilters::default()) {
+            Ok(()) => self.set_status("Filters reset, saved filters cleared.", false),
+            Err(e) => self.set_status(format!("Filters reset; could not clear saved filters: {}", e), true),
+        }
+    }
+
+    /// Show a message in the status bar and record it in the message log
+    /// The terminal changed size: drop overlays laid out for the old size,
+    /// keep selections in range, and repaint every cell on the next frame
+    pub fn handle_resize(&mut self) {
+        self.autocomplete.clear();
+        self.ac_selected = 0;
+        if self.focus == Focus::AutocompleteList {
+            self.focus = Focus::SearchInput;
+        }
+        // Typing again reopens the picker at the new size
+        self.filter_matches.clear();
+        self.filter_match_idx = 0;
+
+        match self.results_state.selected() {
+            Some(_) if self.results.is_empty() => self.results_state.select(None),
+            Some(i) if i >= self.results.len() => self.results_state.select(Some(self.results.len() - 1)),
+            _ => {}
+        }
+        // The list recomputes its offset from the selection on the next draw
+        *self.results_state.offset_mut() = 0;
+
+        self.needs_full_redraw = true;
+    }
+
+    pub fn set_status(&mut self, message: impl Into<String>, is_error: bool) {
+        // Messages can quote API text (names, error bodies)
+        let message = sanitize_display(&message.into()).into_owned();
+        if self.status_log.len() == STATUS_LOG_CAPACITY {
+            self.status_log.pop_front();
+        }
+        self.status_log.push_back(StatusEntry {
+            at: Instant::now(),
+            message: message.clone(),
+            is_error,
+        });
+        self.status = Some((message, is_error));
+    }
+
+    /// The text input of the selected filter row, if it is a text field
+    pub fn filter_input_mut(&mut self) -> Option<&mut Input> {
+        match FilterField::from_index(self.filter_selected) {
+            FilterField::Type => Some(&mut self.filter_type_input),
+            FilterField::Language => Some(&mut self.filter_language_input),
+            FilterField::Region => Some(&mut self.filter_region_input),
+            _ => None,
+        }
+    }
+
+    pub fn update_filter_matches(&mut self) {
+        use crate::place_types::{filter_types, PLACE_TYPE_CATEGORIES};
+        self.filter_matches = match FilterField::from_index(self.filter_selected) {
+            FilterField::Type => {
+                // Best matches first, then grouped so the picker can show category headers
+                let mut matches = filter_types(self.filter_type_input.value(), 6);
+                matches.sort_by_key(|t| {
+                    PLACE_TYPE_CATEGORIES
+                        .iter()
+                        .position(|(_, types)| types.contains(t))
+                });
+                matches
+            }
+            FilterField::Language => {
+                locale::filter_codes(locale::COMMON_LANGUAGES, self.filter_language_input.value(), 6)
+            }
+            FilterField::Region => {
+                locale::filter_codes(locale::COMMON_REGIONS, self.filter_region_input.value(), 6)
+            }
+            _ => Vec::new(),
+        };
+        self.filter_match_idx = 0;
+    }
+
+    /// Check the value of a language or region row; other rows always pass
+    pub fn validate_filter_input(&self) -> Result<(), String> {
+        match FilterField::from_index(self.filter_selected) {
+            FilterField::Language => {
+                let v = self.filter_language_input.value();
+                if v.is_empty() || locale::is_language_code(v) {
+                    Ok(())
+                } else {
+                    Err(format!("'{}' is not a language code (e.g. en, pt-BR)", v))
+                }
+            }
+            FilterField::Region => {
+                let v = self.filter_region_input.value();
+                if v.is_empty() || locale::is_region_code(v) {
+                    Ok(())
+                } else {
+                    Err(format!("'{}' is not a two-letter region code (e.g. us, gb)", v))
+                }
+            }
+            _ => Ok(()),
+        }
+    }
+
+    pub fn language(&self) -> Option<String> {
+        Some(self.filter_language_input.value().to_string()).filter(|v| !v.is_empty())
+    }
+
+    /// Number conventions for counts, ratings, and distances; they follow
+    /// the Language filter as it's edited
+    pub fn number_format(&self) -> NumberFormat {
+        NumberFormat::for_language(self.language().as_deref())
+    }
+
+    /// The results list's address style; `auto` compares places against
+    /// the configured home region, not the Region filter
+    pub fn address_choice(&self) -> AddressChoice<'_> {
+        AddressChoice {
+            style: self.config.address_style(),
+            region: self.config.defaults.region.as_deref(),
         }
     }
 
-    pub fn update_type_matches(&mut self) {
-        use crate::tui::place_types::filter_types;
-        self.filter_type_matches = filter_types(self.filter_type_input.value(), 6);
-        self.filter_type_match_idx = 0;
+    fn region(&self) -> Option<String> {
+        Some(self.filter_region_input.value().to_string()).filter(|v| !v.is_empty())
     }
 
     const RADIUS_OPTIONS: [f64; 7] = [500.0, 1000.0, 2000.0, 5000.0, 10000.0, 25000.0, 50000.0];
@@ -151,6 +502,40 @@ impl App {
         };
     }
 
+    pub fn cycle_min_reviews(&mut self) {
+        self.filter_min_reviews = match self.filter_min_reviews {
+            None => Some(50),
+            Some(n) if n < 200 => Some(200),
+            Some(n) if n < 1000 => Some(1000),
+            _ => None,
+        };
+    }
+
+    /// Extend the selected price range up by one level, filling any gaps
+    /// (starts at Free when nothing is selected)
+    pub fn grow_price_range(&mut self) {
+        let levels = &mut self.filter_price_levels;
+        match (levels.iter().position(|&v| v), levels.iter().rposition(|&v| v)) {
+            (Some(lo), Some(hi)) => {
+                let hi = (hi + 1).min(levels.len() - 1);
+                levels[lo..=hi].fill(true);
+            }
+            _ => levels[0] = true,
+        }
+    }
+
+    /// Drop the highest level from the selected price range, filling any gaps;
+    /// shrinking a single level clears the filter
+    pub fn shrink_price_range(&mut self) {
+        let levels = &mut self.filter_price_levels;
+        if let (Some(lo), Some(hi)) = (levels.iter().position(|&v| v), levels.iter().rposition(|&v| v)) {
+            *levels = [false; 5];
+            if hi > lo {
+                levels[lo..hi].fill(true);
+            }
+        }
+    }
+
     pub fn trigger_autocomplete(&mut self) {
         // Cancel previous autocomplete task
         if let Some(handle) = self.ac_task.take() {
@@ -168,6 +553,9 @@ impl App {
         let tx = self.action_tx.clone();
         let session_token = self.session_token.clone();
         let location = self.location_bias();
+        let language = self.language();
+        let region = self.region();
+        let limit = self.config.autocomplete_limit();
 
         let handle = tokio::spawn(async move {
             tokio::time::sleep(std::time::Duration::from_millis(300)).await;
@@ -177,43 +565,117 @@ impl App {
                 input: query,
                 session_token: Some(session_token),
                 location,
-                limit: Some(5),
-                language: None,
-                region: None,
+                limit: Some(limit),
+                language,
+                region,
+                included_primary_types: Vec::new(),
             };
 
             let result = client.autocomplete(&req).await;
             let _ = tx.send(Action::AutocompleteResult(
                 result.map_err(|e| e.to_string()),
-            ));
+            )).await;
         });
 
         self.ac_task = Some(handle);
     }
 
+    /// Why live previews are currently off despite `tui.instant_results`, if they are
+    fn preview_paused(&self) -> Option<String> {
+        let limit = self.config.instant_results_monthly_limit();
+        if self.searches_this_month >= limit {
+            Some(format!("{} text searches this month (limit {})", self.searches_this_month, limit))
+        } else if self.previews_sent >= PREVIEW_SESSION_BUDGET {
+            Some(format!("{} previews this session", self.previews_sent))
+        } else {
+            None
+        }
+    }
+
+    /// Schedule a live preview search for the current input, replacing any
+    /// pending one (`tui.instant_results`)
+    pub fn trigger_preview(&mut self) {
+        if let Some(handle) = self.preview_task.take() {
+            handle.abort();
+        }
+        if !self.config.instant_results() {
+            return;
+        }
+        if let Some(reason) = self.preview_paused() {
+            if !self.preview_paused_noted {
+                self.preview_paused_noted = true;
+                self.set_status(format!("Live preview paused: {}", reason), false);
+            }
+            return;
+        }
+
+        let query = self.input.value().trim().to_string();
+        if query.chars().count() < PREVIEW_MIN_CHARS {
+            return;
+        }
+
+        self.search_seq += 1;
+        let seq = self.search_seq;
+        let client = Arc::clone(&self.client);
+        let tx = self.action_tx.clone();
+        let req = self.search_request(query);
+
+        let handle = tokio::spawn(async move {
+            tokio::time::sleep(PREVIEW_DEBOUNCE).await;
+            info!(query = %req.query, "Preview search request");
+
+            let result = client.search(&req).await;
+            let _ = tx.send(Action::PreviewResult(seq, result.map_err(|e| e.to_string()))).await;
+        });
+
+        self.preview_task = Some(handle);
+    }
+
     pub fn execute_search(&mut self, query: String) {
         if query.is_empty() {
             return;
         }
 
-        // Cancel any pending autocomplete
+        // Cancel any pending autocomplete and preview
         if let Some(handle) = self.ac_task.take() {
             handle.abort();
         }
+        if let Some(handle) = self.preview_task.take() {
+            handle.abort();
+        }
         self.autocomplete.clear();
         self.ac_selected = 0;
 
         self.loading = true;
-        self.status = Some(("Searching...".to_string(), false));
+        self.set_status("Searching...", false);
 
         // New session token after search (per Google billing best practice)
         self.session_token = uuid::Uuid::new_v4().to_string();
 
+        self.search_seq += 1;
+        let seq = self.search_seq;
         let client = Arc::clone(&self.client);
         let tx = self.action_tx.clone();
-        let location = self.location_bias();
+        let req = self.search_request(query);
 
-        // Build filter values for the spawned task
+        tokio::spawn(async move {
+            info!(
+                query = %req.query,
+                included_type = ?req.included_type,
+                min_rating = ?req.min_rating,
+                price_levels = ?req.price_levels,
+                open_now = req.open_now,
+                "Search request"
+            );
+
+            let result = client.search(&req).await;
+            let _ = tx.send(Action::SearchResult(seq, result.map_err(|e| e.to_string()))).await;
+        });
+    }
+
+    /// A text search for `query` with the current filters
+    fn search_request(&self, query: String) -> SearchRequest {
+        let location = self.location_bias();
         let included_type = {
             let v = self.filter_type_input.value().to_string();
             if v.is_empty() { None } else { Some(v) }
@@ -227,45 +689,54 @@ impl App {
             .filter_map(|(i, _)| price_level_to_api(i as u8).map(String::from))
             .collect();
         let open_now = self.filter_open_now;
+        let include_photos = self.config.request_photos();
+        let limit = self.config.result_limit();
+        let language = self.language();
+        let region = self.region();
 
-        tokio::spawn(async move {
-            info!(
-                query = %query,
-                included_type = ?included_type,
-                min_rating = ?min_rating,
-                price_levels = ?price_levels,
-                open_now = open_now,
-                "Search request"
-            );
-
-            let req = SearchRequest {
-                query,
-                included_type,
-                min_rating,
-                price_levels,
-                open_now,
-                location,
-                limit: Some(10),
-                language: None,
-                region: None,
-            };
-
-            let result = client.search(&req).await;
-            let _ = tx.send(Action::SearchResult(result.map_err(|e| e.to_string())));
-        });
+        SearchRequest {
+            query,
+            included_type,
+            min_rating,
+            price_levels,
+            open_now,
+            location,
+            limit: Some(limit),
+            language,
+            region,
+            include_photos,
+        }
     }
 
-    pub fn fetch_details(&mut self) {
+    /// Load full details for the selected place, from the session cache unless
+    /// `force` is set; a request already in flight for the place is not repeated
+    pub fn fetch_details(&mut self, force: bool) {
         let place_id = match self.selected_place() {
             Some(p) if !p.id.is_empty() => p.id.clone(),
             _ => return,
         };
+        let key = self.details_cache_key(&place_id);
+
+        if !force {
+            if let Some(place) = self.cached_details(&key) {
+                self.detail = Some(place);
+                self.detail_scroll = 0;
+                self.set_status("Details loaded (cached).", false);
+                return;
+            }
+        }
+        if !self.details_in_flight.insert(key.clone()) {
+            self.set_status("Details already loading...", false);
+            return;
+        }
 
         self.loading = true;
-        self.status = Some(("Loading details...".to_string(), false));
+        self.set_status("Loading details...", false);
 
         let client = Arc::clone(&self.client);
         let tx = self.action_tx.clone();
+        let language = self.language();
+        let region = self.region();
 
         tokio::spawn(async move {
             info!(place_id = %place_id, "Details request");
@@ -274,52 +745,441 @@ impl App {
                 place_id,
                 include_reviews: true,
                 include_photos: false,
-                language: None,
-                region: None,
+                language,
+                region,
             };
 
             let result = client.details(&req).await;
-            let _ = tx.send(Action::DetailsResult(result.map_err(|e| e.to_string())));
+            let _ = tx.send(Action::DetailsResult(
+                key,
+                result.map(Box::new).map_err(|e| e.to_string()),
+            )).await;
+        });
+    }
+
+    /// Details depend on the language and region they were fetched in
+    pub fn details_cache_key(&self, place_id: &str) -> String {
+        format!(
+            "{}|{}|{}",
+            place_id,
+            self.filter_language_input.value(),
+            self.filter_region_input.value()
+        )
+    }
+
+    fn cached_details(&mut self, key: &str) -> Option<Place> {
+        let place = self.details_cache.get(key)?.clone();
+        if let Some(pos) = self.details_lru.iter().position(|k| k == key) {
+            self.details_lru.remove(pos);
+        }
+        self.details_lru.push_back(key.to_string());
+        Some(place)
+    }
+
+    pub fn cache_details(&mut self, key: String, place: Place) {
+        if let Some(pos) = self.details_lru.iter().position(|k| *k == key) {
+            self.details_lru.remove(pos);
+        } else if self.details_lru.len() == DETAILS_CACHE_CAPACITY {
+            if let Some(oldest) = self.details_lru.pop_front() {
+                self.details_cache.remove(&oldest);
+            }
+        }
+        self.details_lru.push_back(key.clone());
+        // Raw JSON is kept for the current results and details pane only
+        let mut place = place;
+        place.raw = None;
+        self.details_cache.insert(key, place);
+    }
+
+    /// `J`: open the JSON behind the details pane's place, or the selected
+    /// result's when the pane shows another place. Places without their raw
+    /// JSON (loaded from the details cache) are re-serialized.
+    pub fn open_raw_json(&mut self) {
+        let Some(selected) = self.selected_place() else {
+            return;
+        };
+        let place = self
+            .detail
+            .as_ref()
+            .filter(|d| d.id == selected.id)
+            .unwrap_or(selected);
+        let value = match &place.raw {
+            Some(raw) => Ok(raw.as_ref().clone()),
+            None => serde_json::to_value(place),
+        };
+        let text = match value.and_then(|v| serde_json::to_string_pretty(&v)) {
+            Ok(text) => text,
+            Err(e) => {
+                self.set_status(format!("Could not show JSON: {}", e), true);
+                return;
+            }
+        };
+        let name = place
+            .display_name
+            .as_ref()
+            .map(|n| n.text.clone())
+            .unwrap_or_else(|| place.id.clone());
+        self.raw_json = Some(RawJson {
+            name,
+            as_received: place.raw.is_some(),
+            text,
+        });
+        self.raw_scroll = 0;
+        self.focus = Focus::RawJson;
+    }
+
+    /// `v`: refetch the selected place and keep all of its reviews in the pane
+    /// (the cached copy stays trimmed to `KEPT_REVIEWS`)
+    pub fn fetch_all_reviews(&mut self) {
+        let Some(place_id) = self.selected_place().map(|p| p.id.clone()) else {
+            return;
+        };
+        let key = self.details_cache_key(&place_id);
+        if self.all_reviews.as_ref() == Some(&key) {
+            self.set_status("All reviews already shown.", false);
+            return;
+        }
+        self.all_reviews = Some(key);
+        self.fetch_details(true);
+    }
+
+    pub fn cache_route(&mut self, key: (String, TravelMode), summary: RouteSummary) {
+        if let Some(pos) = self.route_lru.iter().position(|k| *k == key) {
+            self.route_lru.remove(pos);
+        } else if self.route_lru.len() == ROUTE_CACHE_CAPACITY {
+            if let Some(oldest) = self.route_lru.pop_front() {
+                self.route_cache.remove(&oldest);
+            }
+        }
+        self.route_lru.push_back(key.clone());
+        self.route_cache.insert(key, summary);
+    }
+
+    /// Debug key `D`: log entry counts and approximate sizes (serialized JSON
+    /// bytes) of what the session holds, for checking memory over long runs
+    pub fn log_memory_stats(&mut self) {
+        let json_bytes = |places: &mut dyn Iterator<Item = &Place>| -> usize {
+            places
+                .map(|p| serde_json::to_vec(p).map(|v| v.len()).unwrap_or(0))
+                .sum()
+        };
+        let results_bytes = json_bytes(&mut self.results.iter());
+        let details_bytes = json_bytes(&mut self.details_cache.values());
+        let detail_bytes = json_bytes(&mut self.detail.iter());
+        let log_bytes: usize = self.status_log.iter().map(|e| e.message.len()).sum();
+        info!(
+            results = self.results.len(),
+            results_bytes,
+            details_cached = self.details_cache.len(),
+            details_bytes,
+            details_in_flight = self.details_in_flight.len(),
+            detail_bytes,
+            routes_cached = self.route_cache.len(),
+            autocomplete = self.autocomplete.len(),
+            status_log = self.status_log.len(),
+            log_bytes,
+            photo_url_bytes = self
+                .photo_gallery
+                .as_ref()
+                .map_or(0, |g| g.urls.values().map(String::len).sum::<usize>()),
+            "Memory stats"
+        );
+        self.set_status(
+            format!(
+                "Memory: {} results, {} cached details (~{} KB) — see log.",
+                self.results.len(),
+                self.details_cache.len(),
+                details_bytes / 1024
+            ),
+            false,
+        );
+    }
+
+    /// `p`: open the gallery of the selected place's photos at the first
+    /// one, or close it. Search results only carry photo references when
+    /// `tui.request_photos` is on.
+    pub fn toggle_photo_gallery(&mut self) {
+        let Some(place) = self.selected_place() else {
+            return;
+        };
+        let place_id = place.id.clone();
+        let photos: Vec<String> = place
+            .photos
+            .iter()
+            .flatten()
+            .map(|photo| photo.name.clone())
+            .collect();
+
+        if photos.is_empty() {
+            let message = if self.config.request_photos() {
+                "No photos for this place."
+            } else {
+                "Photos are off; set tui.request_photos = true in the config to enable."
+            };
+            self.set_status(message, false);
+            return;
+        }
+        if self.photo_gallery.as_ref().is_some_and(|g| g.place_id == place_id) {
+            self.photo_gallery = None;
+            self.set_status("Photo hidden.", false);
+            return;
+        }
+
+        let count = photos.len();
+        self.photo_gallery = Some(GalleryState {
+            place_id,
+            photos,
+            index: 0,
+            urls: HashMap::new(),
+            pending: HashSet::new(),
+            waiting: None,
+        });
+        self.load_photo(0, true);
+        if count > 1 {
+            self.load_photo(1, false);
+        }
+    }
+
+    /// `]`/`[`: show the next or previous photo, wrapping around, and
+    /// prefetch the one after it; opens the gallery if it isn't open
+    pub fn step_photo(&mut self, forward: bool) {
+        let selected = self.selected_place().map(|p| p.id.clone());
+        let Some(gallery) = self
+            .photo_gallery
+            .as_mut()
+            .filter(|g| Some(&g.place_id) == selected.as_ref())
+        else {
+            self.toggle_photo_gallery();
+            return;
+        };
+        let count = gallery.photos.len();
+        if count < 2 {
+            self.set_status("This place has one photo.", false);
+            return;
+        }
+
+        let step = |i: usize| if forward { (i + 1) % count } else { (i + count - 1) % count };
+        gallery.index = step(gallery.index);
+        gallery.evict();
+        let (index, next) = (gallery.index, step(gallery.index));
+
+        if gallery.urls.contains_key(&index) {
+            self.set_status(format!("Photo {}/{}", index + 1, count), false);
+        } else {
+            self.load_photo(index, true);
+        }
+        self.load_photo(next, false);
+    }
+
+    /// Fetch the URL of gallery photo `index` unless it's cached or on its
+    /// way; `shown` when the user is waiting for it rather than prefetching
+    fn load_photo(&mut self, index: usize, shown: bool) {
+        let Some(gallery) = self.photo_gallery.as_mut() else {
+            return;
+        };
+        if gallery.urls.contains_key(&index) {
+            return;
+        }
+        let count = gallery.photos.len();
+        let in_flight = !gallery.pending.insert(index);
+        let place_id = gallery.place_id.clone();
+        let name = gallery.photos[index].clone();
+        if shown {
+            gallery.waiting = Some(index);
+            self.loading = true;
+            self.set_status(format!("Loading photo {}/{}...", index + 1, count), false);
+        }
+        if in_flight {
+            return;
+        }
+
+        let client = Arc::clone(&self.client);
+        let tx = self.action_tx.clone();
+
+        tokio::spawn(async move {
+            info!(photo = %name, prefetch = !shown, "Photo request");
+
+            let req = PhotoMediaRequest {
+                name,
+                max_width: Some(800),
+                max_height: None,
+            };
+
+            let result = client.photo_media(&req).await;
+            let _ = tx.send(Action::PhotoResult(
+                place_id,
+                index,
+                result.map_err(|e| e.to_string()),
+            )).await;
         });
     }
 
+    /// `c`: pin the selected place, or compare it against the pinned one
+    /// (pressing `c` while comparing returns to the single-place view)
+    pub fn toggle_compare(&mut self) {
+        if self.comparing {
+            self.comparing = false;
+            return;
+        }
+        let Some(selected) = self.selected_place().cloned() else {
+            return;
+        };
+        match &self.pinned {
+            Some(pinned) if pinned.id != selected.id => {
+                self.comparing = true;
+                self.detail = Some(selected);
+                self.detail_scroll = 0;
+            }
+            Some(_) => {
+                self.set_status("Select another place and press c to compare.", false);
+            }
+            None => {
+                self.set_status(format!("Pinned {}.", place_name(&selected)), false);
+                self.pinned = Some(selected);
+            }
+        }
+    }
+
+    /// `C`: drop the pin and leave compare mode
+    pub fn clear_pin(&mut self) {
+        if self.pinned.take().is_some() {
+            self.set_status("Pin cleared.", false);
+        }
+        self.comparing = false;
+    }
+
+    /// Route from the location bias to the selected place, reusing a summary
+    /// already fetched this session for the same place and mode
+    pub fn plan_route(&mut self, mode: TravelMode) {
+        let Some(place_id) = self.selected_place().map(|p| p.id.clone()) else {
+            return;
+        };
+        if self.route_cache.contains_key(&(place_id.clone(), mode)) {
+            self.set_status(format!("Route ({}) from cache.", mode_label(mode)), false);
+            return;
+        }
+        let Some((lat, lng)) = self.config.default_location() else {
+            self.set_status("No origin: set a default location to plan routes.", true);
+            return;
+        };
+
+        self.loading = true;
+        self.set_status(format!("Routing ({})...", mode_label(mode)), false);
+
+        let client = Arc::clone(&self.client);
+        let tx = self.action_tx.clone();
+
+        tokio::spawn(async move {
+            info!(place_id = %place_id, mode = %mode, "Route summary request");
+
+            let origin = LatLng {
+                latitude: lat,
+                longitude: lng,
+            };
+            let result = client.route_summary(&origin, &place_id, mode).await;
+            let _ = tx.send(Action::RouteSummaryResult(
+                place_id,
+                mode,
+                result.map_err(|e| e.to_string()),
+            )).await;
+        });
+    }
+
+    /// Cached route summaries for a place, in a fixed mode order
+    pub fn routes_for(&self, place_id: &str) -> Vec<(TravelMode, RouteSummary)> {
+        ROUTE_MODES
+            .iter()
+            .filter_map(|&mode| {
+                self.route_cache
+                    .get(&(place_id.to_string(), mode))
+                    .map(|summary| (mode, *summary))
+            })
+            .collect()
+    }
+
     pub fn selected_place(&self) -> Option<&Place> {
         self.results_state
             .selected()
             .and_then(|i| self.results.get(i))
     }
 
-    pub fn select_next_result(&mut self) {
+    pub fn select_next_result(&mut self, count: usize) {
+        self.move_selection(count as isize);
+    }
+
+    pub fn select_prev_result(&mut self, count: usize) {
+        self.move_selection(-(count as isize));
+    }
+
+    fn move_selection(&mut self, delta: isize) {
         let len = self.results.len();
         if len == 0 {
             return;
         }
-        let i = self.results_state.selected().map_or(0, |i| {
-            if i + 1 >= len { i } else { i + 1 }
-        });
+        let (i, past_end) = match self.results_state.selected() {
+            Some(current) => step_index(current, delta, len, self.config.wrap_navigation()),
+            None => (0, false),
+        };
+        if past_end && !self.config.wrap_navigation() {
+            self.end_flash = Some(Instant::now());
+        }
         self.results_state.select(Some(i));
         self.update_detail_from_selection();
     }
 
-    pub fn select_prev_result(&mut self) {
+    /// Whether the "end of list" indicator should still be shown
+    pub fn end_flash_active(&self) -> bool {
+        self.end_flash
+            .is_some_and(|at| at.elapsed() < END_FLASH_DURATION)
+    }
+
+    /// Select result `number` (1-based, as shown in the list), clamped to the list
+    pub fn jump_to_result(&mut self, number: usize) {
         let len = self.results.len();
         if len == 0 {
             return;
         }
-        let i = self
-            .results_state
-            .selected()
-            .map_or(0, |i| i.saturating_sub(1));
-        self.results_state.select(Some(i));
+        self.results_state
+            .select(Some(number.clamp(1, len) - 1));
         self.update_detail_from_selection();
     }
 
+    /// Append a digit to the count prefix (ignored once it would exceed 4 digits)
+    pub fn push_count_digit(&mut self, digit: usize) {
+        let current = self.pending_count().unwrap_or(0);
+        let next = current * 10 + digit;
+        if next > 0 && next < 10_000 {
+            self.pending_count = Some((next, Instant::now()));
+        }
+    }
+
+    /// The count prefix, unless it has timed out
+    pub fn pending_count(&self) -> Option<usize> {
+        self.pending_count
+            .filter(|(_, at)| at.elapsed() < COUNT_PREFIX_TIMEOUT)
+            .map(|(n, _)| n)
+    }
+
+    /// Consume the count prefix
+    pub fn take_count(&mut self) -> Option<usize> {
+        let count = self.pending_count();
+        self.pending_count = None;
+        count
+    }
+
     fn update_detail_from_selection(&mut self) {
         self.detail = self.selected_place().cloned();
         self.detail_scroll = 0;
+        // Previews and full review lists belong to the place they were fetched for
+        let id = self.detail.as_ref().map(|p| p.id.as_str());
+        if self.photo_gallery.as_ref().map(|g| g.place_id.as_str()) != id {
+            self.photo_gallery = None;
+        }
+        self.all_reviews = None;
     }
 
-    fn location_bias(&self) -> Option<Circle> {
+    /// Circle searches are biased to: the default location and the radius filter
+    pub fn location_bias(&self) -> Option<Circle> {
         self.config.default_location().map(|(lat, lng)| Circle {
             center: LatLng {
                 latitude: lat,
@@ -329,3 +1189,38 @@ impl App {
         })
     }
 }
+
+/// Drop reviews past `KEPT_REVIEWS`
+pub fn trim_reviews(place: &mut Place) {
+    if let Some(reviews) = place.reviews.as_mut() {
+        reviews.truncate(KEPT_REVIEWS);
+        reviews.shrink_to_fit();
+    }
+}
+
+fn place_name(place: &Place) -> &str {
+    place
+        .display_name
+        .as_ref()
+        .map(|n| n.text.as_str())
+        .unwrap_or("Unknown")
+}
+
+/// Modes offered by the `R` prompt, in display order
+const ROUTE_MODES: [TravelMode; 4] = [
+    TravelMode::Drive,
+    TravelMode::Walk,
+    TravelMode::Bicycle,
+    TravelMode::Transit,
+];
+
+/// How a travel mode reads after a duration, e.g. "12 min drive"
+pub fn mode_label(mode: TravelMode) -> &'static str {
+    match mode {
+        TravelMode::Drive => "drive",
+        TravelMode::Walk => "walk",
+        TravelMode::Bicycle => "bike",
+        TravelMode::TwoWheeler => "ride",
+        TravelMode::Transit => "transit",
+    }
+}
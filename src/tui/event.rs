@@ -1,7 +1,39 @@
-use crate::api::types::{AutocompleteResponse, Place, SearchResponse};
+use crate::api::client::ClientEvent;
+use crate::api::types::{
+    AutocompleteResponse, PhotoMediaResponse, Place, RouteSummary, SearchResponse, TravelMode,
+};
 
+#[allow(clippy::enum_variant_names)]
 pub enum Action {
     AutocompleteResult(Result<AutocompleteResponse, String>),
-    SearchResult(Result<SearchResponse, String>),
-    DetailsResult(Result<Place, String>),
+    /// Results of the search tagged with this sequence number (see `App::search_seq`)
+    SearchResult(u64, Result<SearchResponse, String>),
+    /// Results of a live preview search, tagged like `SearchResult`
+    PreviewResult(u64, Result<SearchResponse, String>),
+    /// Details for the place with this cache key (see `App::details_cache_key`)
+    DetailsResult(String, Result<Box<Place>, String>),
+    /// URL of the photo at this gallery index, for the place with this ID
+    PhotoResult(String, usize, Result<PhotoMediaResponse, String>),
+    /// Route from the location bias to the place with this ID
+    RouteSummaryResult(String, TravelMode, Result<RouteSummary, String>),
+    /// Forwarded from the client observer after every API request
+    Client(ClientEvent),
+}
+
+/// Drop actions a later one in the same batch supersedes, keeping the rest in
+/// order: only the newest autocomplete result is applied, but autocomplete
+/// errors (like every other action) are always kept
+pub fn coalesce(actions: Vec<Action>) -> Vec<Action> {
+    let last_autocomplete = actions
+        .iter()
+        .rposition(|a| matches!(a, Action::AutocompleteResult(_)));
+    actions
+        .into_iter()
+        .enumerate()
+        .filter(|(i, a)| match a {
+            Action::AutocompleteResult(Ok(_)) => Some(*i) == last_autocomplete,
+            _ => true,
+        })
+        .map(|(_, a)| a)
+        .collect()
 }
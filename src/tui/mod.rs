@@ -1,12 +1,12 @@
 mod app;
 mod event;
-mod place_types;
 mod ui;
 mod widgets;
 
 use std::sync::Arc;
 use std::time::Instant;
 
+use colored::Colorize;
 use crossterm::event::{Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
 use crossterm::execute;
 use crossterm::terminal::{
@@ -20,19 +20,23 @@ use tracing::{error, info};
 use tui_input::backend::crossterm::EventHandler;
 use tui_input::Input;
 
-use app::{App, FilterField, Focus};
+use app::{step_index, App, FilterField, Focus};
 use event::Action;
 
-use crate::api::client::Client;
+use crate::api::client::{Client, ClientEvent};
+use crate::api::types::{SearchResponse, TravelMode};
 use crate::config::Config;
+use crate::paths;
 
 /// How quickly two Ctrl+C presses must occur to quit (ms)
 const DOUBLE_CTRL_C_MS: u128 = 500;
+/// Window for the second `q` when quitting with a request in flight
+const CONFIRM_QUIT_MS: u128 = 2000;
+/// Actions that can queue before request tasks wait for the UI to catch up
+const ACTION_CHANNEL_CAPACITY: usize = 64;
 
-fn init_logging() -> tracing_appender::non_blocking::WorkerGuard {
-    let log_dir = dirs::config_dir()
-        .map(|d| d.join("zupo"))
-        .unwrap_or_else(|| std::path::PathBuf::from("."));
+fn init_logging(config: &Config) -> tracing_appender::non_blocking::WorkerGuard {
+    let log_dir = paths::log_dir(&config.paths).unwrap_or_else(|| std::path::PathBuf::from("."));
     let file_appender = tracing_appender::rolling::never(&log_dir, "tui.log");
     let (non_blocking, guard) = tracing_appender::non_blocking(file_appender);
     tracing_subscriber::fmt()
@@ -44,7 +48,7 @@ fn init_logging() -> tracing_appender::non_blocking::WorkerGuard {
 }
 
 pub async fn run(client: Client, config: Config) -> anyhow::Result<()> {
-    let _log_guard = init_logging();
+    let _log_guard = init_logging(&config);
     info!("TUI started");
 
     // Setup terminal
@@ -54,23 +58,53 @@ pub async fn run(client: Client, config: Config) -> anyhow::Result<()> {
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
-    let (action_tx, mut action_rx) = mpsc::unbounded_channel::<Action>();
+    let (action_tx, mut action_rx) = mpsc::channel::<Action>(ACTION_CHANNEL_CAPACITY);
 
-    let client = Arc::new(client);
+    // The observer runs inside the client and can't wait; when the queue is
+    // full its latency and warning events are dropped
+    let observer_tx = action_tx.clone();
+    let client = Arc::new(client.with_raw_places().with_observer(move |event| {
+        let _ = observer_tx.try_send(Action::Client(event.clone()));
+    }));
     let mut app = App::new(client, config, action_tx);
 
     let mut event_stream = crossterm::event::EventStream::new();
 
     // Main event loop
     loop {
+        if std::mem::take(&mut app.needs_full_redraw) {
+            terminal.clear()?;
+        }
         terminal.draw(|f| ui::render(f, &mut app))?;
 
+        // Wake up to redraw once a count prefix or the end-of-list flash expires
+        let count_expiry = app
+            .pending_count
+            .map(|(_, at)| at + app::COUNT_PREFIX_TIMEOUT);
+        let flash_expiry = app.end_flash.map(|at| at + app::END_FLASH_DURATION);
+        let wakeup = count_expiry.into_iter().chain(flash_expiry).min();
+
         tokio::select! {
             Some(Ok(evt)) = event_stream.next() => {
                 handle_crossterm_event(evt, &mut app);
             }
             Some(action) = action_rx.recv() => {
-                handle_action(action, &mut app);
+                // Apply everything queued since the last frame, minus superseded updates
+                let mut batch = vec![action];
+                while let Ok(action) = action_rx.try_recv() {
+                    batch.push(action);
+                }
+                for action in event::coalesce(batch) {
+                    handle_action(action, &mut app);
+                }
+            }
+            _ = tokio::time::sleep_until(wakeup.unwrap_or_else(Instant::now).into()), if wakeup.is_some() => {
+                if app.pending_count().is_none() {
+                    app.pending_count = None;
+                }
+                if !app.end_flash_active() {
+                    app.end_flash = None;
+                }
             }
         }
 
@@ -84,10 +118,20 @@ pub async fn run(client: Client, config: Config) -> anyhow::Result<()> {
     execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
     terminal.show_cursor()?;
 
+    if app.config.persist_filters() {
+        if let Err(e) = Config::save_tui_filters(&app.saved_filters()) {
+            eprintln!("{} could not save TUI filters: {}", "Warning:".yellow(), e);
+        }
+    }
+
     Ok(())
 }
 
 fn handle_crossterm_event(evt: Event, app: &mut App) {
+    if let Event::Resize(..) = evt {
+        app.handle_resize();
+        return;
+    }
     if let Event::Key(key) = evt {
         // Only handle key press events (not release/repeat)
         if key.kind != KeyEventKind::Press {
@@ -104,12 +148,15 @@ fn handle_crossterm_event(evt: Event, app: &mut App) {
                 }
             }
             app.last_ctrl_c = Some(now);
-            app.status = Some(("Press Ctrl+C again to quit".to_string(), false));
+            app.set_status("Press Ctrl+C again to quit", false);
             return;
         }
 
-        // Any other key clears the Ctrl+C state
+        // Any other key clears the Ctrl+C state (and a pending `q` confirmation)
         app.last_ctrl_c = None;
+        if key.code != KeyCode::Char('q') {
+            app.last_quit = None;
+        }
 
         match app.focus {
             Focus::SearchInput => handle_search_input(key, app),
@@ -117,8 +164,28 @@ fn handle_crossterm_event(evt: Event, app: &mut App) {
             Focus::ResultsList => handle_results_nav(key, app),
             Focus::FilterPanel => handle_filter_panel(key, app),
             Focus::FilterEditing => handle_filter_editing(key, app),
+            Focus::RouteMode => handle_route_mode(key, app),
+            Focus::MessageLog => handle_message_log(key, app),
+            Focus::RawJson => handle_raw_json(key, app),
+        }
+    }
+}
+
+/// Quit, unless a request is in flight: then `q` must be pressed twice
+fn confirm_quit(app: &mut App) {
+    if !app.loading {
+        app.should_quit = true;
+        return;
+    }
+    let now = Instant::now();
+    if let Some(last) = app.last_quit {
+        if now.duration_since(last).as_millis() < CONFIRM_QUIT_MS {
+            app.should_quit = true;
+            return;
         }
     }
+    app.last_quit = Some(now);
+    app.set_status("Request in flight; press q again to quit", false);
 }
 
 fn handle_search_input(key: KeyEvent, app: &mut App) {
@@ -150,25 +217,23 @@ fn handle_search_input(key: KeyEvent, app: &mut App) {
             app.ac_selected = 0;
             app.focus = Focus::FilterPanel;
         }
-        KeyCode::Char('q') if app.input.value().is_empty() => {
-            app.should_quit = true;
-        }
         _ => {
             app.input.handle_event(&Event::Key(key));
             app.trigger_autocomplete();
+            app.trigger_preview();
         }
     }
 }
 
 fn handle_autocomplete_nav(key: KeyEvent, app: &mut App) {
     match key.code {
-        KeyCode::Down | KeyCode::Char('j') => {
-            if app.ac_selected + 1 < app.autocomplete.len() {
-                app.ac_selected += 1;
-            }
+        KeyCode::Down | KeyCode::Char('j') if !app.autocomplete.is_empty() => {
+            let wrap = app.config.wrap_navigation();
+            app.ac_selected = step_index(app.ac_selected, 1, app.autocomplete.len(), wrap).0;
         }
-        KeyCode::Up | KeyCode::Char('k') => {
-            app.ac_selected = app.ac_selected.saturating_sub(1);
+        KeyCode::Up | KeyCode::Char('k') if !app.autocomplete.is_empty() => {
+            let wrap = app.config.wrap_navigation();
+            app.ac_selected = step_index(app.ac_selected, -1, app.autocomplete.len(), wrap).0;
         }
         KeyCode::Enter => {
             if let Some(suggestion) = app.autocomplete.get(app.ac_selected) {
@@ -203,9 +268,22 @@ fn handle_autocomplete_nav(key: KeyEvent, app: &mut App) {
 }
 
 fn handle_results_nav(key: KeyEvent, app: &mut App) {
+    if let KeyCode::Char(c @ '0'..='9') = key.code {
+        app.push_count_digit((c as u8 - b'0') as usize);
+        return;
+    }
+    // Every other key consumes the count prefix, if any
+    let count = app.take_count();
+
     match key.code {
         KeyCode::Char('q') => {
-            app.should_quit = true;
+            confirm_quit(app);
+        }
+        KeyCode::F(5) => {
+            app.fetch_details(true);
+        }
+        KeyCode::Char('r') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            app.fetch_details(true);
         }
         KeyCode::Char('/') => {
             app.focus = Focus::SearchInput;
@@ -214,24 +292,144 @@ fn handle_results_nav(key: KeyEvent, app: &mut App) {
             app.focus = Focus::FilterPanel;
         }
         KeyCode::Down | KeyCode::Char('j') => {
-            app.select_next_result();
+            app.select_next_result(count.unwrap_or(1));
         }
         KeyCode::Up | KeyCode::Char('k') => {
-            app.select_prev_result();
+            app.select_prev_result(count.unwrap_or(1));
         }
-        KeyCode::Enter => {
-            app.fetch_details();
+        KeyCode::Enter => match count {
+            Some(n) => app.jump_to_result(n),
+            None => app.fetch_details(false),
+        },
+        KeyCode::Char('p') => {
+            app.toggle_photo_gallery();
+        }
+        KeyCode::Char(']') => {
+            app.step_photo(true);
+        }
+        KeyCode::Char('[') => {
+            app.step_photo(false);
+        }
+        KeyCode::Char('v') => {
+            app.fetch_all_reviews();
+        }
+        KeyCode::Char('D') => {
+            app.log_memory_stats();
+        }
+        KeyCode::Char('c') => {
+            app.toggle_compare();
+        }
+        KeyCode::Char('C') => {
+            app.clear_pin();
+        }
+        KeyCode::Char('R') if app.selected_place().is_some() => {
+            app.focus = Focus::RouteMode;
+        }
+        KeyCode::Char('M') => {
+            // Open scrolled to the newest messages; rendering clamps the offset
+            app.log_scroll = u16::MAX;
+            app.focus = Focus::MessageLog;
+        }
+        KeyCode::Char('J') => {
+            app.open_raw_json();
         }
         KeyCode::Char('g') => {
             app.detail_scroll = app.detail_scroll.saturating_sub(3);
         }
+        KeyCode::Char('G') => match count {
+            Some(n) => app.jump_to_result(n),
+            None => app.detail_scroll = app.detail_scroll.saturating_add(3),
+        },
+        _ => {}
+    }
+}
+
+fn handle_message_log(key: KeyEvent, app: &mut App) {
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('M') => {
+            app.focus = Focus::ResultsList;
+        }
+        KeyCode::Down | KeyCode::Char('j') => {
+            app.log_scroll = app.log_scroll.saturating_add(1);
+        }
+        KeyCode::Up | KeyCode::Char('k') => {
+            app.log_scroll = app.log_scroll.saturating_sub(1);
+        }
+        KeyCode::Char('g') => {
+            app.log_scroll = 0;
+        }
+        KeyCode::Char('G') => {
+            app.log_scroll = u16::MAX;
+        }
+        _ => {}
+    }
+}
+
+fn handle_raw_json(key: KeyEvent, app: &mut App) {
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('J') => {
+            app.raw_json = None;
+            app.focus = Focus::ResultsList;
+        }
+        KeyCode::Down | KeyCode::Char('j') => {
+            app.raw_scroll = app.raw_scroll.saturating_add(1);
+        }
+        KeyCode::Up | KeyCode::Char('k') => {
+            app.raw_scroll = app.raw_scroll.saturating_sub(1);
+        }
+        KeyCode::PageDown | KeyCode::Char(' ') => {
+            app.raw_scroll = app.raw_scroll.saturating_add(10);
+        }
+        KeyCode::PageUp => {
+            app.raw_scroll = app.raw_scroll.saturating_sub(10);
+        }
+        KeyCode::Char('g') => {
+            app.raw_scroll = 0;
+        }
         KeyCode::Char('G') => {
-            app.detail_scroll = app.detail_scroll.saturating_add(3);
+            app.raw_scroll = u16::MAX;
+        }
+        KeyCode::Char('y') => {
+            let Some(text) = app.raw_json.as_ref().map(|r| r.text.clone()) else {
+                return;
+            };
+            match copy_to_clipboard(&text) {
+                Ok(()) => app.set_status(format!("Copied {} bytes of JSON.", text.len()), false),
+                Err(e) => app.set_status(format!("Could not copy: {}", e), true),
+            }
         }
         _ => {}
     }
 }
 
+/// Copy through the terminal with an OSC 52 escape, which also works over
+/// SSH; terminals without OSC 52 support ignore it
+fn copy_to_clipboard(text: &str) -> std::io::Result<()> {
+    use base64::Engine;
+    use std::io::Write;
+
+    let encoded = base64::engine::general_purpose::STANDARD.encode(text);
+    let mut stdout = std::io::stdout();
+    write!(stdout, "\x1b]52;c;{}\x07", encoded)?;
+    stdout.flush()
+}
+
+fn handle_route_mode(key: KeyEvent, app: &mut App) {
+    let mode = match key.code {
+        KeyCode::Char('d') => TravelMode::Drive,
+        KeyCode::Char('w') => TravelMode::Walk,
+        KeyCode::Char('b') => TravelMode::Bicycle,
+        KeyCode::Char('t') => TravelMode::Transit,
+        KeyCode::Esc => {
+            app.focus = Focus::ResultsList;
+            return;
+        }
+        _ => return,
+    };
+    app.focus = Focus::ResultsList;
+    app.plan_route(mode);
+}
+
 fn handle_filter_panel(key: KeyEvent, app: &mut App) {
     match key.code {
         KeyCode::Esc => {
@@ -244,19 +442,18 @@ fn handle_filter_panel(key: KeyEvent, app: &mut App) {
             app.focus = Focus::SearchInput;
         }
         KeyCode::Down | KeyCode::Char('j') => {
-            app.filter_selected = (app.filter_selected + 1) % FilterField::ALL.len();
+            let wrap = app.config.wrap_navigation();
+            app.filter_selected = step_index(app.filter_selected, 1, FilterField::ALL.len(), wrap).0;
         }
         KeyCode::Up | KeyCode::Char('k') => {
-            if app.filter_selected == 0 {
-                app.filter_selected = FilterField::ALL.len() - 1;
-            } else {
-                app.filter_selected -= 1;
-            }
+            let wrap = app.config.wrap_navigation();
+            app.filter_selected = step_index(app.filter_selected, -1, FilterField::ALL.len(), wrap).0;
         }
         KeyCode::Enter => {
             let field = FilterField::from_index(app.filter_selected);
             match field {
-                FilterField::Type => {
+                FilterField::Type | FilterField::Language | FilterField::Region => {
+                    app.update_filter_matches();
                     app.focus = Focus::FilterEditing;
                 }
                 FilterField::Radius => {
@@ -265,6 +462,9 @@ fn handle_filter_panel(key: KeyEvent, app: &mut App) {
                 FilterField::MinRating => {
                     app.cycle_min_rating();
                 }
+                FilterField::MinReviews => {
+                    app.cycle_min_reviews();
+                }
                 FilterField::Price => {
                     let active_count = app.filter_price_levels.iter().filter(|&&v| v).count();
                     if active_count == 0 {
@@ -290,6 +490,15 @@ fn handle_filter_panel(key: KeyEvent, app: &mut App) {
             let idx = (c as u8 - b'0') as usize;
             app.filter_price_levels[idx] = !app.filter_price_levels[idx];
         }
+        KeyCode::Char('>') => {
+            app.grow_price_range();
+        }
+        KeyCode::Char('x') => {
+            app.reset_filters();
+        }
+        KeyCode::Char('<') => {
+            app.shrink_price_range();
+        }
         _ => {}
     }
 }
@@ -297,77 +506,109 @@ fn handle_filter_panel(key: KeyEvent, app: &mut App) {
 fn handle_filter_editing(key: KeyEvent, app: &mut App) {
     match key.code {
         KeyCode::Esc => {
-            app.filter_type_matches.clear();
-            app.filter_type_match_idx = 0;
+            app.filter_matches.clear();
+            app.filter_match_idx = 0;
+            if let Err(e) = app.validate_filter_input() {
+                if let Some(input) = app.filter_input_mut() {
+                    input.reset();
+                }
+                app.set_status(format!("{}; cleared", e), true);
+            }
             app.focus = Focus::FilterPanel;
         }
         KeyCode::Enter => {
             // If there are matches and one is highlighted, pick it
-            if !app.filter_type_matches.is_empty() {
-                let selected = app
-                    .filter_type_matches
-                    .get(app.filter_type_match_idx)
-                    .copied()
-                    .unwrap_or("");
-                if !selected.is_empty() {
-                    app.filter_type_input = Input::new(selected.to_string());
+            if let Some(&selected) = app.filter_matches.get(app.filter_match_idx) {
+                if let Some(input) = app.filter_input_mut() {
+                    *input = Input::new(selected.to_string());
                 }
             }
-            app.filter_type_matches.clear();
-            app.filter_type_match_idx = 0;
+            if let Err(e) = app.validate_filter_input() {
+                app.set_status(e, true);
+                return;
+            }
+            app.filter_matches.clear();
+            app.filter_match_idx = 0;
             app.focus = Focus::FilterPanel;
         }
         KeyCode::Down | KeyCode::Tab => {
-            if !app.filter_type_matches.is_empty() {
-                if app.filter_type_match_idx + 1 < app.filter_type_matches.len() {
-                    app.filter_type_match_idx += 1;
-                }
+            if app.filter_match_idx + 1 < app.filter_matches.len() {
+                app.filter_match_idx += 1;
             }
         }
         KeyCode::Up => {
-            app.filter_type_match_idx = app.filter_type_match_idx.saturating_sub(1);
+            app.filter_match_idx = app.filter_match_idx.saturating_sub(1);
         }
         _ => {
-            app.filter_type_input.handle_event(&Event::Key(key));
-            app.update_type_matches();
+            if let Some(input) = app.filter_input_mut() {
+                input.handle_event(&Event::Key(key));
+            }
+            app.update_filter_matches();
         }
     }
 }
 
 fn handle_action(action: Action, app: &mut App) {
     match action {
+        Action::Client(ClientEvent::RequestFinished {
+            endpoint, elapsed, ..
+        }) => {
+            info!("{} took {} ms", endpoint, elapsed.as_millis());
+            if endpoint == "searchText" {
+                app.searches_this_month += 1;
+            }
+            app.last_request = Some((endpoint, elapsed));
+        }
+        Action::Client(ClientEvent::Warning(warning)) => {
+            app.set_status(format!("Warning: {}", warning), false);
+        }
+        Action::Client(ClientEvent::Coalesced { endpoint }) => {
+            info!("{} shared an in-flight request", endpoint);
+        }
+        Action::Client(ClientEvent::Throttled { delay, qps }) => {
+            info!("Rate limit: waited {} ms to stay under {} requests per second", delay.as_millis(), qps);
+        }
         Action::AutocompleteResult(Ok(resp)) => {
             info!("Autocomplete: {} suggestions", resp.suggestions.len());
             app.autocomplete = resp.suggestions;
             app.ac_selected = 0;
+            app.ac_offset = 0;
         }
         Action::AutocompleteResult(Err(e)) => {
             error!("Autocomplete error: {}", e);
             app.autocomplete.clear();
-            app.status = Some((format!("Autocomplete error: {}", e), true));
+            app.set_status(format!("Autocomplete error: {}", e), true);
         }
-        Action::SearchResult(Ok(resp)) => {
-            info!("Search: {} results", resp.places.len());
+        Action::SearchResult(seq, result) => {
+            // Whatever it returns, the search is no longer in flight
             app.loading = false;
-            if resp.places.is_empty() {
-                app.status = Some(("No results found.".to_string(), false));
-                app.results.clear();
-                app.results_state.select(None);
-                app.detail = None;
-            } else {
-                app.status = Some((format!("{} results", resp.places.len()), false));
-                app.results = resp.places;
-                app.results_state.select(Some(0));
-                app.detail = app.results.first().cloned();
-                app.detail_scroll = 0;
+            if seq != app.search_seq {
+                info!("Ignoring results of superseded search {}", seq);
+                return;
+            }
+            match result {
+                Ok(resp) => show_search_results(app, resp, false),
+                Err(e) => {
+                    error!("Search error: {}", e);
+                    app.set_status(format!("Search error: {}", e), true);
+                }
             }
         }
-        Action::SearchResult(Err(e)) => {
-            error!("Search error: {}", e);
-            app.loading = false;
-            app.status = Some((format!("Search error: {}", e), true));
+        Action::PreviewResult(seq, result) => {
+            app.previews_sent += 1;
+            if seq != app.search_seq {
+                info!("Ignoring results of superseded preview {}", seq);
+                return;
+            }
+            match result {
+                Ok(resp) => show_search_results(app, resp, true),
+                Err(e) => {
+                    error!("Preview search error: {}", e);
+                    app.set_status(format!("Preview error: {}", e), true);
+                }
+            }
         }
-        Action::DetailsResult(Ok(place)) => {
+        Action::DetailsResult(key, Ok(place)) => {
             let name = place
                 .display_name
                 .as_ref()
@@ -375,14 +616,114 @@ fn handle_action(action: Action, app: &mut App) {
                 .unwrap_or("?");
             info!("Details loaded: {}", name);
             app.loading = false;
-            app.status = Some(("Details loaded.".to_string(), false));
-            app.detail = Some(place);
-            app.detail_scroll = 0;
+            app.details_in_flight.remove(&key);
+            app.set_status("Details loaded.", false);
+            let mut place = *place;
+            let full = (app.all_reviews.as_ref() == Some(&key)).then(|| place.clone());
+            app::trim_reviews(&mut place);
+            // Only replace the pane if the user is still on this place
+            let current = app.selected_place().map(|p| app.details_cache_key(&p.id));
+            if current.as_deref() == Some(key.as_str()) {
+                app.detail = Some(full.unwrap_or_else(|| place.clone()));
+                app.detail_scroll = 0;
+            }
+            app.cache_details(key, place);
         }
-        Action::DetailsResult(Err(e)) => {
+        Action::DetailsResult(key, Err(e)) => {
+            app.details_in_flight.remove(&key);
             error!("Details error: {}", e);
             app.loading = false;
-            app.status = Some((format!("Details error: {}", e), true));
+            app.set_status(format!("Details error: {}", e), true);
+        }
+        Action::PhotoResult(place_id, index, result) => {
+            // Ignore photos of a gallery the user has already closed or left
+            let Some(gallery) = app.photo_gallery.as_mut().filter(|g| g.place_id == place_id) else {
+                return;
+            };
+            gallery.pending.remove(&index);
+            let shown = gallery.waiting == Some(index);
+            if shown {
+                gallery.waiting = None;
+            }
+            let count = gallery.photos.len();
+            match result {
+                Ok(resp) if !resp.photo_uri.is_empty() => {
+                    // A prefetch may land after the user moved on; keep it only if still near
+                    if gallery.distance(index) <= app::GALLERY_WINDOW {
+                        gallery.urls.insert(index, resp.photo_uri);
+                    }
+                    if shown {
+                        app.loading = false;
+                        app.set_status(format!("Photo {}/{} loaded.", index + 1, count), false);
+                    }
+                }
+                Ok(_) => {
+                    if shown {
+                        app.loading = false;
+                        app.set_status("No photo URL returned.", false);
+                    }
+                }
+                Err(e) => {
+                    error!("Photo error: {}", e);
+                    if shown {
+                        app.loading = false;
+                        app.set_status(format!("Photo error: {}", e), true);
+                    }
+                }
+            }
+        }
+        Action::RouteSummaryResult(place_id, mode, Ok(summary)) => {
+            app.loading = false;
+            app.set_status(format!("Route ({}) loaded.", app::mode_label(mode)), false);
+            app.cache_route((place_id, mode), summary);
+        }
+        Action::RouteSummaryResult(_, _, Err(e)) => {
+            error!("Route error: {}", e);
+            app.loading = false;
+            app.set_status(format!("Route error: {}", e), true);
         }
     }
 }
+
+/// Replace the results pane with a search's places; `preview` marks results
+/// of a live preview search, which Enter later replaces
+fn show_search_results(app: &mut App, mut resp: SearchResponse, preview: bool) {
+    info!("Search: {} results (preview: {})", resp.places.len(), preview);
+    app.previewing = preview;
+
+    // The API has no review-count filter, so apply it before counting
+    let mut dropped = 0;
+    if let Some(min) = app.filter_min_reviews {
+        let before = resp.places.len();
+        resp.places
+            .retain(|p| p.user_rating_count.unwrap_or(0) >= min);
+        dropped = before - resp.places.len();
+    }
+    let mut hidden = if dropped > 0 {
+        format!(
+            " ({} with fewer than {} reviews hidden)",
+            dropped,
+            app.filter_min_reviews.unwrap_or(0)
+        )
+    } else {
+        String::new()
+    };
+    if preview {
+        hidden.push_str(" (live preview)");
+    }
+
+    if resp.places.is_empty() {
+        app.set_status(format!("No results found.{}", hidden), false);
+        app.results.clear();
+        app.results_state.select(None);
+        app.detail = None;
+    } else {
+        app.set_status(format!("{} results{}", resp.places.len(), hidden), false);
+        app.results = resp.places;
+        app.results_state.select(Some(0));
+        app.photo_gallery = None;
+        app.all_reviews = None;
+        app.detail = app.results.first().cloned();
+        app.detail_scroll = 0;
+    }
+}
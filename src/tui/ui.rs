@@ -1,19 +1,38 @@
-use ratatui::layout::{Constraint, Direction, Layout, Rect};
-use ratatui::style::{Color, Style};
-use ratatui::widgets::{Block, Borders, Clear, Paragraph, Widget};
+use ratatui::layout::{Alignment, Constraint, Direction, Layout, Rect, Size};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph, Widget, Wrap};
 
-use crate::tui::app::{App, Focus};
+use crate::format::sanitize_display;
+use crate::tui::app::{App, FilterField, Focus};
 use crate::tui::widgets::{filter_panel, place_details, places_list, search_bar, status_bar};
 
+/// Smallest terminal the layout fits in
+const MIN_WIDTH: u16 = 80;
+const MIN_HEIGHT: u16 = 18;
+/// Below this height the filter panel collapses to a summary line unless focused
+const COMPACT_HEIGHT: u16 = 30;
+/// Most suggestions the autocomplete dropdown shows at once; it scrolls beyond
+const DROPDOWN_ROWS: u16 = 6;
+/// Fewest rows an overlay is drawn with: one line between its borders
+const OVERLAY_MIN_HEIGHT: u16 = 3;
+
 pub fn render(frame: &mut ratatui::Frame, app: &mut App) {
     let area = frame.area();
+    if area.width < MIN_WIDTH || area.height < MIN_HEIGHT {
+        render_too_small(area, frame.buffer_mut());
+        return;
+    }
+    let filters_focused = matches!(app.focus, Focus::FilterPanel | Focus::FilterEditing);
+    let compact_filters = area.height < COMPACT_HEIGHT && !filters_focused;
 
     // search bar | filter panel | main content | status bar
     let vertical = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
             Constraint::Length(3), // search bar
-            Constraint::Length(7), // filter panel (5 rows + border)
+            // filter panel: 8 rows + border, or a one-line summary
+            Constraint::Length(if compact_filters { 3 } else { 10 }),
             Constraint::Min(5),   // main content
             Constraint::Length(1), // status bar
         ])
@@ -28,7 +47,11 @@ pub fn render(frame: &mut ratatui::Frame, app: &mut App) {
     search_bar::render_search_bar(search_area, frame.buffer_mut(), app);
 
     // Render filter panel
-    filter_panel::render_filter_panel(filter_area, frame.buffer_mut(), app);
+    if compact_filters {
+        filter_panel::render_filter_summary(filter_area, frame.buffer_mut(), app);
+    } else {
+        filter_panel::render_filter_panel(filter_area, frame.buffer_mut(), app);
+    }
 
     // Main split pane: results list | details
     let horizontal = Layout::default()
@@ -45,78 +68,270 @@ pub fn render(frame: &mut ratatui::Frame, app: &mut App) {
     // Status bar
     status_bar::render_status_bar(status_area, frame.buffer_mut(), app);
 
-    // Autocomplete dropdown overlay
+    // Overlays stay clear of the status bar
+    let overlay_frame = Rect {
+        height: status_area.y - area.y,
+        ..area
+    };
+
+    // Autocomplete dropdown overlay: one row per suggestion plus borders
     if !app.autocomplete.is_empty() && !app.input.value().is_empty() {
-        let dropdown_y = search_area.y + search_area.height;
-        let dropdown_height = (app.autocomplete.len() as u16 + 2).min(8);
-        let dropdown_area = Rect {
-            x: search_area.x,
-            y: dropdown_y,
-            width: search_area.width.min(area.width),
-            height: dropdown_height.min(filter_area.height + main_area.height),
+        let rows = (app.autocomplete.len() as u16).min(DROPDOWN_ROWS);
+        let desired = Size::new(search_area.width, rows + 2);
+        if let Some(dropdown_area) = fit_overlay(search_area, desired, overlay_frame) {
+            search_bar::render_autocomplete_dropdown(dropdown_area, frame.buffer_mut(), app);
+        }
+    }
+
+    // Picker overlay (when editing the type, language, or region filter)
+    if app.focus == Focus::FilterEditing && !app.filter_matches.is_empty() {
+        let desired = Size::new(45, (picker_rows(app).len() as u16 + 2).min(14));
+        if let Some(picker_area) = fit_overlay(filter_area, desired, overlay_frame) {
+            render_filter_picker(picker_area, frame.buffer_mut(), app);
+        }
+    }
+
+    // Message log popup
+    if app.focus == Focus::MessageLog {
+        // Computed in u32 so very wide terminals can't overflow
+        let width = ((area.width as u32 * 4 / 5) as u16).max(20).min(area.width);
+        let height = ((area.height as u32 * 7 / 10) as u16).max(5).min(area.height);
+        let popup_area = Rect {
+            x: area.x + area.width.saturating_sub(width) / 2,
+            y: area.y + area.height.saturating_sub(height) / 2,
+            width,
+            height,
         };
-        search_bar::render_autocomplete_dropdown(dropdown_area, frame.buffer_mut(), app);
+        render_message_log(popup_area, frame.buffer_mut(), app);
     }
 
-    // Type picker overlay (when editing the type filter)
-    if app.focus == Focus::FilterEditing && !app.filter_type_matches.is_empty() {
-        // Position below the Type row in the filter panel (row 0 + border = y+1)
-        let picker_y = filter_area.y + filter_area.height;
-        let picker_height = (app.filter_type_matches.len() as u16 + 2).min(8);
-        let picker_width = 45.min(area.width);
-        let picker_area = Rect {
-            x: filter_area.x,
-            y: picker_y,
-            width: picker_width,
-            height: picker_height.min(main_area.height),
+    // Raw JSON popup
+    if app.focus == Focus::RawJson {
+        let width = ((area.width as u32 * 4 / 5) as u16).max(20).min(area.width);
+        let height = ((area.height as u32 * 4 / 5) as u16).max(5).min(area.height);
+        let popup_area = Rect {
+            x: area.x + area.width.saturating_sub(width) / 2,
+            y: area.y + area.height.saturating_sub(height) / 2,
+            width,
+            height,
         };
-        render_type_picker(picker_area, frame.buffer_mut(), app);
+        render_raw_json(popup_area, frame.buffer_mut(), app);
+    }
+}
+
+/// Where to draw an overlay of `desired` size attached to `anchor`: left-aligned
+/// with it and below it, or above it when there's more room there, shrunk to
+/// stay inside `frame`. None when fewer than `OVERLAY_MIN_HEIGHT` rows fit.
+fn fit_overlay(anchor: Rect, desired: Size, frame: Rect) -> Option<Rect> {
+    let width = desired.width.min(frame.width);
+    if width == 0 {
+        return None;
+    }
+    let x = anchor.x.clamp(frame.x, frame.right() - width);
+
+    let below = frame.bottom().saturating_sub(anchor.bottom().max(frame.y));
+    let above = anchor.y.min(frame.bottom()).saturating_sub(frame.y);
+    let (y, height) = if desired.height <= below || below >= above {
+        let height = desired.height.min(below);
+        (frame.bottom() - below, height)
+    } else {
+        let height = desired.height.min(above);
+        (frame.y + above - height, height)
+    };
+    (height >= OVERLAY_MIN_HEIGHT).then_some(Rect { x, y, width, height })
+}
+
+/// Shown instead of the layout when the terminal can't fit it
+fn render_too_small(area: Rect, buf: &mut ratatui::buffer::Buffer) {
+    let lines = vec![
+        Line::from(Span::styled(
+            "Terminal too small",
+            Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+        )),
+        Line::from(format!(
+            "{}×{}, need {}×{}",
+            area.width, area.height, MIN_WIDTH, MIN_HEIGHT
+        )),
+        Line::from(Span::styled(
+            "Enlarge the window, or press Ctrl+C twice to quit",
+            Style::default().fg(Color::DarkGray),
+        )),
+    ];
+    let top = area.height.saturating_sub(lines.len() as u16) / 2;
+    let text_area = Rect {
+        y: area.y + top,
+        height: area.height - top,
+        ..area
+    };
+    Paragraph::new(lines)
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true })
+        .render(text_area, buf);
+}
+
+fn render_message_log(area: Rect, buf: &mut ratatui::buffer::Buffer, app: &mut App) {
+    Clear.render(area, buf);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Yellow))
+        .title(format!(" Messages ({}) ", app.status_log.len()));
+    let inner_height = block.inner(area).height;
+
+    let lines: Vec<Line> = if app.status_log.is_empty() {
+        vec![Line::from(Span::styled(
+            " No messages yet.",
+            Style::default().fg(Color::DarkGray),
+        ))]
+    } else {
+        app.status_log
+            .iter()
+            .map(|entry| {
+                let style = if entry.is_error {
+                    Style::default().fg(Color::Red)
+                } else {
+                    Style::default().fg(Color::White)
+                };
+                Line::from(vec![
+                    Span::styled(
+                        format!(" {:>8}  ", format_age(entry.at.elapsed())),
+                        Style::default().fg(Color::DarkGray),
+                    ),
+                    Span::styled(entry.message.clone(), style),
+                ])
+            })
+            .collect()
+    };
+
+    // Clamp so scrolling past the newest message stops at the bottom
+    let max_scroll = (lines.len() as u16).saturating_sub(inner_height);
+    app.log_scroll = app.log_scroll.min(max_scroll);
+
+    Paragraph::new(lines)
+        .block(block)
+        .scroll((app.log_scroll, 0))
+        .render(area, buf);
+}
+
+fn render_raw_json(area: Rect, buf: &mut ratatui::buffer::Buffer, app: &mut App) {
+    let Some(raw) = app.raw_json.as_ref() else {
+        return;
+    };
+    Clear.render(area, buf);
+
+    let source = if raw.as_received { "as received" } else { "re-serialized" };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Yellow))
+        .title(format!(" {} — JSON, {} ", sanitize_display(&raw.name), source))
+        .title_bottom(Line::from(" j/k: scroll  y: copy  Esc/J: close ").right_aligned());
+    let inner_height = block.inner(area).height;
+
+    // JSON escapes control characters, but not bidi overrides and the like;
+    // the indentation is kept apart since sanitizing collapses it
+    let lines: Vec<Line> = raw
+        .text
+        .lines()
+        .map(|line| {
+            let body = line.trim_start_matches(' ');
+            let indent = &line[..line.len() - body.len()];
+            Line::from(format!("{}{}", indent, sanitize_display(body)))
+        })
+        .collect();
+
+    let max_scroll = (lines.len() as u16).saturating_sub(inner_height);
+    app.raw_scroll = app.raw_scroll.min(max_scroll);
+
+    Paragraph::new(lines)
+        .block(block)
+        .scroll((app.raw_scroll, 0))
+        .render(area, buf);
+}
+
+/// Age of a message, e.g. "8s ago", "3m ago", "1h ago"
+fn format_age(elapsed: std::time::Duration) -> String {
+    let secs = elapsed.as_secs();
+    if secs < 60 {
+        format!("{}s ago", secs)
+    } else if secs < 3600 {
+        format!("{}m ago", secs / 60)
+    } else {
+        format!("{}h ago", secs / 3600)
     }
 }
 
-fn render_type_picker(area: Rect, buf: &mut ratatui::buffer::Buffer, app: &App) {
+fn render_filter_picker(area: Rect, buf: &mut ratatui::buffer::Buffer, app: &App) {
     Clear.render(area, buf);
 
     let block = Block::default()
         .borders(Borders::ALL)
         .border_style(Style::default().fg(Color::Yellow))
-        .title(" Types (↑↓ select, Enter confirm) ");
+        .title(match FilterField::from_index(app.filter_selected) {
+            FilterField::Language => " Languages (↑↓ select, Enter confirm) ",
+            FilterField::Region => " Regions (↑↓ select, Enter confirm) ",
+            _ => " Types (↑↓ select, Enter confirm) ",
+        });
 
     let inner = block.inner(area);
     block.render(area, buf);
 
-    for (i, &type_name) in app
-        .filter_type_matches
-        .iter()
+    for (i, row) in picker_rows(app)
+        .into_iter()
         .take(inner.height as usize)
         .enumerate()
     {
-        let y = inner.y + i as u16;
-        if y >= inner.y + inner.height {
-            break;
-        }
-
-        let style = if i == app.filter_type_match_idx {
-            Style::default().bg(Color::Yellow).fg(Color::Black)
-        } else {
-            Style::default().fg(Color::White)
+        let (text, style) = match row {
+            PickerRow::Header(category) => (
+                category.to_string(),
+                Style::default()
+                    .fg(Color::DarkGray)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            PickerRow::Item(idx, name) => {
+                let style = if idx == app.filter_match_idx {
+                    Style::default().bg(Color::Yellow).fg(Color::Black)
+                } else {
+                    Style::default().fg(Color::White)
+                };
+                (format!("  {}", name), style)
+            }
         };
 
-        let row = Rect {
+        let row_area = Rect {
             x: inner.x,
-            y,
+            y: inner.y + i as u16,
             width: inner.width,
             height: 1,
         };
-
-        let display = if type_name.len() > inner.width as usize {
-            &type_name[..inner.width as usize]
-        } else {
-            type_name
-        };
-
+        let display: String = text.chars().take(inner.width as usize).collect();
         Paragraph::new(format!(" {}", display))
             .style(style)
-            .render(row, buf);
+            .render(row_area, buf);
+    }
+}
+
+enum PickerRow {
+    Header(&'static str),
+    /// Index into `filter_matches` and the value shown
+    Item(usize, &'static str),
+}
+
+/// Picker rows; type matches get a header each time the category changes
+fn picker_rows(app: &App) -> Vec<PickerRow> {
+    let is_type = FilterField::from_index(app.filter_selected) == FilterField::Type;
+    let mut rows = Vec::new();
+    let mut current = None;
+    for (idx, &name) in app.filter_matches.iter().enumerate() {
+        if is_type {
+            let category = crate::place_types::category_of(name);
+            if category != current {
+                if let Some(c) = category {
+                    rows.push(PickerRow::Header(c));
+                }
+                current = category;
+            }
+        }
+        rows.push(PickerRow::Item(idx, name));
     }
+    rows
 }
@@ -4,6 +4,7 @@ use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Borders, Paragraph, Widget};
 
+use crate::format::format_distance;
 use crate::tui::app::{App, FilterField, Focus};
 
 pub fn render_filter_panel(area: Rect, buf: &mut Buffer, app: &App) {
@@ -18,17 +19,30 @@ pub fn render_filter_panel(area: Rect, buf: &mut Buffer, app: &App) {
     let block = Block::default()
         .borders(Borders::ALL)
         .border_style(border_style)
-        .title(" Filters (0-4: toggle price) ");
+        .title(" Filters (0-4: toggle price, </>: range) ");
 
     let inner = block.inner(area);
     block.render(area, buf);
 
     let rows = [
-        render_type_row(app),
+        render_text_row(
+            app,
+            FilterField::Type,
+            "Type:       ",
+            "any (e.g. restaurant, cafe, bar, thai_restaurant)",
+        ),
         render_radius_row(app),
         render_min_rating_row(app),
+        render_min_reviews_row(app),
         render_price_row(app),
         render_open_now_row(app),
+        render_text_row(
+            app,
+            FilterField::Language,
+            "Language:   ",
+            "default (e.g. en, de, pt-BR)",
+        ),
+        render_text_row(app, FilterField::Region, "Region:     ", "default (e.g. us, gb, jp)"),
     ];
 
     for (i, line) in rows.iter().enumerate() {
@@ -64,20 +78,31 @@ pub fn render_filter_panel(area: Rect, buf: &mut Buffer, app: &App) {
     }
 }
 
-fn render_type_row(app: &App) -> Vec<Span<'static>> {
+/// A free-text row (type, language, region) with an inline cursor while editing
+fn render_text_row(
+    app: &App,
+    field: FilterField,
+    label: &'static str,
+    placeholder: &'static str,
+) -> Vec<Span<'static>> {
     let is_editing = app.focus == Focus::FilterEditing
-        && FilterField::from_index(app.filter_selected) == FilterField::Type;
-    let val = app.filter_type_input.value();
+        && FilterField::from_index(app.filter_selected) == field;
+    let input = match field {
+        FilterField::Language => &app.filter_language_input,
+        FilterField::Region => &app.filter_region_input,
+        _ => &app.filter_type_input,
+    };
+    let val = input.value();
 
     let mut spans = vec![
         Span::styled(
-            "Type:       ",
+            label,
             Style::default().add_modifier(Modifier::BOLD),
         ),
     ];
 
     if is_editing {
-        let cursor_pos = app.filter_type_input.visual_cursor();
+        let cursor_pos = input.visual_cursor();
         let (before, after) = val.split_at(
             val.char_indices()
                 .nth(cursor_pos)
@@ -98,7 +123,7 @@ fn render_type_row(app: &App) -> Vec<Span<'static>> {
         spans.push(Span::raw(rest.to_string()));
     } else if val.is_empty() {
         spans.push(Span::styled(
-            "any (e.g. restaurant, cafe, bar, thai_restaurant)".to_string(),
+            placeholder,
             Style::default().fg(Color::DarkGray),
         ));
     } else {
@@ -109,12 +134,7 @@ fn render_type_row(app: &App) -> Vec<Span<'static>> {
 }
 
 fn render_radius_row(app: &App) -> Vec<Span<'static>> {
-    let r = app.filter_radius;
-    let display = if r >= 1000.0 {
-        format!("{:.0} km", r / 1000.0)
-    } else {
-        format!("{:.0} m", r)
-    };
+    let display = format_distance(app.filter_radius, app.config.units(), app.number_format());
 
     vec![
         Span::styled(
@@ -154,6 +174,31 @@ fn render_min_rating_row(app: &App) -> Vec<Span<'static>> {
     ]
 }
 
+fn render_min_reviews_row(app: &App) -> Vec<Span<'static>> {
+    let display = match app.filter_min_reviews {
+        None => "any".to_string(),
+        Some(n) => format!("{}+", n),
+    };
+
+    let color = if app.filter_min_reviews.is_some() {
+        Color::Yellow
+    } else {
+        Color::DarkGray
+    };
+
+    vec![
+        Span::styled(
+            "Reviews:    ",
+            Style::default().add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(display, Style::default().fg(color)),
+        Span::styled(
+            "  (Enter to cycle)".to_string(),
+            Style::default().fg(Color::DarkGray),
+        ),
+    ]
+}
+
 fn render_price_row(app: &App) -> Vec<Span<'static>> {
     let labels = ["Free", "$", "$$", "$$$", "$$$$"];
     let mut spans = vec![
@@ -194,7 +239,7 @@ fn render_price_row(app: &App) -> Vec<Span<'static>> {
     // Show toggle hint even when "any"
     if !any_active {
         spans.push(Span::styled(
-            "  (0-4 to toggle)".to_string(),
+            "  (0-4 to toggle, </> for a range)".to_string(),
             Style::default().fg(Color::DarkGray),
         ));
     }
@@ -221,3 +266,49 @@ fn render_open_now_row(app: &App) -> Vec<Span<'static>> {
         ),
     ]
 }
+
+/// Collapsed panel for short terminals: the active filters on one line
+pub fn render_filter_summary(area: Rect, buf: &mut Buffer, app: &App) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::DarkGray))
+        .title(" Filters (Tab/f to expand) ");
+
+    let mut parts: Vec<String> = Vec::new();
+    let type_value = app.filter_type_input.value();
+    if !type_value.is_empty() {
+        parts.push(type_value.to_string());
+    }
+    parts.push(format_distance(app.filter_radius, app.config.units(), app.number_format()));
+    if let Some(r) = app.filter_min_rating {
+        parts.push(format!("{:.1}+ ★", r));
+    }
+    if let Some(n) = app.filter_min_reviews {
+        parts.push(format!("{}+ reviews", n));
+    }
+    let labels = ["Free", "$", "$$", "$$$", "$$$$"];
+    let prices: Vec<&str> = labels
+        .iter()
+        .zip(app.filter_price_levels)
+        .filter(|(_, on)| *on)
+        .map(|(label, _)| *label)
+        .collect();
+    if !prices.is_empty() {
+        parts.push(prices.join(" "));
+    }
+    if app.filter_open_now {
+        parts.push("open now".into());
+    }
+    for input in [&app.filter_language_input, &app.filter_region_input] {
+        if !input.value().is_empty() {
+            parts.push(input.value().to_string());
+        }
+    }
+
+    Paragraph::new(Line::from(vec![
+        Span::raw(" "),
+        Span::styled(parts.join("  ·  "), Style::default().fg(Color::Cyan)),
+    ]))
+    .block(block)
+    .render(area, buf);
+}
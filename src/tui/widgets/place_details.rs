@@ -1,13 +1,26 @@
+use std::time::Duration;
+
 use ratatui::buffer::Buffer;
-use ratatui::layout::Rect;
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
 use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Borders, Paragraph, Widget, Wrap};
 
-use crate::api::types::{price_level_display, Place};
-use crate::tui::app::App;
+use crate::api::types::{price_level_display, LatLng, Place, Units};
+use crate::format::{format_count, format_distance, format_rating, humanize_duration, sanitize_display, NumberFormat};
+use crate::geo::{distance_and_direction, haversine_distance};
+use crate::hours::{self, open_state_now, OpenState};
+use crate::render::truncate;
+use crate::tui::app::{mode_label, App, KEPT_REVIEWS};
+
+pub fn render_place_details(area: Rect, buf: &mut Buffer, app: &mut App) {
+    if app.comparing {
+        if let (Some(pinned), Some(selected)) = (&app.pinned, &app.detail) {
+            render_comparison(area, buf, app, pinned, selected);
+            return;
+        }
+    }
 
-pub fn render_place_details(area: Rect, buf: &mut Buffer, app: &App) {
     let block = Block::default()
         .borders(Borders::ALL)
         .border_style(Style::default().fg(Color::DarkGray))
@@ -25,24 +38,27 @@ pub fn render_place_details(area: Rect, buf: &mut Buffer, app: &App) {
         }
     };
 
-    let lines = build_detail_lines(place);
+    let lines = build_detail_lines(place, app);
+    let inner = block.inner(area);
 
-    let paragraph = Paragraph::new(lines)
-        .block(block)
-        .wrap(Wrap { trim: false })
-        .scroll((app.detail_scroll, 0));
+    let paragraph = Paragraph::new(lines).wrap(Wrap { trim: false });
+    // Keep the last line reachable but never scroll past it, whatever the pane size
+    let max_scroll = paragraph.line_count(inner.width).saturating_sub(inner.height as usize);
+    app.detail_scroll = app.detail_scroll.min(max_scroll.min(u16::MAX as usize) as u16);
+    let paragraph = paragraph.block(block).scroll((app.detail_scroll, 0));
 
     paragraph.render(area, buf);
 }
 
-fn build_detail_lines(place: &Place) -> Vec<Line<'static>> {
+fn build_detail_lines(place: &Place, app: &App) -> Vec<Line<'static>> {
     let mut lines: Vec<Line<'static>> = Vec::new();
+    let nf = app.number_format();
 
     // Name
     let name = place
         .display_name
         .as_ref()
-        .map(|n| n.text.clone())
+        .map(|n| sanitize_display(&n.text).into_owned())
         .unwrap_or_else(|| "Unknown".to_string());
 
     lines.push(Line::from(Span::styled(
@@ -55,7 +71,7 @@ fn build_detail_lines(place: &Place) -> Vec<Line<'static>> {
     // Type
     if let Some(ref pt) = place.primary_type_display_name {
         lines.push(Line::from(Span::styled(
-            pt.text.clone(),
+            sanitize_display(&pt.text).into_owned(),
             Style::default().fg(Color::DarkGray),
         )));
     }
@@ -77,7 +93,7 @@ fn build_detail_lines(place: &Place) -> Vec<Line<'static>> {
         lines.push(Line::from(vec![
             Span::styled("Rating: ", Style::default().add_modifier(Modifier::BOLD)),
             Span::styled(stars, Style::default().fg(Color::Yellow)),
-            Span::raw(format!(" {} ({} reviews)", rating, count)),
+            Span::raw(format!(" {} ({} reviews)", format_rating(rating, nf), format_count(count as u64, nf))),
         ]));
     }
 
@@ -98,7 +114,7 @@ fn build_detail_lines(place: &Place) -> Vec<Line<'static>> {
         };
         lines.push(Line::from(vec![
             Span::styled("Status: ", Style::default().add_modifier(Modifier::BOLD)),
-            Span::styled(display.to_string(), Style::default().fg(color)),
+            Span::styled(sanitize_display(display).into_owned(), Style::default().fg(color)),
         ]));
     }
 
@@ -123,7 +139,19 @@ fn build_detail_lines(place: &Place) -> Vec<Line<'static>> {
     if let Some(ref addr) = place.formatted_address {
         lines.push(Line::from(vec![
             Span::styled("Address: ", Style::default().add_modifier(Modifier::BOLD)),
-            Span::raw(addr.clone()),
+            Span::raw(sanitize_display(addr).into_owned()),
+        ]));
+    }
+
+    // Distance from the location bias center
+    let away = app
+        .location_bias()
+        .and_then(|bias| distance_and_direction(&bias.center, place, app.config.units(), nf));
+    if let Some(away) = away {
+        lines.push(Line::from(vec![
+            Span::styled("Distance:", Style::default().add_modifier(Modifier::BOLD)),
+            Span::raw(format!(" {}", away)),
+            Span::styled(" from you", Style::default().fg(Color::DarkGray)),
         ]));
     }
 
@@ -135,7 +163,7 @@ fn build_detail_lines(place: &Place) -> Vec<Line<'static>> {
     if let Some(ph) = phone {
         lines.push(Line::from(vec![
             Span::styled("Phone:   ", Style::default().add_modifier(Modifier::BOLD)),
-            Span::raw(ph.to_string()),
+            Span::raw(sanitize_display(ph).into_owned()),
         ]));
     }
 
@@ -143,7 +171,7 @@ fn build_detail_lines(place: &Place) -> Vec<Line<'static>> {
     if let Some(ref uri) = place.website_uri {
         lines.push(Line::from(vec![
             Span::styled("Website: ", Style::default().add_modifier(Modifier::BOLD)),
-            Span::styled(uri.clone(), Style::default().fg(Color::Blue)),
+            Span::styled(sanitize_display(uri).into_owned(), Style::default().fg(Color::Blue)),
         ]));
     }
 
@@ -151,7 +179,41 @@ fn build_detail_lines(place: &Place) -> Vec<Line<'static>> {
     if let Some(ref uri) = place.google_maps_uri {
         lines.push(Line::from(vec![
             Span::styled("Maps:    ", Style::default().add_modifier(Modifier::BOLD)),
-            Span::styled(uri.clone(), Style::default().fg(Color::Blue)),
+            Span::styled(sanitize_display(uri).into_owned(), Style::default().fg(Color::Blue)),
+        ]));
+    }
+
+    // Routes from the location bias (fetched on demand with `R`)
+    for (mode, summary) in app.routes_for(&place.id) {
+        let mut parts = Vec::new();
+        if let Some(secs) = summary.duration_seconds {
+            let travel = humanize_duration(Duration::from_secs(secs));
+            parts.push(format!("{} {}", travel, mode_label(mode)));
+        } else {
+            parts.push(mode_label(mode).to_string());
+        }
+        if let Some(meters) = summary.distance_meters {
+            parts.push(format_distance(meters as f64, app.config.units(), nf));
+        }
+        lines.push(Line::from(vec![
+            Span::styled("Route:   ", Style::default().add_modifier(Modifier::BOLD)),
+            Span::raw(parts.join(", ")),
+        ]));
+    }
+
+    // Photo gallery (opened with `p`, stepped with `]`/`[`)
+    if let Some(gallery) = app.photo_gallery.as_ref().filter(|g| g.place_id == place.id) {
+        let photo = match gallery.current_url() {
+            Some(url) => Span::styled(sanitize_display(url).into_owned(), Style::default().fg(Color::Blue)),
+            None => Span::styled("loading…", Style::default().fg(Color::DarkGray)),
+        };
+        lines.push(Line::from(vec![
+            Span::styled("Photo:   ", Style::default().add_modifier(Modifier::BOLD)),
+            Span::styled(
+                format!("{}/{} ", gallery.index + 1, gallery.photos.len()),
+                Style::default().fg(Color::Yellow),
+            ),
+            photo,
         ]));
     }
 
@@ -163,7 +225,7 @@ fn build_detail_lines(place: &Place) -> Vec<Line<'static>> {
                 "Summary",
                 Style::default().add_modifier(Modifier::BOLD),
             )));
-            lines.push(Line::from(text.clone()));
+            lines.push(Line::from(sanitize_display(text).into_owned()));
         }
     }
 
@@ -180,9 +242,14 @@ fn build_detail_lines(place: &Place) -> Vec<Line<'static>> {
                     "Hours",
                     Style::default().add_modifier(Modifier::BOLD),
                 )));
+                let descs = if app.config.hours_start_today() {
+                    hours::descriptions_from(descs, hours::weekday_now(place))
+                } else {
+                    descs.iter().collect()
+                };
                 for desc in descs {
                     lines.push(Line::from(Span::styled(
-                        format!("  {}", desc),
+                        format!("  {}", sanitize_display(desc)),
                         Style::default().fg(Color::DarkGray),
                     )));
                 }
@@ -190,19 +257,44 @@ fn build_detail_lines(place: &Place) -> Vec<Line<'static>> {
         }
     }
 
+    // Local notes (`zupo note`)
+    let notes = app.notes.for_place(&place.id);
+    if !notes.is_empty() {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            "Your notes",
+            Style::default().add_modifier(Modifier::BOLD),
+        )));
+        for note in notes {
+            lines.push(Line::from(vec![
+                Span::styled("  • ", Style::default().fg(Color::Yellow)),
+                Span::raw(sanitize_display(&note.text).into_owned()),
+                Span::styled(format!("  {}", note.date()), Style::default().fg(Color::DarkGray)),
+            ]));
+        }
+    }
+
     // Reviews
     if let Some(ref reviews) = place.reviews {
         if !reviews.is_empty() {
             lines.push(Line::from(""));
-            lines.push(Line::from(Span::styled(
+            let mut header = vec![Span::styled(
                 format!("Reviews ({})", reviews.len()),
                 Style::default().add_modifier(Modifier::BOLD),
-            )));
-            for (i, review) in reviews.iter().take(3).enumerate() {
+            )];
+            let full = app.all_reviews.as_ref() == Some(&app.details_cache_key(&place.id));
+            if reviews.len() >= KEPT_REVIEWS && !full {
+                header.push(Span::styled(
+                    "  v: fetch more",
+                    Style::default().fg(Color::DarkGray),
+                ));
+            }
+            lines.push(Line::from(header));
+            for (i, review) in reviews.iter().enumerate() {
                 let author = review
                     .author_attribution
                     .as_ref()
-                    .map(|a| a.display_name.clone())
+                    .map(|a| sanitize_display(&a.display_name).into_owned())
                     .unwrap_or_else(|| "Anonymous".to_string());
                 let rating = review.rating.unwrap_or(0.0);
                 let time = review
@@ -214,14 +306,18 @@ fn build_detail_lines(place: &Place) -> Vec<Line<'static>> {
                         format!("  {}. {} ", i + 1, author),
                         Style::default().add_modifier(Modifier::BOLD),
                     ),
-                    Span::styled(format!("{:.1}★", rating), Style::default().fg(Color::Yellow)),
-                    Span::styled(format!("  {}", time), Style::default().fg(Color::DarkGray)),
+                    Span::styled(format!("{}★", format_rating(rating, nf)), Style::default().fg(Color::Yellow)),
+                    Span::styled(
+                        format!("  {}", sanitize_display(time)),
+                        Style::default().fg(Color::DarkGray),
+                    ),
                 ]));
                 if let Some(ref text) = review.text {
-                    let truncated = if text.text.len() > 150 {
-                        format!("{}...", &text.text[..150])
+                    let text = sanitize_display(&text.text);
+                    let truncated = if text.len() > 150 {
+                        format!("{}...", truncate(&text, 150))
                     } else {
-                        text.text.clone()
+                        text.into_owned()
                     };
                     lines.push(Line::from(Span::styled(
                         format!("     {}", truncated),
@@ -237,9 +333,102 @@ fn build_detail_lines(place: &Place) -> Vec<Line<'static>> {
         lines.push(Line::from(""));
         lines.push(Line::from(vec![
             Span::styled("ID: ", Style::default().fg(Color::DarkGray)),
-            Span::styled(place.id.clone(), Style::default().fg(Color::DarkGray)),
+            Span::styled(
+                sanitize_display(&place.id).into_owned(),
+                Style::default().fg(Color::DarkGray),
+            ),
         ]));
     }
 
     lines
 }
+
+/// Compare mode: the pinned place and the selected one in two columns
+fn render_comparison(area: Rect, buf: &mut Buffer, app: &App, pinned: &Place, selected: &Place) {
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(area);
+
+    let origin = app.config.default_location().map(|(lat, lng)| LatLng {
+        latitude: lat,
+        longitude: lng,
+    });
+    let units = app.config.units();
+    let nf = app.number_format();
+
+    for (column, (title, place)) in columns
+        .iter()
+        .zip([(" Pinned ", pinned), (" Selected ", selected)])
+    {
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::DarkGray))
+            .title(title);
+        Paragraph::new(comparison_lines(place, origin.as_ref(), units, nf))
+            .block(block)
+            .wrap(Wrap { trim: false })
+            .render(*column, buf);
+    }
+}
+
+fn comparison_lines(place: &Place, origin: Option<&LatLng>, units: Units, nf: NumberFormat) -> Vec<Line<'static>> {
+    let name = place
+        .display_name
+        .as_ref()
+        .map(|n| sanitize_display(&n.text).into_owned())
+        .unwrap_or_else(|| "Unknown".to_string());
+
+    let rating = place
+        .rating
+        .map(|r| format_rating(r, nf))
+        .unwrap_or_else(|| "—".to_string());
+    let reviews = place
+        .user_rating_count
+        .map(|n| format_count(n as u64, nf))
+        .unwrap_or_else(|| "—".to_string());
+    let price = place
+        .price_level
+        .as_deref()
+        .map(|p| price_level_display(p).to_string())
+        .unwrap_or_else(|| "—".to_string());
+    let distance = match (origin, place.location.as_ref()) {
+        (Some(o), Some(loc)) => format_distance(haversine_distance(o, loc), units, nf),
+        _ => "—".to_string(),
+    };
+    let state = open_state_now(place);
+    let (open, open_color) = match state {
+        OpenState::AlwaysOpen => ("Open 24 hours".to_string(), Color::Green),
+        OpenState::OpenFor(_) => match state.closing_soon() {
+            Some(m) => (format!("Open, closes in {} min", m), Color::Yellow),
+            None => ("Open".to_string(), Color::Green),
+        },
+        OpenState::Closed => ("Closed".to_string(), Color::Red),
+        OpenState::Unknown => match place.current_opening_hours.as_ref().and_then(|h| h.open_now) {
+            Some(true) => ("Open".to_string(), Color::Green),
+            Some(false) => ("Closed".to_string(), Color::Red),
+            None => ("—".to_string(), Color::DarkGray),
+        },
+    };
+    let address = place
+        .formatted_address
+        .as_deref()
+        .or(place.short_formatted_address.as_deref())
+        .map(|a| sanitize_display(a).into_owned())
+        .unwrap_or_else(|| "—".to_string());
+
+    let bold = Style::default().add_modifier(Modifier::BOLD);
+    vec![
+        Line::from(Span::styled(
+            name,
+            Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD),
+        )),
+        Line::from(""),
+        Line::from(vec![Span::styled("Rating:   ", bold), Span::styled(rating, Style::default().fg(Color::Yellow))]),
+        Line::from(vec![Span::styled("Reviews:  ", bold), Span::raw(reviews)]),
+        Line::from(vec![Span::styled("Price:    ", bold), Span::raw(price)]),
+        Line::from(vec![Span::styled("Distance: ", bold), Span::raw(distance)]),
+        Line::from(vec![Span::styled("Open:     ", bold), Span::styled(open, Style::default().fg(open_color))]),
+        Line::from(vec![Span::styled("Address:  ", bold), Span::raw(address)]),
+    ]
+}
@@ -4,11 +4,21 @@ use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Borders, List, ListItem, StatefulWidget, Widget};
 
-use crate::api::types::{price_level_display, Place};
+use crate::api::types::{price_level_display, LatLng, Place};
+use crate::format::{
+    format_count, format_distance, format_rating, list_address, place_label, sanitize_display, AddressChoice,
+    NumberFormat,
+};
+use crate::geo::{haversine_distance, FarCheck};
 use crate::tui::app::{App, Focus};
 
+/// Columns taken by the "▶ " highlight symbol
+const HIGHLIGHT_SYMBOL_WIDTH: u16 = 2;
+/// Below this item width the address line is omitted
+const ADDRESS_MIN_WIDTH: usize = 36;
+
 pub fn render_places_list(area: Rect, buf: &mut Buffer, app: &mut App) {
-    let is_focused = app.focus == Focus::ResultsList;
+    let is_focused = matches!(app.focus, Focus::ResultsList | Focus::RouteMode);
 
     let border_style = if is_focused {
         Style::default().fg(Color::Cyan)
@@ -18,14 +28,21 @@ pub fn render_places_list(area: Rect, buf: &mut Buffer, app: &mut App) {
 
     let title = if app.results.is_empty() {
         " Results ".to_string()
+    } else if app.previewing {
+        format!(" Results ({}) (live preview) ", app.results.len())
     } else {
         format!(" Results ({}) ", app.results.len())
     };
 
-    let block = Block::default()
+    let mut block = Block::default()
         .borders(Borders::ALL)
         .border_style(border_style)
         .title(title);
+    if app.end_flash_active() {
+        block = block.title_bottom(
+            Line::from(Span::styled(" — end — ", Style::default().fg(Color::DarkGray))).centered(),
+        );
+    }
 
     if app.results.is_empty() {
         let empty = ratatui::widgets::Paragraph::new("  No results yet. Type a query and press Enter.")
@@ -35,11 +52,35 @@ pub fn render_places_list(area: Rect, buf: &mut Buffer, app: &mut App) {
         return;
     }
 
+    // Room left for item text after the borders and the highlight symbol
+    let width = block.inner(area).width.saturating_sub(HIGHLIGHT_SYMBOL_WIDTH) as usize;
+    let origin = app.config.default_location().map(|(lat, lng)| LatLng {
+        latitude: lat,
+        longitude: lng,
+    });
+    let units = app.config.units();
+    let nf = app.number_format();
+    let addresses = app.address_choice();
+    let far_check = app
+        .location_bias()
+        .filter(|_| app.config.far_warning())
+        .map(|bias| FarCheck::new(&bias, app.config.far_factor()));
+
     let items: Vec<ListItem> = app
         .results
         .iter()
         .enumerate()
-        .map(|(i, place)| place_to_list_item(i, place))
+        .map(|(i, place)| {
+            let distance = origin
+                .as_ref()
+                .zip(place.location.as_ref())
+                .map(|(o, loc)| format_distance(haversine_distance(o, loc), units, nf));
+            let far = far_check
+                .as_ref()
+                .and_then(|check| check.far(place))
+                .map(|meters| format!("⚠ {} from your location", format_distance(meters, units, nf)));
+            place_to_list_item(i, place, distance, far, width, nf, addresses)
+        })
         .collect();
 
     let list = List::new(items)
@@ -54,17 +95,28 @@ pub fn render_places_list(area: Rect, buf: &mut Buffer, app: &mut App) {
     StatefulWidget::render(list, area, buf, &mut app.results_state);
 }
 
-fn place_to_list_item(index: usize, place: &Place) -> ListItem<'static> {
-    let name = place
-        .display_name
-        .as_ref()
-        .map(|n| n.text.clone())
-        .unwrap_or_else(|| "Unknown".to_string());
+/// `distance` is shown right-aligned on the first line when `width` leaves room;
+/// a `far` warning goes on a line of its own above the name
+fn place_to_list_item(
+    index: usize,
+    place: &Place,
+    distance: Option<String>,
+    far: Option<String>,
+    width: usize,
+    nf: NumberFormat,
+    addresses: AddressChoice,
+) -> ListItem<'static> {
+    let (name, fallback) = place_label(place);
+    let name_style = if fallback {
+        Style::default().fg(Color::DarkGray)
+    } else {
+        Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)
+    };
 
     let type_str = place
         .primary_type_display_name
         .as_ref()
-        .map(|t| t.text.clone())
+        .map(|t| sanitize_display(&t.text).into_owned())
         .or_else(|| place.primary_type.clone())
         .unwrap_or_default();
 
@@ -74,12 +126,22 @@ fn place_to_list_item(index: usize, place: &Place) -> ListItem<'static> {
             format!("{}. ", index + 1),
             Style::default().fg(Color::DarkGray),
         ),
-        Span::styled(name, Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+        Span::styled(name.clone(), name_style),
     ];
     if !type_str.is_empty() {
         line1_spans.push(Span::raw("  "));
         line1_spans.push(Span::styled(type_str, Style::default().fg(Color::DarkGray)));
     }
+    if let Some(distance) = distance {
+        // Right-align by display width (names may contain wide characters);
+        // the distance is dropped when it would not fit after a gap
+        let used = Line::from(line1_spans.clone()).width();
+        let dist_width = Span::raw(distance.as_str()).width();
+        if used + 2 + dist_width <= width {
+            line1_spans.push(Span::raw(" ".repeat(width - used - dist_width)));
+            line1_spans.push(Span::styled(distance, Style::default().fg(Color::Cyan)));
+        }
+    }
 
     // Line 2: rating + price
     let mut meta_parts: Vec<Span> = Vec::new();
@@ -87,7 +149,7 @@ fn place_to_list_item(index: usize, place: &Place) -> ListItem<'static> {
         let stars = tui_star_string(rating);
         let count = place.user_rating_count.unwrap_or(0);
         meta_parts.push(Span::styled(stars, Style::default().fg(Color::Yellow)));
-        meta_parts.push(Span::raw(format!(" {} ({})", rating, count)));
+        meta_parts.push(Span::raw(format!(" {} ({})", format_rating(rating, nf), format_count(count as u64, nf))));
     }
     if let Some(ref price) = place.price_level {
         if !meta_parts.is_empty() {
@@ -95,21 +157,40 @@ fn place_to_list_item(index: usize, place: &Place) -> ListItem<'static> {
         }
         meta_parts.push(Span::raw(price_level_display(price).to_string()));
     }
+    if let Some(n) = place.photos.as_ref().map(Vec::len).filter(|&n| n > 0) {
+        if !meta_parts.is_empty() {
+            meta_parts.push(Span::styled("  ·  ", Style::default().fg(Color::DarkGray)));
+        }
+        meta_parts.push(Span::styled(format!("📷 {}", n), Style::default().fg(Color::DarkGray)));
+    }
+    if let Some(open) = place.current_opening_hours.as_ref().and_then(|h| h.open_now) {
+        let (label, color) = if open {
+            ("Open", Color::Green)
+        } else {
+            ("Closed", Color::Red)
+        };
+        if !meta_parts.is_empty() {
+            meta_parts.push(Span::styled("  ·  ", Style::default().fg(Color::DarkGray)));
+        }
+        meta_parts.push(Span::styled(label, Style::default().fg(color)));
+    }
 
     // Line 3: address
-    let addr = place
-        .formatted_address
-        .as_deref()
-        .or(place.short_formatted_address.as_deref())
-        .unwrap_or("");
+    let addr = sanitize_display(list_address(place, addresses).unwrap_or(""));
 
-    let mut lines = vec![Line::from(line1_spans)];
+    let mut lines = Vec::new();
+    if let Some(far) = far {
+        lines.push(Line::from(Span::styled(far, Style::default().fg(Color::Yellow))));
+    }
+    lines.push(Line::from(line1_spans));
     if !meta_parts.is_empty() {
         lines.push(Line::from(meta_parts));
     }
-    if !addr.is_empty() {
+    // Narrow panes drop the address line first; it is also left out when it
+    // already stands in for the name
+    if !addr.is_empty() && width >= ADDRESS_MIN_WIDTH && !(fallback && addr == name) {
         lines.push(Line::from(Span::styled(
-            addr.to_string(),
+            addr.into_owned(),
             Style::default().fg(Color::DarkGray),
         )));
     }
@@ -4,6 +4,8 @@ use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Borders, Clear, Paragraph, Widget};
 
+use crate::api::types::FormattedText;
+use crate::format::sanitize_display;
 use crate::tui::app::{App, Focus};
 
 pub fn render_search_bar(area: Rect, buf: &mut Buffer, app: &App) {
@@ -58,12 +60,14 @@ pub fn render_search_bar(area: Rect, buf: &mut Buffer, app: &App) {
     paragraph.render(area, buf);
 }
 
-pub fn render_autocomplete_dropdown(area: Rect, buf: &mut Buffer, app: &App) {
+/// Suggestions in `area` (sized by the caller), scrolled so the selected one
+/// is visible; the title shows which of them are on screen
+pub fn render_autocomplete_dropdown(area: Rect, buf: &mut Buffer, app: &mut App) {
     if app.autocomplete.is_empty() || app.input.value().is_empty() {
         return;
     }
 
-    let items: Vec<String> = app
+    let items: Vec<Vec<(String, bool)>> = app
         .autocomplete
         .iter()
         .map(|s| {
@@ -71,61 +75,66 @@ pub fn render_autocomplete_dropdown(area: Rect, buf: &mut Buffer, app: &App) {
                 let main = pp
                     .structured_format
                     .as_ref()
-                    .and_then(|sf| sf.main_text.as_ref())
-                    .map(|t| t.text.as_str())
-                    .unwrap_or("");
+                    .and_then(|sf| sf.main_text.as_ref());
                 let secondary = pp
                     .structured_format
                     .as_ref()
                     .and_then(|sf| sf.secondary_text.as_ref())
                     .map(|t| t.text.as_str())
                     .unwrap_or("");
-                if secondary.is_empty() {
-                    main.to_string()
-                } else {
-                    format!("{} — {}", main, secondary)
+                let mut parts = main.map(owned_segments).unwrap_or_default();
+                if !secondary.is_empty() {
+                    parts.push((format!(" — {}", sanitize_display(secondary)), false));
                 }
+                parts
             } else if let Some(ref qp) = s.query_prediction {
-                let text = qp.text.as_ref().map(|t| t.text.as_str()).unwrap_or("?");
-                format!("🔍 {}", text)
+                let mut parts = vec![("🔍 ".to_string(), false)];
+                match qp.text {
+                    Some(ref t) => parts.extend(owned_segments(t)),
+                    None => parts.push(("?".to_string(), false)),
+                }
+                parts
             } else {
-                String::new()
+                Vec::new()
             }
         })
         .collect();
 
-    let count = items.len().min(5);
-    if count == 0 {
+    if items.is_empty() {
         return;
     }
 
-    // Position dropdown below the search bar
-    let dropdown_height = count as u16 + 2; // +2 for borders
-    let dropdown = Rect {
-        x: area.x,
-        y: area.y,
-        width: area.width,
-        height: dropdown_height.min(area.height),
-    };
-
     // Clear the area first
-    Clear.render(dropdown, buf);
+    Clear.render(area, buf);
 
     let block = Block::default()
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Yellow))
-        .title(" Suggestions ");
-
-    let inner = block.inner(dropdown);
-    block.render(dropdown, buf);
+        .border_style(Style::default().fg(Color::Yellow));
+    let inner = block.inner(area);
+    let rows = (inner.height as usize).max(1);
+
+    // Keep the selection in the window, and the window within the list
+    if app.ac_selected < app.ac_offset {
+        app.ac_offset = app.ac_selected;
+    } else if app.ac_selected >= app.ac_offset + rows {
+        app.ac_offset = app.ac_selected + 1 - rows;
+    }
+    app.ac_offset = app.ac_offset.min(items.len().saturating_sub(rows));
+    let shown = rows.min(items.len() - app.ac_offset);
+
+    block
+        .title(format!(
+            " Suggestions {}–{} of {} ",
+            app.ac_offset + 1,
+            app.ac_offset + shown,
+            items.len()
+        ))
+        .render(area, buf);
 
     let is_ac_focused = app.focus == Focus::AutocompleteList;
 
-    for (i, item) in items.iter().take(inner.height as usize).enumerate() {
-        let y = inner.y + i as u16;
-        if y >= inner.y + inner.height {
-            break;
-        }
+    for (row, (i, item)) in items.iter().enumerate().skip(app.ac_offset).take(shown).enumerate() {
+        let y = inner.y + row as u16;
 
         let style = if is_ac_focused && i == app.ac_selected {
             Style::default().bg(Color::Yellow).fg(Color::Black)
@@ -140,14 +149,28 @@ pub fn render_autocomplete_dropdown(area: Rect, buf: &mut Buffer, app: &App) {
             height: 1,
         };
 
-        let truncated = if item.len() > inner.width as usize {
-            &item[..inner.width as usize]
-        } else {
-            item
-        };
+        // Paragraph clips to the line area, so no manual (byte-based) truncation
+        let spans: Vec<Span> = item
+            .iter()
+            .map(|(text, matched)| {
+                let seg_style = if *matched {
+                    style.add_modifier(Modifier::BOLD | Modifier::UNDERLINED)
+                } else {
+                    style
+                };
+                Span::styled(text.clone(), seg_style)
+            })
+            .collect();
 
-        Paragraph::new(truncated.to_string())
+        Paragraph::new(Line::from(spans))
             .style(style)
             .render(line_area, buf);
     }
 }
+
+fn owned_segments(text: &FormattedText) -> Vec<(String, bool)> {
+    text.segments()
+        .into_iter()
+        .map(|(seg, matched)| (sanitize_display(seg).into_owned(), matched))
+        .collect()
+}
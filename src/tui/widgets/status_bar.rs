@@ -4,15 +4,20 @@ use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{Paragraph, Widget};
 
+use crate::api::client::SLOW_REQUEST_THRESHOLD;
+use crate::format::format_elapsed;
 use crate::tui::app::{App, Focus};
 
 pub fn render_status_bar(area: Rect, buf: &mut Buffer, app: &App) {
     let keybinds = match app.focus {
-        Focus::SearchInput => "Enter: search  Tab: filters  ↓: suggestions  Esc: results",
+        Focus::SearchInput => "Enter: search  Tab: filters  ↓: suggestions  Esc: results  Ctrl+C ×2: quit",
         Focus::AutocompleteList => "j/↓: next  k/↑: prev  Enter: select  Esc: back",
-        Focus::ResultsList => "j/↓: next  k/↑: prev  Enter: details  /: search  Tab/f: filters",
-        Focus::FilterPanel => "j/↓/k/↑: navigate  Enter: edit/toggle  0-4: price  Tab: results  /: search",
+        Focus::ResultsList => "j/↓: next  k/↑: prev  N Enter/NG: go to N  Enter: details  F5/^R: refresh  p: photo  ]/[: next/prev photo  v: more reviews  R: route  c: pin/compare  C: unpin  M: messages  J: raw JSON  D: log memory stats  /: search  Tab/f: filters  q: quit",
+        Focus::FilterPanel => "j/↓/k/↑: navigate  Enter: edit/toggle  0-4: price  </>: price range  x: reset  Tab: results  /: search",
         Focus::FilterEditing => "type value, Enter/Esc: confirm",
+        Focus::MessageLog => "j/↓/k/↑: scroll  g/G: oldest/newest  Esc/M: close",
+        Focus::RawJson => "j/↓/k/↑: scroll  Space/PgDn, PgUp: page  g/G: top/bottom  y: copy  Esc/J: close",
+        Focus::RouteMode => "Route by  d: drive  w: walk  b: bike  t: transit  Esc: cancel",
     };
 
     let mut spans = vec![Span::styled(
@@ -20,6 +25,14 @@ pub fn render_status_bar(area: Rect, buf: &mut Buffer, app: &App) {
         Style::default().fg(Color::DarkGray),
     )];
 
+    if let Some(count) = app.pending_count() {
+        spans.push(Span::raw(" │ "));
+        spans.push(Span::styled(
+            format!("count: {}", count),
+            Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD),
+        ));
+    }
+
     if let Some((ref msg, is_error)) = app.status {
         spans.push(Span::raw(" │ "));
         let style = if is_error {
@@ -32,6 +45,19 @@ pub fn render_status_bar(area: Rect, buf: &mut Buffer, app: &App) {
         spans.push(Span::styled(msg.clone(), style));
     }
 
+    if let Some((ref endpoint, elapsed)) = app.last_request {
+        let style = if elapsed > SLOW_REQUEST_THRESHOLD {
+            Style::default().fg(Color::Yellow)
+        } else {
+            Style::default().fg(Color::DarkGray)
+        };
+        spans.push(Span::raw(" │ "));
+        spans.push(Span::styled(
+            format!("{} {}", endpoint, format_elapsed(elapsed)),
+            style,
+        ));
+    }
+
     if app.loading {
         spans.push(Span::raw(" "));
         spans.push(Span::styled(
@@ -0,0 +1,217 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::Config;
+use crate::paths;
+
+/// Built-in list prices in USD per 1,000 requests, keyed by endpoint label.
+/// Tiers match the field masks zupo sends; `config.pricing` overrides them.
+const DEFAULT_PRICES: &[(&str, f64)] = &[
+    ("searchText", 35.0),
+    ("searchNearby", 35.0),
+    ("details", 20.0),
+    ("autocomplete", 2.83),
+    ("photo media", 7.0),
+    ("computeRoutes", 5.0),
+    ("download", 0.0),
+];
+
+pub const DISCLAIMER: &str = "Costs are estimates from list prices per 1,000 requests. \
+They ignore free tiers, volume discounts, and cheaper SKUs for narrow field masks; \
+check the Google Cloud console for actual billing.";
+
+/// Requests made by this process, per endpoint label
+#[derive(Debug, Default)]
+pub struct Tally {
+    counts: Mutex<BTreeMap<String, u64>>,
+    /// Calls answered by an identical request already in flight (not billed)
+    coalesced: Mutex<BTreeMap<String, u64>>,
+}
+
+impl Tally {
+    pub fn record(&self, endpoint: &str) {
+        if let Ok(mut counts) = self.counts.lock() {
+            *counts.entry(endpoint.to_string()).or_default() += 1;
+        }
+    }
+
+    pub fn record_coalesced(&self, endpoint: &str) {
+        if let Ok(mut coalesced) = self.coalesced.lock() {
+            *coalesced.entry(endpoint.to_string()).or_default() += 1;
+        }
+    }
+
+    pub fn total(&self) -> u64 {
+        self.counts
+            .lock()
+            .map(|c| c.values().sum())
+            .unwrap_or(0)
+    }
+
+    /// Add this process's counts to the monthly usage file
+    pub fn persist(&self, cfg: &Config) {
+        let counts = self.counts.lock().map(|c| c.clone()).unwrap_or_default();
+        let coalesced = self.coalesced.lock().map(|c| c.clone()).unwrap_or_default();
+        add_to_usage_file(cfg, counts, coalesced);
+    }
+
+    /// Like `persist`, but the counts are reset, so a long-running command can
+    /// call it after each round of requests without counting any twice
+    pub fn flush(&self, cfg: &Config) {
+        let counts = self.counts.lock().map(|mut c| std::mem::take(&mut *c)).unwrap_or_default();
+        let coalesced = self
+            .coalesced
+            .lock()
+            .map(|mut c| std::mem::take(&mut *c))
+            .unwrap_or_default();
+        add_to_usage_file(cfg, counts, coalesced);
+    }
+}
+
+/// Add counts to the monthly usage file.
+/// Best-effort: errors are ignored so usage tracking never fails a command.
+fn add_to_usage_file(cfg: &Config, counts: BTreeMap<String, u64>, coalesced: BTreeMap<String, u64>) {
+    if counts.is_empty() && coalesced.is_empty() {
+        return;
+    }
+    let Some(path) = usage_path(cfg) else {
+        return;
+    };
+
+    let mut usage = MonthlyUsage::load_current(cfg);
+    for (endpoint, n) in counts {
+        *usage.requests.entry(endpoint).or_default() += n;
+    }
+    for (endpoint, n) in coalesced {
+        *usage.coalesced.entry(endpoint).or_default() += n;
+    }
+    let _ = usage.write(&path);
+}
+
+/// Cumulative request counts for one calendar month (UTC)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MonthlyUsage {
+    /// "YYYY-MM"
+    pub month: String,
+    #[serde(default)]
+    pub requests: BTreeMap<String, u64>,
+    /// Calls that shared an identical in-flight request instead of being sent
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub coalesced: BTreeMap<String, u64>,
+}
+
+/// One row of the `zupo stats` table
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EndpointUsage {
+    pub endpoint: String,
+    pub requests: u64,
+    /// Calls that shared an in-flight request; not included in `requests`
+    pub coalesced: u64,
+    /// None when no price is known for the endpoint
+    pub estimated_cost_usd: Option<f64>,
+}
+
+impl MonthlyUsage {
+    /// Usage for the current month; a file from an earlier month counts as empty
+    pub fn load_current(cfg: &Config) -> Self {
+        let month = current_month();
+        let stored = usage_path(cfg)
+            .and_then(|p| fs::read_to_string(p).ok())
+            .and_then(|c| serde_json::from_str::<MonthlyUsage>(&c).ok());
+        match stored {
+            Some(usage) if usage.month == month => usage,
+            _ => MonthlyUsage {
+                month,
+                requests: BTreeMap::new(),
+                coalesced: BTreeMap::new(),
+            },
+        }
+    }
+
+    /// Per-endpoint rows with estimated costs
+    pub fn rows(&self, cfg: &Config) -> Vec<EndpointUsage> {
+        self.requests
+            .iter()
+            .map(|(endpoint, &requests)| EndpointUsage {
+                endpoint: endpoint.clone(),
+                requests,
+                coalesced: self.coalesced.get(endpoint).copied().unwrap_or(0),
+                estimated_cost_usd: price_per_thousand(endpoint, cfg)
+                    .map(|price| price * requests as f64 / 1000.0),
+            })
+            .collect()
+    }
+
+    fn write(&self, path: &PathBuf) -> std::io::Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let contents = serde_json::to_string_pretty(self).map_err(std::io::Error::other)?;
+        let tmp = path.with_extension(format!("json.tmp.{}", std::process::id()));
+        fs::write(&tmp, contents)?;
+        fs::rename(&tmp, path).inspect_err(|_| {
+            let _ = fs::remove_file(&tmp);
+        })
+    }
+}
+
+/// Delete the usage file (no-op if missing)
+pub fn reset(cfg: &Config) -> Result<(), String> {
+    let Some(path) = usage_path(cfg) else {
+        return Ok(());
+    };
+    match fs::remove_file(&path) {
+        Ok(()) => Ok(()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(e) => Err(format!("could not remove {}: {}", path.display(), e)),
+    }
+}
+
+/// Estimated cost in USD of `requests` calls to `endpoint`; None without a price
+pub fn estimated_cost(endpoint: &str, requests: u64, cfg: &Config) -> Option<f64> {
+    price_per_thousand(endpoint, cfg).map(|price| price * requests as f64 / 1000.0)
+}
+
+fn price_per_thousand(endpoint: &str, cfg: &Config) -> Option<f64> {
+    cfg.pricing.get(endpoint).copied().or_else(|| {
+        DEFAULT_PRICES
+            .iter()
+            .find(|(name, _)| *name == endpoint)
+            .map(|&(_, price)| price)
+    })
+}
+
+fn usage_path(cfg: &Config) -> Option<PathBuf> {
+    paths::state_dir(&cfg.paths).map(|d| d.join("usage.json"))
+}
+
+/// Current month in UTC as "YYYY-MM"
+fn current_month() -> String {
+    let secs = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let (year, month, _) = civil_from_days((secs / 86_400) as i64);
+    format!("{:04}-{:02}", year, month)
+}
+
+/// Civil year, month, and day for a count of days since 1970-01-01
+/// (Howard Hinnant's `civil_from_days`)
+pub(crate) fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
@@ -0,0 +1,170 @@
+#![allow(dead_code)]
+
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+use assert_cmd::Command;
+use tempfile::TempDir;
+
+/// One request as the mock saw it
+#[derive(Debug, Clone)]
+pub struct Request {
+    pub method: String,
+    pub path: String,
+    pub body: String,
+}
+
+type Responder = dyn Fn(&Request) -> (u16, String) + Send + Sync;
+
+/// A stand-in for the Places and Routes APIs on 127.0.0.1. Every request gets
+/// the responder's status and JSON body; connections are closed after each
+/// response.
+pub struct MockPlaces {
+    pub addr: SocketAddr,
+    requests: Arc<Mutex<Vec<Request>>>,
+}
+
+impl MockPlaces {
+    pub fn start(respond: impl Fn(&Request) -> (u16, String) + Send + Sync + 'static) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let respond: Arc<Responder> = Arc::new(respond);
+        let seen = requests.clone();
+        thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                let respond = respond.clone();
+                let seen = seen.clone();
+                thread::spawn(move || serve(stream, &*respond, &seen));
+            }
+        });
+        MockPlaces { addr, requests }
+    }
+
+    /// Answer every request with 200 and `body`
+    pub fn always(body: &str) -> Self {
+        let body = body.to_string();
+        Self::start(move |_| (200, body.clone()))
+    }
+
+    pub fn url(&self) -> String {
+        format!("http://{}", self.addr)
+    }
+
+    pub fn requests(&self) -> Vec<Request> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+fn serve(stream: TcpStream, respond: &Responder, seen: &Mutex<Vec<Request>>) {
+    let mut reader = BufReader::new(stream.try_clone().unwrap());
+    let mut line = String::new();
+    if reader.read_line(&mut line).unwrap_or(0) == 0 {
+        return;
+    }
+    let mut parts = line.split_whitespace();
+    let method = parts.next().unwrap_or_default().to_string();
+    let path = parts.next().unwrap_or_default().to_string();
+
+    let mut length = 0;
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header).unwrap_or(0) == 0 || header.trim().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.eq_ignore_ascii_case("content-length") {
+                length = value.trim().parse().unwrap_or(0);
+            }
+        }
+    }
+    let mut body = vec![0; length];
+    let _ = reader.read_exact(&mut body);
+
+    let request = Request {
+        method,
+        path,
+        body: String::from_utf8_lossy(&body).into_owned(),
+    };
+    let (status, body) = respond(&request);
+    seen.lock().unwrap().push(request);
+
+    let mut stream = stream;
+    let _ = write!(
+        stream,
+        "HTTP/1.1 {} Mock\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        body.len(),
+        body
+    );
+}
+
+/// A port nothing listens on, for offline tests
+pub fn closed_port_url() -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    drop(listener);
+    format!("http://{}", addr)
+}
+
+/// Config, state, and cache directories of one test run
+pub struct Home {
+    pub dir: TempDir,
+}
+
+impl Home {
+    pub fn new() -> Self {
+        Home {
+            dir: TempDir::new().unwrap(),
+        }
+    }
+
+    pub fn config_dir(&self) -> std::path::PathBuf {
+        self.dir.path().join("config")
+    }
+
+    pub fn config_file(&self) -> std::path::PathBuf {
+        self.config_dir().join("config.toml")
+    }
+
+    pub fn write_config(&self, contents: &str) {
+        std::fs::create_dir_all(self.config_dir()).unwrap();
+        std::fs::write(self.config_file(), contents).unwrap();
+    }
+
+    /// `zupo` with an API key, isolated from the user's config, state, cache,
+    /// `.env`, and `ZUPO_*` environment
+    pub fn zupo(&self) -> Command {
+        Command::from_std(self.zupo_process())
+    }
+
+    /// `zupo` as above, for tests that run it in the background
+    pub fn zupo_process(&self) -> std::process::Command {
+        let mut cmd = std::process::Command::new(assert_cmd::cargo::cargo_bin("zupo"));
+        for (name, _) in std::env::vars() {
+            if name.starts_with("ZUPO_") {
+                cmd.env_remove(name);
+            }
+        }
+        cmd.current_dir(self.dir.path())
+            .env("GOOGLE_PLACES_API_KEY", "test-key")
+            .env("ZUPO_CONFIG_DIR", self.config_dir())
+            .env("ZUPO_STATE_DIR", self.dir.path().join("state"))
+            .env("ZUPO_CACHE_DIR", self.dir.path().join("cache"))
+            .env("NO_COLOR", "1");
+        cmd
+    }
+}
+
+/// A minimal place as the API returns it
+pub fn place_json(id: &str, name: &str) -> String {
+    format!(
+        r#"{{"id":"{id}","displayName":{{"text":"{name}"}},"formattedAddress":"1 Main St","location":{{"latitude":48.2,"longitude":16.37}},"rating":4.5,"userRatingCount":10}}"#
+    )
+}
+
+pub fn places_json(places: &[String]) -> String {
+    format!(r#"{{"places":[{}]}}"#, places.join(","))
+}
@@ -0,0 +1,177 @@
+mod common;
+
+use common::Home;
+
+/// A config setting every section and field `config export` writes
+const FULL_CONFIG: &str = r#"
+[location]
+default_lat = 48.2082
+default_lng = 16.3738
+default_radius = 1500.0
+label = "home"
+
+[defaults]
+units = "imperial"
+language = "de"
+region = "at"
+
+[paths]
+cache_dir = "/tmp/zupo-cache"
+state_dir = "/tmp/zupo-state"
+log_dir = "/tmp/zupo-logs"
+
+[ranking]
+rating_weight = 0.5
+reviews_weight = 0.3
+distance_weight = 0.2
+prior_rating = 3.5
+prior_reviews = 20.0
+
+[tui]
+wrap_navigation = true
+request_photos = true
+result_limit = 15
+autocomplete_limit = 4
+instant_results = true
+instant_results_monthly_limit = 500
+persist_filters = false
+
+[tui.filters]
+included_type = "cafe"
+radius = 800.0
+min_rating = 4.0
+min_reviews = 10
+price_levels = [1, 2]
+open_now = true
+
+[filters]
+far_factor = 4.0
+far_warning = false
+max_distance = 3000.0
+
+[output]
+hours_start_today = false
+photo_size = "40x20"
+address_style = "short"
+
+[network]
+max_qps = 5.0
+pool_idle_timeout = 60
+pool_max_idle_per_host = 4
+tcp_keepalive = 15
+
+[locations.work]
+lat = 48.19
+lng = 16.4
+radius = 500.0
+
+[locations.gym]
+lat = 48.21
+lng = 16.35
+
+[commands.search]
+limit = 15
+open_now = true
+
+[commands.nearby]
+radius = 750
+
+[pricing]
+searchText = 40.0
+"photo media" = 8.0
+"#;
+
+fn export(home: &Home) -> String {
+    let out = home.zupo().args(["config", "export"]).output().unwrap();
+    assert!(out.status.success(), "{}", String::from_utf8_lossy(&out.stderr));
+    String::from_utf8(out.stdout).unwrap()
+}
+
+fn import(home: &Home, file: &std::path::Path, mode: &str) {
+    home.zupo()
+        .args(["config", "import", mode])
+        .arg(file)
+        .assert()
+        .success();
+}
+
+fn parse(contents: &str) -> toml::Table {
+    toml::from_str(contents).unwrap()
+}
+
+/// Source config exported to a file, plus its export
+fn exported_source() -> (Home, std::path::PathBuf, String) {
+    let source = Home::new();
+    source.write_config(FULL_CONFIG);
+    let exported = export(&source);
+    let file = source.dir.path().join("exported.toml");
+    std::fs::write(&file, &exported).unwrap();
+    (source, file, exported)
+}
+
+#[test]
+fn export_keeps_every_field() {
+    let (_source, _file, exported) = exported_source();
+    assert_eq!(parse(&exported), parse(FULL_CONFIG));
+}
+
+#[test]
+fn import_into_an_empty_config_is_lossless() {
+    let (_source, file, exported) = exported_source();
+
+    for mode in ["--merge", "--replace"] {
+        let target = Home::new();
+        import(&target, &file, mode);
+        assert_eq!(parse(&export(&target)), parse(&exported), "import {}", mode);
+    }
+}
+
+#[test]
+fn replace_drops_everything_not_in_the_import() {
+    let (_source, file, exported) = exported_source();
+    let target = Home::new();
+    target.write_config(
+        "[locations.old]\nlat = 1.0\nlng = 2.0\n\n[commands.route]\nmax_waypoints = 3\n\n[pricing]\ncomputeRoutes = 9.0\n",
+    );
+
+    import(&target, &file, "--replace");
+
+    assert_eq!(parse(&export(&target)), parse(&exported));
+    let backup = std::fs::read_to_string(target.config_dir().join("config.toml.bak")).unwrap();
+    assert!(backup.contains("[locations.old]"));
+}
+
+#[test]
+fn merge_keeps_existing_entries_the_import_lacks() {
+    let (_source, file, _exported) = exported_source();
+    let target = Home::new();
+    target.write_config("[locations.old]\nlat = 1.0\nlng = 2.0\n\n[commands.route]\nmax_waypoints = 3\n");
+
+    import(&target, &file, "--merge");
+
+    let merged = parse(&export(&target));
+    let locations = merged["locations"].as_table().unwrap();
+    assert!(locations.contains_key("old"));
+    assert!(locations.contains_key("work"));
+    assert!(locations.contains_key("gym"));
+    let commands = merged["commands"].as_table().unwrap();
+    assert!(commands.contains_key("route"));
+    assert!(commands.contains_key("search"));
+}
+
+#[test]
+fn invalid_import_leaves_the_config_alone() {
+    let target = Home::new();
+    target.write_config("[locations.old]\nlat = 1.0\nlng = 2.0\n");
+    let before = std::fs::read_to_string(target.config_file()).unwrap();
+    let bad = target.dir.path().join("bad.toml");
+    std::fs::write(&bad, "[location]\ndefault_lat = 123.0\ndefault_lng = 0.0\n").unwrap();
+
+    target
+        .zupo()
+        .args(["config", "import", "--replace"])
+        .arg(&bad)
+        .assert()
+        .code(2);
+    assert_eq!(std::fs::read_to_string(target.config_file()).unwrap(), before);
+}
@@ -0,0 +1,132 @@
+mod common;
+
+use common::{place_json, places_json, Home, MockPlaces};
+
+fn mock() -> MockPlaces {
+    MockPlaces::always(&places_json(&[place_json("ChIJ-env-test-000001", "Cafe Env")]))
+}
+
+#[test]
+fn zupo_format_does_not_conflict_with_modes_that_ignore_it() {
+    let home = Home::new();
+    home.write_config("[locations.home]\nlat = 48.2\nlng = 16.37\n");
+    let api = mock();
+
+    for args in [
+        &["search", "-q", "cafe", "--count"][..],
+        &["search", "-q", "cafe", "--explain"],
+        &["nearby", "--lat", "48.2", "--lng", "16.37", "--count"],
+        &["nearby", "--at", "home"],
+    ] {
+        for format in ["text", "markdown", "csv", "table"] {
+            home.zupo()
+                .env("ZUPO_FORMAT", format)
+                .env("ZUPO_BASE_URL", api.url())
+                .args(args)
+                .assert()
+                .success();
+        }
+    }
+}
+
+#[test]
+fn zupo_format_sets_the_default_format() {
+    let home = Home::new();
+    let api = mock();
+
+    let out = home
+        .zupo()
+        .env("ZUPO_FORMAT", "csv")
+        .args(["--base-url", &api.url(), "search", "-q", "cafe"])
+        .output()
+        .unwrap();
+    assert!(out.status.success());
+    assert!(String::from_utf8_lossy(&out.stdout).starts_with("id,name,"));
+}
+
+#[test]
+fn explicit_format_beats_zupo_format() {
+    let home = Home::new();
+    let api = mock();
+
+    let out = home
+        .zupo()
+        .env("ZUPO_FORMAT", "csv")
+        .args(["--base-url", &api.url(), "search", "-q", "cafe", "--format", "markdown"])
+        .output()
+        .unwrap();
+    assert!(out.status.success());
+    assert!(!String::from_utf8_lossy(&out.stdout).starts_with("id,name,"));
+}
+
+#[test]
+fn explicit_format_is_ignored_with_count() {
+    let home = Home::new();
+    let api = mock();
+
+    let out = home
+        .zupo()
+        .args(["--base-url", &api.url(), "search", "-q", "cafe", "--count", "--format", "csv"])
+        .output()
+        .unwrap();
+    assert!(out.status.success());
+    assert_eq!(String::from_utf8_lossy(&out.stdout).trim(), "1");
+    assert!(String::from_utf8_lossy(&out.stderr).contains("--format csv is ignored with --count"));
+}
+
+#[test]
+fn zupo_lang_and_region_fill_the_request_unless_flags_are_given() {
+    let home = Home::new();
+    let api = mock();
+
+    home.zupo()
+        .env("ZUPO_LANG", "de")
+        .env("ZUPO_REGION", "at")
+        .args(["--base-url", &api.url(), "search", "-q", "cafe"])
+        .assert()
+        .success();
+    home.zupo()
+        .env("ZUPO_LANG", "de")
+        .args(["--base-url", &api.url(), "search", "-q", "cafe", "--lang", "fr"])
+        .assert()
+        .success();
+
+    let requests = api.requests();
+    assert!(requests[0].body.contains(r#""languageCode":"de""#));
+    assert!(requests[0].body.contains(r#""regionCode":"at""#));
+    assert!(requests[1].body.contains(r#""languageCode":"fr""#));
+}
+
+#[test]
+fn zupo_default_location_needs_both_coordinates() {
+    Home::new()
+        .zupo()
+        .env("ZUPO_DEFAULT_LAT", "48.2")
+        .args(["nearby"])
+        .assert()
+        .code(2);
+}
+
+#[test]
+fn zupo_default_location_beats_the_config_but_not_flags() {
+    let home = Home::new();
+    home.write_config("[location]\ndefault_lat = 10.0\ndefault_lng = 20.0\n");
+    let api = mock();
+
+    home.zupo()
+        .env("ZUPO_DEFAULT_LAT", "48.5")
+        .env("ZUPO_DEFAULT_LNG", "16.5")
+        .args(["--base-url", &api.url(), "nearby"])
+        .assert()
+        .success();
+    home.zupo()
+        .env("ZUPO_DEFAULT_LAT", "48.5")
+        .env("ZUPO_DEFAULT_LNG", "16.5")
+        .args(["--base-url", &api.url(), "nearby", "--lat", "1.5", "--lng", "2.5"])
+        .assert()
+        .success();
+
+    let requests = api.requests();
+    assert!(requests[0].body.contains(r#""latitude":48.5"#));
+    assert!(requests[1].body.contains(r#""latitude":1.5"#));
+}
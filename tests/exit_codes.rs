@@ -0,0 +1,100 @@
+mod common;
+
+use common::{closed_port_url, place_json, places_json, Home, MockPlaces};
+
+/// The example polyline from Google's encoding docs (three points in California)
+const POLYLINE: &str = "_p~iF~ps|U_ulLnnqC_mqNvxq`@";
+
+fn routes_or(places: String) -> MockPlaces {
+    MockPlaces::start(move |req| {
+        if req.path.contains("computeRoutes") {
+            let route = format!(
+                r#"{{"routes":[{{"polyline":{{"encodedPolyline":"{}"}},"distanceMeters":750000,"duration":"27000s"}}]}}"#,
+                POLYLINE
+            );
+            (200, route)
+        } else {
+            (200, places.clone())
+        }
+    })
+}
+
+/// Every `--fail-on-empty` command, with the flags it needs against `api`
+fn commands(api: &MockPlaces) -> Vec<Vec<String>> {
+    let url = api.url();
+    [
+        &["search", "-q", "cafe"][..],
+        &["nearby", "--lat", "48.2", "--lng", "16.37"],
+        &["resolve", "--location", "Stephansplatz"],
+        &["route", "-q", "cafe", "--from", "A", "--to", "B", "--routes-base-url", &url],
+    ]
+    .iter()
+    .map(|args| {
+        let mut all = vec!["--base-url".to_string(), url.clone()];
+        all.extend(args.iter().map(|a| a.to_string()));
+        all
+    })
+    .collect()
+}
+
+#[test]
+fn success_is_0() {
+    let home = Home::new();
+    let api = routes_or(places_json(&[place_json("ChIJ-exit-test-00001", "Cafe")]));
+
+    for args in commands(&api) {
+        home.zupo().arg("--fail-on-empty").args(&args).assert().code(0);
+    }
+}
+
+#[test]
+fn api_error_is_1() {
+    let api = MockPlaces::start(|_| {
+        (403, r#"{"error":{"code":403,"message":"API key not valid","status":"PERMISSION_DENIED"}}"#.into())
+    });
+
+    Home::new()
+        .zupo()
+        .args(["--base-url", &api.url(), "search", "-q", "cafe"])
+        .assert()
+        .code(1);
+}
+
+#[test]
+fn invalid_input_is_2() {
+    let home = Home::new();
+
+    // Rejected by clap
+    home.zupo().args(["search", "-q", "cafe", "--limit", "many"]).assert().code(2);
+    // Rejected before any request
+    home.zupo()
+        .args(["nearby", "--lat", "91", "--lng", "16.37"])
+        .assert()
+        .code(2);
+    // No API key
+    home.zupo()
+        .env_remove("GOOGLE_PLACES_API_KEY")
+        .args(["search", "-q", "cafe"])
+        .assert()
+        .code(2);
+}
+
+#[test]
+fn nothing_found_with_fail_on_empty_is_3() {
+    let home = Home::new();
+    let api = routes_or("{}".into());
+
+    for args in commands(&api) {
+        home.zupo().args(&args).assert().code(0);
+        home.zupo().arg("--fail-on-empty").args(&args).assert().code(3);
+    }
+}
+
+#[test]
+fn unreachable_api_is_4() {
+    Home::new()
+        .zupo()
+        .args(["--base-url", &closed_port_url(), "search", "-q", "cafe"])
+        .assert()
+        .code(4);
+}
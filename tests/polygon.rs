@@ -0,0 +1,73 @@
+mod common;
+
+use common::{place_json, places_json, Home, MockPlaces};
+use serde_json::Value;
+
+/// Two islands a degree of longitude (~111 km) apart
+const TOO_LARGE: &str = r#"{"type":"MultiPolygon","coordinates":[
+    [[[0.0,0.0],[0.01,0.0],[0.01,0.01],[0.0,0.01],[0.0,0.0]]],
+    [[[1.0,0.0],[1.01,0.0],[1.01,0.01],[1.0,0.01],[1.0,0.0]]]]}"#;
+
+/// An area around Taveuni, Fiji, split into two polygons at the antimeridian
+const SPLIT_AT_ANTIMERIDIAN: &str = r#"{"type":"MultiPolygon","coordinates":[
+    [[[179.9,-17.1],[180.0,-17.1],[180.0,-16.9],[179.9,-16.9],[179.9,-17.1]]],
+    [[[-180.0,-17.1],[-179.9,-17.1],[-179.9,-16.9],[-180.0,-16.9],[-180.0,-17.1]]]]}"#;
+
+/// A place at latitude -17 and `lng`
+fn place_at(id: &str, lng: f64) -> String {
+    place_json(id, "Inside").replace(
+        r#""latitude":48.2,"longitude":16.37"#,
+        &format!(r#""latitude":-17.0,"longitude":{}"#, lng),
+    )
+}
+
+#[test]
+fn too_large_polygon_is_rejected_before_any_request() {
+    let home = Home::new();
+    let api = MockPlaces::always(&places_json(&[]));
+    let path = home.dir.path().join("large.geojson");
+    std::fs::write(&path, TOO_LARGE).unwrap();
+    let path = path.to_str().unwrap();
+
+    for args in [&["search", "-q", "cafe", "--polygon", path][..], &["nearby", "--polygon", path]] {
+        let out = home.zupo().args(["--base-url", &api.url()]).args(args).output().unwrap();
+        assert_eq!(out.status.code(), Some(2));
+        let stderr = String::from_utf8_lossy(&out.stderr);
+        assert!(stderr.contains("is too large"), "{}", stderr);
+        assert!(stderr.contains("max 50 km"), "{}", stderr);
+    }
+    assert!(api.requests().is_empty());
+}
+
+#[test]
+fn polygon_split_at_the_antimeridian_is_searched_as_one_area() {
+    let home = Home::new();
+    let api = MockPlaces::always(&places_json(&[
+        place_at("ChIJ-polygon-east-001", 179.95),
+        place_at("ChIJ-polygon-west-001", -179.95),
+        place_json("ChIJ-polygon-out-0001", "Outside"),
+    ]));
+    let path = home.dir.path().join("fiji.geojson");
+    std::fs::write(&path, SPLIT_AT_ANTIMERIDIAN).unwrap();
+
+    let out = home
+        .zupo()
+        .args(["--base-url", &api.url(), "--json", "nearby", "--polygon", path.to_str().unwrap()])
+        .output()
+        .unwrap();
+    assert!(out.status.success(), "{}", String::from_utf8_lossy(&out.stderr));
+
+    let sent: Value = serde_json::from_str(&api.requests()[0].body).unwrap();
+    let circle = &sent["locationRestriction"]["circle"];
+    assert!((circle["center"]["longitude"].as_f64().unwrap().abs() - 180.0).abs() < 1e-6);
+    assert!(circle["radius"].as_f64().unwrap() < 20_000.0);
+
+    let found: Value = serde_json::from_slice(&out.stdout).unwrap();
+    let ids: Vec<&str> = found["places"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|p| p["id"].as_str().unwrap())
+        .collect();
+    assert_eq!(ids, ["ChIJ-polygon-east-001", "ChIJ-polygon-west-001"]);
+}
@@ -0,0 +1,273 @@
+mod common;
+
+use std::io::Read;
+use std::net::{TcpListener, TcpStream};
+use std::process::{Child, Stdio};
+use std::time::{Duration, Instant};
+
+use common::{place_json, places_json, Home, MockPlaces};
+use serde_json::{json, Value};
+
+/// `zupo serve` running in the background against a mock backend; killed on drop
+struct Server {
+    child: Child,
+    url: String,
+    _home: Home,
+}
+
+impl Server {
+    fn start(backend: &MockPlaces, args: &[&str]) -> Self {
+        Self::start_in(Home::new(), backend, args)
+    }
+
+    fn start_in(home: Home, backend: &MockPlaces, args: &[&str]) -> Self {
+        let port = {
+            let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+            listener.local_addr().unwrap().port()
+        };
+        let listen = format!("127.0.0.1:{}", port);
+        let child = home
+            .zupo_process()
+            .args(["--base-url", &backend.url(), "serve", "--listen", &listen])
+            .args(args)
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .spawn()
+            .unwrap();
+
+        let deadline = Instant::now() + Duration::from_secs(10);
+        while TcpStream::connect(&listen).is_err() {
+            assert!(Instant::now() < deadline, "zupo serve did not start listening");
+            std::thread::sleep(Duration::from_millis(20));
+        }
+        Server {
+            child,
+            url: format!("http://{}", listen),
+            _home: home,
+        }
+    }
+
+    async fn post(&self, path: &str, body: Value) -> (u16, Value) {
+        let response = reqwest::Client::new()
+            .post(format!("{}{}", self.url, path))
+            .json(&body)
+            .send()
+            .await
+            .unwrap();
+        let status = response.status().as_u16();
+        (status, response.json().await.unwrap())
+    }
+}
+
+impl Drop for Server {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+fn backend() -> MockPlaces {
+    MockPlaces::start(|req| {
+        if req.path.starts_with("/places:autocomplete") {
+            let body = r#"{"suggestions":[{"placePrediction":{"placeId":"p1","text":{"text":"Cafe One, Main St"}}}]}"#;
+            (200, body.to_string())
+        } else if req.path.starts_with("/places/") {
+            (200, place_json("p1", "Cafe One"))
+        } else {
+            (200, places_json(&[place_json("p1", "Cafe One"), place_json("p2", "Cafe Two")]))
+        }
+    })
+}
+
+#[tokio::test]
+async fn search_answers_with_backend_places() {
+    let places = backend();
+    let server = Server::start(&places, &[]);
+
+    let (status, body) = server.post("/search", json!({"query": "coffee", "limit": 2})).await;
+
+    assert_eq!(status, 200);
+    assert_eq!(body["places"][0]["id"], "p1");
+    assert_eq!(body["places"][1]["displayName"]["text"], "Cafe Two");
+    let requests = places.requests();
+    assert_eq!(requests.len(), 1);
+    assert!(requests[0].path.starts_with("/places:searchText"));
+    assert!(requests[0].body.contains(r#""textQuery":"coffee""#));
+}
+
+#[tokio::test]
+async fn nearby_answers_with_backend_places() {
+    let places = backend();
+    let server = Server::start(&places, &[]);
+
+    let (status, body) = server
+        .post("/nearby", json!({"lat": 48.2, "lng": 16.37, "radius": 500.0}))
+        .await;
+
+    assert_eq!(status, 200);
+    assert_eq!(body["places"][0]["id"], "p1");
+    assert!(places.requests()[0].path.starts_with("/places:searchNearby"));
+}
+
+#[tokio::test]
+async fn details_answers_with_the_place() {
+    let places = backend();
+    let server = Server::start(&places, &[]);
+
+    let (status, body) = server
+        .post("/details", json!({"placeId": "places/ChIJN1t_tDeuEmsRUsoyG83frY4"}))
+        .await;
+
+    assert_eq!(status, 200);
+    assert_eq!(body["displayName"]["text"], "Cafe One");
+    assert!(places.requests()[0].path.starts_with("/places/ChIJN1t_tDeuEmsRUsoyG83frY4"));
+}
+
+#[tokio::test]
+async fn autocomplete_answers_with_suggestions() {
+    let places = backend();
+    let server = Server::start(&places, &[]);
+
+    let (status, body) = server.post("/autocomplete", json!({"input": "caf"})).await;
+
+    assert_eq!(status, 200);
+    assert_eq!(body["suggestions"][0]["placePrediction"]["placeId"], "p1");
+    assert!(places.requests()[0].body.contains(r#""input":"caf""#));
+}
+
+#[tokio::test]
+async fn defaults_from_config_fill_missing_locale() {
+    let places = backend();
+    let home = Home::new();
+    home.write_config("[defaults]\nlanguage = \"de\"\n");
+    let server = Server::start_in(home, &places, &[]);
+
+    let (status, _) = server.post("/search", json!({"query": "coffee"})).await;
+
+    assert_eq!(status, 200);
+    assert!(places.requests()[0].body.contains(r#""languageCode":"de""#));
+}
+
+#[tokio::test]
+async fn validation_errors_are_400_without_calling_the_backend() {
+    let places = backend();
+    let server = Server::start(&places, &[]);
+
+    let (status, body) = server.post("/search", json!({"query": ""})).await;
+
+    assert_eq!(status, 400);
+    assert_eq!(body["error"]["field"], "query");
+    assert!(places.requests().is_empty());
+}
+
+#[tokio::test]
+async fn backend_errors_map_to_gateway_statuses() {
+    let places = MockPlaces::start(|_| (403, r#"{"error":{"message":"API key not valid"}}"#.to_string()));
+    let server = Server::start(&places, &[]);
+
+    let (status, body) = server.post("/search", json!({"query": "coffee"})).await;
+
+    assert_eq!(status, 502);
+    assert_eq!(body["error"]["upstreamStatus"], 403);
+    assert!(body["error"]["message"].as_str().unwrap().contains("API key not valid"));
+}
+
+#[tokio::test]
+async fn unknown_paths_and_methods_are_rejected() {
+    let places = backend();
+    let server = Server::start(&places, &[]);
+    let client = reqwest::Client::new();
+
+    let (status, _) = server.post("/photos", json!({})).await;
+    assert_eq!(status, 404);
+
+    let response = client.get(format!("{}/search", server.url)).send().await.unwrap();
+    assert_eq!(response.status().as_u16(), 405);
+    assert_eq!(response.headers()["allow"], "POST");
+
+    let response = client
+        .post(format!("{}/search", server.url))
+        .header("content-type", "text/plain")
+        .body(r#"{"query":"coffee"}"#)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status().as_u16(), 415);
+    assert!(places.requests().is_empty());
+}
+
+#[tokio::test]
+async fn non_loopback_host_is_forbidden() {
+    let places = backend();
+    let server = Server::start(&places, &[]);
+
+    let response = reqwest::Client::new()
+        .post(format!("{}/search", server.url))
+        .header("host", "evil.example:80")
+        .json(&json!({"query": "coffee"}))
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(response.status().as_u16(), 403);
+    assert!(places.requests().is_empty());
+}
+
+#[tokio::test]
+async fn token_is_required_when_set() {
+    let places = backend();
+    let server = Server::start(&places, &["--token", "s3cret"]);
+    let client = reqwest::Client::new();
+
+    let (status, _) = server.post("/search", json!({"query": "coffee"})).await;
+    assert_eq!(status, 401);
+
+    let response = client
+        .post(format!("{}/search", server.url))
+        .bearer_auth("wrong")
+        .json(&json!({"query": "coffee"}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status().as_u16(), 401);
+
+    let response = client
+        .post(format!("{}/search", server.url))
+        .bearer_auth("s3cret")
+        .json(&json!({"query": "coffee"}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status().as_u16(), 200);
+    assert_eq!(places.requests().len(), 1);
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn interrupt_shuts_down_cleanly() {
+    let places = backend();
+    let mut server = Server::start(&places, &[]);
+    let (status, _) = server.post("/search", json!({"query": "coffee"})).await;
+    assert_eq!(status, 200);
+
+    let killed = std::process::Command::new("kill")
+        .args(["-INT", &server.child.id().to_string()])
+        .status()
+        .unwrap();
+    assert!(killed.success());
+
+    let deadline = Instant::now() + Duration::from_secs(15);
+    let exit = loop {
+        if let Some(exit) = server.child.try_wait().unwrap() {
+            break exit;
+        }
+        assert!(Instant::now() < deadline, "zupo serve did not exit after SIGINT");
+        std::thread::sleep(Duration::from_millis(20));
+    };
+    let mut stderr = String::new();
+    server.child.stderr.take().unwrap().read_to_string(&mut stderr).unwrap();
+
+    assert!(exit.success(), "exit {:?}, stderr: {}", exit, stderr);
+    assert!(stderr.contains("Listening on"));
+    assert!(stderr.contains("Shutting down"));
+}